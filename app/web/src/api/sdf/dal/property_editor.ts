@@ -5,6 +5,7 @@
 // * require a key/index (map or array)
 
 import { LabelList } from "@/api/sdf/dal/label_list";
+import { SecretDefinition } from "@/api/sdf/dal/secret";
 
 export enum PropertyEditorPropKind {
   Array = "array",
@@ -56,6 +57,7 @@ export interface PropertyEditorPropWidgetKindSelect {
 export interface PropertyEditorPropWidgetKindSecretSelect {
   kind: "secretSelect";
   options: LabelList<string | number>;
+  definition?: SecretDefinition;
 }
 
 export interface PropertyEditorPropWidgetKindColor {
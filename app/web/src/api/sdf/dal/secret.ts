@@ -3,20 +3,14 @@ import { StandardModel } from "@/api/sdf/dal/standard_model";
 export interface Secret extends StandardModel {
   name: string;
   objectType: SecretObjectType;
-  kind: SecretKind;
+  // The name of the secret's SecretDefinition
+  kind: string;
 }
 
 export enum SecretObjectType {
   Credential = "credential",
 }
 
-export enum SecretKind {
-  DockerHub = "dockerHub",
-  AwsAccessKey = "awsAccessKey",
-  HelmRepo = "helmRepo",
-  AzureServicePrincipal = "azureServicePrincipal",
-}
-
 export enum SecretVersion {
   V1 = "v1",
 }
@@ -25,8 +19,18 @@ export enum SecretAlgorithm {
   Sealedbox = "sealedbox",
 }
 
-export interface SecretKindFields {
-  secretKind: SecretKind;
-  displayName: string;
-  fields: { keyName: string; displayName: string; password: boolean }[];
+export interface SecretDefinitionField {
+  name: string;
+  displayName?: string;
+  sensitive: boolean;
+  required: boolean;
+  pattern?: string;
+}
+
+export interface SecretDefinition {
+  id: string;
+  kind: string;
+  displayName?: string;
+  description?: string;
+  fields: SecretDefinitionField[];
 }
@@ -43,6 +43,7 @@ export interface ListedVariantDef {
 
 export interface VariantDef extends ListedVariantDef {
   link?: string;
+  refreshIntervalSecs?: number;
   schemaVariantId?: string;
   code: string;
   handler: string;
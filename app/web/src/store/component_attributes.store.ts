@@ -37,6 +37,12 @@ export interface SetTypeArgs {
   value?: unknown;
 }
 
+export interface SetRefreshIntervalArgs {
+  componentId: string;
+  // falls back to the schema variant's interval when left out
+  refreshIntervalSecs?: number;
+}
+
 export const useComponentAttributesStore = (componentId: ComponentId) => {
   const changeSetsStore = useChangeSetsStore();
   const changeSetId = changeSetsStore.selectedChangeSetId;
@@ -256,6 +262,27 @@ export const useComponentAttributesStore = (componentId: ComponentId) => {
             },
           });
         },
+
+        async SET_COMPONENT_REFRESH_INTERVAL(payload: SetRefreshIntervalArgs) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          const statusStore = useStatusStore();
+          statusStore.markUpdateStarted();
+
+          return new ApiRequest<{ success: true }>({
+            method: "post",
+            url: "component/set_refresh_interval",
+            params: {
+              ...payload,
+              ...visibilityParams,
+            },
+            onFail() {
+              statusStore.cancelUpdateStarted();
+            },
+          });
+        },
       },
       onActivated() {
         this.reloadPropertyEditorData();
@@ -17,8 +17,14 @@ export type FixStatus =
   | "failure"
   | "running"
   | "error"
+  | "cancelled"
   | "unstarted";
 
+export type FixBatchFailurePolicy =
+  | "continue"
+  | "skipDependents"
+  | "stopOnFirstFailure";
+
 export enum ActionKind {
   Create = "create",
   Delete = "delete",
@@ -86,6 +92,44 @@ export type FixBatch = {
   fixes: Fix[];
   startedAt?: string;
   finishedAt?: string;
+  failurePolicy: FixBatchFailurePolicy;
+  cancelledAt?: string;
+};
+
+export type FixScheduleId = string;
+export type FixSchedule = {
+  id: FixScheduleId;
+  author: string;
+  fixes: {
+    attributeValueId: AttributeValueId;
+    componentId: ComponentId;
+    actionPrototypeId: ActionPrototypeId;
+  }[];
+  failurePolicy: FixBatchFailurePolicy;
+  nextRunAt?: string;
+  intervalSecs?: number;
+  lastRunAt?: string;
+  lastFixBatchId?: FixBatchId;
+  lastRunError?: string;
+  cancelledAt?: string;
+  createdAt: string;
+};
+
+export type ActionPlan = {
+  supported: boolean;
+  status?: "ok" | "warning" | "error";
+  changes?: unknown;
+  message?: string;
+  logs: string[];
+};
+
+export type FixPlan = {
+  attributeValueId: AttributeValueId;
+  componentId: ComponentId;
+  componentName: string;
+  actionPrototypeId: ActionPrototypeId;
+  actionKind: ActionKind;
+  plan: ActionPlan;
 };
 
 export interface ConfirmationStats {
@@ -109,6 +153,7 @@ export const useFixesStore = () => {
         confirmations: [] as Array<Confirmation>,
         recommendations: [] as Array<Recommendation>,
         fixBatches: [] as Array<FixBatch>,
+        fixSchedules: [] as Array<FixSchedule>,
         runningFixBatch: undefined as FixBatchId | undefined,
         populatingFixes: false,
         recommendationsSelection: {} as Record<
@@ -279,13 +324,17 @@ export const useFixesStore = () => {
             onSuccess: (response) => {
               this.fixBatches = response;
               this.runningFixBatch = response.find(
-                (batch) => !["success", "failure"].includes(batch.status ?? ""),
+                (batch) =>
+                  !["success", "failure", "cancelled"].includes(
+                    batch.status ?? "",
+                  ),
               )?.id;
             },
           });
         },
         async EXECUTE_FIXES_FROM_RECOMMENDATIONS(
           recommendations: Array<Recommendation>,
+          failurePolicy?: FixBatchFailurePolicy,
         ) {
           return new ApiRequest({
             method: "post",
@@ -295,6 +344,7 @@ export const useFixesStore = () => {
                 componentId: r.componentId,
                 actionPrototypeId: r.actionPrototypeId,
               })),
+              failurePolicy,
               visibility_change_set_pk: nilId(),
             },
             url: "/fix/run",
@@ -304,10 +354,104 @@ export const useFixesStore = () => {
             },
           });
         },
+        async PLAN_FIXES_FROM_RECOMMENDATIONS(
+          recommendations: Array<Recommendation>,
+        ) {
+          return new ApiRequest<{ plans: FixPlan[]; plannedAt: string }>({
+            method: "post",
+            params: {
+              list: recommendations.map((r) => ({
+                attributeValueId: r.confirmationAttributeValueId,
+                componentId: r.componentId,
+                actionPrototypeId: r.actionPrototypeId,
+              })),
+              visibility_change_set_pk: nilId(),
+            },
+            url: "/fix/plan",
+          });
+        },
+        async SCHEDULE_FIXES_FROM_RECOMMENDATIONS(
+          recommendations: Array<Recommendation>,
+          runAt: Date,
+          intervalSecs?: number,
+          failurePolicy?: FixBatchFailurePolicy,
+        ) {
+          return new ApiRequest<{ id: FixScheduleId }>({
+            method: "post",
+            params: {
+              list: recommendations.map((r) => ({
+                attributeValueId: r.confirmationAttributeValueId,
+                componentId: r.componentId,
+                actionPrototypeId: r.actionPrototypeId,
+              })),
+              runAt: runAt.toISOString(),
+              intervalSecs,
+              failurePolicy,
+              visibility_change_set_pk: nilId(),
+            },
+            url: "/fix/schedule",
+            onSuccess: () => {
+              this.LOAD_FIX_SCHEDULES();
+            },
+          });
+        },
+        async LOAD_FIX_SCHEDULES() {
+          return new ApiRequest<Array<FixSchedule>>({
+            url: "/fix/list_schedules",
+            params: {
+              visibility_change_set_pk: nilId(),
+            },
+            onSuccess: (response) => {
+              this.fixSchedules = response;
+            },
+          });
+        },
+        async CANCEL_FIX_SCHEDULE(id: FixScheduleId) {
+          return new ApiRequest({
+            method: "post",
+            params: {
+              id,
+              visibility_change_set_pk: nilId(),
+            },
+            url: "/fix/cancel_schedule",
+            onSuccess: () => {
+              this.LOAD_FIX_SCHEDULES();
+            },
+          });
+        },
+        async CANCEL_FIX_BATCH(id: FixBatchId) {
+          return new ApiRequest({
+            method: "post",
+            params: {
+              id,
+              visibility_change_set_pk: nilId(),
+            },
+            url: "/fix/cancel",
+            onSuccess: () => {
+              this.LOAD_FIX_BATCHES();
+            },
+          });
+        },
+        async RETRY_FIX_BATCH(id: FixBatchId) {
+          return new ApiRequest<{ id: FixBatchId }>({
+            method: "post",
+            params: {
+              id,
+              visibility_change_set_pk: nilId(),
+            },
+            url: "/fix/retry",
+            onSuccess: (response) => {
+              this.runningFixBatch = response.id;
+              this.LOAD_CONFIRMATIONS();
+              this.LOAD_FIX_BATCHES();
+            },
+          });
+        },
       },
       async onActivated() {
         this.LOAD_CONFIRMATIONS();
         this.LOAD_FIX_BATCHES();
+        this.LOAD_FIX_SCHEDULES();
 
         const realtimeStore = useRealtimeStore();
         realtimeStore.subscribe(
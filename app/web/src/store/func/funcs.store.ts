@@ -40,6 +40,8 @@ export type FuncWithDetails = FuncSummary & {
   code: string;
   types: string;
   isRevertible: boolean;
+  deterministic: boolean;
+  supportsDryRun: boolean;
   associations?: FuncAssociations;
 };
 
@@ -404,6 +406,8 @@ export const useFuncStore = () => {
           variant: FuncVariant;
           name?: string;
           options?: CreateFuncOptions;
+          // defaults to "javascript"; only actions and attribute funcs can be "python"
+          runtime?: "javascript" | "python";
         }) {
           return new ApiRequest<FuncSummary>({
             method: "post",
@@ -1,4 +1,9 @@
+import { defineStore } from "pinia";
+import * as _ from "lodash-es";
+import { addStoreHooks, ApiRequest } from "@si/vue-lib/pinia";
+import { SecretDefinition } from "@/api/sdf/dal/secret";
 import { ActorAndTimestamp } from "./components.store";
+import { useChangeSetsStore } from "./change_sets.store";
 
 export type SecretId = string;
 export type DefinitionId = string;
@@ -12,6 +17,40 @@ export type Secret = {
   updatedInfo: ActorAndTimestamp;
 };
 
+export const useSecretsStore = () => {
+  const changeSetsStore = useChangeSetsStore();
+  const changeSetId = changeSetsStore.selectedChangeSetId;
+  return addStoreHooks(
+    defineStore(`cs${changeSetId || "NONE"}/secrets`, {
+      state: () => ({
+        // the kinds of secret the workspace knows about and the fields each one asks for
+        secretDefinitions: [] as SecretDefinition[],
+      }),
+      getters: {
+        secretDefinitionsByKind(): Record<string, SecretDefinition> {
+          return _.keyBy(this.secretDefinitions, (d) => d.kind);
+        },
+      },
+      actions: {
+        async LOAD_SECRET_DEFINITIONS() {
+          return new ApiRequest<{ list: SecretDefinition[] }>({
+            url: "secret/list_secret_definitions",
+            params: {
+              visibility_change_set_pk: changeSetId,
+            },
+            onSuccess: (response) => {
+              this.secretDefinitions = response.list;
+            },
+          });
+        },
+      },
+      onActivated() {
+        this.LOAD_SECRET_DEFINITIONS();
+      },
+    }),
+  )();
+};
+
 // backend endpoint
 // give me all secrets organized by definition id
 // create a secret of the given defintion id with the given encrypted payload
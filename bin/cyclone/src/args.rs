@@ -1,7 +1,10 @@
 use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
 use clap::{ArgAction, Parser};
-use cyclone_server::{Config, ConfigError, IncomingStream};
+use cyclone_server::{
+    Config, ConfigError, ExecutionSandbox, IncomingStream, Runtime, SandboxNetwork,
+    DEFAULT_SANDBOX_BWRAP_PATH,
+};
 
 const NAME: &str = "cyclone";
 
@@ -92,6 +95,12 @@ pub(crate) struct Args {
     #[arg(long, env = "SI_LANG_SERVER", hide_env = true)]
     pub(crate) lang_server: PathBuf,
 
+    /// Path to the lang server program of another runtime [example: python=/usr/local/bin/lang-py]
+    ///
+    /// May be given once per runtime.
+    #[arg(long, value_name = "RUNTIME=PATH", value_parser = parse_runtime_lang_server)]
+    pub(crate) runtime_lang_server: Vec<(Runtime, PathBuf)>,
+
     /// Limits execution requests to 1 before shutting down
     #[arg(long, group = "request_limiting")]
     pub(crate) oneshot: bool,
@@ -100,6 +109,39 @@ pub(crate) struct Args {
     #[arg(long, group = "request_limiting")]
     pub(crate) limit_requests: Option<u32>,
 
+    /// Default timeout in seconds for a function execution, unless the request sets its own (none
+    /// by default: executions run until they finish)
+    #[arg(long)]
+    pub(crate) execution_timeout: Option<u64>,
+
+    /// Longest timeout in seconds a request may set for a function execution
+    #[arg(long)]
+    pub(crate) execution_max_timeout: Option<u64>,
+
+    /// Limits the data segment and private writable memory of each function execution's process
+    /// to the given bytes
+    #[arg(long)]
+    pub(crate) execution_memory_limit: Option<u64>,
+
+    /// Limits the CPU time of each function execution's process to the given seconds
+    #[arg(long)]
+    pub(crate) execution_cpu_limit: Option<u64>,
+
+    /// Runs each function execution in its own sandbox of Linux namespaces, using bubblewrap
+    ///
+    /// The sandbox has a read-only root filesystem, a private scratch directory and no network
+    /// unless it's shared.
+    #[arg(long)]
+    pub(crate) sandbox: bool,
+
+    /// Path to the bubblewrap program used to build sandboxes
+    #[arg(long, default_value = DEFAULT_SANDBOX_BWRAP_PATH, requires = "sandbox")]
+    pub(crate) sandbox_bwrap: PathBuf,
+
+    /// Shares the host network with sandboxes
+    #[arg(long, requires = "sandbox")]
+    pub(crate) sandbox_share_net: bool,
+
     /// Cyclone decryption key file location [example: /run/cyclone/cyclone.key]
     #[arg(long)]
     pub(crate) decryption_key: PathBuf,
@@ -119,6 +161,9 @@ impl TryFrom<Args> for Config {
         }
 
         builder.try_lang_server_path(args.lang_server)?;
+        for (runtime, path) in args.runtime_lang_server {
+            builder.try_runtime_lang_server_path(runtime, path)?;
+        }
 
         if args.enable_watch {
             builder.watch(Some(Duration::from_secs(args.watch_timeout)));
@@ -144,10 +189,41 @@ impl TryFrom<Args> for Config {
             builder.limit_requests(limit_requests);
         }
 
+        if let Some(secs) = args.execution_timeout {
+            builder.execution_timeout(Duration::from_secs(secs));
+        }
+        if let Some(secs) = args.execution_max_timeout {
+            builder.execution_max_timeout(Duration::from_secs(secs));
+        }
+        builder.execution_memory_limit(args.execution_memory_limit);
+        builder.execution_cpu_limit(args.execution_cpu_limit.map(Duration::from_secs));
+
+        if args.sandbox {
+            let network = if args.sandbox_share_net {
+                SandboxNetwork::Host
+            } else {
+                SandboxNetwork::None
+            };
+            builder.execution_sandbox(ExecutionSandbox {
+                bwrap_path: args.sandbox_bwrap,
+                network,
+                // Executions are handed the secrets they need, so they never need the key
+                hidden_paths: vec![args.decryption_key],
+            });
+        }
+
         builder.build().map_err(Into::into)
     }
 }
 
+fn parse_runtime_lang_server(value: &str) -> Result<(Runtime, PathBuf), String> {
+    let (runtime, path) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected RUNTIME=PATH, found '{value}'"))?;
+    let runtime: Runtime = runtime.parse().map_err(|err| format!("{err}"))?;
+    Ok((runtime, PathBuf::from(path)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +233,14 @@ mod tests {
         use clap::CommandFactory;
         Args::command().debug_assert()
     }
+
+    #[test]
+    fn parses_runtime_lang_server() {
+        assert_eq!(
+            parse_runtime_lang_server("python=/usr/local/bin/lang-py").expect("failed to parse"),
+            (Runtime::Python, PathBuf::from("/usr/local/bin/lang-py"))
+        );
+        assert!(parse_runtime_lang_server("/usr/local/bin/lang-py").is_err());
+        assert!(parse_runtime_lang_server("cobol=/usr/local/bin/lang-cobol").is_err());
+    }
 }
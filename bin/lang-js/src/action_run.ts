@@ -16,6 +16,9 @@ const debug = Debug("langJs:actionRun");
 
 export interface ActionRunRequest extends RequestWithCode {
   args: unknown;
+  // When set, the function must describe the changes it would make as its `plan` instead of
+  // making them. Functions see it as the `dryRun` global.
+  dryRun?: boolean;
 }
 
 export type ActionRunResult =
@@ -26,6 +29,7 @@ export interface ActionRunResultSuccess extends ResultSuccess {
   payload: unknown;
   health: "ok" | "warning" | "error";
   message?: string;
+  plan?: unknown;
 }
 export type ActionRunResultFailure = ResultFailure;
 
@@ -38,6 +42,7 @@ export async function executeActionRun(
   debug({ code });
 
   const sandbox = createSandbox(FunctionKind.ActionRun, request.executionId);
+  sandbox.dryRun = request.dryRun ?? false;
   const vm = createNodeVm(sandbox);
 
   const result = await execute(vm, code, request.executionId, request.args);
@@ -130,6 +135,7 @@ async function execute(
       payload: actionRunResult.payload,
       health: actionRunResult.status as "ok" | "warning" | "error",
       message: actionRunResult.message as string | undefined,
+      plan: actionRunResult.plan,
     };
     return result;
   } catch (err) {
@@ -81,6 +81,7 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
 
     let (_resource_job_client, resource_job_processor) = JobProcessor::connect(&config).await?;
     let (_, status_receiver_job_processor) = JobProcessor::connect(&config).await?;
+    let (_, fix_scheduler_job_processor) = JobProcessor::connect(&config).await?;
 
     let pg_pool = Server::create_pg_pool(config.pg_pool()).await?;
 
@@ -131,6 +132,17 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
                 module_index_url,
             )?;
             let second_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let third_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+
+            Server::start_fix_scheduler(
+                pg_pool.clone(),
+                nats.clone(),
+                fix_scheduler_job_processor,
+                veritech.clone(),
+                encryption_key,
+                third_shutdown_broadcast_rx,
+            )
+            .await;
 
             Server::start_resource_refresh_scheduler(
                 pg_pool.clone(),
@@ -169,6 +181,17 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
             )
             .await?;
             let second_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let third_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+
+            Server::start_fix_scheduler(
+                pg_pool.clone(),
+                nats.clone(),
+                fix_scheduler_job_processor,
+                veritech.clone(),
+                encryption_key,
+                third_shutdown_broadcast_rx,
+            )
+            .await;
 
             Server::start_resource_refresh_scheduler(
                 pg_pool.clone(),
@@ -230,6 +230,18 @@
             '';
           };
 
+          # A single Python script with no dependencies beyond the interpreter, so there's nothing
+          # for Buck2 to build: it's installed with its shebang pointing at Nix's Python.
+          lang-py = stdenv.mkDerivation {
+            name = "lang-py";
+            src = ./bin/lang-py;
+            buildInputs = [python3];
+            installPhase = ''
+              install -Dm755 lang-py "$out/bin/lang-py"
+              patchShebangs --host "$out/bin/lang-py"
+            '';
+          };
+
           module-index = binDerivation {pkgName = "module-index";};
 
           pinga = binDerivation {pkgName = "pinga";};
@@ -317,6 +329,7 @@
               gh
               jq
               pgcli
+              python3
               reindeer
               shellcheck
               shfmt
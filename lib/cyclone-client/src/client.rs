@@ -453,7 +453,7 @@ mod tests {
     use buck2_resources::Buck2Resources;
     use cyclone_core::{
         ComponentKind, ComponentView, FunctionResult, ProgressMessage, ResolverFunctionComponent,
-        ValidationRequest,
+        Runtime, ValidationRequest,
     };
     use cyclone_server::{Config, ConfigBuilder, DecryptionKey, Server, UdsIncomingStream};
     use futures::StreamExt;
@@ -477,14 +477,22 @@ mod tests {
             .into_temp_path()
     }
 
-    #[allow(clippy::disallowed_methods)] // Used to determine if running in development
     fn lang_server_path() -> String {
+        dev_lang_server_path("lang-js", "bin/lang-js/target/lang-js")
+    }
+
+    fn python_lang_server_path() -> String {
+        dev_lang_server_path("lang-py", "bin/lang-py/lang-py")
+    }
+
+    #[allow(clippy::disallowed_methods)] // Used to determine if running in development
+    fn dev_lang_server_path(resource: &str, repo_path: &str) -> String {
         if env::var("BUCK_RUN_BUILD_ID").is_ok() || env::var("BUCK_BUILD_ID").is_ok() {
             let resources = Buck2Resources::read().expect("failed to read buck2 resources");
 
             let lang_server_cmd_path = resources
-                .get_ends_with("lang-js")
-                .expect("failed to get lang-js resource")
+                .get_ends_with(resource)
+                .unwrap_or_else(|err| panic!("failed to get {resource} resource: {err}"))
                 .to_string_lossy()
                 .to_string();
 
@@ -496,11 +504,12 @@ mod tests {
             lang_server_cmd_path
         } else if let Ok(dir) = env::var("CARGO_MANIFEST_DIR") {
             let lang_server_cmd_path = Path::new(&dir)
-                .join("../../bin/lang-js/target/lang-js")
+                .join("../..")
+                .join(repo_path)
                 .canonicalize()
-                .expect(
-                    "failed to canonicalize local dev build of <root>/bin/lang-js/target/lang-js",
-                )
+                .unwrap_or_else(|err| {
+                    panic!("failed to canonicalize local dev build of <root>/{repo_path}: {err}")
+                })
                 .to_string_lossy()
                 .to_string();
 
@@ -788,6 +797,8 @@ mod tests {
                     return v;
                 }"#,
             ),
+            timeout_secs: None,
+            runtime: Runtime::JavaScript,
         };
 
         // Start the protocol
@@ -878,6 +889,8 @@ mod tests {
                     return v;
                 }"#,
             ),
+            timeout_secs: None,
+            runtime: Runtime::JavaScript,
         };
 
         // Start the protocol
@@ -1048,6 +1061,9 @@ mod tests {
                     return { status: 'ok' };
                 }"#,
             ),
+            timeout_secs: None,
+            runtime: Runtime::JavaScript,
+            dry_run: false,
         };
 
         // Start the protocol
@@ -1123,6 +1139,9 @@ mod tests {
                     return { status: 'ok' };
                 }"#,
             ),
+            timeout_secs: None,
+            runtime: Runtime::JavaScript,
+            dry_run: false,
         };
 
         // Start the protocol
@@ -1179,6 +1198,167 @@ mod tests {
         }
     }
 
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test)]
+    async fn http_execute_action_run_timeout() {
+        let (_, key) = gen_keys();
+        let mut builder = Config::builder();
+        let mut client = http_client_for_running_server(builder.enable_action_run(true), key).await;
+
+        let req = ActionRunRequest {
+            execution_id: "1234".to_string(),
+            handler: "workit".to_string(),
+            args: Default::default(),
+            code_base64: base64_encode(
+                r#"function workit() {
+                    while (true) {}
+                }"#,
+            ),
+            timeout_secs: Some(1),
+            runtime: Runtime::JavaScript,
+            dry_run: false,
+        };
+
+        // Start the protocol
+        let mut progress = client
+            .execute_action_run(req)
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+
+        loop {
+            match progress.next().await {
+                None => break,
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(unexpected) => panic!("output stream should be done: {unexpected:?}"),
+            };
+        }
+        let result = progress.finish().await.expect("failed to return result");
+        match result {
+            FunctionResult::Success(success) => {
+                panic!("result should be failure; success={success:?}")
+            }
+            FunctionResult::Failure(failure) => {
+                assert_eq!("1234", failure.execution_id);
+                assert!(failure.error.is_timeout());
+            }
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test)]
+    async fn http_execute_action_run_dry_run() {
+        let (_, key) = gen_keys();
+        let mut builder = Config::builder();
+        let mut client = http_client_for_running_server(builder.enable_action_run(true), key).await;
+
+        let req = ActionRunRequest {
+            execution_id: "1234".to_string(),
+            handler: "workit".to_string(),
+            args: Default::default(),
+            code_base64: base64_encode(
+                r#"function workit() {
+                    if (!dryRun) {
+                        throw new Error('should not run for real');
+                    }
+                    return { status: 'ok', plan: { create: ['bucket'] } };
+                }"#,
+            ),
+            timeout_secs: None,
+            runtime: Runtime::JavaScript,
+            dry_run: true,
+        };
+
+        let mut progress = client
+            .execute_action_run(req)
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+
+        loop {
+            match progress.next().await {
+                None => break,
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(unexpected) => panic!("output stream should be done: {unexpected:?}"),
+            };
+        }
+        let result = progress.finish().await.expect("failed to return result");
+        match result {
+            FunctionResult::Success(success) => {
+                assert_eq!(Some(json!({ "create": ["bucket"] })), success.plan);
+                assert_eq!(None, success.payload);
+            }
+            FunctionResult::Failure(failure) => {
+                panic!("result should be success; failure={failure:?}")
+            }
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test)]
+    async fn http_execute_python_action_run() {
+        let (_, key) = gen_keys();
+        let mut builder = Config::builder();
+        builder
+            .enable_action_run(true)
+            .try_runtime_lang_server_path(Runtime::Python, python_lang_server_path())
+            .expect("failed to resolve python lang server path");
+        let mut client = http_client_for_running_server(&mut builder, key).await;
+
+        let req = ActionRunRequest {
+            execution_id: "1234".to_string(),
+            handler: "workit".to_string(),
+            args: json!({ "bucket": "poop" }),
+            code_base64: base64_encode(
+                "def workit(args):\n    print('first')\n    return {'status': 'ok', 'payload': args}\n",
+            ),
+            timeout_secs: None,
+            runtime: Runtime::Python,
+            dry_run: false,
+        };
+
+        let mut progress = client
+            .execute_action_run(req)
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+
+        loop {
+            match progress.next().await {
+                Some(Ok(ProgressMessage::OutputStream(output))) => {
+                    assert_eq!(output.message, "first");
+                    break;
+                }
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(Err(err)) => panic!("failed to receive 'first' output: err={err:?}"),
+                None => panic!("output stream ended early"),
+            };
+        }
+        loop {
+            match progress.next().await {
+                None => break,
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(unexpected) => panic!("output stream should be done: {unexpected:?}"),
+            };
+        }
+        let result = progress.finish().await.expect("failed to return result");
+        match result {
+            FunctionResult::Success(success) => {
+                assert_eq!("1234", success.execution_id);
+                assert_eq!(Some(json!({ "bucket": "poop" })), success.payload);
+            }
+            FunctionResult::Failure(failure) => {
+                panic!("result should be success; failure={failure:?}")
+            }
+        }
+    }
+
     #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
     #[test(tokio::test)]
     async fn http_execute_reconciliation() {
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{RedactionFlag, Runtime};
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionRunRequest {
@@ -7,6 +9,16 @@ pub struct ActionRunRequest {
     pub handler: String,
     pub code_base64: String,
     pub args: serde_json::Value,
+    /// Overrides the server's default execution timeout, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// The runtime the function is written for.
+    #[serde(default, skip_serializing_if = "Runtime::is_default")]
+    pub runtime: Runtime,
+    /// Asks the function to describe the changes it would make, as its result's `plan`, instead
+    /// of making them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
 }
 
 #[remain::sorted]
@@ -27,4 +39,20 @@ pub struct ActionRunResultSuccess {
     pub message: Option<String>,
     // Collects the error if the function throws
     pub error: Option<String>,
+    /// The changes the function would make, when it was run as a dry run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<serde_json::Value>,
+    /// Whether or not cyclone redacted sensitive values from the function's output or result.
+    #[serde(default)]
+    pub redacted: bool,
+}
+
+impl RedactionFlag for ActionRunResultSuccess {
+    fn redacted(&self) -> bool {
+        self.redacted
+    }
+
+    fn set_redacted(&mut self) {
+        self.redacted = true;
+    }
 }
@@ -21,6 +21,7 @@ mod progress;
 mod readiness;
 mod reconciliation;
 mod resolver_function;
+mod runtime;
 mod schema_variant_definition;
 mod sensitive_container;
 mod validation;
@@ -32,7 +33,7 @@ pub use encryption_key::{EncryptionKey, EncryptionKeyError};
 pub use liveness::{LivenessStatus, LivenessStatusParseError};
 pub use progress::{
     FunctionResult, FunctionResultFailure, FunctionResultFailureError, Message, OutputStream,
-    ProgressMessage,
+    ProgressMessage, RedactionFlag,
 };
 pub use readiness::{ReadinessStatus, ReadinessStatusParseError};
 pub use reconciliation::{ReconciliationRequest, ReconciliationResultSuccess};
@@ -40,6 +41,7 @@ pub use resolver_function::{
     ResolverFunctionComponent, ResolverFunctionRequest, ResolverFunctionResponseType,
     ResolverFunctionResultSuccess,
 };
+pub use runtime::{Runtime, RuntimeParseError};
 pub use schema_variant_definition::{
     SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess,
 };
@@ -95,6 +95,35 @@ pub enum FunctionResult<S> {
     Success(S),
 }
 
+/// Flags a function result whose output or result had sensitive values redacted from it before it
+/// left cyclone.
+pub trait RedactionFlag {
+    /// Whether or not anything was redacted.
+    fn redacted(&self) -> bool;
+
+    /// Marks the result as redacted.
+    fn set_redacted(&mut self);
+}
+
+impl<S> RedactionFlag for FunctionResult<S>
+where
+    S: RedactionFlag,
+{
+    fn redacted(&self) -> bool {
+        match self {
+            Self::Failure(failure) => failure.redacted(),
+            Self::Success(success) => success.redacted(),
+        }
+    }
+
+    fn set_redacted(&mut self) {
+        match self {
+            Self::Failure(failure) => failure.set_redacted(),
+            Self::Success(success) => success.set_redacted(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize, Clone)]
 pub struct FunctionResultFailure {
     pub execution_id: String,
@@ -102,6 +131,19 @@ pub struct FunctionResultFailure {
     // FIXME(nick,wendy): get the Utc::now() shape as well
     // (perhaps struct Foo { raw: Utc::now(), timestamp: crate::timestamp() } )
     pub timestamp: u64,
+    /// Whether or not cyclone redacted sensitive values from the function's output or result.
+    #[serde(default)]
+    pub redacted: bool,
+}
+
+impl RedactionFlag for FunctionResultFailure {
+    fn redacted(&self) -> bool {
+        self.redacted
+    }
+
+    fn set_redacted(&mut self) {
+        self.redacted = true;
+    }
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize, Clone)]
@@ -110,6 +152,18 @@ pub struct FunctionResultFailureError {
     pub message: String,
 }
 
+impl FunctionResultFailureError {
+    /// The kind of failure reported when a function runs past its execution timeout and is
+    /// killed.
+    pub const KIND_TIMEOUT: &'static str = "timeout";
+
+    /// Whether or not the function was killed for running past its execution timeout.
+    #[must_use]
+    pub fn is_timeout(&self) -> bool {
+        self.kind == Self::KIND_TIMEOUT
+    }
+}
+
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Fail {
     pub message: String,
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::RedactionFlag;
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReconciliationRequest {
@@ -17,4 +19,17 @@ pub struct ReconciliationResultSuccess {
     pub updates: HashMap<String, serde_json::Value>,
     pub actions: Vec<String>,
     pub message: Option<String>,
+    /// Whether or not cyclone redacted sensitive values from the function's output or result.
+    #[serde(default)]
+    pub redacted: bool,
+}
+
+impl RedactionFlag for ReconciliationResultSuccess {
+    fn redacted(&self) -> bool {
+        self.redacted
+    }
+
+    fn set_redacted(&mut self) {
+        self.redacted = true;
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::ComponentView;
+use crate::{ComponentView, RedactionFlag, Runtime};
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,6 +11,12 @@ pub struct ResolverFunctionRequest {
     pub component: ResolverFunctionComponent,
     pub response_type: ResolverFunctionResponseType,
     pub code_base64: String,
+    /// Overrides the server's default execution timeout, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// The runtime the function is written for.
+    #[serde(default, skip_serializing_if = "Runtime::is_default")]
+    pub runtime: Runtime,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
@@ -51,4 +57,17 @@ pub struct ResolverFunctionResultSuccess {
     pub data: Value,
     pub unset: bool,
     pub timestamp: u64,
+    /// Whether or not cyclone redacted sensitive values from the function's output or result.
+    #[serde(default)]
+    pub redacted: bool,
+}
+
+impl RedactionFlag for ResolverFunctionResultSuccess {
+    fn redacted(&self) -> bool {
+        self.redacted
+    }
+
+    fn set_redacted(&mut self) {
+        self.redacted = true;
+    }
 }
@@ -0,0 +1,58 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("failed to parse '{0}' into Runtime")]
+pub struct RuntimeParseError(String);
+
+/// The language runtime a function is written for.
+///
+/// Every runtime is served by its own lang server executable, which cyclone spawns once per
+/// execution with the function kind as its only argument (e.g. `actionRun`). The lang server reads
+/// the request as JSON on stdin and writes output and result messages as JSON lines on stdout.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    /// JavaScript and TypeScript, served by `lang-js`.
+    #[default]
+    JavaScript,
+    /// Python 3, served by `lang-py`.
+    Python,
+}
+
+impl Runtime {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::JavaScript => "javascript",
+            Self::Python => "python",
+        }
+    }
+
+    /// Whether or not this is the default runtime, so it can be left out of requests.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Runtime {
+    type Err = RuntimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "javascript" | "js" | "typescript" | "ts" => Ok(Self::JavaScript),
+            "python" | "py" => Ok(Self::Python),
+            invalid => Err(RuntimeParseError(invalid.to_string())),
+        }
+    }
+}
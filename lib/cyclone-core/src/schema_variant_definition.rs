@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::RedactionFlag;
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SchemaVariantDefinitionRequest {
@@ -13,4 +15,17 @@ pub struct SchemaVariantDefinitionRequest {
 pub struct SchemaVariantDefinitionResultSuccess {
     pub execution_id: String,
     pub definition: serde_json::Value,
+    /// Whether or not cyclone redacted sensitive values from the function's output or result.
+    #[serde(default)]
+    pub redacted: bool,
+}
+
+impl RedactionFlag for SchemaVariantDefinitionResultSuccess {
+    fn redacted(&self) -> bool {
+        self.redacted
+    }
+
+    fn set_redacted(&mut self) {
+        self.redacted = true;
+    }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::RedactionFlag;
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ValidationRequest {
@@ -15,4 +17,17 @@ pub struct ValidationResultSuccess {
     pub execution_id: String,
     pub valid: bool,
     pub message: Option<String>,
+    /// Whether or not cyclone redacted sensitive values from the function's output or result.
+    #[serde(default)]
+    pub redacted: bool,
+}
+
+impl RedactionFlag for ValidationResultSuccess {
+    fn redacted(&self) -> bool {
+        self.redacted
+    }
+
+    fn set_redacted(&mut self) {
+        self.redacted = true;
+    }
 }
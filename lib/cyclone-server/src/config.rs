@@ -1,9 +1,11 @@
 use std::{
+    collections::HashMap,
     net::{SocketAddr, ToSocketAddrs},
     path::{Path, PathBuf},
     time::Duration,
 };
 
+use cyclone_core::Runtime;
 use derive_builder::Builder;
 use si_settings::{CanonicalFile, CanonicalFileError};
 use thiserror::Error;
@@ -23,6 +25,9 @@ pub enum ConfigError {
 
 type Result<T> = std::result::Result<T, ConfigError>;
 
+/// The longest wall-clock time a request may ask for a function to run.
+pub const DEFAULT_EXECUTION_MAX_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
 #[derive(Debug, Builder)]
 pub struct Config {
     #[builder(default)]
@@ -49,11 +54,31 @@ pub struct Config {
     #[builder(default = "IncomingStream::default()")]
     incoming_stream: IncomingStream,
 
+    /// The lang server for the default runtime, JavaScript.
     #[builder(try_setter, setter(into))]
     lang_server_path: CanonicalFile,
 
+    /// Lang servers for the other runtimes.
+    #[builder(default, setter(custom))]
+    runtime_lang_server_paths: HashMap<Runtime, CanonicalFile>,
+
     #[builder(setter(into), default)]
     limit_requests: Option<u32>,
+
+    #[builder(setter(into), default)]
+    execution_timeout: Option<Duration>,
+
+    #[builder(default = "DEFAULT_EXECUTION_MAX_TIMEOUT")]
+    execution_max_timeout: Duration,
+
+    #[builder(setter(into), default)]
+    execution_memory_limit: Option<u64>,
+
+    #[builder(setter(into), default)]
+    execution_cpu_limit: Option<Duration>,
+
+    #[builder(setter(into, strip_option), default)]
+    execution_sandbox: Option<ExecutionSandbox>,
 }
 
 impl Config {
@@ -117,11 +142,77 @@ impl Config {
         self.lang_server_path.as_path()
     }
 
+    /// Gets the lang server path for the given runtime, if one is registered.
+    #[must_use]
+    pub fn lang_server_path_for(&self, runtime: Runtime) -> Option<&Path> {
+        if runtime == Runtime::default() {
+            return Some(self.lang_server_path());
+        }
+        self.runtime_lang_server_paths
+            .get(&runtime)
+            .map(CanonicalFile::as_path)
+    }
+
+    /// Gets the lang server path of every registered runtime.
+    #[must_use]
+    pub fn lang_server_paths(&self) -> HashMap<Runtime, PathBuf> {
+        let mut paths: HashMap<Runtime, PathBuf> = self
+            .runtime_lang_server_paths
+            .iter()
+            .map(|(runtime, path)| (*runtime, path.as_path().to_path_buf()))
+            .collect();
+        paths.insert(Runtime::default(), self.lang_server_path().to_path_buf());
+        paths
+    }
+
     /// Gets a reference to the config's limit requests.
     #[must_use]
     pub fn limit_requests(&self) -> Option<u32> {
         self.limit_requests
     }
+
+    /// Gets the config's default execution timeout, if any. Without one, executions only time out
+    /// if their request sets a timeout.
+    #[must_use]
+    pub fn execution_timeout(&self) -> Option<Duration> {
+        self.execution_timeout
+    }
+
+    /// Gets the config's maximum execution timeout, which caps the timeout a request can set.
+    #[must_use]
+    pub fn execution_max_timeout(&self) -> Duration {
+        self.execution_max_timeout
+    }
+
+    /// Gets the config's execution memory limit, which caps the data segment and private writable
+    /// mappings of each lang server process in bytes.
+    #[must_use]
+    pub fn execution_memory_limit(&self) -> Option<u64> {
+        self.execution_memory_limit
+    }
+
+    /// Gets the config's execution CPU limit, which caps the CPU time of each lang server process.
+    #[must_use]
+    pub fn execution_cpu_limit(&self) -> Option<Duration> {
+        self.execution_cpu_limit
+    }
+
+    /// Gets the config's execution sandbox, which every lang server process runs in if set.
+    #[must_use]
+    pub fn execution_sandbox(&self) -> Option<&ExecutionSandbox> {
+        self.execution_sandbox.as_ref()
+    }
+
+    /// Gets the limits applied to every function execution.
+    #[must_use]
+    pub fn execution_limits(&self) -> ExecutionLimits {
+        ExecutionLimits {
+            timeout: self.execution_timeout,
+            max_timeout: self.execution_max_timeout,
+            memory_bytes: self.execution_memory_limit,
+            cpu_time: self.execution_cpu_limit,
+        }
+    }
 }
 
 impl ConfigBuilder {
@@ -132,6 +223,96 @@ impl ConfigBuilder {
     pub fn unix_domain_socket(&mut self, path: impl Into<PathBuf>) -> &mut Self {
         self.incoming_stream(IncomingStream::unix_domain_socket(path))
     }
+
+    /// Registers the lang server for a runtime other than the default one, which is set with
+    /// [`lang_server_path`](Self::lang_server_path).
+    pub fn try_runtime_lang_server_path<P>(
+        &mut self,
+        runtime: Runtime,
+        path: P,
+    ) -> Result<&mut Self>
+    where
+        P: TryInto<CanonicalFile, Error = CanonicalFileError>,
+    {
+        if runtime == Runtime::default() {
+            return Ok(self.try_lang_server_path(path)?);
+        }
+        self.runtime_lang_server_paths
+            .get_or_insert_with(HashMap::new)
+            .insert(runtime, path.try_into()?);
+        Ok(self)
+    }
+}
+
+/// Limits applied to the lang server process of every function execution.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExecutionLimits {
+    /// The default wall-clock timeout, used when a request doesn't provide its own. Executions run
+    /// until they finish when neither sets one.
+    pub timeout: Option<Duration>,
+    /// The longest timeout a request can set. Longer ones are cut down to it.
+    pub max_timeout: Duration,
+    /// The maximum size of the process's data segment and private writable mappings
+    /// (`RLIMIT_DATA`), in bytes. Unlike the address space, this leaves out the large regions V8
+    /// reserves up front without using them.
+    pub memory_bytes: Option<u64>,
+    /// The maximum CPU time of the process (`RLIMIT_CPU`), rounded up to whole seconds.
+    pub cpu_time: Option<Duration>,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            max_timeout: DEFAULT_EXECUTION_MAX_TIMEOUT,
+            memory_bytes: None,
+            cpu_time: None,
+        }
+    }
+}
+
+/// The program used to build execution sandboxes unless another one is configured, which is looked
+/// up on `PATH`.
+pub const DEFAULT_SANDBOX_BWRAP_PATH: &str = "bwrap";
+
+/// Runs the lang server process of every function execution in a sandbox of fresh Linux
+/// namespaces, built with [bubblewrap](https://github.com/containers/bubblewrap).
+///
+/// Each sandbox has its own mount, pid, ipc and uts namespaces, sees the host filesystem
+/// read-only and gets an empty tmpfs as its working and temp directory. Unless its network is
+/// shared, it also has its own network namespace with only a loopback interface. The sandbox and
+/// everything written in it are torn down once the lang server process exits, so an execution
+/// can't read what another one left behind.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionSandbox {
+    /// The `bwrap` program.
+    pub bwrap_path: PathBuf,
+    /// The network the sandbox can reach.
+    pub network: SandboxNetwork,
+    /// Paths hidden from executions, such as the decryption key. Directories are replaced by an
+    /// empty tmpfs and files by an empty file.
+    pub hidden_paths: Vec<PathBuf>,
+}
+
+impl Default for ExecutionSandbox {
+    fn default() -> Self {
+        Self {
+            bwrap_path: PathBuf::from(DEFAULT_SANDBOX_BWRAP_PATH),
+            network: SandboxNetwork::default(),
+            hidden_paths: Vec::new(),
+        }
+    }
+}
+
+/// The network an [`ExecutionSandbox`] can reach.
+#[remain::sorted]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum SandboxNetwork {
+    /// Shares the host network.
+    Host,
+    /// Only a loopback interface.
+    #[default]
+    None,
 }
 
 #[remain::sorted]
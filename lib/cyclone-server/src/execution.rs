@@ -12,11 +12,11 @@ use bytes_lines_codec::BytesLinesCodec;
 use cyclone_core::{
     process::{self, ShutdownError},
     FunctionResult, FunctionResultFailure, FunctionResultFailureError, Message, OutputStream,
-    SensitiveString,
+    RedactionFlag, Runtime, SensitiveString,
 };
 use futures::{SinkExt, StreamExt, TryStreamExt};
+use nix::sys::resource::{setrlimit, Resource};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::Value;
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::{
@@ -27,23 +27,30 @@ use tokio_serde::{formats::SymmetricalJson, Deserializer, Framed, SymmetricallyF
 use tokio_util::codec::{Decoder, FramedRead, FramedWrite};
 
 use crate::{
-    request::{DecryptRequest, ListSecrets},
-    DecryptionKey, DecryptionKeyError, WebSocketMessage,
+    redact::Redactor,
+    request::{DecryptRequest, ListSecrets, RequestRuntime, RequestTimeout},
+    sandbox,
+    state::{LangServers, Sandbox},
+    DecryptionKey, DecryptionKeyError, ExecutionLimits, WebSocketMessage,
 };
 
 const TX_TIMEOUT_SECS: Duration = Duration::from_secs(5);
 
 pub fn new<Request, LangServerSuccess, Success>(
-    lang_server_path: impl Into<PathBuf>,
+    lang_servers: LangServers,
     lang_server_debugging: bool,
     key: Arc<DecryptionKey>,
     command: String,
+    limits: ExecutionLimits,
+    sandbox: Sandbox,
 ) -> Execution<Request, LangServerSuccess, Success> {
     Execution {
-        lang_server_path: lang_server_path.into(),
+        lang_servers,
         lang_server_debugging,
         key,
         command,
+        limits,
+        sandbox,
         request_marker: PhantomData,
         lang_server_success_marker: PhantomData,
         success_marker: PhantomData,
@@ -73,6 +80,8 @@ pub enum ExecutionError {
     SendTimeout(#[source] tokio::time::error::Elapsed),
     #[error("unexpected websocket message type: {0:?}")]
     UnexpectedMessageType(WebSocketMessage),
+    #[error("no lang server is registered for the {0} runtime")]
+    UnsupportedRuntime(Runtime),
     #[error("failed to close websocket")]
     WSClose(#[source] axum::Error),
     #[error("failed to receive websocket message--stream is closed")]
@@ -87,10 +96,12 @@ type Result<T> = std::result::Result<T, ExecutionError>;
 
 #[derive(Debug)]
 pub struct Execution<Request, LangServerSuccess, Success> {
-    lang_server_path: PathBuf,
+    lang_servers: LangServers,
     lang_server_debugging: bool,
     key: Arc<DecryptionKey>,
     command: String,
+    limits: ExecutionLimits,
+    sandbox: Sandbox,
     request_marker: PhantomData<Request>,
     lang_server_success_marker: PhantomData<LangServerSuccess>,
     success_marker: PhantomData<Success>,
@@ -98,7 +109,14 @@ pub struct Execution<Request, LangServerSuccess, Success> {
 
 impl<Request, LangServerSuccess, Success> Execution<Request, LangServerSuccess, Success>
 where
-    Request: DecryptRequest + ListSecrets + Serialize + DeserializeOwned + Unpin + core::fmt::Debug,
+    Request: DecryptRequest
+        + ListSecrets
+        + RequestRuntime
+        + RequestTimeout
+        + Serialize
+        + DeserializeOwned
+        + Unpin
+        + core::fmt::Debug,
     LangServerSuccess: DeserializeOwned,
     Success: Serialize,
 {
@@ -110,8 +128,25 @@ where
         Self::ws_send_start(ws).await?;
         // Now that the server said to start, I am going to read my message!
         let request = Self::read_request(ws).await?;
+        let execution_id = request.execution_id().to_owned();
+        let timeout = request.effective_timeout(self.limits.timeout, self.limits.max_timeout);
         let credentials: Vec<SensitiveString> = request.list_secrets(&self.key)?;
-        let mut command = Command::new(&self.lang_server_path);
+        let mut redactor = Redactor::new(&credentials);
+        if request.redact_sensitive_keys() {
+            redactor = redactor.with_sensitive_keys();
+        }
+        let redactor = Arc::new(redactor);
+        let runtime = request.runtime();
+        let lang_server_path = self
+            .lang_servers
+            .path_for(runtime)
+            .ok_or(ExecutionError::UnsupportedRuntime(runtime))?
+            .to_path_buf();
+        let mut command = match self.sandbox.get() {
+            Some(sandbox) => sandbox::command(sandbox, &lang_server_path),
+            None => Command::new(&lang_server_path),
+        };
+        let program = PathBuf::from(command.as_std().get_program());
         command
             .arg(&self.command)
             .stdin(Stdio::piped())
@@ -120,10 +155,11 @@ where
         if self.lang_server_debugging {
             command.env("DEBUG", "*").env("DEBUG_DEPTH", "5");
         }
+        Self::set_resource_limits(&mut command, self.limits);
         debug!(cmd = ?command, "spawning child process");
         let mut child = command
             .spawn()
-            .map_err(|err| ExecutionError::ChildSpawn(err, self.lang_server_path.clone()))?;
+            .map_err(|err| ExecutionError::ChildSpawn(err, program))?;
 
         let stdin = child.stdin.take().ok_or(ExecutionError::ChildIO("stdin"))?;
         Self::child_send_function_request(stdin, request, &self.key).await?;
@@ -149,11 +185,39 @@ where
             child,
             stdout,
             stderr,
-            credentials,
+            redactor,
+            execution_id,
+            timeout,
             success_marker: self.success_marker,
         })
     }
 
+    /// Caps the memory and CPU time of the spawned lang server process.
+    fn set_resource_limits(command: &mut Command, limits: ExecutionLimits) {
+        let memory_bytes = limits.memory_bytes;
+        let cpu_secs = limits
+            .cpu_time
+            .map(|cpu_time| cpu_time.as_secs() + u64::from(cpu_time.subsec_nanos() > 0));
+        if memory_bytes.is_none() && cpu_secs.is_none() {
+            return;
+        }
+
+        // SAFETY: the closure runs in the forked child before `exec` and only calls
+        // `setrlimit(2)`, which is async-signal-safe.
+        unsafe {
+            command.pre_exec(move || {
+                if let Some(bytes) = memory_bytes {
+                    setrlimit(Resource::RLIMIT_DATA, bytes, bytes)?;
+                }
+                if let Some(secs) = cpu_secs {
+                    // The soft limit sends `SIGXCPU`, the hard limit a second later `SIGKILL`
+                    setrlimit(Resource::RLIMIT_CPU, secs, secs + 1)?;
+                }
+                Ok(())
+            });
+        }
+    }
+
     async fn read_request(ws: &mut WebSocket) -> Result<Request> {
         let request = match ws.next().await {
             Some(Ok(WebSocketMessage::Text(json_str))) => {
@@ -211,127 +275,198 @@ pub struct ExecutionStarted<LangServerSuccess, Success> {
     child: Child,
     stdout: SiFramed<SiMessage<LangServerSuccess>>,
     stderr: FramedRead<ChildStderr, BytesLinesCodec>,
-    credentials: Vec<SensitiveString>,
+    redactor: Arc<Redactor>,
+    execution_id: String,
+    timeout: Option<Duration>,
     success_marker: PhantomData<Success>,
 }
 
 // TODO: implement shutdown oneshot
-async fn handle_stderr(
-    stderr: FramedRead<ChildStderr, BytesLinesCodec>,
-    credentials: Vec<SensitiveString>,
-) {
+async fn handle_stderr(stderr: FramedRead<ChildStderr, BytesLinesCodec>, redactor: Arc<Redactor>) {
     async fn handle_stderr_fallible(
         mut stderr: FramedRead<ChildStderr, BytesLinesCodec>,
-        credentials: Vec<SensitiveString>,
+        redactor: Arc<Redactor>,
     ) -> Result<()> {
+        // Each line is held until the next one arrives so a credential split across the two can
+        // be redacted from both
+        let mut pending: Option<String> = None;
         while let Some(line) = stderr.next().await {
             let line = line.map_err(ExecutionError::ChildRecvIO)?;
-            let mut line = String::from_utf8_lossy(line.as_ref());
-            for credential in &credentials {
-                // Note: This brings a possibility of random substrings being matched out of
-                // context, exposing that we have a secret by censoring it But trying to infer word
-                // boundary might leak the plaintext credential which is arguably worse
-                if line.contains(credential.as_str()) {
-                    line = line.replace(credential.as_str(), "[redacted]").into();
-                }
+            let mut line = String::from_utf8_lossy(line.as_ref()).into_owned();
+            redactor.redact_str(&mut line);
+            if let Some(mut previous) = pending.take() {
+                redactor.redact_split(&mut previous, &mut line);
+                eprintln!("{previous}");
             }
-            eprintln!("{line}");
+            pending = Some(line);
+        }
+        if let Some(previous) = pending {
+            eprintln!("{previous}");
         }
         Ok(())
     }
-    if let Err(error) = handle_stderr_fallible(stderr, credentials).await {
+    if let Err(error) = handle_stderr_fallible(stderr, redactor).await {
         error!("Unable to collect stderr: {}", error);
     }
 }
 
 impl<LangServerSuccess, Success> ExecutionStarted<LangServerSuccess, Success>
 where
-    Success: Serialize + Unpin + fmt::Debug,
+    Success: Serialize + Unpin + fmt::Debug + RedactionFlag,
     LangServerSuccess: Serialize + DeserializeOwned + Unpin + fmt::Debug + Into<Success>,
     SymmetricalJson<SiMessage<LangServerSuccess>>: Deserializer<SiMessage<LangServerSuccess>>,
     SiDecoderError: From<SiJsonError<LangServerSuccess>>,
 {
     pub async fn process(self, ws: &mut WebSocket) -> Result<ExecutionClosing<Success>> {
-        tokio::spawn(handle_stderr(self.stderr, self.credentials.clone()));
-
-        let mut stream = self
-            .stdout
-            .map(|ls_result| match ls_result {
-                Ok(ls_msg) => match ls_msg {
+        tokio::spawn(handle_stderr(self.stderr, self.redactor.clone()));
+
+        let redactor = self.redactor;
+        let execution_id = self.execution_id;
+        let mut stdout = self.stdout;
+        // Each output message is held until the next one arrives so a credential split across
+        // the two can be redacted from both
+        let mut pending: Option<LangServerOutput> = None;
+        let mut redactions: usize = 0;
+
+        let forward = async {
+            while let Some(ls_msg) = stdout
+                .try_next()
+                .await
+                .map_err(ExecutionError::ChildRecvIO)?
+            {
+                match ls_msg {
                     LangServerMessage::Output(mut output) => {
-                        Self::filter_output(&mut output, &self.credentials)?;
-                        Ok(Message::OutputStream(output.into()))
+                        if Self::filter_output(pending.as_mut(), &mut output, &redactor) {
+                            redactions += 1;
+                        }
+                        if let Some(previous) = pending.replace(output) {
+                            Self::ws_send_output(ws, previous).await?;
+                        }
                     }
                     LangServerMessage::Result(mut result) => {
-                        Self::filter_result(&mut result, &self.credentials)?;
-                        Ok(Message::Result(result.into()))
+                        if let Some(previous) = pending.take() {
+                            Self::ws_send_output(ws, previous).await?;
+                        }
+                        if Self::filter_result(&mut result, &redactor)? {
+                            redactions += 1;
+                        }
+                        let mut result: FunctionResult<Success> = result.into();
+                        if redactions > 0 {
+                            info!(
+                                execution_id = %execution_id,
+                                redactions,
+                                "redacted sensitive values from function execution",
+                            );
+                            result.set_redacted();
+                        }
+                        Self::ws_send(ws, Message::Result(result)).await?;
                     }
-                },
-                Err(err) => Err(ExecutionError::ChildRecvIO(err)),
-            })
-            .map(|msg_result: Result<_>| match msg_result {
-                Ok(msg) => match msg
-                    .serialize_to_string()
-                    .map_err(ExecutionError::JSONSerialize)
-                {
-                    Ok(json_str) => Ok(WebSocketMessage::Text(json_str)),
-                    Err(err) => Err(err),
-                },
-                Err(err) => Err(err),
-            });
+                }
+            }
+            if let Some(previous) = pending.take() {
+                Self::ws_send_output(ws, previous).await?;
+            }
+            Ok::<_, ExecutionError>(())
+        };
 
-        while let Some(msg) = stream.try_next().await? {
-            ws.send(msg).await.map_err(ExecutionError::WSSendIO)?;
+        let mut child = self.child;
+        let forwarded = match self.timeout {
+            Some(timeout) => time::timeout(timeout, forward)
+                .await
+                .map_err(|_elapsed| timeout),
+            None => Ok(forward.await),
+        };
+        match forwarded {
+            Ok(forwarded) => forwarded?,
+            Err(timeout) => {
+                warn!(
+                    execution_id = %execution_id,
+                    timeout = ?timeout,
+                    "function execution timed out, killing child process",
+                );
+                process::child_shutdown(&mut child, Some(process::Signal::SIGKILL), None).await?;
+                if let Some(previous) = pending.take() {
+                    Self::ws_send_output(ws, previous).await?;
+                }
+                Self::ws_send_timeout(ws, execution_id, timeout, redactions > 0).await?;
+            }
         }
 
         Ok(ExecutionClosing {
-            child: self.child,
+            child,
             success_marker: PhantomData,
         })
     }
 
-    fn filter_output(output: &mut LangServerOutput, credentials: &[SensitiveString]) -> Result<()> {
-        // Note: This brings a possibility of random substrings being matched out of context,
-        // exposing that we have a secret by censoring it But trying to infer word boundary might
-        // leak the plaintext credential which is arguably worse
-        for credential in credentials {
-            if output.message.contains(credential.as_str()) {
-                output.message = output.message.replace(credential.as_str(), "[redacted]");
-            }
-        }
+    async fn ws_send_output(ws: &mut WebSocket, output: LangServerOutput) -> Result<()> {
+        Self::ws_send(ws, Message::OutputStream(output.into())).await
+    }
+
+    async fn ws_send(ws: &mut WebSocket, msg: Message<Success>) -> Result<()> {
+        let msg = msg
+            .serialize_to_string()
+            .map_err(ExecutionError::JSONSerialize)?;
+        ws.send(WebSocketMessage::Text(msg))
+            .await
+            .map_err(ExecutionError::WSSendIO)
+    }
+
+    async fn ws_send_timeout(
+        ws: &mut WebSocket,
+        execution_id: String,
+        timeout: Duration,
+        redacted: bool,
+    ) -> Result<()> {
+        let msg = Message::<Success>::Result(FunctionResult::Failure(FunctionResultFailure {
+            execution_id,
+            error: FunctionResultFailureError {
+                kind: FunctionResultFailureError::KIND_TIMEOUT.to_owned(),
+                message: format!(
+                    "function execution timed out after {} seconds",
+                    timeout.as_secs()
+                ),
+            },
+            timestamp: crate::timestamp(),
+            redacted,
+        }))
+        .serialize_to_string()
+        .map_err(ExecutionError::JSONSerialize)?;
 
+        time::timeout(TX_TIMEOUT_SECS, ws.send(WebSocketMessage::Text(msg)))
+            .await
+            .map_err(ExecutionError::SendTimeout)?
+            .map_err(ExecutionError::WSSendIO)?;
         Ok(())
     }
 
+    /// Redacts credentials from an output message, including one split between it and the
+    /// output message before it. Returns whether or not anything was redacted.
+    fn filter_output(
+        previous: Option<&mut LangServerOutput>,
+        output: &mut LangServerOutput,
+        redactor: &Redactor,
+    ) -> bool {
+        let mut redacted = redactor.redact_str(&mut output.message);
+        if let Some(previous) = previous {
+            redacted |= redactor.redact_split(&mut previous.message, &mut output.message);
+        }
+        redacted
+    }
+
+    /// Redacts credentials, and the values of sensitive keys if the request asked for it, from a
+    /// result. Returns whether or not anything was redacted.
     fn filter_result(
         result: &mut LangServerResult<LangServerSuccess>,
-        credentials: &[SensitiveString],
-    ) -> Result<()> {
+        redactor: &Redactor,
+    ) -> Result<bool> {
         let mut value = serde_json::to_value(&result).map_err(ExecutionError::JSONSerialize)?;
-        // Note: This brings a possibility of random substrings being matched out of context,
-        // exposing that we have a secret by censoring it But trying to infer word boundary might
-        // leak the plaintext credential which is arguably worse
-        for credential in credentials {
-            let mut work_queue = vec![&mut value];
-            while let Some(work) = work_queue.pop() {
-                match work {
-                    Value::Array(values) => work_queue.extend(values),
-                    Value::Object(object) => object.values_mut().for_each(|v| work_queue.push(v)),
-                    Value::String(v) if v.contains(credential.as_str()) => {
-                        *v = v.replace(credential.as_str(), "[redacted]");
-                    }
-                    Value::String(_) => {}
-                    // For now credentials can only be strings, although we should reconsider it
-                    Value::Null => {}
-                    Value::Number(_) => {}
-                    Value::Bool(_) => {}
-                }
-            }
+        if !redactor.redact_value(&mut value) {
+            return Ok(false);
         }
         let mut filtered_result: LangServerResult<LangServerSuccess> =
             serde_json::from_value(value).map_err(ExecutionError::JSONDeserialize)?;
         std::mem::swap(result, &mut filtered_result);
-        Ok(())
+        Ok(true)
     }
 }
 
@@ -458,6 +593,7 @@ where
                     message: failure.error.message,
                 },
                 timestamp: crate::timestamp(),
+                redacted: false,
             }),
         }
     }
@@ -3,9 +3,11 @@ mod decryption_key;
 mod execution;
 mod extract;
 mod handlers;
+mod redact;
 mod request;
 mod result;
 mod routes;
+mod sandbox;
 mod server;
 mod state;
 mod timestamp;
@@ -14,7 +16,11 @@ mod uds;
 mod watch;
 
 pub use axum::extract::ws::Message as WebSocketMessage;
-pub use config::{Config, ConfigBuilder, ConfigError, IncomingStream};
+pub use config::{
+    Config, ConfigBuilder, ConfigError, ExecutionLimits, ExecutionSandbox, IncomingStream,
+    SandboxNetwork, DEFAULT_EXECUTION_MAX_TIMEOUT, DEFAULT_SANDBOX_BWRAP_PATH,
+};
+pub use cyclone_core::Runtime;
 pub use decryption_key::{DecryptionKey, DecryptionKeyError};
 pub use server::{Server, ShutdownSource};
 pub use timestamp::timestamp;
@@ -0,0 +1,339 @@
+//! Redaction of decrypted credentials from everything an execution sends back out of cyclone.
+//!
+//! A function can leak a credential without printing it verbatim, so besides the plaintext we
+//! look for the encodings a function is likely to produce (base64, URL-encoding and JSON string
+//! escaping) and for credentials split across consecutive lines of output. When asked to, values
+//! of keys that look sensitive are redacted from results whether or not they match a known
+//! credential.
+
+use base64::{engine::general_purpose, Engine};
+use cyclone_core::SensitiveString;
+use serde_json::Value;
+
+pub const REDACTED: &str = "[redacted]";
+
+/// Base64 fragments shorter than this are too likely to match unrelated output.
+const MIN_BASE64_FRAGMENT_LEN: usize = 4;
+
+/// Characters `encodeURIComponent` leaves as-is besides the RFC 3986 unreserved characters.
+const URI_COMPONENT_SAFE: &[u8] = b"!'()*";
+
+/// Keys whose string values are redacted from results when the [`Redactor`] is built
+/// [`with_sensitive_keys`](Redactor::with_sensitive_keys). Keys are compared by suffix,
+/// lowercased and with `_` and `-` removed, so `secretAccessKey` and `db_password` both match.
+const SENSITIVE_KEY_SUFFIXES: &[&str] = &[
+    "accesskey",
+    "apikey",
+    "authorization",
+    "credential",
+    "credentials",
+    "passphrase",
+    "passwd",
+    "password",
+    "privatekey",
+    "secret",
+    "token",
+];
+
+#[derive(Clone, Debug, Default)]
+pub struct Redactor {
+    /// Every form of every credential to look for, longest first so that an encoding containing
+    /// another is redacted whole.
+    patterns: Vec<SensitiveString>,
+    /// Whether or not the values of keys that look sensitive are redacted from values.
+    sensitive_keys: bool,
+}
+
+impl Redactor {
+    pub fn new(credentials: &[SensitiveString]) -> Self {
+        let mut patterns = Vec::new();
+        for credential in credentials {
+            let credential = credential.as_str();
+            if credential.is_empty() {
+                continue;
+            }
+            patterns.push(credential.to_owned());
+            patterns.extend(json_escape(credential));
+            patterns.push(url_encode(credential, URI_COMPONENT_SAFE, false));
+            patterns.push(url_encode(credential, b"", false));
+            patterns.push(url_encode(credential, b"", true));
+            patterns.extend(base64_fragments(credential.as_bytes()));
+        }
+        patterns.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        patterns.dedup();
+
+        Self {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+            sensitive_keys: false,
+        }
+    }
+
+    /// Also redacts the value of every key that looks sensitive from values, whether or not it
+    /// matches a known credential.
+    #[must_use]
+    pub fn with_sensitive_keys(mut self) -> Self {
+        self.sensitive_keys = true;
+        self
+    }
+
+    /// Redacts every credential from `text`, including credentials wrapped over its lines.
+    /// Returns whether or not anything was redacted.
+    pub fn redact_str(&self, text: &mut String) -> bool {
+        let mut redacted = false;
+        for pattern in &self.patterns {
+            // Note: This brings a possibility of random substrings being matched out of context,
+            // exposing that we have a secret by censoring it But trying to infer word boundary
+            // might leak the plaintext credential which is arguably worse
+            if text.contains(pattern.as_str()) {
+                *text = text.replace(pattern.as_str(), REDACTED);
+                redacted = true;
+            }
+        }
+        if text.contains('\n') {
+            redacted |= self.redact_lines(text);
+        }
+        redacted
+    }
+
+    /// Redacts a credential that starts at the end of `previous` and continues at the start of
+    /// `current`, as when a function prints a long credential wrapped over lines. Returns whether
+    /// or not anything was redacted.
+    pub fn redact_split(&self, previous: &mut String, current: &mut String) -> bool {
+        let mut redacted = false;
+        for pattern in &self.patterns {
+            let pattern = pattern.as_str();
+            let head_end = previous.trim_end().len();
+            let tail_start = current.len() - current.trim_start().len();
+            for (split, _) in pattern.char_indices().skip(1) {
+                let (head, tail) = pattern.split_at(split);
+                if previous[..head_end].ends_with(head) && current[tail_start..].starts_with(tail) {
+                    previous.replace_range(head_end - head.len()..head_end, REDACTED);
+                    current.replace_range(tail_start..tail_start + tail.len(), REDACTED);
+                    redacted = true;
+                    break;
+                }
+            }
+        }
+        redacted
+    }
+
+    /// Redacts every credential from the strings in `value` and, if built
+    /// [`with_sensitive_keys`](Self::with_sensitive_keys), the value of every key that looks
+    /// sensitive. Returns whether or not anything was redacted.
+    pub fn redact_value(&self, value: &mut Value) -> bool {
+        let mut redacted = false;
+        let mut work_queue = vec![value];
+        while let Some(work) = work_queue.pop() {
+            match work {
+                Value::Array(values) => work_queue.extend(values),
+                Value::Object(object) => {
+                    for (key, value) in object.iter_mut() {
+                        let sensitive = self.sensitive_keys
+                            && is_sensitive_key(key)
+                            && value
+                                .as_str()
+                                .map_or(false, |v| !v.is_empty() && v != REDACTED);
+                        if sensitive {
+                            *value = Value::String(REDACTED.to_owned());
+                            redacted = true;
+                        } else {
+                            work_queue.push(value);
+                        }
+                    }
+                }
+                Value::String(v) => redacted |= self.redact_str(v),
+                // For now credentials can only be strings, although we should reconsider it
+                Value::Null | Value::Number(_) | Value::Bool(_) => {}
+            }
+        }
+        redacted
+    }
+
+    fn redact_lines(&self, text: &mut String) -> bool {
+        let mut lines: Vec<String> = text.split('\n').map(ToOwned::to_owned).collect();
+        let mut redacted = false;
+        for index in 1..lines.len() {
+            let (before, after) = lines.split_at_mut(index);
+            redacted |= self.redact_split(&mut before[index - 1], &mut after[0]);
+        }
+        if redacted {
+            *text = lines.join("\n");
+        }
+        redacted
+    }
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key: String = key
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+    SENSITIVE_KEY_SUFFIXES
+        .iter()
+        .any(|suffix| key.ends_with(suffix))
+}
+
+/// The credential as it appears inside a JSON string.
+fn json_escape(credential: &str) -> Option<String> {
+    let quoted = serde_json::to_string(credential).ok()?;
+    quoted.get(1..quoted.len() - 1).map(ToOwned::to_owned)
+}
+
+/// Percent-encodes every byte of the credential except the RFC 3986 unreserved characters and
+/// `safe`, optionally encoding spaces as `+` as forms do.
+fn url_encode(credential: &str, safe: &[u8], space_as_plus: bool) -> String {
+    let mut encoded = String::with_capacity(credential.len());
+    for byte in credential.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(char::from(byte));
+            }
+            b' ' if space_as_plus => encoded.push('+'),
+            byte if safe.contains(&byte) => encoded.push(char::from(byte)),
+            byte => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// The parts of the base64 encoding of the credential that don't depend on what surrounds it.
+/// A credential encoded as part of a longer string starts at one of three offsets within a base64
+/// quantum, so for each offset we keep only the characters made entirely of credential bits.
+fn base64_fragments(credential: &[u8]) -> Vec<String> {
+    let mut fragments = Vec::new();
+    for offset in 0..3 {
+        let mut bytes = vec![0; offset];
+        bytes.extend_from_slice(credential);
+        let start = (offset * 8 + 5) / 6;
+        let end = bytes.len() * 8 / 6;
+        for engine in [
+            &general_purpose::STANDARD_NO_PAD,
+            &general_purpose::URL_SAFE_NO_PAD,
+        ] {
+            let encoded = engine.encode(&bytes);
+            if let Some(fragment) = encoded.get(start..end) {
+                if fragment.len() >= MIN_BASE64_FRAGMENT_LEN {
+                    fragments.push(fragment.to_owned());
+                }
+            }
+        }
+    }
+    fragments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "Varginha's UFO/crashed+1996";
+
+    fn redactor() -> Redactor {
+        Redactor::new(&[SECRET.to_owned().into()])
+    }
+
+    #[test]
+    fn redacts_plaintext() {
+        let mut text = format!("logging in with {SECRET}!");
+        assert!(redactor().redact_str(&mut text));
+        assert_eq!(text, "logging in with [redacted]!");
+    }
+
+    #[test]
+    fn redacts_base64_at_every_offset() {
+        for prefix in ["", "u:", "us:", "usr:"] {
+            for engine in [&general_purpose::STANDARD, &general_purpose::URL_SAFE] {
+                let encoded = engine.encode(format!("{prefix}{SECRET}"));
+                let mut text = format!("Authorization: Basic {encoded}");
+                assert!(redactor().redact_str(&mut text), "{prefix:?}");
+                assert!(text.contains(REDACTED));
+                // Only the encoded prefix and the quanta shared with it and the padding remain
+                let remaining = text.replace(REDACTED, "").len();
+                assert!(
+                    remaining <= "Authorization: Basic ".len() + 12,
+                    "{prefix:?}: {text}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn redacts_url_encoded_and_json_escaped() {
+        let redactor = redactor();
+
+        let mut text = "https://example.com/?key=Varginha%27s%20UFO%2Fcrashed%2B1996".to_owned();
+        assert!(redactor.redact_str(&mut text));
+        assert_eq!(text, "https://example.com/?key=[redacted]");
+
+        let mut text = "key=Varginha%27s+UFO%2Fcrashed%2B1996".to_owned();
+        assert!(redactor.redact_str(&mut text));
+        assert_eq!(text, "key=[redacted]");
+
+        let redactor = Redactor::new(&["quote\"back\\slash".to_owned().into()]);
+        let mut text = r#"{"value":"quote\"back\\slash"}"#.to_owned();
+        assert!(redactor.redact_str(&mut text));
+        assert_eq!(text, r#"{"value":"[redacted]"}"#);
+    }
+
+    #[test]
+    fn redacts_split_across_lines() {
+        let redactor = redactor();
+
+        let mut previous = "the secret is Varginha's U".to_owned();
+        let mut current = "FO/crashed+1996 and that's it".to_owned();
+        assert!(redactor.redact_split(&mut previous, &mut current));
+        assert_eq!(previous, "the secret is [redacted]");
+        assert_eq!(current, "[redacted] and that's it");
+
+        let mut text = "the secret is Varginha's U\nFO/crashed+1996".to_owned();
+        assert!(redactor.redact_str(&mut text));
+        assert_eq!(text, "the secret is [redacted]\n[redacted]");
+    }
+
+    #[test]
+    fn redacts_sensitive_keys() {
+        let mut value = serde_json::json!({
+            "username": "fletcher",
+            "db_password": "hunter2",
+            "auth": { "secretAccessKey": "abc", "accessKeyId": "AKIA" },
+            "tokens": [{ "apiToken": "xyz" }],
+            "note": format!("uses {SECRET}"),
+        });
+        assert!(redactor().with_sensitive_keys().redact_value(&mut value));
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "username": "fletcher",
+                "db_password": "[redacted]",
+                "auth": { "secretAccessKey": "[redacted]", "accessKeyId": "AKIA" },
+                "tokens": [{ "apiToken": "[redacted]" }],
+                "note": "uses [redacted]",
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_sensitive_keys_alone_by_default() {
+        let mut value = serde_json::json!({
+            "tokenName": "deploy",
+            "password": "user data",
+            "note": format!("uses {SECRET}"),
+        });
+        assert!(redactor().redact_value(&mut value));
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "tokenName": "deploy",
+                "password": "user data",
+                "note": "uses [redacted]",
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_unrelated_output_alone() {
+        let mut text = "nothing to see here".to_owned();
+        assert!(!redactor().redact_str(&mut text));
+        assert_eq!(text, "nothing to see here");
+    }
+}
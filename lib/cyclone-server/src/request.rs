@@ -1,20 +1,107 @@
 use cyclone_core::{
     ActionRunRequest, ComponentKind, ComponentView, ReconciliationRequest, ResolverFunctionRequest,
-    SchemaVariantDefinitionRequest, SensitiveString, ValidationRequest,
+    Runtime, SchemaVariantDefinitionRequest, SensitiveString, ValidationRequest,
 };
 use serde_json::Value;
+use std::time::Duration;
 
 use crate::{DecryptionKey, DecryptionKeyError};
 
 pub trait ListSecrets {
     fn list_secrets(&self, key: &DecryptionKey)
         -> Result<Vec<SensitiveString>, DecryptionKeyError>;
+
+    /// Whether or not the values of keys that look sensitive are redacted from the result even
+    /// when they don't match a known credential. Off by default, since the results of most
+    /// functions are user data, where a `password` prop is just a value.
+    fn redact_sensitive_keys(&self) -> bool {
+        false
+    }
 }
 
 pub trait DecryptRequest {
     fn decrypt_request(self, key: &DecryptionKey) -> Result<serde_json::Value, DecryptionKeyError>;
 }
 
+pub trait RequestTimeout {
+    /// The execution id to report a timeout failure against.
+    fn execution_id(&self) -> &str;
+
+    /// Overrides the server's default execution timeout for this request.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// The timeout to run this request with: its own or else `default`, but never more than `max`
+    /// however long a client asks for. Without either, the request runs until it finishes.
+    fn effective_timeout(&self, default: Option<Duration>, max: Duration) -> Option<Duration> {
+        self.timeout().or(default).map(|timeout| timeout.min(max))
+    }
+}
+
+impl RequestTimeout for ResolverFunctionRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+}
+
+impl RequestTimeout for ActionRunRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+}
+
+impl RequestTimeout for ReconciliationRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+}
+
+impl RequestTimeout for ValidationRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+}
+
+impl RequestTimeout for SchemaVariantDefinitionRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+}
+
+pub trait RequestRuntime {
+    /// The runtime whose lang server executes this request.
+    fn runtime(&self) -> Runtime {
+        Runtime::default()
+    }
+}
+
+impl RequestRuntime for ResolverFunctionRequest {
+    fn runtime(&self) -> Runtime {
+        self.runtime
+    }
+}
+
+impl RequestRuntime for ActionRunRequest {
+    fn runtime(&self) -> Runtime {
+        self.runtime
+    }
+}
+
+impl RequestRuntime for ReconciliationRequest {}
+
+impl RequestRuntime for ValidationRequest {}
+
+impl RequestRuntime for SchemaVariantDefinitionRequest {}
+
 impl ListSecrets for ComponentView {
     fn list_secrets(
         &self,
@@ -48,7 +135,18 @@ impl ListSecrets for ComponentView {
                             .as_str()
                             .ok_or(DecryptionKeyError::EncryptedSecretNotFound)?;
                         let decrypted = key.decode_and_decrypt(encoded)?;
-                        secret_objects.push(serde_json::de::from_slice::<Value>(&decrypted)?);
+                        let mut secret_object = serde_json::de::from_slice::<Value>(&decrypted)?;
+                        // When the secret's definition says which of its fields are sensitive,
+                        // the rest (a username, a region) are left out and so never redacted
+                        if let (Some(sensitive_fields), Some(fields)) = (
+                            object.get("sensitiveFields").and_then(Value::as_array),
+                            secret_object.as_object_mut(),
+                        ) {
+                            fields.retain(|name, _| {
+                                sensitive_fields.iter().any(|v| v.as_str() == Some(name))
+                            });
+                        }
+                        secret_objects.push(secret_object);
                     } else {
                         object.into_iter().for_each(|(_, v)| work_queue.push(v));
                     }
@@ -186,6 +284,12 @@ impl ListSecrets for ActionRunRequest {
         // TODO(fnichol): we'll need to populate/consume secrets here shortly
         Ok(vec![])
     }
+
+    /// An action's payload is whatever the cloud provider sent back, which can hold freshly
+    /// issued credentials we were never told about.
+    fn redact_sensitive_keys(&self) -> bool {
+        true
+    }
 }
 
 impl DecryptRequest for ActionRunRequest {
@@ -277,6 +381,37 @@ mod tests {
         sodiumoxide::crypto::box_::gen_keypair()
     }
 
+    struct TimeoutRequest(Option<u64>);
+
+    impl RequestTimeout for TimeoutRequest {
+        fn execution_id(&self) -> &str {
+            "timeout"
+        }
+
+        fn timeout(&self) -> Option<Duration> {
+            self.0.map(Duration::from_secs)
+        }
+    }
+
+    #[test]
+    fn effective_timeout_is_capped() {
+        let (default, max) = (Duration::from_secs(600), Duration::from_secs(3600));
+
+        assert_eq!(None, TimeoutRequest(None).effective_timeout(None, max));
+        assert_eq!(
+            Some(default),
+            TimeoutRequest(None).effective_timeout(Some(default), max)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(5)),
+            TimeoutRequest(Some(5)).effective_timeout(Some(default), max)
+        );
+        assert_eq!(
+            Some(max),
+            TimeoutRequest(Some(u64::MAX)).effective_timeout(None, max)
+        );
+    }
+
     #[test]
     fn redact() {
         let (pkey, skey) = gen_keypair();
@@ -311,6 +446,41 @@ mod tests {
         assert_eq!(secrets[0].as_str(), "Varginha's UFO");
     }
 
+    #[test]
+    fn redact_only_sensitive_fields() {
+        let (pkey, skey) = gen_keypair();
+        let decryption_key = DecryptionKey::from(skey);
+
+        let secret = serde_json::to_string(&serde_json::json!({
+            "username": "fletcher",
+            "password": "Varginha's UFO",
+        }))
+        .expect("Unable to serialize secret");
+        let encoded = encrypt_and_encode(secret.as_bytes(), &pkey);
+
+        let secrets = ComponentView {
+            kind: ComponentKind::Credential,
+            properties: serde_json::json!({
+                "secret": {
+                    "name": "ufo",
+                    "secret_kind": "dockerHub",
+                    "object_type": "credential",
+                    "message": {
+                        "cycloneEncryptedDataMarker": true,
+                        "encryptedSecret": encoded,
+                        "sensitiveFields": ["password"],
+                    },
+                },
+            }),
+        }
+        .list_secrets(&decryption_key)
+        .expect("Unable to list secrets");
+        assert_eq!(
+            vec!["Varginha's UFO"],
+            secrets.iter().map(|s| s.as_str()).collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn decrypt() {
         let (pkey, skey) = gen_keypair();
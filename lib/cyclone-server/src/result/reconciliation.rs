@@ -21,6 +21,7 @@ impl From<LangServerReconciliationResultSuccess> for ReconciliationResultSuccess
             updates: value.updates,
             actions: value.actions,
             message: value.message,
+            redacted: false,
         }
     }
 }
@@ -18,6 +18,7 @@ impl From<LangServerResolverFunctionResultSuccess> for ResolverFunctionResultSuc
             data: value.data,
             unset: value.unset,
             timestamp: crate::timestamp(),
+            redacted: false,
         }
     }
 }
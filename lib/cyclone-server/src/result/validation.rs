@@ -15,6 +15,7 @@ impl From<LangServerValidationResultSuccess> for ValidationResultSuccess {
             execution_id: value.execution_id,
             valid: value.valid,
             message: value.message,
+            redacted: false,
         }
     }
 }
@@ -0,0 +1,116 @@
+//! Builds the commands that run lang servers inside an [`ExecutionSandbox`].
+
+use std::{ffi::OsString, path::Path};
+
+use tokio::process::Command;
+
+use crate::{ExecutionSandbox, SandboxNetwork};
+
+/// The scratch directory of a sandbox, which is its working directory, `HOME` and `TMPDIR`.
+const SCRATCH_DIR: &str = "/tmp";
+
+/// Builds a command that runs the program in a new sandbox. Arguments added to the command are
+/// passed to the program.
+pub fn command(sandbox: &ExecutionSandbox, program: &Path) -> Command {
+    let mut command = Command::new(&sandbox.bwrap_path);
+    command.args(args(sandbox)).arg("--").arg(program);
+    command
+}
+
+fn args(sandbox: &ExecutionSandbox) -> Vec<OsString> {
+    let mut args: Vec<OsString> = ["--unshare-all", "--die-with-parent", "--new-session"]
+        .map(OsString::from)
+        .into();
+    if sandbox.network != SandboxNetwork::None {
+        args.push("--share-net".into());
+    }
+    args.extend(
+        [
+            "--ro-bind",
+            "/",
+            "/",
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+            "--tmpfs",
+            SCRATCH_DIR,
+            "--chdir",
+            SCRATCH_DIR,
+            "--setenv",
+            "HOME",
+            SCRATCH_DIR,
+            "--setenv",
+            "TMPDIR",
+            SCRATCH_DIR,
+        ]
+        .map(OsString::from),
+    );
+    for path in &sandbox.hidden_paths {
+        if path.is_dir() {
+            args.extend(["--tmpfs".into(), path.into()]);
+        } else if path.exists() {
+            args.extend(["--ro-bind".into(), "/dev/null".into(), path.into()]);
+        }
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn has_args(args: &[OsString], expected: &[&str]) -> bool {
+        args.windows(expected.len())
+            .any(|window| window.iter().zip(expected).all(|(arg, e)| arg == *e))
+    }
+
+    #[test]
+    fn isolates_network_by_default() {
+        let args = args(&ExecutionSandbox::default());
+
+        assert!(has_args(&args, &["--unshare-all"]));
+        assert!(!has_args(&args, &["--share-net"]));
+        assert!(has_args(&args, &["--ro-bind", "/", "/"]));
+        assert!(has_args(&args, &["--tmpfs", SCRATCH_DIR]));
+    }
+
+    #[test]
+    fn hides_paths() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file = dir.path().join("cyclone.key");
+        std::fs::write(&file, "secret").expect("failed to write file");
+        let sandbox = ExecutionSandbox {
+            hidden_paths: vec![
+                dir.path().to_path_buf(),
+                file.clone(),
+                PathBuf::from("/does/not/exist"),
+            ],
+            ..Default::default()
+        };
+        let args = args(&sandbox);
+
+        let dir = dir.path().to_str().expect("non-utf8 path");
+        let file = file.to_str().expect("non-utf8 path");
+        assert!(has_args(&args, &["--tmpfs", dir]));
+        assert!(has_args(&args, &["--ro-bind", "/dev/null", file]));
+        assert!(!has_args(&args, &["/does/not/exist"]));
+    }
+
+    #[test]
+    fn shares_host_network() {
+        let sandbox = ExecutionSandbox {
+            network: SandboxNetwork::Host,
+            ..Default::default()
+        };
+        let command = command(&sandbox, Path::new("/usr/local/bin/lang-js"));
+        let args: Vec<OsString> = command.as_std().get_args().map(Into::into).collect();
+
+        assert!(has_args(&args, &["--unshare-all"]));
+        assert!(has_args(&args, &["--share-net"]));
+        assert!(args.ends_with(&["--".into(), "/usr/local/bin/lang-js".into()]));
+    }
+}
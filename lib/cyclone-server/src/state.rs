@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     ops::Deref,
     path::{Path, PathBuf},
     sync::Arc,
@@ -6,35 +7,55 @@ use std::{
 };
 
 use axum::extract::FromRef;
+use cyclone_core::Runtime;
 use tokio::sync::mpsc;
 
+use crate::{ExecutionLimits, ExecutionSandbox};
+
 #[derive(Clone, FromRef)]
 pub struct AppState {
-    lang_server_path: LangServerPath,
+    lang_servers: LangServers,
     decryption_key: DecryptionKey,
     telemetry_level: TelemetryLevel,
+    execution_limits: ExecutionLimits,
+    sandbox: Sandbox,
 }
 
 impl AppState {
     pub fn new(
-        lang_server_path: impl Into<PathBuf>,
+        lang_server_paths: HashMap<Runtime, PathBuf>,
         decryption_key: crate::DecryptionKey,
         telemetry_level: Box<dyn telemetry::TelemetryLevel>,
+        execution_limits: ExecutionLimits,
+        execution_sandbox: Option<ExecutionSandbox>,
     ) -> Self {
         Self {
-            lang_server_path: LangServerPath(Arc::new(lang_server_path.into())),
+            lang_servers: LangServers(Arc::new(lang_server_paths)),
             decryption_key: DecryptionKey(Arc::new(decryption_key)),
             telemetry_level: TelemetryLevel(Arc::new(telemetry_level)),
+            execution_limits,
+            sandbox: Sandbox(execution_sandbox.map(Arc::new)),
         }
     }
 }
 
+/// The lang server program registered for each [`Runtime`].
+#[derive(Clone, Debug, FromRef)]
+pub struct LangServers(Arc<HashMap<Runtime, PathBuf>>);
+
+impl LangServers {
+    pub fn path_for(&self, runtime: Runtime) -> Option<&Path> {
+        self.0.get(&runtime).map(PathBuf::as_path)
+    }
+}
+
+/// The sandbox every lang server process runs in, if executions are sandboxed.
 #[derive(Clone, Debug, FromRef)]
-pub struct LangServerPath(Arc<PathBuf>);
+pub struct Sandbox(Option<Arc<ExecutionSandbox>>);
 
-impl LangServerPath {
-    pub fn as_path(&self) -> &Path {
-        self.0.as_path()
+impl Sandbox {
+    pub fn get(&self) -> Option<&ExecutionSandbox> {
+        self.0.as_deref()
     }
 }
 
@@ -187,7 +187,7 @@ pub async fn create_secret(ctx: &DalContext, key_pair_pk: KeyPairPk) -> Secret {
         ctx,
         &name,
         SecretObjectType::Credential,
-        SecretKind::DockerHub,
+        SecretKind::DOCKER_HUB,
         &encrypt_message(ctx, key_pair_pk, &serde_json::json!({ "name": name })).await,
         key_pair_pk,
         Default::default(),
@@ -207,7 +207,7 @@ pub async fn create_secret_with_message(
         ctx,
         &name,
         SecretObjectType::Credential,
-        SecretKind::DockerHub,
+        SecretKind::DOCKER_HUB,
         &encrypt_message(ctx, key_pair_pk, message).await,
         key_pair_pk,
         Default::default(),
@@ -8,13 +8,17 @@ use si_data_nats::NatsError;
 use si_data_pg::PgError;
 use si_pkg::ActionFuncSpecKind;
 use telemetry::prelude::*;
+use veritech_client::{FunctionResult, ResourceStatus};
 
 use crate::{
-    component::view::ComponentViewError, func::backend::js_action::ActionRunResult,
+    component::view::ComponentViewError,
+    func::backend::js_action::{ActionRunResult, FuncBackendJsAction},
+    func::backend::{FuncDispatch, FuncDispatchContext},
+    func::execution::FuncExecutionSubject,
     impl_standard_model, pk, standard_model, standard_model_accessor, Component, ComponentId,
-    ComponentView, DalContext, FuncBinding, FuncBindingError, FuncBindingReturnValueError, FuncId,
-    HistoryEventError, SchemaVariantId, StandardModel, StandardModelError, Tenancy, Timestamp,
-    TransactionsError, Visibility, WsEvent, WsEventError,
+    ComponentView, DalContext, FixId, Func, FuncBackendError, FuncBinding, FuncBindingError,
+    FuncBindingReturnValueError, FuncId, HistoryEventError, SchemaVariantId, StandardModel,
+    StandardModelError, Tenancy, Timestamp, TransactionsError, Visibility, WsEvent, WsEventError,
 };
 
 const FIND_FOR_CONTEXT: &str = include_str!("./queries/action_prototype/find_for_context.sql");
@@ -34,6 +38,8 @@ pub enum ActionPrototypeError {
     #[error(transparent)]
     ComponentView(#[from] ComponentViewError),
     #[error(transparent)]
+    FuncBackend(#[from] FuncBackendError),
+    #[error(transparent)]
     FuncBinding(#[from] FuncBindingError),
     #[error(transparent)]
     FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
@@ -106,6 +112,20 @@ impl From<&ActionKind> for ActionFuncSpecKind {
     }
 }
 
+/// What running an [`ActionPrototype`] would do, as described by its [`Func`] in a dry run.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPlan {
+    /// Whether or not the [`Func`] supports dry runs. When it doesn't, it isn't invoked and
+    /// nothing else is populated.
+    pub supported: bool,
+    pub status: Option<ResourceStatus>,
+    /// The changes the action would make, in the shape its [`Func`] describes them.
+    pub changes: Option<serde_json::Value>,
+    pub message: Option<String>,
+    pub logs: Vec<String>,
+}
+
 // Hrm - is this a universal resolver context? -- Adam
 impl Default for ActionPrototypeContext {
     fn default() -> Self {
@@ -302,17 +322,21 @@ impl ActionPrototype {
         context
     }
 
+    /// Runs the action for the [`Component`]. If the action runs as part of a
+    /// [`Fix`](crate::Fix), its execution is recorded against the [`FixId`] as well.
     pub async fn run(
         &self,
         ctx: &DalContext,
         component_id: ComponentId,
+        fix_id: Option<FixId>,
         trigger_dependent_values_update: bool,
     ) -> ActionPrototypeResult<Option<ActionRunResult>> {
         let component_view = ComponentView::new(ctx, component_id).await?;
-        let (_, return_value) = FuncBinding::create_and_execute(
+        let (_, return_value) = FuncBinding::create_and_execute_for(
             ctx,
             serde_json::to_value(component_view)?,
             self.func_id(),
+            FuncExecutionSubject::for_component(component_id).with_fix(fix_id),
         )
         .await?;
 
@@ -360,4 +384,54 @@ impl ActionPrototype {
             None => None,
         })
     }
+
+    /// Asks the action's [`Func`] what running it for the [`Component`] would do, without
+    /// running it. Nothing is recorded and the [`Component`]'s resource is left as-is.
+    pub async fn plan(
+        &self,
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ActionPrototypeResult<ActionPlan> {
+        let func = Func::get_by_id(ctx, &self.func_id)
+            .await?
+            .ok_or(ActionPrototypeError::FuncNotFound(self.func_id, self.id))?;
+        if !func.supports_dry_run() {
+            return Ok(ActionPlan::default());
+        }
+
+        let component_view = ComponentView::new(ctx, component_id).await?;
+        let (context, mut rx) = FuncDispatchContext::new(ctx);
+        let mut executor =
+            FuncBackendJsAction::create(context, &func, &serde_json::to_value(component_view)?)?;
+        executor.request.dry_run = true;
+        let result = executor.dispatch().await;
+
+        let mut logs = Vec::new();
+        while let Some(output) = rx.recv().await {
+            logs.push(output);
+        }
+        logs.sort_by_key(|log| log.timestamp);
+        let logs = logs.into_iter().map(|log| log.message).collect();
+
+        Ok(match result {
+            Ok(FunctionResult::Success(success)) => ActionPlan {
+                supported: true,
+                status: Some(success.status),
+                changes: success.plan,
+                message: success.message.or(success.error),
+                logs,
+            },
+            Ok(FunctionResult::Failure(failure))
+            | Err(FuncBackendError::FunctionResultActionRun(FunctionResult::Failure(failure))) => {
+                ActionPlan {
+                    supported: true,
+                    status: Some(ResourceStatus::Error),
+                    changes: None,
+                    message: Some(failure.error.message),
+                    logs,
+                }
+            }
+            Err(err) => return Err(err.into()),
+        })
+    }
 }
@@ -57,6 +57,7 @@ use crate::{
         binding_return_value::{
             FuncBindingReturnValue, FuncBindingReturnValueError, FuncBindingReturnValueId,
         },
+        execution::FuncExecutionSubject,
     },
     impl_standard_model,
     job::definition::DependentValuesUpdate,
@@ -753,6 +754,7 @@ impl AttributeValue {
     ) -> AttributeValueResult<(Option<serde_json::Value>, AttributeValueId)> {
         // TODO(nick,paulo,zack,jacob): ensure we do not _have_ to do this in the future.
         let ctx = &ctx.clone_without_deleted_visibility();
+        standard_model::require_writable(ctx).await?;
 
         let row = ctx.txns()
             .await?
@@ -1106,32 +1108,34 @@ impl AttributeValue {
         }
 
         let func_id = attribute_prototype.func_id();
-        let (func_binding, mut func_binding_return_value) = match FuncBinding::create_and_execute(
-            ctx,
-            serde_json::to_value(func_binding_args.clone())?,
-            attribute_prototype.func_id(),
-        )
-        .instrument(debug_span!(
-            "Func execution",
-            "func.id" = %func_id,
-            ?func_binding_args,
-        ))
-        .await
-        {
-            Ok(function_return_value) => function_return_value,
-            Err(FuncBindingError::FuncBackendResultFailure {
-                kind,
-                message,
-                backend,
-            }) => {
-                return Err(AttributeValueError::FuncBackendResultFailure {
+        let (func_binding, mut func_binding_return_value) =
+            match FuncBinding::create_and_execute_for(
+                ctx,
+                serde_json::to_value(func_binding_args.clone())?,
+                attribute_prototype.func_id(),
+                FuncExecutionSubject::for_component(self.context.component_id()),
+            )
+            .instrument(debug_span!(
+                "Func execution",
+                "func.id" = %func_id,
+                ?func_binding_args,
+            ))
+            .await
+            {
+                Ok(function_return_value) => function_return_value,
+                Err(FuncBindingError::FuncBackendResultFailure {
                     kind,
                     message,
                     backend,
-                })
-            }
-            Err(err) => Err(err)?,
-        };
+                }) => {
+                    return Err(AttributeValueError::FuncBackendResultFailure {
+                        kind,
+                        message,
+                        backend,
+                    })
+                }
+                Err(err) => Err(err)?,
+            };
 
         self.set_func_binding_id(ctx, *func_binding.id()).await?;
         self.set_func_binding_return_value_id(ctx, *func_binding_return_value.id())
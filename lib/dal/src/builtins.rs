@@ -23,7 +23,7 @@ use crate::{
     AttributeContextBuilderError, AttributePrototypeArgumentError, AttributePrototypeError,
     AttributeReadContext, AttributeValueError, AttributeValueId, DalContext, ExternalProviderId,
     FuncError, InternalProviderId, PropError, PropId, SchemaError, SchemaVariantId,
-    StandardModelError, TransactionsError, ValidationPrototypeError,
+    SecretDefinition, SecretError, StandardModelError, TransactionsError, ValidationPrototypeError,
 };
 
 // Private builtins modules.
@@ -106,6 +106,8 @@ pub enum BuiltinsError {
     SchemaVariant(#[from] SchemaVariantError),
     #[error("schema variant definition error")]
     SchemaVariantDefinition(#[from] SchemaVariantDefinitionError),
+    #[error("secret error: {0}")]
+    Secret(#[from] SecretError),
     #[error("serde json error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("encountered serde json error for func ({0}): {1}")]
@@ -145,6 +147,7 @@ pub enum SelectedTestBuiltinSchemas {
 /// Migrate all "builtins" in a definitive order.
 ///
 /// 1. [`Funcs`](crate::Func)
+/// 1. [`SecretDefinitions`](crate::SecretDefinition)
 /// 1. [`Schemas`](crate::Schema)
 /// 1. ['ActionPrototypes'](crate::ActionPrototype)
 pub async fn migrate(
@@ -155,6 +158,8 @@ pub async fn migrate(
     func::migrate_intrinsics(ctx).await?;
     info!("migrating builtin functions");
     func::migrate(ctx).await?;
+    info!("migrating builtin secret definitions");
+    SecretDefinition::create_builtins(ctx).await?;
 
     match selected_test_builtin_schemas {
         Some(found_selected_test_builtin_schemas) => {
@@ -27,6 +27,8 @@ struct FunctionMetadata {
     arguments: Option<Vec<FunctionMetadataArgument>>,
     response_type: FuncBackendResponseType,
     hidden: Option<bool>,
+    deterministic: Option<bool>,
+    supports_dry_run: Option<bool>,
     display_name: Option<String>,
     description: Option<String>,
     link: Option<String>,
@@ -175,6 +177,14 @@ pub async fn migrate(ctx: &DalContext) -> BuiltinsResult<()> {
             .set_hidden(ctx, func_metadata.hidden.unwrap_or(false))
             .await
             .expect("cannot set func hidden");
+        new_func
+            .set_deterministic(ctx, func_metadata.deterministic.unwrap_or(false))
+            .await
+            .expect("cannot set func deterministic");
+        new_func
+            .set_supports_dry_run(ctx, func_metadata.supports_dry_run.unwrap_or(false))
+            .await
+            .expect("cannot set func supports dry run");
         new_func
             .set_builtin(ctx, true)
             .await
@@ -51,6 +51,9 @@ impl MigrationDriver {
             .build()?;
 
         let fallout_create_action_code = "async function create() {
+                if (dryRun) {
+                    return { plan: { create: { \"poop\": true } }, status: \"ok\" };
+                }
                 return { payload: { \"poop\": true }, status: \"ok\" };
             }";
         let fallout_create_action_func = FuncSpec::builder()
@@ -59,6 +62,7 @@ impl MigrationDriver {
             .handler("create")
             .backend_kind(FuncSpecBackendKind::JsAction)
             .response_type(FuncSpecBackendResponseType::Action)
+            .supports_dry_run(true)
             .build()?;
 
         let fallout_scaffold_func = "function createAsset() {\
@@ -82,6 +86,7 @@ impl MigrationDriver {
             .handler("translate")
             .backend_kind(FuncSpecBackendKind::JsAttribute)
             .response_type(FuncSpecBackendResponseType::Json)
+            .deterministic(true)
             .argument(
                 FuncArgumentSpec::builder()
                     .name("payload")
@@ -52,6 +52,9 @@ impl MigrationDriver {
             .build()?;
 
         let starfield_create_action_code = "async function create() {
+                if (dryRun) {
+                    return { plan: { create: { \"poop\": true } }, status: \"ok\" };
+                }
                 return { payload: { \"poop\": true }, status: \"ok\" };
             }";
         let starfield_create_action_func = FuncSpec::builder()
@@ -60,6 +63,7 @@ impl MigrationDriver {
             .handler("create")
             .backend_kind(FuncSpecBackendKind::JsAction)
             .response_type(FuncSpecBackendResponseType::Action)
+            .supports_dry_run(true)
             .build()?;
 
         let starfield_refresh_action_code =
@@ -105,6 +109,7 @@ impl MigrationDriver {
             .handler("falloutEntriesToGalaxies")
             .backend_kind(FuncSpecBackendKind::JsAttribute)
             .response_type(FuncSpecBackendResponseType::Array)
+            .deterministic(true)
             .argument(
                 FuncArgumentSpec::builder()
                     .name("entries")
@@ -135,6 +140,7 @@ impl MigrationDriver {
             .handler("translate")
             .backend_kind(FuncSpecBackendKind::JsAttribute)
             .response_type(FuncSpecBackendResponseType::Json)
+            .deterministic(true)
             .argument(
                 FuncArgumentSpec::builder()
                     .name("payload")
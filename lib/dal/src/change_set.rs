@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::string::FromUtf8Error;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
@@ -6,24 +9,78 @@ use strum::{Display, EnumString};
 use telemetry::prelude::*;
 use thiserror::Error;
 
+use crate::change_status::ChangeStatusError;
+use crate::edge::{EdgeError, EdgeId};
 use crate::label_list::LabelList;
-use crate::standard_model::object_option_from_row_option;
+use crate::standard_model::{object_option_from_row_option, objects_from_rows};
 use crate::ws_event::{WsEvent, WsEventError, WsPayload};
 use crate::{
-    pk, HistoryEvent, HistoryEventError, LabelListError, StandardModelError, Tenancy, Timestamp,
-    TransactionsError, UserError, UserPk, Visibility,
+    pk, AttributeValue, AttributeValueError, AttributeValueId, DependentValuesUpdate, Edge,
+    HistoryActor, HistoryEvent, HistoryEventError, LabelListError, StandardModel,
+    StandardModelError, Tenancy, Timestamp, TransactionsError, User, UserError, UserPk, Visibility,
+    WorkspaceError, WorkspacePermission,
+};
+use crate::{
+    Component, ComponentError, ComponentId, ComponentType, ComponentViewError, DalContext,
+    WsEventResult,
 };
-use crate::{Component, ComponentError, DalContext, WsEventResult};
 
-const CHANGE_SET_OPEN_LIST: &str = include_str!("queries/change_set/open_list.sql");
+pub mod conflict;
+pub mod diff;
+pub mod review;
+
+pub use conflict::{
+    ChangeSetConflict, ChangeSetConflictKind, ChangeSetConflictObject, ChangeSetConflictResolution,
+    ChangeSetConflictStrategy,
+};
+pub use diff::{
+    ChangeSetDiff, ComponentChangeDiff, EdgeChangeDiff, FuncChangeDiff, PropChangeDiff,
+    SchemaVariantDefinitionChangeDiff,
+};
+pub use review::{
+    ChangeSetApprovalStatus, ChangeSetReview, ChangeSetReviewPk, ChangeSetReviewStatus,
+    ChangeSetReviewSummary,
+};
+
+const CHANGE_SET_LIST: &str = include_str!("queries/change_set/list.sql");
+const CHANGE_SET_HAS_ARCHIVED_OBJECTS: &str =
+    include_str!("queries/change_set/has_archived_objects.sql");
+const CHANGE_SET_LIST_REVERTED_OBJECTS: &str =
+    include_str!("queries/change_set/list_reverted_objects.sql");
+const CHANGE_SET_LIST_REVERTED_ATTRIBUTE_VALUES: &str =
+    include_str!("queries/change_set/list_reverted_attribute_values.sql");
+const CHANGE_SET_LIST_APPLIED: &str = include_str!("queries/change_set/list_applied.sql");
 const CHANGE_SET_GET_BY_PK: &str = include_str!("queries/change_set/get_by_pk.sql");
+const CHANGE_SET_UPDATE_STATUS: &str = include_str!("queries/change_set/update_status.sql");
+const CHANGE_SET_REQUEST_REVIEW: &str = include_str!("queries/change_set/request_review.sql");
+const CHANGE_SET_MARK_REBASED: &str = include_str!("queries/change_set/mark_rebased.sql");
+const CHANGE_SET_LIST_HEAD_ATTRIBUTE_VALUES_UPDATED_SINCE: &str =
+    include_str!("queries/change_set/list_head_attribute_values_updated_since.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ChangeSetError {
+    #[error("change set {0} does not satisfy the workspace approval policy")]
+    ApprovalRequired(ChangeSetPk),
+    #[error(transparent)]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("change set {0} cannot be reopened (status: {1})")]
+    CannotReopen(ChangeSetPk, ChangeSetStatus),
+    #[error("change set not found: {0}")]
+    ChangeSetNotFound(ChangeSetPk),
+    #[error(transparent)]
+    ChangeStatus(#[from] ChangeStatusError),
     #[error(transparent)]
     Component(#[from] ComponentError),
     #[error(transparent)]
+    ComponentView(#[from] ComponentViewError),
+    #[error("error decoding code_base64: {0}")]
+    Decode(#[from] base64::DecodeError),
+    #[error(transparent)]
+    Edge(#[from] EdgeError),
+    #[error("utf8 encoding error: {0}")]
+    FromUtf8(#[from] FromUtf8Error),
+    #[error(transparent)]
     HistoryEvent(#[from] HistoryEventError),
     #[error("invalid user actor pk")]
     InvalidActor(UserPk),
@@ -31,29 +88,57 @@ pub enum ChangeSetError {
     LabelList(#[from] LabelListError),
     #[error(transparent)]
     Nats(#[from] NatsError),
+    #[error("change set {0} has not been applied (status: {1})")]
+    NotApplied(ChangeSetPk, ChangeSetStatus),
+    #[error("change set {0} was applied without archiving head and cannot be reverted")]
+    NothingToRevert(ChangeSetPk),
+    #[error("change set {0} is not open (status: {1})")]
+    NotOpen(ChangeSetPk, ChangeSetStatus),
+    #[error("change set {0} has not been marked ready for review")]
+    NotReadyForReview(ChangeSetPk),
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
     #[error(transparent)]
     Pg(#[from] PgError),
+    #[error("user {0} is not a member of the change set's workspace")]
+    ReviewerNotInWorkspace(UserPk),
+    #[error("change set reviews must be performed by a user")]
+    ReviewRequiresUser,
+    #[error("user {0} cannot review a change set they requested review for")]
+    SelfReview(UserPk),
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
     StandardModel(#[from] StandardModelError),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
+    #[error("unknown change status: {0}")]
+    UnknownChangeStatus(String),
+    #[error("unknown change set conflict kind: {0}")]
+    UnknownConflictKind(String),
+    #[error("unknown change set conflict object kind: {0}")]
+    UnknownConflictObjectKind(String),
+    #[error("change set {0} has {1} unresolved conflict(s) with head; rebase before applying")]
+    UnresolvedConflicts(ChangeSetPk, usize),
     #[error(transparent)]
     User(#[from] UserError),
     #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
     WsEvent(#[from] WsEventError),
 }
 
 pub type ChangeSetResult<T> = Result<T, ChangeSetError>;
 
 #[remain::sorted]
-#[derive(Deserialize, Serialize, Debug, Display, EnumString, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Display, EnumString, PartialEq, Eq, Clone, Copy)]
 pub enum ChangeSetStatus {
     Abandoned,
     Applied,
     Closed,
     Failed,
+    /// A read-only view of _head_ as it was at a point in time. See [`ChangeSet::new_as_of()`].
+    Historical,
     Open,
 }
 
@@ -65,12 +150,40 @@ pub struct ChangeSet {
     pub name: String,
     pub note: Option<String>,
     pub status: ChangeSetStatus,
+    /// When the change set was last rebased onto _head_, if ever.
+    pub rebased_at: Option<DateTime<Utc>>,
+    /// When the change set was last marked ready for review, if ever.
+    pub review_requested_at: Option<DateTime<Utc>>,
+    pub review_requested_by_user_pk: Option<UserPk>,
+    /// When the change set was applied to _head_, if it has been.
+    pub applied_at: Option<DateTime<Utc>>,
+    /// For [`Historical`](ChangeSetStatus::Historical) change sets, the moment in time they show
+    /// _head_ as of.
+    pub as_of: Option<DateTime<Utc>>,
+    /// The applied change set this change set reverts, if it was created by
+    /// [`ChangeSet::revert()`].
+    pub reverts_change_set_pk: Option<ChangeSetPk>,
+    /// When the change set this change set reverts was applied. The revert is based on _head_ as
+    /// of then, so anything changed on _head_ since shows up as a conflict.
+    pub reverts_applied_at: Option<DateTime<Utc>>,
     #[serde(flatten)]
     pub tenancy: Tenancy,
     #[serde(flatten)]
     pub timestamp: Timestamp,
 }
 
+/// The outcome of [`ChangeSet::rebase()`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetRebaseReport {
+    pub change_set_pk: ChangeSetPk,
+    /// Whether or not the rebase was performed. This is `false` when at least one conflict did
+    /// not have a resolution provided.
+    pub rebased: bool,
+    /// All conflicts found between the change set and _head_ prior to the rebase.
+    pub conflicts: Vec<ChangeSetConflict>,
+}
+
 impl ChangeSet {
     #[instrument(skip(ctx, name, note))]
     pub async fn new(
@@ -105,6 +218,240 @@ impl ChangeSet {
         Ok(object)
     }
 
+    /// Creates a read-only [`Historical`](ChangeSetStatus::Historical) change set that shows
+    /// _head_ as it was at `as_of`. Reading through its [`Visibility`] (e.g. to render the
+    /// [`Diagram`](crate::Diagram), a [`ComponentView`](crate::ComponentView) or the property
+    /// editor) reverts everything applied after that moment.
+    ///
+    /// Only changes made by applying change sets are reverted; changes written directly to _head_
+    /// (e.g. resource refreshes) are shown as they are now.
+    ///
+    /// Nothing can be written through the returned change set (see
+    /// [`require_writable`](crate::standard_model::require_writable)). Asking for the same
+    /// `as_of` again returns the same change set unless something has been applied since, and
+    /// historical change sets that have not been asked for in a day are removed.
+    #[instrument(skip(ctx))]
+    pub async fn new_as_of(ctx: &DalContext, as_of: DateTime<Utc>) -> ChangeSetResult<Self> {
+        let name = format!("As of {}", as_of.format("%Y-%m-%d %H:%M:%S UTC"));
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM change_set_create_as_of_v1($1, $2, $3)",
+                &[&name, &as_of, ctx.tenancy()],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.create_as_of",
+            "Historical Change Set created",
+            &json,
+        )
+        .await?;
+        let object: Self = serde_json::from_value(json)?;
+        WsEvent::change_set_created(ctx, object.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+        Ok(object)
+    }
+
+    /// Creates a read-only [`Historical`](ChangeSetStatus::Historical) change set that shows
+    /// _head_ as it was right after the applied change set for the provided [`ChangeSetPk`].
+    #[instrument(skip(ctx))]
+    pub async fn new_as_of_change_set(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> ChangeSetResult<Self> {
+        let change_set = Self::get_by_pk(ctx, &change_set_pk)
+            .await?
+            .ok_or(ChangeSetError::ChangeSetNotFound(change_set_pk))?;
+        match (change_set.status, change_set.applied_at) {
+            (ChangeSetStatus::Applied, Some(applied_at)) => Self::new_as_of(ctx, applied_at).await,
+            _ => Err(ChangeSetError::NotApplied(change_set_pk, change_set.status)),
+        }
+    }
+
+    /// Creates a new open change set containing the inverse of everything the applied change set
+    /// for the provided [`ChangeSetPk`] did: [`Components`](Component), [`Edges`](Edge) and
+    /// [`AttributeValues`](AttributeValue) it modified or deleted are restored to the version it
+    /// replaced, and everything it created is deleted. The returned change set can be reviewed
+    /// and applied like any other.
+    ///
+    /// The new change set is based on _head_ as it was right after the apply, so anything changed
+    /// on _head_ since then shows up in its [`conflicts`](Self::conflicts()) and must be resolved
+    /// by a [`rebase`](Self::rebase()) before it can be applied.
+    #[instrument(skip(ctx))]
+    pub async fn revert(ctx: &DalContext, change_set_pk: ChangeSetPk) -> ChangeSetResult<Self> {
+        let change_set = Self::get_by_pk(ctx, &change_set_pk)
+            .await?
+            .ok_or(ChangeSetError::ChangeSetNotFound(change_set_pk))?;
+        if change_set.status != ChangeSetStatus::Applied {
+            return Err(ChangeSetError::NotApplied(change_set_pk, change_set.status));
+        }
+
+        // Change sets applied before head was archived on apply cannot be inverted.
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                CHANGE_SET_HAS_ARCHIVED_OBJECTS,
+                &[ctx.tenancy(), &change_set_pk],
+            )
+            .await?;
+        let has_archived_objects: bool = row.try_get("has_archived_objects")?;
+        if !has_archived_objects {
+            return Err(ChangeSetError::NothingToRevert(change_set_pk));
+        }
+
+        let name = format!("Revert {}", change_set.name);
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM change_set_revert_v1($1, $2, $3)",
+                &[&change_set_pk, &name, ctx.tenancy()],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let _history_event =
+            HistoryEvent::new(ctx, "change_set.revert", "Change Set reverted", &json).await?;
+        let object: Self = serde_json::from_value(json)?;
+
+        let revert_ctx =
+            ctx.clone_with_new_visibility(Visibility::new_change_set(object.pk, false));
+        Self::revert_components_and_edges(&revert_ctx, change_set_pk).await?;
+        Self::revert_attribute_values(&revert_ctx, change_set_pk).await?;
+
+        WsEvent::change_set_created(ctx, object.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+        Ok(object)
+    }
+
+    /// Restores the [`Components`](Component) and [`Edges`](Edge) the reverted change set deleted
+    /// and deletes the ones it created, through the same paths the diagram uses.
+    async fn revert_components_and_edges(
+        ctx: &DalContext,
+        reverted_change_set_pk: ChangeSetPk,
+    ) -> ChangeSetResult<()> {
+        let ctx_with_deleted = &ctx.clone_with_delete_visibility();
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                CHANGE_SET_LIST_REVERTED_OBJECTS,
+                &[ctx.tenancy(), &reverted_change_set_pk],
+            )
+            .await?;
+        let (mut deleted_components, mut created_components) = (Vec::new(), Vec::new());
+        let (mut deleted_edge_ids, mut created_edge_ids) = (Vec::new(), Vec::new());
+        for row in rows {
+            let table_name: String = row.try_get("table_name")?;
+            let existed_before_apply: bool = row.try_get("existed_before_apply")?;
+            if table_name == "edges" {
+                let edge_id: EdgeId = row.try_get("object_id")?;
+                if existed_before_apply {
+                    deleted_edge_ids.push(edge_id);
+                } else {
+                    created_edge_ids.push(edge_id);
+                }
+                continue;
+            }
+
+            let component_id: ComponentId = row.try_get("object_id")?;
+            let component = match Component::get_by_id(ctx_with_deleted, &component_id).await? {
+                Some(component) => component,
+                None => continue,
+            };
+            let is_frame = component.get_type(ctx_with_deleted).await? != ComponentType::Component;
+            if existed_before_apply {
+                if component.visibility().deleted_at.is_some() {
+                    deleted_components.push((is_frame, component_id));
+                }
+            } else if component.visibility().deleted_at.is_none() {
+                created_components.push((is_frame, component));
+            }
+        }
+
+        // Frames are restored before the components inside them, and deleted after them.
+        deleted_components.sort_by_key(|(is_frame, _)| !*is_frame);
+        for (_, component_id) in deleted_components {
+            Component::restore_and_propagate(ctx, component_id).await?;
+        }
+
+        // Restoring a component brings back the edges deleted along with it.
+        for edge_id in deleted_edge_ids {
+            if let Some(edge) = Edge::get_by_id(ctx_with_deleted, &edge_id).await? {
+                if edge.visibility().deleted_at.is_some() {
+                    Edge::restore_by_id(ctx, edge_id).await?;
+                }
+            }
+        }
+
+        for edge_id in created_edge_ids {
+            if let Some(mut edge) = Edge::get_by_id(ctx, &edge_id).await? {
+                edge.delete_and_propagate(ctx).await?;
+            }
+        }
+
+        created_components.sort_by_key(|(is_frame, _)| *is_frame);
+        for (_, mut component) in created_components {
+            component.delete_and_propagate(ctx).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets every [`AttributeValue`] the reverted change set set back to the value it replaced.
+    async fn revert_attribute_values(
+        ctx: &DalContext,
+        reverted_change_set_pk: ChangeSetPk,
+    ) -> ChangeSetResult<()> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                CHANGE_SET_LIST_REVERTED_ATTRIBUTE_VALUES,
+                &[ctx.tenancy(), ctx.visibility(), &reverted_change_set_pk],
+            )
+            .await?;
+        for row in rows {
+            let attribute_value_id: AttributeValueId = row.try_get("attribute_value_id")?;
+            let previous_value: Option<serde_json::Value> = row.try_get("previous_value")?;
+
+            let attribute_value = AttributeValue::get_by_id(ctx, &attribute_value_id)
+                .await?
+                .ok_or(AttributeValueError::NotFound(
+                    attribute_value_id,
+                    *ctx.visibility(),
+                ))?;
+            let parent_attribute_value_id = attribute_value
+                .parent_attribute_value(ctx)
+                .await?
+                .map(|parent| *parent.id());
+            AttributeValue::update_for_context(
+                ctx,
+                attribute_value_id,
+                parent_attribute_value_id,
+                attribute_value.context,
+                previous_value,
+                attribute_value.key.clone(),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
     pub fn generate_name() -> String {
         Utc::now().format("%Y-%m-%d-%H:%M").to_string()
     }
@@ -115,6 +462,22 @@ impl ChangeSet {
         ctx: &mut DalContext,
         run_confirmations: bool,
     ) -> ChangeSetResult<()> {
+        if self.status != ChangeSetStatus::Open {
+            return Err(ChangeSetError::NotOpen(self.pk, self.status));
+        }
+        User::require_permission_for_actor(ctx, WorkspacePermission::Apply).await?;
+
+        let conflicts = self.conflicts(ctx).await?;
+        if !conflicts.is_empty() {
+            return Err(ChangeSetError::UnresolvedConflicts(
+                self.pk,
+                conflicts.len(),
+            ));
+        }
+        if !ChangeSetApprovalStatus::new(ctx, self).await?.satisfied {
+            return Err(ChangeSetError::ApprovalRequired(self.pk));
+        }
+
         let actor = serde_json::to_value(ctx.history_actor())?;
         let row = ctx
             .txns()
@@ -127,6 +490,7 @@ impl ChangeSet {
             .await?;
         let updated_at: DateTime<Utc> = row.try_get("timestamp_updated_at")?;
         self.timestamp.updated_at = updated_at;
+        self.applied_at = Some(updated_at);
         self.status = ChangeSetStatus::Applied;
         let _history_event = HistoryEvent::new(
             ctx,
@@ -158,18 +522,374 @@ impl ChangeSet {
         Ok(())
     }
 
+    /// The point on _head_ this change set is based on: when it was last rebased, when the change
+    /// set it reverts was applied, or when it was created, in that order.
+    pub fn base(&self) -> DateTime<Utc> {
+        self.rebased_at
+            .or(self.reverts_applied_at)
+            .unwrap_or(self.timestamp.created_at)
+    }
+
+    /// Lists all [`conflicts`](ChangeSetConflict) between this change set and changes made on
+    /// _head_ since the change set's [`base`](Self::base()).
+    #[instrument(skip_all)]
+    pub async fn conflicts(&self, ctx: &DalContext) -> ChangeSetResult<Vec<ChangeSetConflict>> {
+        ChangeSetConflict::list(ctx, self.pk, self.base()).await
+    }
+
+    /// Brings the changes made on _head_ since this change set's [`base`](Self::base()) into the
+    /// change set.
+    ///
+    /// Changes on _head_ that do not conflict with the change set are already visible through
+    /// it; values derived from them are recalculated within the change set. If any
+    /// [`conflict`](ChangeSetConflict) is found without a matching entry in `resolutions`, nothing
+    /// is changed and the returned report has `rebased` set to `false`.
+    #[instrument(skip(ctx, resolutions))]
+    pub async fn rebase(
+        &mut self,
+        ctx: &DalContext,
+        resolutions: &[ChangeSetConflictResolution],
+    ) -> ChangeSetResult<ChangeSetRebaseReport> {
+        if self.status != ChangeSetStatus::Open {
+            return Err(ChangeSetError::NotOpen(self.pk, self.status));
+        }
+
+        let conflicts = self.conflicts(ctx).await?;
+        let strategies: HashMap<ChangeSetConflictObject, ChangeSetConflictStrategy> = resolutions
+            .iter()
+            .map(|resolution| (resolution.object, resolution.strategy))
+            .collect();
+        if conflicts
+            .iter()
+            .any(|conflict| !strategies.contains_key(&conflict.object))
+        {
+            return Ok(ChangeSetRebaseReport {
+                change_set_pk: self.pk,
+                rebased: false,
+                conflicts,
+            });
+        }
+
+        let change_set_ctx =
+            ctx.clone_with_new_visibility(Visibility::new_change_set(self.pk, false));
+        for conflict in &conflicts {
+            if let Some(strategy) = strategies.get(&conflict.object) {
+                Self::resolve_conflict(&change_set_ctx, conflict, *strategy).await?;
+            }
+        }
+
+        // Recalculate everything that depends on values changed on head so that values derived
+        // within the change set do not shadow newer head values.
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                CHANGE_SET_LIST_HEAD_ATTRIBUTE_VALUES_UPDATED_SINCE,
+                &[ctx.tenancy(), &self.base()],
+            )
+            .await?;
+        let mut attribute_value_ids = Vec::with_capacity(rows.len());
+        for row in rows {
+            let attribute_value_id: AttributeValueId = row.try_get("id")?;
+            attribute_value_ids.push(attribute_value_id);
+        }
+        if !attribute_value_ids.is_empty() {
+            change_set_ctx
+                .enqueue_job(DependentValuesUpdate::new(
+                    change_set_ctx.access_builder(),
+                    *change_set_ctx.visibility(),
+                    attribute_value_ids,
+                ))
+                .await?;
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(CHANGE_SET_MARK_REBASED, &[ctx.tenancy(), &self.pk])
+            .await?;
+        self.rebased_at = row.try_get("rebased_at")?;
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.rebase",
+            "Change Set rebased",
+            &serde_json::json![{ "pk": &self.pk, "resolutions": resolutions }],
+        )
+        .await?;
+
+        WsEvent::change_set_rebased(&change_set_ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(ChangeSetRebaseReport {
+            change_set_pk: self.pk,
+            rebased: true,
+            conflicts,
+        })
+    }
+
+    async fn resolve_conflict(
+        change_set_ctx: &DalContext,
+        conflict: &ChangeSetConflict,
+        strategy: ChangeSetConflictStrategy,
+    ) -> ChangeSetResult<()> {
+        match (conflict.kind, strategy) {
+            (ChangeSetConflictKind::BothModified, ChangeSetConflictStrategy::KeepChangeSet)
+            | (
+                ChangeSetConflictKind::DeletedInChangeSet,
+                ChangeSetConflictStrategy::KeepChangeSet,
+            ) => {
+                // Nothing to do: the change set's version wins on apply.
+            }
+            (ChangeSetConflictKind::BothModified, ChangeSetConflictStrategy::TakeHead) => {
+                // Drop the change set's copy so that head's value shows through again.
+                if let ChangeSetConflictObject::AttributeValue(attribute_value_id) = conflict.object
+                {
+                    if let Some(attribute_value) =
+                        AttributeValue::get_by_id(change_set_ctx, &attribute_value_id).await?
+                    {
+                        if attribute_value.visibility().change_set_pk
+                            == change_set_ctx.visibility().change_set_pk
+                        {
+                            attribute_value.hard_delete(change_set_ctx).await?;
+                        }
+                    }
+                }
+            }
+            (ChangeSetConflictKind::DeletedInChangeSet, ChangeSetConflictStrategy::TakeHead) => {
+                Component::restore_and_propagate(change_set_ctx, conflict.component_id).await?;
+            }
+            (ChangeSetConflictKind::DeletedOnHead, ChangeSetConflictStrategy::TakeHead) => {
+                match conflict.object {
+                    // The edge only exists in the change set and points at a component that no
+                    // longer exists, so it is discarded entirely.
+                    ChangeSetConflictObject::Edge(edge_id) => {
+                        if let Some(edge) = Edge::get_by_id(change_set_ctx, &edge_id).await? {
+                            edge.hard_delete(change_set_ctx).await?;
+                        }
+                    }
+                    _ => {
+                        if let Some(mut component) =
+                            Component::get_by_id(change_set_ctx, &conflict.component_id).await?
+                        {
+                            component.delete_and_propagate(change_set_ctx).await?;
+                        }
+                    }
+                }
+            }
+            (ChangeSetConflictKind::DeletedOnHead, ChangeSetConflictStrategy::KeepChangeSet) => {
+                // Keeping the change set's edits to a component deleted on head means bringing
+                // the component back.
+                let deleted_ctx = change_set_ctx.clone_with_delete_visibility();
+                let is_deleted = Component::get_by_id(&deleted_ctx, &conflict.component_id)
+                    .await?
+                    .map(|component| component.visibility().deleted_at.is_some())
+                    .unwrap_or(false);
+                if is_deleted {
+                    Component::restore_and_propagate(change_set_ctx, conflict.component_id).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks this change set as ready for review by the [`User`](crate::User) acting in the
+    /// [`DalContext`]. Requesting review again invalidates all previous reviews.
+    #[instrument(skip(ctx))]
+    pub async fn request_review(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
+        if self.status != ChangeSetStatus::Open {
+            return Err(ChangeSetError::NotOpen(self.pk, self.status));
+        }
+        let user_pk = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => Some(*user_pk),
+            HistoryActor::SystemInit => None,
+        };
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                CHANGE_SET_REQUEST_REVIEW,
+                &[ctx.tenancy(), &self.pk, &user_pk],
+            )
+            .await?;
+        self.review_requested_at = row.try_get("review_requested_at")?;
+        self.review_requested_by_user_pk = user_pk;
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.request_review",
+            "Change Set marked ready for review",
+            &serde_json::json![{ "pk": &self.pk }],
+        )
+        .await?;
+
+        WsEvent::change_set_review_requested(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Abandons this change set, permanently discarding everything that was changed in it.
+    #[instrument(skip(ctx))]
+    pub async fn abandon(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
+        if !matches!(
+            self.status,
+            ChangeSetStatus::Open | ChangeSetStatus::Historical
+        ) {
+            return Err(ChangeSetError::NotOpen(self.pk, self.status));
+        }
+
+        let actor = serde_json::to_value(ctx.history_actor())?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT timestamp_updated_at FROM change_set_abandon_v1($1, $2, $3)",
+                &[&self.pk, &actor, ctx.tenancy()],
+            )
+            .await?;
+        self.timestamp.updated_at = row.try_get("timestamp_updated_at")?;
+        self.status = ChangeSetStatus::Abandoned;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.abandon",
+            "Change Set abandoned",
+            &serde_json::json![{ "pk": &self.pk }],
+        )
+        .await?;
+
+        WsEvent::change_set_canceled(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Closes this change set without applying it. Unlike [`Self::abandon()`], everything changed
+    /// in the change set is kept so that it can be [`reopened`](Self::reopen()) later.
+    #[instrument(skip(ctx))]
+    pub async fn close(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
+        if self.status != ChangeSetStatus::Open {
+            return Err(ChangeSetError::NotOpen(self.pk, self.status));
+        }
+
+        self.update_status(ctx, ChangeSetStatus::Closed).await?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.close",
+            "Change Set closed",
+            &serde_json::json![{ "pk": &self.pk }],
+        )
+        .await?;
+
+        WsEvent::change_set_closed(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Reopens a [`closed`](ChangeSetStatus::Closed) or [`failed`](ChangeSetStatus::Failed)
+    /// change set.
+    #[instrument(skip(ctx))]
+    pub async fn reopen(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
+        if !matches!(
+            self.status,
+            ChangeSetStatus::Closed | ChangeSetStatus::Failed
+        ) {
+            return Err(ChangeSetError::CannotReopen(self.pk, self.status));
+        }
+
+        self.update_status(ctx, ChangeSetStatus::Open).await?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.reopen",
+            "Change Set reopened",
+            &serde_json::json![{ "pk": &self.pk }],
+        )
+        .await?;
+
+        WsEvent::change_set_reopened(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_status(
+        &mut self,
+        ctx: &DalContext,
+        status: ChangeSetStatus,
+    ) -> ChangeSetResult<()> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                CHANGE_SET_UPDATE_STATUS,
+                &[ctx.tenancy(), &self.pk, &status.to_string()],
+            )
+            .await?;
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+        self.status = status;
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     pub async fn list_open(ctx: &DalContext) -> ChangeSetResult<LabelList<ChangeSetPk>> {
+        Self::list_with_status(ctx, Some(ChangeSetStatus::Open)).await
+    }
+
+    /// Lists change sets with the provided [`status`](ChangeSetStatus), most recently updated
+    /// first. If no status is provided, change sets of every status but
+    /// [`Historical`](ChangeSetStatus::Historical) are listed.
+    #[instrument(skip(ctx))]
+    pub async fn list_with_status(
+        ctx: &DalContext,
+        status: Option<ChangeSetStatus>,
+    ) -> ChangeSetResult<LabelList<ChangeSetPk>> {
+        let status = status.map(|status| status.to_string());
         let rows = ctx
             .txns()
             .await?
             .pg()
-            .query(CHANGE_SET_OPEN_LIST, &[ctx.tenancy()])
+            .query(CHANGE_SET_LIST, &[ctx.tenancy(), &status])
             .await?;
         let results = LabelList::from_rows(rows)?;
         Ok(results)
     }
 
+    /// Lists applied change sets, most recently applied first. When `before` is provided, only
+    /// change sets applied at or before that moment are returned.
+    #[instrument(skip(ctx))]
+    pub async fn list_applied(
+        ctx: &DalContext,
+        before: Option<DateTime<Utc>>,
+    ) -> ChangeSetResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(CHANGE_SET_LIST_APPLIED, &[ctx.tenancy(), &before])
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+
     #[instrument(skip_all)]
     pub async fn get_by_pk(
         ctx: &DalContext,
@@ -201,6 +921,13 @@ impl WsEvent {
         WsEvent::new(ctx, WsPayload::ChangeSetApplied(change_set_pk)).await
     }
 
+    pub async fn change_set_rebased(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ChangeSetRebased(change_set_pk)).await
+    }
+
     pub async fn change_set_canceled(
         ctx: &DalContext,
         change_set_pk: ChangeSetPk,
@@ -208,6 +935,20 @@ impl WsEvent {
         WsEvent::new(ctx, WsPayload::ChangeSetCanceled(change_set_pk)).await
     }
 
+    pub async fn change_set_closed(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ChangeSetClosed(change_set_pk)).await
+    }
+
+    pub async fn change_set_reopened(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ChangeSetReopened(change_set_pk)).await
+    }
+
     pub async fn change_set_written(ctx: &DalContext) -> WsEventResult<Self> {
         WsEvent::new(
             ctx,
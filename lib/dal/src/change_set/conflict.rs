@@ -0,0 +1,125 @@
+//! This module contains [`ChangeSetConflict`], which represents an object that was changed both
+//! in a [`ChangeSet`](crate::ChangeSet) and on _head_ since the [`ChangeSet`](crate::ChangeSet)
+//! was opened (or last rebased).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgRow;
+use strum::{AsRefStr, Display, EnumString};
+
+use crate::change_set::{ChangeSetError, ChangeSetResult};
+use crate::edge::EdgeId;
+use crate::{AttributeValueId, ChangeSetPk, ComponentId, DalContext, Visibility};
+
+const LIST_CONFLICTS: &str = include_str!("../queries/change_set/list_conflicts.sql");
+
+/// The object involved in a [`ChangeSetConflict`].
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "id")]
+pub enum ChangeSetConflictObject {
+    AttributeValue(AttributeValueId),
+    Component(ComponentId),
+    Edge(EdgeId),
+}
+
+/// Describes how the object in a [`ChangeSetConflict`] diverged between _head_ and the
+/// [`ChangeSet`](crate::ChangeSet).
+#[remain::sorted]
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ChangeSetConflictKind {
+    /// The same prop was set to different values on _head_ and in the change set.
+    BothModified,
+    /// The [`Component`](crate::Component) was deleted in the change set, but edited on _head_.
+    DeletedInChangeSet,
+    /// The [`Component`](crate::Component) was deleted on _head_, but edited (or connected to) in
+    /// the change set.
+    DeletedOnHead,
+}
+
+/// How a [`ChangeSetConflict`] should be resolved during a rebase.
+#[remain::sorted]
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ChangeSetConflictStrategy {
+    /// Keep the change set's version of the object. It will overwrite _head_ on apply.
+    KeepChangeSet,
+    /// Discard the change set's version of the object in favor of _head_.
+    TakeHead,
+}
+
+/// A caller-provided resolution for the [`ChangeSetConflict`] on a given
+/// [`object`](ChangeSetConflictObject).
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetConflictResolution {
+    pub object: ChangeSetConflictObject,
+    pub strategy: ChangeSetConflictStrategy,
+}
+
+/// An object that was changed both in a [`ChangeSet`](crate::ChangeSet) and on _head_ since the
+/// [`ChangeSet`](crate::ChangeSet) was opened (or last rebased).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetConflict {
+    pub object: ChangeSetConflictObject,
+    pub kind: ChangeSetConflictKind,
+    pub component_id: ComponentId,
+    /// The path of the [`Prop`](crate::Prop) for [`AttributeValue`](crate::AttributeValue)
+    /// conflicts.
+    pub prop_path: Option<String>,
+}
+
+impl ChangeSetConflict {
+    /// Lists all conflicts between the [`ChangeSet`](crate::ChangeSet) for the provided
+    /// [`ChangeSetPk`] and _head_, considering only changes made on _head_ after `base`.
+    pub async fn list(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+        base: DateTime<Utc>,
+    ) -> ChangeSetResult<Vec<Self>> {
+        let visibility = Visibility::new_change_set(change_set_pk, false);
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_CONFLICTS,
+                &[ctx.tenancy(), &visibility, &change_set_pk, &base],
+            )
+            .await?;
+
+        let mut conflicts = Vec::with_capacity(rows.len());
+        for row in rows {
+            conflicts.push(Self::from_row(row)?);
+        }
+        Ok(conflicts)
+    }
+
+    fn from_row(row: PgRow) -> ChangeSetResult<Self> {
+        let object_kind: String = row.try_get("object_kind")?;
+        let object = match object_kind.as_str() {
+            "attributeValue" => ChangeSetConflictObject::AttributeValue(row.try_get("object_id")?),
+            "component" => ChangeSetConflictObject::Component(row.try_get("object_id")?),
+            "edge" => ChangeSetConflictObject::Edge(row.try_get("object_id")?),
+            _ => return Err(ChangeSetError::UnknownConflictObjectKind(object_kind)),
+        };
+        let kind: String = row.try_get("conflict_kind")?;
+
+        Ok(Self {
+            object,
+            kind: kind
+                .parse()
+                .map_err(|_| ChangeSetError::UnknownConflictKind(kind))?,
+            component_id: row.try_get("component_id")?,
+            prop_path: row.try_get("prop_path")?,
+        })
+    }
+}
@@ -0,0 +1,344 @@
+//! This module contains [`ChangeSetDiff`], a structured report of everything that differs between
+//! a [`ChangeSet`](crate::ChangeSet) and _head_.
+
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose, Engine};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgRow;
+use telemetry::prelude::*;
+
+use crate::change_set::{ChangeSetError, ChangeSetResult};
+use crate::change_status::{ChangeStatus, ComponentChangeStatus};
+use crate::component::diff::ComponentDiff;
+use crate::edge::EdgeId;
+use crate::schema::variant::definition::SchemaVariantDefinitionId;
+use crate::{
+    ChangeSetPk, CodeLanguage, CodeView, ComponentId, ComponentView, ComponentViewProperties,
+    DalContext, FuncId, Visibility,
+};
+
+const LIST_EDGE_CHANGES: &str = include_str!("../queries/change_set/list_edge_changes.sql");
+const LIST_FUNC_CHANGES: &str = include_str!("../queries/change_set/list_func_changes.sql");
+const LIST_SCHEMA_VARIANT_DEFINITION_CHANGES: &str =
+    include_str!("../queries/change_set/list_schema_variant_definition_changes.sql");
+
+const NEWLINE: &str = "\n";
+
+/// Everything that differs between a [`ChangeSet`](crate::ChangeSet) and _head_. Generated by
+/// [`Self::new()`].
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetDiff {
+    pub change_set_pk: ChangeSetPk,
+    pub components: Vec<ComponentChangeDiff>,
+    pub edges: Vec<EdgeChangeDiff>,
+    pub funcs: Vec<FuncChangeDiff>,
+    pub schema_variant_definitions: Vec<SchemaVariantDefinitionChangeDiff>,
+}
+
+/// The changes made to a single [`Component`](crate::Component) in a
+/// [`ChangeSet`](crate::ChangeSet).
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentChangeDiff {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub status: ChangeStatus,
+    /// Every prop path whose value differs from _head_.
+    pub props: Vec<PropChangeDiff>,
+    /// The [`ComponentDiff`] for added and modified [`Components`](crate::Component).
+    pub code_diff: Option<ComponentDiff>,
+}
+
+/// The value found at a given prop path on _head_ (`before`) and in the
+/// [`ChangeSet`](crate::ChangeSet) (`after`). Either side is empty when the path does not exist
+/// there.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PropChangeDiff {
+    pub path: String,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// An [`Edge`](crate::Edge) that was added or removed in a [`ChangeSet`](crate::ChangeSet).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeChangeDiff {
+    pub edge_id: EdgeId,
+    pub status: ChangeStatus,
+    pub head_component_id: ComponentId,
+    pub head_socket_name: Option<String>,
+    pub tail_component_id: ComponentId,
+    pub tail_socket_name: Option<String>,
+}
+
+/// A [`Func`](crate::Func) that was added, modified or deleted in a
+/// [`ChangeSet`](crate::ChangeSet).
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncChangeDiff {
+    pub func_id: FuncId,
+    pub name: String,
+    pub status: ChangeStatus,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// The line diff between `before` and `after`, if the code changed.
+    pub code_diff: Option<CodeView>,
+}
+
+/// A [`SchemaVariantDefinition`](crate::schema::variant::definition::SchemaVariantDefinition)
+/// that was added, modified or deleted in a [`ChangeSet`](crate::ChangeSet).
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVariantDefinitionChangeDiff {
+    pub schema_variant_definition_id: SchemaVariantDefinitionId,
+    pub name: String,
+    pub status: ChangeStatus,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// The line diff between `before` and `after`, if the definition changed.
+    pub definition_diff: Option<CodeView>,
+}
+
+impl ChangeSetDiff {
+    #[instrument(skip(ctx))]
+    pub async fn new(ctx: &DalContext, change_set_pk: ChangeSetPk) -> ChangeSetResult<Self> {
+        let change_set_ctx =
+            ctx.clone_with_new_visibility(Visibility::new_change_set(change_set_pk, false));
+
+        Ok(Self {
+            change_set_pk,
+            components: Self::list_component_changes(&change_set_ctx).await?,
+            edges: Self::list_edge_changes(&change_set_ctx).await?,
+            funcs: Self::list_func_changes(&change_set_ctx).await?,
+            schema_variant_definitions: Self::list_schema_variant_definition_changes(
+                &change_set_ctx,
+            )
+            .await?,
+        })
+    }
+
+    async fn list_component_changes(ctx: &DalContext) -> ChangeSetResult<Vec<ComponentChangeDiff>> {
+        let head_ctx = ctx.clone_with_head();
+
+        let mut components = Vec::new();
+        for group in ComponentChangeStatus::new(ctx).await?.stats() {
+            let (before, after, code_diff) = match group.component_status {
+                ChangeStatus::Added => (
+                    None,
+                    Self::flattened_properties(ctx, group.component_id).await?,
+                    Some(ComponentDiff::new(ctx, group.component_id).await?),
+                ),
+                ChangeStatus::Deleted => (
+                    Self::flattened_properties(&head_ctx, group.component_id).await?,
+                    None,
+                    None,
+                ),
+                ChangeStatus::Modified => (
+                    Self::flattened_properties(&head_ctx, group.component_id).await?,
+                    Self::flattened_properties(ctx, group.component_id).await?,
+                    Some(ComponentDiff::new(ctx, group.component_id).await?),
+                ),
+                ChangeStatus::Unmodified => continue,
+            };
+
+            components.push(ComponentChangeDiff {
+                component_id: group.component_id,
+                component_name: group.component_name().to_owned(),
+                status: group.component_status,
+                props: prop_diffs(before.unwrap_or_default(), after.unwrap_or_default()),
+                code_diff,
+            });
+        }
+        Ok(components)
+    }
+
+    /// Flattens the non-private properties of a [`Component`](crate::Component) into a map of
+    /// prop paths (e.g. "/root/domain/region") to leaf values.
+    async fn flattened_properties(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ChangeSetResult<Option<BTreeMap<String, serde_json::Value>>> {
+        let view = ComponentView::new(ctx, component_id).await?;
+        if view.properties.is_null() {
+            return Ok(None);
+        }
+        let mut properties = ComponentViewProperties::try_from(view)?;
+        properties.drop_private();
+
+        let mut flattened = BTreeMap::new();
+        flatten_value("/root", properties.to_value()?, &mut flattened);
+        Ok(Some(flattened))
+    }
+
+    async fn list_edge_changes(ctx: &DalContext) -> ChangeSetResult<Vec<EdgeChangeDiff>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_EDGE_CHANGES,
+                &[
+                    ctx.tenancy(),
+                    &ctx.visibility().change_set_pk,
+                    ctx.visibility(),
+                ],
+            )
+            .await?;
+
+        let mut edges = Vec::with_capacity(rows.len());
+        for row in rows {
+            edges.push(EdgeChangeDiff {
+                edge_id: row.try_get("edge_id")?,
+                status: change_status_from_row(&row)?,
+                head_component_id: row.try_get("head_component_id")?,
+                head_socket_name: row.try_get("head_socket_name")?,
+                tail_component_id: row.try_get("tail_component_id")?,
+                tail_socket_name: row.try_get("tail_socket_name")?,
+            });
+        }
+        Ok(edges)
+    }
+
+    async fn list_func_changes(ctx: &DalContext) -> ChangeSetResult<Vec<FuncChangeDiff>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FUNC_CHANGES,
+                &[ctx.tenancy(), &ctx.visibility().change_set_pk],
+            )
+            .await?;
+
+        let mut funcs = Vec::with_capacity(rows.len());
+        for row in rows {
+            let before = decode_code(row.try_get("before_code_base64")?)?;
+            let after = decode_code(row.try_get("after_code_base64")?)?;
+            funcs.push(FuncChangeDiff {
+                func_id: row.try_get("func_id")?,
+                name: row.try_get("name")?,
+                status: change_status_from_row(&row)?,
+                code_diff: code_diff(before.as_deref(), after.as_deref()),
+                before,
+                after,
+            });
+        }
+        Ok(funcs)
+    }
+
+    async fn list_schema_variant_definition_changes(
+        ctx: &DalContext,
+    ) -> ChangeSetResult<Vec<SchemaVariantDefinitionChangeDiff>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_SCHEMA_VARIANT_DEFINITION_CHANGES,
+                &[ctx.tenancy(), &ctx.visibility().change_set_pk],
+            )
+            .await?;
+
+        let mut definitions = Vec::with_capacity(rows.len());
+        for row in rows {
+            let before: Option<String> = row.try_get("before_definition")?;
+            let after: Option<String> = row.try_get("after_definition")?;
+            definitions.push(SchemaVariantDefinitionChangeDiff {
+                schema_variant_definition_id: row.try_get("schema_variant_definition_id")?,
+                name: row.try_get("name")?,
+                status: change_status_from_row(&row)?,
+                definition_diff: code_diff(before.as_deref(), after.as_deref()),
+                before,
+                after,
+            });
+        }
+        Ok(definitions)
+    }
+}
+
+fn change_status_from_row(row: &PgRow) -> ChangeSetResult<ChangeStatus> {
+    let change_status: String = row.try_get("change_status")?;
+    change_status
+        .parse()
+        .map_err(|_| ChangeSetError::UnknownChangeStatus(change_status))
+}
+
+fn decode_code(code_base64: Option<String>) -> ChangeSetResult<Option<String>> {
+    Ok(match code_base64 {
+        Some(code_base64) => Some(String::from_utf8(
+            general_purpose::STANDARD_NO_PAD.decode(code_base64)?,
+        )?),
+        None => None,
+    })
+}
+
+/// Produces a unified-style line diff, or [`None`] if nothing changed.
+fn code_diff(before: Option<&str>, after: Option<&str>) -> Option<CodeView> {
+    let (before, after) = (before.unwrap_or_default(), after.unwrap_or_default());
+    if before == after {
+        return None;
+    }
+
+    let lines: Vec<String> = diff::lines(before, after)
+        .into_iter()
+        .map(|diff_object| match diff_object {
+            diff::Result::Left(left) => format!("-{left}"),
+            diff::Result::Both(unchanged, _) => format!(" {unchanged}"),
+            diff::Result::Right(right) => format!("+{right}"),
+        })
+        .collect();
+    Some(CodeView::new(CodeLanguage::Diff, Some(lines.join(NEWLINE))))
+}
+
+fn flatten_value(
+    path: &str,
+    value: serde_json::Value,
+    flattened: &mut BTreeMap<String, serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                flatten_value(&format!("{path}/{key}"), child, flattened);
+            }
+        }
+        serde_json::Value::Array(array) if !array.is_empty() => {
+            for (index, child) in array.into_iter().enumerate() {
+                flatten_value(&format!("{path}/{index}"), child, flattened);
+            }
+        }
+        leaf => {
+            flattened.insert(path.to_owned(), leaf);
+        }
+    }
+}
+
+fn prop_diffs(
+    mut before: BTreeMap<String, serde_json::Value>,
+    after: BTreeMap<String, serde_json::Value>,
+) -> Vec<PropChangeDiff> {
+    let mut diffs = Vec::new();
+    for (path, after_value) in after {
+        let before_value = before.remove(&path);
+        if before_value.as_ref() != Some(&after_value) {
+            diffs.push(PropChangeDiff {
+                path,
+                before: before_value,
+                after: Some(after_value),
+            });
+        }
+    }
+    diffs.extend(
+        before
+            .into_iter()
+            .map(|(path, before_value)| PropChangeDiff {
+                path,
+                before: Some(before_value),
+                after: None,
+            }),
+    );
+    diffs.sort_by(|a, b| a.path.cmp(&b.path));
+    diffs
+}
@@ -0,0 +1,265 @@
+//! This module contains [`ChangeSetReview`], which allows [`Users`](crate::User) associated with a
+//! [`Workspace`](crate::Workspace) to approve (or request changes to) a
+//! [`ChangeSet`](crate::ChangeSet) before it is applied.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+
+use crate::change_set::{ChangeSet, ChangeSetError, ChangeSetResult, ChangeSetStatus};
+use crate::change_status::{ChangeStatus, ComponentChangeStatus};
+use crate::component::diff::ComponentDiff;
+use crate::standard_model::objects_from_rows;
+use crate::ws_event::{WsEvent, WsPayload};
+use crate::{
+    pk, ChangeSetPk, DalContext, HistoryActor, HistoryEvent, Tenancy, Timestamp, User, UserPk,
+    Visibility, Workspace, WsEventResult,
+};
+
+const LIST_REVIEWS: &str = include_str!("../queries/change_set/list_reviews.sql");
+const LAST_WRITTEN_AT: &str = include_str!("../queries/change_set/last_written_at.sql");
+
+pk!(ChangeSetReviewPk);
+
+/// The verdict of a [`ChangeSetReview`].
+#[remain::sorted]
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, AsRefStr,
+)]
+pub enum ChangeSetReviewStatus {
+    Approved,
+    ChangesRequested,
+    Commented,
+}
+
+/// A review left on a [`ChangeSet`] by a [`User`] associated with its [`Workspace`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChangeSetReview {
+    pub pk: ChangeSetReviewPk,
+    pub change_set_pk: ChangeSetPk,
+    pub reviewer_user_pk: UserPk,
+    pub status: ChangeSetReviewStatus,
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub tenancy: Tenancy,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+impl ChangeSetReview {
+    /// Leaves a review on the provided [`ChangeSet`] as the [`User`] acting in the
+    /// [`DalContext`]. The [`ChangeSet`] must be open and [`ready for
+    /// review`](ChangeSet::request_review()), and users cannot review a change set they
+    /// requested review for themselves.
+    #[instrument(skip(ctx, comment))]
+    pub async fn new(
+        ctx: &DalContext,
+        change_set: &ChangeSet,
+        status: ChangeSetReviewStatus,
+        comment: Option<String>,
+    ) -> ChangeSetResult<Self> {
+        if change_set.status != ChangeSetStatus::Open {
+            return Err(ChangeSetError::NotOpen(change_set.pk, change_set.status));
+        }
+        if change_set.review_requested_at.is_none() {
+            return Err(ChangeSetError::NotReadyForReview(change_set.pk));
+        }
+
+        let reviewer_user_pk = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => *user_pk,
+            HistoryActor::SystemInit => return Err(ChangeSetError::ReviewRequiresUser),
+        };
+        if change_set.review_requested_by_user_pk == Some(reviewer_user_pk) {
+            return Err(ChangeSetError::SelfReview(reviewer_user_pk));
+        }
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(ChangeSetError::NoWorkspaceInTenancy)?;
+        if !User::is_member_of_workspace(ctx, reviewer_user_pk, workspace_pk).await? {
+            return Err(ChangeSetError::ReviewerNotInWorkspace(reviewer_user_pk));
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM change_set_review_create_v1($1, $2, $3, $4, $5)",
+                &[
+                    &change_set.pk,
+                    &reviewer_user_pk,
+                    &status.to_string(),
+                    &comment,
+                    ctx.tenancy(),
+                ],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let _history_event =
+            HistoryEvent::new(ctx, "change_set.review", "Change Set reviewed", &json).await?;
+        let object: Self = serde_json::from_value(json)?;
+
+        WsEvent::change_set_reviewed(ctx, change_set.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(object)
+    }
+
+    /// Lists every review left on the [`ChangeSet`] for the provided [`ChangeSetPk`], oldest
+    /// first.
+    #[instrument(skip(ctx))]
+    pub async fn list_for_change_set(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> ChangeSetResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST_REVIEWS, &[ctx.tenancy(), &change_set_pk])
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+}
+
+/// Whether or not a [`ChangeSet`] satisfies its [`Workspace's`](crate::Workspace) approval
+/// policy.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetApprovalStatus {
+    pub required_approvals: i32,
+    /// When anything in the change set was last written. Reviews left before then are stale.
+    pub last_written_at: Option<DateTime<Utc>>,
+    /// Reviewers whose latest current review is an approval.
+    pub approved_by: Vec<UserPk>,
+    /// Reviewers whose latest current review asks for changes.
+    pub changes_requested_by: Vec<UserPk>,
+    pub satisfied: bool,
+}
+
+impl ChangeSetApprovalStatus {
+    /// Evaluates the [`Workspace's`](crate::Workspace) approval policy for the provided
+    /// [`ChangeSet`]. Only current reviews are considered: reviews left before review was last
+    /// requested, or before the change set was last written to, are not.
+    #[instrument(skip_all)]
+    pub async fn new(ctx: &DalContext, change_set: &ChangeSet) -> ChangeSetResult<Self> {
+        let required_approvals = match change_set.tenancy.workspace_pk() {
+            Some(workspace_pk) => Workspace::get_by_pk(ctx, &workspace_pk)
+                .await?
+                .map(|workspace| *workspace.required_change_set_approvals())
+                .unwrap_or_default(),
+            None => 0,
+        };
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(LAST_WRITTEN_AT, &[ctx.tenancy(), &change_set.pk])
+            .await?;
+        let last_written_at: Option<DateTime<Utc>> = row.try_get("last_written_at")?;
+
+        let mut latest_by_reviewer: HashMap<UserPk, ChangeSetReviewStatus> = HashMap::new();
+        if let Some(review_requested_at) = change_set.review_requested_at {
+            let current_since = last_written_at.map_or(review_requested_at, |written| {
+                written.max(review_requested_at)
+            });
+            for review in ChangeSetReview::list_for_change_set(ctx, change_set.pk).await? {
+                if review.timestamp.created_at < current_since
+                    || review.status == ChangeSetReviewStatus::Commented
+                {
+                    continue;
+                }
+                latest_by_reviewer.insert(review.reviewer_user_pk, review.status);
+            }
+        }
+
+        let mut approved_by = Vec::new();
+        let mut changes_requested_by = Vec::new();
+        for (reviewer_user_pk, status) in latest_by_reviewer {
+            match status {
+                ChangeSetReviewStatus::Approved => approved_by.push(reviewer_user_pk),
+                ChangeSetReviewStatus::ChangesRequested => {
+                    changes_requested_by.push(reviewer_user_pk)
+                }
+                ChangeSetReviewStatus::Commented => {}
+            }
+        }
+        approved_by.sort();
+        changes_requested_by.sort();
+
+        let satisfied = required_approvals <= 0
+            || (change_set.review_requested_at.is_some()
+                && changes_requested_by.is_empty()
+                && approved_by.len() >= required_approvals as usize);
+
+        Ok(Self {
+            required_approvals,
+            last_written_at,
+            approved_by,
+            changes_requested_by,
+            satisfied,
+        })
+    }
+}
+
+/// Everything a reviewer needs to review a [`ChangeSet`]: which
+/// [`Components`](crate::Component) were added, modified or deleted, how they differ from
+/// _head_, and the reviews left so far.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetReviewSummary {
+    pub approval: ChangeSetApprovalStatus,
+    pub reviews: Vec<ChangeSetReview>,
+    pub component_stats: ComponentChangeStatus,
+    pub component_diffs: Vec<ComponentDiff>,
+}
+
+impl ChangeSetReviewSummary {
+    #[instrument(skip_all)]
+    pub async fn new(ctx: &DalContext, change_set: &ChangeSet) -> ChangeSetResult<Self> {
+        let change_set_ctx =
+            ctx.clone_with_new_visibility(Visibility::new_change_set(change_set.pk, false));
+
+        let component_stats = ComponentChangeStatus::new(&change_set_ctx).await?;
+        let mut component_diffs = Vec::new();
+        for group in component_stats.stats() {
+            if matches!(
+                group.component_status,
+                ChangeStatus::Added | ChangeStatus::Modified
+            ) {
+                component_diffs
+                    .push(ComponentDiff::new(&change_set_ctx, group.component_id).await?);
+            }
+        }
+
+        Ok(Self {
+            approval: ChangeSetApprovalStatus::new(ctx, change_set).await?,
+            reviews: ChangeSetReview::list_for_change_set(ctx, change_set.pk).await?,
+            component_stats,
+            component_diffs,
+        })
+    }
+}
+
+impl WsEvent {
+    pub async fn change_set_review_requested(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ChangeSetReviewRequested(change_set_pk)).await
+    }
+
+    pub async fn change_set_reviewed(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ChangeSetReviewed(change_set_pk)).await
+    }
+}
@@ -71,6 +71,11 @@ impl ComponentChangeStatus {
         Ok(component_stats)
     }
 
+    /// Returns the per-[`Component`](crate::Component) statistics.
+    pub fn stats(&self) -> &[ComponentChangeStatusGroup] {
+        &self.stats
+    }
+
     #[instrument(skip_all)]
     pub async fn list_added(
         ctx: &DalContext,
@@ -134,6 +139,10 @@ pub struct ComponentChangeStatusGroup {
 }
 
 impl ComponentChangeStatusGroup {
+    pub fn component_name(&self) -> &str {
+        &self.component_name
+    }
+
     pub fn new_from_rows(
         rows: Vec<PgRow>,
         component_status: ChangeStatus,
@@ -26,16 +26,16 @@ use crate::validation::ValidationConstructorError;
 use crate::ws_event::WsEventError;
 use crate::{
     impl_standard_model, node::NodeId, pk, provider::internal::InternalProviderError,
-    standard_model, standard_model_accessor, standard_model_belongs_to, standard_model_has_many,
-    ActionPrototypeError, AttributeContext, AttributeContextBuilderError, AttributeContextError,
-    AttributePrototype, AttributePrototypeArgument, AttributePrototypeArgumentError,
-    AttributePrototypeError, AttributePrototypeId, AttributeReadContext, ComponentType, DalContext,
-    EdgeError, ExternalProvider, ExternalProviderError, ExternalProviderId, FixError, FixId, Func,
-    FuncBackendKind, FuncError, HistoryActor, HistoryEventError, InternalProvider,
-    InternalProviderId, Node, NodeError, PropError, PropId, RootPropChild, Schema, SchemaError,
-    SchemaId, Socket, StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError,
-    UserPk, ValidationPrototypeError, ValidationResolverError, Visibility, WorkspaceError, WsEvent,
-    WsEventResult, WsPayload,
+    standard_model, standard_model_accessor, standard_model_accessor_ro, standard_model_belongs_to,
+    standard_model_has_many, ActionPrototypeError, AttributeContext, AttributeContextBuilderError,
+    AttributeContextError, AttributePrototype, AttributePrototypeArgument,
+    AttributePrototypeArgumentError, AttributePrototypeError, AttributePrototypeId,
+    AttributeReadContext, ComponentType, DalContext, EdgeError, ExternalProvider,
+    ExternalProviderError, ExternalProviderId, FixError, FixId, Func, FuncBackendKind, FuncError,
+    HistoryActor, HistoryEventError, InternalProvider, InternalProviderId, Node, NodeError,
+    PropError, PropId, RootPropChild, Schema, SchemaError, SchemaId, Socket, StandardModel,
+    StandardModelError, Tenancy, Timestamp, TransactionsError, UserPk, ValidationPrototypeError,
+    ValidationResolverError, Visibility, WorkspaceError, WsEvent, WsEventResult, WsPayload,
 };
 use crate::{AttributeValueId, QualificationError};
 use crate::{Edge, FixResolverError, NodeKind};
@@ -245,6 +245,11 @@ pub struct Component {
     kind: ComponentKind,
     pub deletion_user_pk: Option<UserPk>,
     needs_destroy: bool,
+    /// How often the resource scheduler refreshes the resource, overriding the
+    /// [`SchemaVariant`](crate::SchemaVariant)'s interval when set.
+    refresh_interval_secs: Option<i64>,
+    /// When the resource scheduler last enqueued a refresh of the resource.
+    resource_refresh_enqueued_at: Option<DateTime<Utc>>,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -387,6 +392,8 @@ impl Component {
 
     standard_model_accessor!(kind, Enum(ComponentKind), ComponentResult);
     standard_model_accessor!(needs_destroy, bool, ComponentResult);
+    standard_model_accessor!(refresh_interval_secs, OptionBigInt<i64>, ComponentResult);
+    standard_model_accessor_ro!(resource_refresh_enqueued_at, Option<DateTime<Utc>>);
 
     standard_model_belongs_to!(
         lookup_fn: schema,
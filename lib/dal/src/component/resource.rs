@@ -1,5 +1,6 @@
 //! This module contains the ability to work with "resources" for [`Components`](crate::Component).
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -19,6 +20,30 @@ use crate::{
 use crate::{RootPropChild, WsEventResult};
 
 impl Component {
+    /// Claims the refresh of the resource for the resource scheduler, returning whether it was
+    /// claimed. It isn't if another scheduler enqueued a refresh since the [`Component`] was
+    /// fetched, or is enqueuing one now.
+    pub async fn claim_resource_refresh(&mut self, ctx: &DalContext) -> ComponentResult<bool> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT enqueued_at FROM component_claim_resource_refresh_v1($1, $2, $3)",
+                &[ctx.tenancy(), &self.id, &self.resource_refresh_enqueued_at],
+            )
+            .await?;
+        let enqueued_at: Option<DateTime<Utc>> = row.try_get("enqueued_at")?;
+
+        Ok(match enqueued_at {
+            Some(enqueued_at) => {
+                self.resource_refresh_enqueued_at = Some(enqueued_at);
+                true
+            }
+            None => false,
+        })
+    }
+
     /// Calls [`Self::resource_by_id`] using the [`ComponentId`](Component) off [`Component`].
     pub async fn resource(&self, ctx: &DalContext) -> ComponentResult<ActionRunResult> {
         Self::resource_by_id(ctx, self.id).await
@@ -167,7 +192,7 @@ impl Component {
             None => return Ok(()),
         };
 
-        action.run(ctx, *self.id(), true).await?;
+        action.run(ctx, *self.id(), None, true).await?;
 
         Ok(())
     }
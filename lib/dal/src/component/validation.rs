@@ -12,6 +12,7 @@ use crate::func::backend::{
 };
 use crate::func::binding::FuncBinding;
 use crate::func::binding_return_value::FuncBindingReturnValue;
+use crate::func::execution::FuncExecutionSubject;
 use crate::ComponentError;
 use crate::{
     AttributeReadContext, Component, DalContext, ExternalProviderId, Func, FuncBackendKind,
@@ -83,8 +84,13 @@ impl Component {
         };
 
         // Now, we can load in the mutated args!
-        let (func_binding, _) =
-            FuncBinding::create_and_execute(ctx, mutated_args, *func.id()).await?;
+        let (func_binding, _) = FuncBinding::create_and_execute_for(
+            ctx,
+            mutated_args,
+            *func.id(),
+            FuncExecutionSubject::for_component(self.id),
+        )
+        .await?;
 
         let attribute_value_id = *attribute_value.id();
 
@@ -7,7 +7,8 @@ use crate::{
     component::ComponentKind, func::binding_return_value::FuncBindingReturnValueId,
     AttributeReadContext, AttributeValue, AttributeValueError, Component, ComponentId, DalContext,
     EncryptedSecret, FuncBindingReturnValue, InternalProvider, InternalProviderError, PropError,
-    PropId, SchemaVariantId, SecretError, SecretId, StandardModel, StandardModelError,
+    PropId, SchemaVariantId, SecretDefinition, SecretError, SecretId, StandardModel,
+    StandardModelError,
 };
 
 pub mod properties;
@@ -148,14 +149,26 @@ impl ComponentView {
                         .encryption_key()
                         .encrypt_and_encode(serde_json::to_string(&decrypted_secret.message())?);
 
+                    // Only the fields the definition marks as sensitive are redacted from what the
+                    // function sends back. Without a definition, every field is.
+                    let mut marker = serde_json::json!({
+                        "cycloneEncryptedDataMarker": true,
+                        "encryptedSecret": encoded
+                    });
+                    if let Some(definition) =
+                        SecretDefinition::find_for_kind(ctx, &decrypted_secret.kind()).await?
+                    {
+                        marker["sensitiveFields"] = definition
+                            .fields()
+                            .iter()
+                            .filter(|field| field.sensitive)
+                            .map(|field| field.name.as_str())
+                            .collect();
+                    }
+
                     *value = serde_json::to_value(&decrypted_secret)?;
                     match value.pointer_mut("/message") {
-                        Some(v) => {
-                            *v = serde_json::json!({
-                                "cycloneEncryptedDataMarker": true,
-                                "encryptedSecret": encoded
-                            })
-                        }
+                        Some(v) => *v = marker,
                         None => {
                             return Err(ComponentViewError::JSONPointerNotFound(
                                 value.clone(),
@@ -14,7 +14,7 @@ use crate::{
         processor::{JobQueueProcessor, JobQueueProcessorError},
         producer::{BlockingJobError, BlockingJobResult, JobProducer},
     },
-    HistoryActor, StandardModel, Tenancy, TenancyError, Visibility,
+    HistoryActor, SecretBackends, StandardModel, Tenancy, TenancyError, Visibility,
 };
 
 /// A context type which contains handles to common core service dependencies.
@@ -37,6 +37,8 @@ pub struct ServicesContext {
     pkgs_path: Option<PathBuf>,
     /// The URL of the module index
     module_index_url: Option<String>,
+    /// The backends used to resolve secrets stored outside of SI.
+    secret_backends: Arc<SecretBackends>,
 }
 
 impl ServicesContext {
@@ -58,6 +60,7 @@ impl ServicesContext {
             encryption_key,
             pkgs_path,
             module_index_url,
+            secret_backends: Arc::new(SecretBackends::from_env()),
         }
     }
 
@@ -93,6 +96,12 @@ impl ServicesContext {
         self.encryption_key.clone()
     }
 
+    /// Replaces the backends used to resolve secrets stored outside of SI, which are otherwise
+    /// [configured from the environment](SecretBackends::from_env).
+    pub fn set_secret_backends(&mut self, secret_backends: SecretBackends) {
+        self.secret_backends = Arc::new(secret_backends);
+    }
+
     /// Builds and returns a new [`Connections`].
     pub async fn connections(&self) -> PgPoolResult<Connections> {
         let pg_conn = self.pg_pool.get().await?;
@@ -433,6 +442,11 @@ impl DalContext {
         &self.services_context.encryption_key
     }
 
+    /// Gets a reference to the DAL context's secret backends.
+    pub fn secret_backends(&self) -> &SecretBackends {
+        &self.services_context.secret_backends
+    }
+
     /// Gets a reference to the dal context's tenancy.
     pub fn tenancy(&self) -> &Tenancy {
         &self.tenancy
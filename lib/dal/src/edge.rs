@@ -514,8 +514,10 @@ impl Edge {
         let tail_socket_id = &deleted_edge.tail_socket_id();
 
         // Note(victor): We hard delete the edge on the changeset so the status calculations
-        // does not think it is a newly created one (Yeah yeah I know I know)
-        deleted_edge.hard_delete(ctx_with_deleted).await?;
+        // does not think it is a newly created one (Yeah yeah I know I know). Edges that are
+        // deleted on head as well (e.g. when reverting an applied change set) are undeleted instead.
+        standard_model::restore_by_pk(ctx_with_deleted, Self::table_name(), deleted_edge.pk())
+            .await?;
 
         // Restore the Attribute Prototype Argument
         let head_component_id = *{
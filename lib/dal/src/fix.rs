@@ -15,14 +15,17 @@ use crate::{
     func::backend::js_action::ActionRunResult, impl_standard_model, pk, standard_model,
     standard_model_accessor, standard_model_accessor_ro, standard_model_belongs_to, ActionKind,
     ActionPrototype, ActionPrototypeError, ActionPrototypeId, AttributeValueId, Component,
-    ComponentError, ComponentId, DalContext, FixBatch, FixResolverError, FuncError,
+    ComponentError, ComponentId, DalContext, EdgeError, FixBatch, FixResolverError, FuncError,
     HistoryEventError, ResourceView, SchemaError, StandardModel, StandardModelError, Tenancy,
-    Timestamp, TransactionsError, Visibility, WsEvent, WsEventError, WsEventResult, WsPayload,
+    Timestamp, TransactionsError, UserError, Visibility, WsEvent, WsEventError, WsEventResult,
+    WsPayload,
 };
 use veritech_client::ResourceStatus;
 
 pub mod batch;
+pub mod graph;
 pub mod resolver;
+pub mod schedule;
 
 /// The completion status of a [`Fix`] or [`FixBatch`](crate::FixBatch).
 #[remain::sorted]
@@ -44,6 +47,9 @@ pub mod resolver;
 #[serde(rename_all = "camelCase")]
 #[strum(serialize_all = "camelCase")]
 pub enum FixCompletionStatus {
+    /// The [`Fix`] did not run since its [`FixBatch`](crate::FixBatch) was cancelled, or at least
+    /// one [`Fix`] in the [`FixBatch`](crate::FixBatch) did not.
+    Cancelled,
     /// The [`Fix`] or at least one [`Fix`] in the [`FixBatch`](crate::FixBatch) executed with
     /// error(s).
     Error,
@@ -73,12 +79,24 @@ pub enum FixError {
     BatchAlreadyFinished(FixId, FixBatchId),
     #[error("cannot set batch for {0}: fix batch ({1}) already started")]
     BatchAlreadyStarted(FixId, FixBatchId),
+    #[error("cannot retry fix batch ({0}) since it has not finished")]
+    BatchNotFinished(FixBatchId),
+    #[error("cannot cancel fix batch ({0}) since it already finished")]
+    CannotCancelFinishedBatch(FixBatchId),
     #[error(transparent)]
     Component(#[from] ComponentError),
+    #[error(transparent)]
+    Edge(#[from] EdgeError),
     #[error("completion status is empty")]
     EmptyCompletionStatus,
+    #[error("fix schedule has no fixes")]
+    EmptyFixSchedule,
     #[error(transparent)]
     FixResolver(#[from] FixResolverError),
+    #[error("fix schedule interval of {0:?} is shorter than the minimum")]
+    FixScheduleIntervalTooShort(std::time::Duration),
+    #[error("fixes can only be scheduled on head")]
+    FixScheduleNotOnHead,
     #[error(transparent)]
     Func(#[from] FuncError),
     #[error(transparent)]
@@ -97,6 +115,8 @@ pub enum FixError {
     MissingStartedTimestampForFix(FixId),
     #[error("no fixes in batch: fix batch is empty")]
     NoFixesInBatch(FixBatchId),
+    #[error("no fixes to retry: every fix in fix batch ({0}) succeeded")]
+    NoFixesToRetry(FixBatchId),
     #[error("cannot stamp batch or fix as finished since it has not yet been started")]
     NotYetStarted,
     #[error(transparent)]
@@ -110,6 +130,8 @@ pub enum FixError {
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
     #[error(transparent)]
+    User(#[from] UserError),
+    #[error(transparent)]
     WsEvent(#[from] WsEventError),
 }
 
@@ -250,7 +272,10 @@ impl Fix {
         self.stamp_started(ctx).await?;
 
         Ok(
-            match action_prototype.run(ctx, self.component_id, false).await {
+            match action_prototype
+                .run(ctx, self.component_id, Some(self.id), false)
+                .await
+            {
                 Ok(Some(run_result)) => {
                     let completion_status = match run_result.status {
                         ResourceStatus::Ok | ResourceStatus::Warning => {
@@ -368,7 +393,10 @@ impl Fix {
         Ok(Some(FixHistoryView {
             id: self.id,
             status: if resource.is_none() {
-                FixCompletionStatus::Unstarted
+                match self.completion_status() {
+                    Some(FixCompletionStatus::Cancelled) => FixCompletionStatus::Cancelled,
+                    _ => FixCompletionStatus::Unstarted,
+                }
             } else {
                 self.completion_status()
                     .copied()
@@ -1,20 +1,49 @@
 //! This module contains [`FixBatch`], which groups [`Fixs`](crate::Fix)
 //! and indicates whether or not all "fixes" in the group have completed executing.
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
 use telemetry::prelude::*;
 
+use crate::standard_model::TypeHint;
 use crate::{
     fix::{FixCompletionStatus, FixError, FixResult},
-    impl_standard_model, pk, standard_model, standard_model_accessor, standard_model_has_many,
-    DalContext, Fix, StandardModel, Tenancy, Timestamp, Visibility, WsEvent, WsEventResult,
-    WsPayload,
+    impl_standard_model, pk, standard_model, standard_model_accessor, standard_model_accessor_ro,
+    standard_model_has_many, DalContext, Fix, StandardModel, Tenancy, Timestamp, User, Visibility,
+    WorkspacePermission, WsEvent, WsEventResult, WsPayload,
 };
 
 pk!(FixBatchPk);
 pk!(FixBatchId);
 
+/// What a [`FixBatch`] does when one of its [`fixes`](crate::Fix) doesn't succeed.
+#[remain::sorted]
+#[derive(
+    Deserialize,
+    Serialize,
+    AsRefStr,
+    Display,
+    EnumString,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum FixBatchFailurePolicy {
+    /// Keep running every fix, including the ones depending on the fix that didn't succeed.
+    Continue,
+    /// Keep running the fixes that don't depend on the fix that didn't succeed.
+    #[default]
+    SkipDependents,
+    /// Stop starting fixes, letting the ones already running finish.
+    StopOnFirstFailure,
+}
+
 /// A batch of [`Fixs`](crate::Fix). Every [`Fix`](crate::Fix)
 /// must belong at one and only one [`batch`](Self).
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
@@ -41,6 +70,9 @@ pub struct FixBatch {
     finished_at: Option<String>,
     /// Indicates the state of the [`FixBatch`] when finished.
     completion_status: Option<FixCompletionStatus>,
+    failure_policy: FixBatchFailurePolicy,
+    /// Indicates when the [`FixBatch`] was cancelled when populated.
+    cancelled_at: Option<DateTime<Utc>>,
 }
 
 impl_standard_model! {
@@ -55,6 +87,8 @@ impl_standard_model! {
 impl FixBatch {
     #[instrument(skip_all)]
     pub async fn new(ctx: &DalContext, author: impl AsRef<str>) -> FixResult<Self> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::Apply).await?;
+
         let author = author.as_ref();
         let row = ctx
             .txns()
@@ -76,6 +110,8 @@ impl FixBatch {
         Option<Enum(FixCompletionStatus)>,
         FixResult
     );
+    standard_model_accessor!(failure_policy, Enum(FixBatchFailurePolicy), FixResult);
+    standard_model_accessor_ro!(cancelled_at, Option<DateTime<Utc>>);
 
     // TODO(nick): store the order (and what's sequential, conditional, parallel, etc.) someday.
     standard_model_has_many!(
@@ -99,12 +135,20 @@ impl FixBatch {
                     .completion_status()
                     .ok_or(FixError::EmptyCompletionStatus)?
                 {
-                    FixCompletionStatus::Success => {}
+                    // Fixes are only left unstarted when an upstream fix didn't succeed, which
+                    // already determines the status.
+                    FixCompletionStatus::Success | FixCompletionStatus::Unstarted => {}
                     FixCompletionStatus::Failure => {
                         // If we see failures, we should still continue to see if there's an error.
-                        batch_completion_status = FixCompletionStatus::Failure
+                        if batch_completion_status == FixCompletionStatus::Success {
+                            batch_completion_status = FixCompletionStatus::Failure
+                        }
+                    }
+                    FixCompletionStatus::Cancelled => {
+                        // Cancellations take precedence over failures, but not over errors.
+                        batch_completion_status = FixCompletionStatus::Cancelled
                     }
-                    FixCompletionStatus::Error | FixCompletionStatus::Unstarted => {
+                    FixCompletionStatus::Error => {
                         // Only break on an error since errors take precedence over failures.
                         batch_completion_status = FixCompletionStatus::Error;
                         break;
@@ -135,6 +179,73 @@ impl FixBatch {
         }
     }
 
+    /// Cancels the batch: the [`fixes`](crate::Fix) that haven't started are cancelled instead
+    /// of run, while the running ones finish. Cancelling a cancelled batch does nothing.
+    pub async fn cancel(&mut self, ctx: &DalContext) -> FixResult<()> {
+        if self.finished_at.is_some() {
+            return Err(FixError::CannotCancelFinishedBatch(self.id));
+        }
+        if self.cancelled_at.is_some() {
+            return Ok(());
+        }
+
+        let cancelled_at = Utc::now();
+        self.timestamp.updated_at = standard_model::update(
+            ctx,
+            Self::table_name(),
+            "cancelled_at",
+            self.id(),
+            cancelled_at,
+            TypeHint::TimestampWithTimeZone,
+        )
+        .await?;
+        self.cancelled_at = Some(cancelled_at);
+        Ok(())
+    }
+
+    /// Creates a new batch, with the same failure policy, from the [`fixes`](crate::Fix) of this
+    /// finished one that didn't succeed: the ones that failed and the ones that didn't run
+    /// because of them or a cancellation.
+    pub async fn retry(
+        &self,
+        ctx: &DalContext,
+        author: impl AsRef<str>,
+    ) -> FixResult<(Self, Vec<Fix>)> {
+        if self.finished_at.is_none() {
+            return Err(FixError::BatchNotFinished(self.id));
+        }
+
+        let mut unsuccessful: Vec<Fix> = self
+            .fixes(ctx)
+            .await?
+            .into_iter()
+            .filter(|fix| fix.completion_status() != Some(&FixCompletionStatus::Success))
+            .collect();
+        if unsuccessful.is_empty() {
+            return Err(FixError::NoFixesToRetry(self.id));
+        }
+        // Fix ids are ordered by creation, which is the order they were requested in
+        unsuccessful.sort_by_key(|fix| *fix.id());
+
+        let mut batch = Self::new(ctx, author).await?;
+        batch.set_failure_policy(ctx, self.failure_policy).await?;
+        let mut fixes = Vec::with_capacity(unsuccessful.len());
+        for fix in unsuccessful {
+            fixes.push(
+                Fix::new(
+                    ctx,
+                    batch.id,
+                    *fix.attribute_value_id(),
+                    *fix.component_id(),
+                    *fix.action_prototype_id(),
+                )
+                .await?,
+            );
+        }
+
+        Ok((batch, fixes))
+    }
+
     pub fn author(&self) -> String {
         self.author.clone()
     }
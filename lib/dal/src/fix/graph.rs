@@ -0,0 +1,368 @@
+//! This module contains [`FixGraph`], which orders the [`fixes`](crate::Fix) of a
+//! [`FixBatch`](crate::FixBatch) by the relationships between their
+//! [`Components`](crate::Component), so independent fixes can run at the same time.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use crate::edge::EdgeKind;
+use crate::fix::FixResult;
+use crate::job::definition::FixItem;
+use crate::socket::SocketKind;
+use crate::{
+    ActionKind, ComponentId, DalContext, Edge, Fix, FixBatchFailurePolicy, FixCompletionStatus,
+    FixError, FixId, Socket, StandardModel,
+};
+
+/// The [`Components`](crate::Component) each [`Component`](crate::Component) directly depends
+/// on.
+type ComponentDependencies = HashMap<ComponentId, HashSet<ComponentId>>;
+
+/// Whether or not a [`Fix`] in a [`FixGraph`] can run, given the fixes that have finished.
+#[remain::sorted]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixReadiness {
+    /// An upstream [`Fix`] finished without succeeding, so this one must not run.
+    Blocked(FixId),
+    /// Every upstream [`Fix`] finished as the [`FixBatchFailurePolicy`] requires.
+    Ready,
+    /// Some upstream [`Fix`] has not finished yet.
+    Waiting,
+}
+
+/// The upstream [`fixes`](Fix) that each fix in a batch waits on.
+///
+/// A [`Component`](crate::Component) depends on the ones it's connected to through its input
+/// sockets and on the frame it's in, directly or through other components. A fix waits on the
+/// fixes of the components its own component depends on, except that deletions are ordered the
+/// other way around: a component is deleted before the ones it depends on. Fixes of the same
+/// component run in the order they were requested.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixGraph {
+    dependencies: HashMap<FixId, Vec<FixId>>,
+}
+
+impl FixGraph {
+    /// Plans the given fixes, in the order they were requested, from the current
+    /// [`Edges`](Edge) between their [`Components`](crate::Component).
+    pub async fn new(ctx: &DalContext, fixes: &[FixItem]) -> FixResult<Self> {
+        let (live, with_deleted) = Self::component_dependencies(ctx).await?;
+
+        let mut planned = Vec::with_capacity(fixes.len());
+        for fix_item in fixes {
+            let fix = Fix::get_by_id(ctx, &fix_item.id)
+                .await?
+                .ok_or(FixError::MissingFix(fix_item.id))?;
+            planned.push((fix_item.id, fix_item.component_id, *fix.action_kind()));
+        }
+
+        Ok(Self::plan(&planned, &live, &with_deleted))
+    }
+
+    /// The fixes that must finish before the given one can run.
+    pub fn dependencies(&self, id: FixId) -> &[FixId] {
+        self.dependencies.get(&id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether or not the fix can run. Under [`FixBatchFailurePolicy::Continue`], upstream fixes
+    /// only need to finish, whether or not they succeeded.
+    pub fn readiness(
+        &self,
+        id: FixId,
+        finished: &HashMap<FixId, FixCompletionStatus>,
+        failure_policy: FixBatchFailurePolicy,
+    ) -> FixReadiness {
+        let mut readiness = FixReadiness::Ready;
+        for dependency in self.dependencies(id) {
+            match finished.get(dependency) {
+                Some(FixCompletionStatus::Success) => {}
+                Some(_) if failure_policy == FixBatchFailurePolicy::Continue => {}
+                Some(_) => return FixReadiness::Blocked(*dependency),
+                None => readiness = FixReadiness::Waiting,
+            }
+        }
+        readiness
+    }
+
+    fn plan(
+        fixes: &[(FixId, ComponentId, ActionKind)],
+        live: &ComponentDependencies,
+        with_deleted: &ComponentDependencies,
+    ) -> Self {
+        let mut upstream: HashMap<(ComponentId, bool), HashSet<ComponentId>> = HashMap::new();
+        for (_, component_id, action_kind) in fixes {
+            let deleting = *action_kind == ActionKind::Delete;
+            upstream
+                .entry((*component_id, deleting))
+                .or_insert_with(|| {
+                    // Deleted components have lost their edges, so deletions are ordered by the
+                    // edges they had
+                    transitive_dependencies(
+                        if deleting { with_deleted } else { live },
+                        *component_id,
+                    )
+                });
+        }
+
+        let mut dependencies: HashMap<FixId, Vec<FixId>> = HashMap::new();
+        for (index, (id, component_id, action_kind)) in fixes.iter().enumerate() {
+            let deleting = *action_kind == ActionKind::Delete;
+            let fix_dependencies: Vec<FixId> = fixes
+                .iter()
+                .enumerate()
+                .filter(
+                    |(other_index, (_, other_component_id, other_action_kind))| {
+                        let other_deleting = *other_action_kind == ActionKind::Delete;
+                        if other_component_id == component_id {
+                            *other_index < index
+                        } else if deleting != other_deleting {
+                            false
+                        } else if deleting {
+                            upstream[&(*other_component_id, true)].contains(component_id)
+                        } else {
+                            upstream[&(*component_id, false)].contains(other_component_id)
+                        }
+                    },
+                )
+                .map(|(_, (other_id, _, _))| *other_id)
+                .collect();
+            dependencies.insert(*id, fix_dependencies);
+        }
+
+        let mut graph = Self { dependencies };
+        graph.break_cycles(fixes.iter().map(|(id, _, _)| *id));
+        graph
+    }
+
+    /// Aggregation frames connect components both ways, which can make fixes wait on each other.
+    /// When no remaining fix can be scheduled, the earliest requested one stops waiting on the
+    /// other remaining fixes.
+    fn break_cycles(&mut self, order: impl IntoIterator<Item = FixId>) {
+        let mut remaining: Vec<FixId> = order.into_iter().collect();
+        let mut scheduled: HashSet<FixId> = HashSet::new();
+        while !remaining.is_empty() {
+            let position = remaining.iter().position(|id| {
+                self.dependencies(*id)
+                    .iter()
+                    .all(|dependency| scheduled.contains(dependency))
+            });
+            let id = match position {
+                Some(position) => remaining.remove(position),
+                None => {
+                    let id = remaining.remove(0);
+                    warn!(
+                        fix_id = %id,
+                        "fix is in a dependency cycle, ignoring its remaining dependencies",
+                    );
+                    if let Some(dependencies) = self.dependencies.get_mut(&id) {
+                        dependencies.retain(|dependency| scheduled.contains(dependency));
+                    }
+                    id
+                }
+            };
+            scheduled.insert(id);
+        }
+    }
+
+    /// Gathers what each [`Component`](crate::Component) depends on from the configuration
+    /// [`Edges`](Edge) and the symbolic ones placing components in frames, both without and with
+    /// the ones that were deleted.
+    async fn component_dependencies(
+        ctx: &DalContext,
+    ) -> FixResult<(ComponentDependencies, ComponentDependencies)> {
+        let ctx_with_deleted = &ctx.clone_with_delete_visibility();
+
+        let mut edges = Edge::list_for_kind(ctx_with_deleted, EdgeKind::Configuration).await?;
+        edges.extend(Edge::list_for_kind(ctx_with_deleted, EdgeKind::Symbolic).await?);
+
+        let mut frame_sockets = HashMap::new();
+        let mut live = ComponentDependencies::new();
+        let mut with_deleted = ComponentDependencies::new();
+        for edge in edges {
+            let tail_socket_id = edge.tail_socket_id();
+            let is_frame = match frame_sockets.get(&tail_socket_id) {
+                Some(is_frame) => *is_frame,
+                None => {
+                    let is_frame = Socket::get_by_id(ctx_with_deleted, &tail_socket_id)
+                        .await?
+                        .map(|socket| *socket.kind() == SocketKind::Frame)
+                        .unwrap_or(false);
+                    frame_sockets.insert(tail_socket_id, is_frame);
+                    is_frame
+                }
+            };
+            // Symbolic edges only matter when they put a component in a frame
+            if *edge.kind() == EdgeKind::Symbolic && !is_frame {
+                continue;
+            }
+
+            let head = ComponentId::from(edge.head_object_id());
+            let tail = ComponentId::from(edge.tail_object_id());
+            // Frame edges go from the child to its frame, which the child depends on. Other
+            // edges go from the component providing a value to the one consuming it.
+            let (dependent, dependency) = if is_frame { (tail, head) } else { (head, tail) };
+            if dependent == dependency {
+                continue;
+            }
+
+            if edge.visibility().deleted_at.is_none() {
+                live.entry(dependent).or_default().insert(dependency);
+            }
+            with_deleted
+                .entry(dependent)
+                .or_default()
+                .insert(dependency);
+        }
+
+        Ok((live, with_deleted))
+    }
+}
+
+fn transitive_dependencies(
+    dependencies: &ComponentDependencies,
+    component_id: ComponentId,
+) -> HashSet<ComponentId> {
+    let mut found = HashSet::new();
+    let mut stack = vec![component_id];
+    while let Some(current) = stack.pop() {
+        for dependency in dependencies.get(&current).into_iter().flatten() {
+            if *dependency != component_id && found.insert(*dependency) {
+                stack.push(*dependency);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids<T: From<ulid::Ulid>>(count: usize) -> Vec<T> {
+        (0..count).map(|_| T::from(ulid::Ulid::new())).collect()
+    }
+
+    fn depends(pairs: &[(ComponentId, ComponentId)]) -> ComponentDependencies {
+        let mut dependencies = ComponentDependencies::new();
+        for (dependent, dependency) in pairs {
+            dependencies
+                .entry(*dependent)
+                .or_default()
+                .insert(*dependency);
+        }
+        dependencies
+    }
+
+    #[test]
+    fn creates_wait_on_transitive_upstream_creates() {
+        let fix_ids: Vec<FixId> = ids(3);
+        let component_ids: Vec<ComponentId> = ids(4);
+        // The instance consumes the subnet, which consumes the vpc through a component that
+        // isn't being fixed
+        let (vpc, subnet, instance, other) = (
+            component_ids[0],
+            component_ids[1],
+            component_ids[2],
+            component_ids[3],
+        );
+        let live = depends(&[(instance, subnet), (subnet, other), (other, vpc)]);
+        let fixes = [
+            (fix_ids[0], instance, ActionKind::Create),
+            (fix_ids[1], subnet, ActionKind::Create),
+            (fix_ids[2], vpc, ActionKind::Create),
+        ];
+
+        let graph = FixGraph::plan(&fixes, &live, &live);
+
+        assert_eq!(graph.dependencies(fix_ids[0]), &[fix_ids[1], fix_ids[2]]);
+        assert_eq!(graph.dependencies(fix_ids[1]), &[fix_ids[2]]);
+        assert!(graph.dependencies(fix_ids[2]).is_empty());
+    }
+
+    #[test]
+    fn deletes_wait_on_downstream_deletes_and_independent_fixes_do_not_wait() {
+        let fix_ids: Vec<FixId> = ids(3);
+        let component_ids: Vec<ComponentId> = ids(3);
+        let (subnet, instance, unrelated) = (component_ids[0], component_ids[1], component_ids[2]);
+        let with_deleted = depends(&[(instance, subnet)]);
+        let fixes = [
+            (fix_ids[0], subnet, ActionKind::Delete),
+            (fix_ids[1], instance, ActionKind::Delete),
+            (fix_ids[2], unrelated, ActionKind::Create),
+        ];
+
+        let graph = FixGraph::plan(&fixes, &ComponentDependencies::new(), &with_deleted);
+
+        assert_eq!(graph.dependencies(fix_ids[0]), &[fix_ids[1]]);
+        assert!(graph.dependencies(fix_ids[1]).is_empty());
+        assert!(graph.dependencies(fix_ids[2]).is_empty());
+    }
+
+    #[test]
+    fn breaks_cycles_in_request_order() {
+        let fix_ids: Vec<FixId> = ids(2);
+        let component_ids: Vec<ComponentId> = ids(2);
+        let live = depends(&[
+            (component_ids[0], component_ids[1]),
+            (component_ids[1], component_ids[0]),
+        ]);
+        let fixes = [
+            (fix_ids[0], component_ids[0], ActionKind::Create),
+            (fix_ids[1], component_ids[1], ActionKind::Create),
+        ];
+
+        let graph = FixGraph::plan(&fixes, &live, &live);
+
+        assert!(graph.dependencies(fix_ids[0]).is_empty());
+        assert_eq!(graph.dependencies(fix_ids[1]), &[fix_ids[0]]);
+    }
+
+    #[test]
+    fn blocks_on_unsuccessful_dependencies() {
+        let fix_ids: Vec<FixId> = ids(3);
+        let graph = FixGraph {
+            dependencies: HashMap::from([(fix_ids[2], vec![fix_ids[0], fix_ids[1]])]),
+        };
+        let mut finished = HashMap::from([(fix_ids[0], FixCompletionStatus::Success)]);
+
+        let policy = FixBatchFailurePolicy::SkipDependents;
+
+        assert_eq!(
+            FixReadiness::Ready,
+            graph.readiness(fix_ids[0], &finished, policy)
+        );
+        assert_eq!(
+            FixReadiness::Waiting,
+            graph.readiness(fix_ids[2], &finished, policy)
+        );
+
+        finished.insert(fix_ids[1], FixCompletionStatus::Failure);
+        assert_eq!(
+            FixReadiness::Blocked(fix_ids[1]),
+            graph.readiness(fix_ids[2], &finished, policy)
+        );
+    }
+
+    #[test]
+    fn continue_policy_runs_after_unsuccessful_dependencies() {
+        let fix_ids: Vec<FixId> = ids(3);
+        let graph = FixGraph {
+            dependencies: HashMap::from([(fix_ids[2], vec![fix_ids[0], fix_ids[1]])]),
+        };
+        let mut finished = HashMap::from([(fix_ids[0], FixCompletionStatus::Error)]);
+        let policy = FixBatchFailurePolicy::Continue;
+
+        assert_eq!(
+            FixReadiness::Waiting,
+            graph.readiness(fix_ids[2], &finished, policy)
+        );
+
+        finished.insert(fix_ids[1], FixCompletionStatus::Failure);
+        assert_eq!(
+            FixReadiness::Ready,
+            graph.readiness(fix_ids[2], &finished, policy)
+        );
+    }
+}
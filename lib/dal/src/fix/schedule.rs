@@ -0,0 +1,239 @@
+//! This module contains [`FixSchedule`], which starts a [`FixBatch`] at a future time, either once
+//! or on a recurring interval, on behalf of the user who scheduled it.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use crate::fix::batch::FixBatchId;
+use crate::fix::{FixError, FixResult};
+use crate::job::definition::{FixItem, FixesJob};
+use crate::standard_model::TypeHint;
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, standard_model_accessor_ro,
+    ActionPrototype, ActionPrototypeId, AttributeValueId, Component, ComponentId, DalContext, Fix,
+    FixBatch, FixBatchFailurePolicy, StandardModel, Tenancy, Timestamp, User, UserPk, Visibility,
+    WorkspacePermission,
+};
+
+/// Recurring schedules can't run more often than this.
+pub const MIN_FIX_SCHEDULE_INTERVAL: Duration = Duration::from_secs(60);
+
+pk!(FixSchedulePk);
+pk!(FixScheduleId);
+
+/// A fix to run when a [`FixSchedule`] is due, as it would be requested through `fix/run`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FixScheduleItem {
+    pub attribute_value_id: AttributeValueId,
+    pub component_id: ComponentId,
+    pub action_prototype_id: ActionPrototypeId,
+}
+
+/// Starts a [`FixBatch`] of the same fixes when it's due. Schedules are run by the fix scheduler
+/// (see [`tasks`](crate::tasks)), which enqueues a [`FixesJob`] for each batch.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FixSchedule {
+    pk: FixSchedulePk,
+    id: FixScheduleId,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+
+    /// The author of every [`FixBatch`] started by the schedule.
+    author: String,
+    /// The user who scheduled the fixes, on behalf of whom they run.
+    user_pk: UserPk,
+    fixes: Vec<FixScheduleItem>,
+    failure_policy: FixBatchFailurePolicy,
+    /// When the schedule is due next, if it is.
+    next_run_at: Option<DateTime<Utc>>,
+    /// How often a recurring schedule runs.
+    interval_secs: Option<i64>,
+    last_run_at: Option<DateTime<Utc>>,
+    /// The [`FixBatch`] started by the last run, if it started one.
+    last_fix_batch_id: Option<FixBatchId>,
+    /// Why the last run failed to start a [`FixBatch`], if it did.
+    last_run_error: Option<String>,
+    cancelled_at: Option<DateTime<Utc>>,
+}
+
+impl_standard_model! {
+    model: FixSchedule,
+    pk: FixSchedulePk,
+    id: FixScheduleId,
+    table_name: "fix_schedules",
+    history_event_label_base: "fix_schedule",
+    history_event_message_name: "Fix Schedule"
+}
+
+impl FixSchedule {
+    /// Schedules the fixes to run at the given time, and then every `interval` if given. Fixes
+    /// only run on head, so they can only be scheduled there.
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        author: impl AsRef<str>,
+        user_pk: UserPk,
+        fixes: Vec<FixScheduleItem>,
+        failure_policy: FixBatchFailurePolicy,
+        run_at: DateTime<Utc>,
+        interval: Option<Duration>,
+    ) -> FixResult<Self> {
+        if !ctx.visibility().is_head() {
+            return Err(FixError::FixScheduleNotOnHead);
+        }
+        if fixes.is_empty() {
+            return Err(FixError::EmptyFixSchedule);
+        }
+        let interval_secs = match interval {
+            Some(interval) if interval < MIN_FIX_SCHEDULE_INTERVAL => {
+                return Err(FixError::FixScheduleIntervalTooShort(interval));
+            }
+            Some(interval) => Some(interval.as_secs() as i64),
+            None => None,
+        };
+
+        let author = author.as_ref();
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM fix_schedule_create_v1($1, $2, $3, $4, $5, $6, $7, $8)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &author,
+                    &user_pk,
+                    &serde_json::to_value(&fixes)?,
+                    &failure_policy.as_ref(),
+                    &run_at,
+                    &interval_secs,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    standard_model_accessor_ro!(author, String);
+    standard_model_accessor_ro!(user_pk, UserPk);
+    standard_model_accessor_ro!(failure_policy, FixBatchFailurePolicy);
+    standard_model_accessor_ro!(next_run_at, Option<DateTime<Utc>>);
+    standard_model_accessor_ro!(interval_secs, Option<i64>);
+    standard_model_accessor_ro!(last_run_at, Option<DateTime<Utc>>);
+    standard_model_accessor_ro!(cancelled_at, Option<DateTime<Utc>>);
+    standard_model_accessor!(last_fix_batch_id, Option<Pk(FixBatchId)>, FixResult);
+    standard_model_accessor!(last_run_error, Option<String>, FixResult);
+
+    pub fn fixes(&self) -> &[FixScheduleItem] {
+        &self.fixes
+    }
+
+    /// Stops the schedule from running again. Cancelling a cancelled schedule does nothing.
+    pub async fn cancel(&mut self, ctx: &DalContext) -> FixResult<()> {
+        if self.cancelled_at.is_some() {
+            return Ok(());
+        }
+
+        let cancelled_at = Utc::now();
+        standard_model::update(
+            ctx,
+            Self::table_name(),
+            "cancelled_at",
+            self.id(),
+            cancelled_at,
+            TypeHint::TimestampWithTimeZone,
+        )
+        .await?;
+        self.timestamp.updated_at = standard_model::update(
+            ctx,
+            Self::table_name(),
+            "next_run_at",
+            self.id(),
+            None::<DateTime<Utc>>,
+            TypeHint::TimestampWithTimeZone,
+        )
+        .await?;
+        self.cancelled_at = Some(cancelled_at);
+        self.next_run_at = None;
+        Ok(())
+    }
+
+    /// Claims the schedules that are due on head in every workspace, moving each to its next run.
+    /// The caller must run them, since they won't be claimed again until then, and record why on
+    /// the schedule if one fails to run (see [`Self::set_last_run_error`]).
+    pub async fn claim_due(ctx: &DalContext) -> FixResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query("SELECT object FROM fix_schedules_claim_due_v1()", &[])
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Starts a [`FixBatch`] of the scheduled fixes and enqueues the [`FixesJob`] running it.
+    /// Fixes whose [`Component`] or [`ActionPrototype`] no longer exists are left out, and no
+    /// batch is started if that leaves none.
+    ///
+    /// The user who scheduled the fixes must still be able to apply them in the workspace, since
+    /// they may have lost access since.
+    pub async fn run(&mut self, ctx: &DalContext) -> FixResult<Option<FixBatchId>> {
+        User::require_permission(ctx, self.user_pk, WorkspacePermission::Apply).await?;
+
+        let deleted_ctx = &ctx.clone_with_delete_visibility();
+
+        let mut runnable = Vec::with_capacity(self.fixes.len());
+        for item in &self.fixes {
+            let component_exists = Component::get_by_id(deleted_ctx, &item.component_id)
+                .await?
+                .is_some();
+            let action_exists = ActionPrototype::get_by_id(ctx, &item.action_prototype_id)
+                .await?
+                .is_some();
+            if component_exists && action_exists {
+                runnable.push(item);
+            } else {
+                warn!(
+                    fix_schedule_id = %self.id,
+                    component_id = %item.component_id,
+                    action_prototype_id = %item.action_prototype_id,
+                    "scheduled fix no longer exists, leaving it out",
+                );
+            }
+        }
+        if runnable.is_empty() {
+            return Ok(None);
+        }
+
+        let mut batch = FixBatch::new(ctx, &self.author).await?;
+        batch.set_failure_policy(ctx, self.failure_policy).await?;
+        let mut fixes = Vec::with_capacity(runnable.len());
+        for item in runnable {
+            let fix = Fix::new(
+                ctx,
+                *batch.id(),
+                item.attribute_value_id,
+                item.component_id,
+                item.action_prototype_id,
+            )
+            .await?;
+            fixes.push(FixItem::from(&fix));
+        }
+
+        ctx.enqueue_job(FixesJob::new(ctx, fixes, *batch.id()))
+            .await?;
+        self.set_last_fix_batch_id(ctx, Some(*batch.id())).await?;
+        self.set_last_run_error(ctx, None).await?;
+
+        Ok(Some(*batch.id()))
+    }
+}
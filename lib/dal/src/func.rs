@@ -9,9 +9,11 @@ use telemetry::prelude::*;
 use thiserror::Error;
 
 use crate::func::argument::FuncArgumentError;
+use crate::func::execution_cache::{FuncExecutionCache, FuncExecutionCacheError};
+use crate::standard_model::TypeHint;
 use crate::{
     generate_unique_id, impl_standard_model, pk, standard_model, standard_model_accessor,
-    standard_model_accessor_ro, DalContext, FuncBinding, FuncDescriptionContents,
+    standard_model_accessor_ro, DalContext, FuncBinding, FuncDescriptionContents, HistoryEvent,
     HistoryEventError, StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError,
     Visibility,
 };
@@ -24,6 +26,7 @@ pub mod binding;
 pub mod binding_return_value;
 pub mod description;
 pub mod execution;
+pub mod execution_cache;
 pub mod identity;
 pub mod intrinsics;
 
@@ -42,6 +45,8 @@ pub enum FuncError {
     FuncArgument(#[from] FuncArgumentError),
     #[error("func binding error: {0}")]
     FuncBinding(String),
+    #[error("func execution cache error: {0}")]
+    FuncExecutionCache(#[from] FuncExecutionCacheError),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     /// Could not find [`FuncArgument`](crate::FuncArgument) corresponding to the identity [`Func`].
@@ -107,6 +112,12 @@ pub struct Func {
     handler: Option<String>,
     code_base64: Option<String>,
     code_sha256: String,
+    /// Whether or not the func returns the same result for the same code and arguments, and so
+    /// whether or not its results can be cached. Funcs are assumed not to until marked otherwise.
+    deterministic: bool,
+    /// Whether or not the func's action honours dry runs, describing the changes it would make
+    /// instead of making them, and so whether or not it can be planned.
+    supports_dry_run: bool,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -180,6 +191,10 @@ impl Func {
         new_func.set_builtin(ctx, self.builtin).await?;
         new_func.set_handler(ctx, self.handler()).await?;
         new_func.set_code_base64(ctx, self.code_base64()).await?;
+        new_func.set_deterministic(ctx, self.deterministic).await?;
+        new_func
+            .set_supports_dry_run(ctx, self.supports_dry_run)
+            .await?;
 
         Ok(new_func)
     }
@@ -256,6 +271,59 @@ impl Func {
         FuncResult
     );
     standard_model_accessor!(handler, Option<String>, FuncResult);
-    standard_model_accessor!(code_base64, Option<String>, FuncResult);
+    standard_model_accessor!(@get_column_as_option_str code_base64);
     standard_model_accessor_ro!(code_sha256, String);
+    standard_model_accessor!(deterministic, bool, FuncResult);
+    standard_model_accessor!(supports_dry_run, bool, FuncResult);
+
+    /// Sets the code, refreshing its hash and dropping the cached results of the previous code if
+    /// no other [`Func`] has it (see [`FuncExecutionCache`]).
+    #[instrument(skip_all, level = "trace")]
+    pub async fn set_code_base64(
+        &mut self,
+        ctx: &DalContext,
+        value: Option<impl Into<String>>,
+    ) -> FuncResult<()> {
+        let value: Option<String> = value.map(Into::into);
+        let updated_at = standard_model::update(
+            ctx,
+            Self::table_name(),
+            "code_base64",
+            self.id(),
+            &value,
+            TypeHint::Text,
+        )
+        .await?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            &Self::history_event_label(vec!["updated"]),
+            &Self::history_event_message("updated"),
+            &serde_json::json![{
+                "pk": self.pk,
+                "id": self.id,
+                "field": "code_base64",
+                "value": &value,
+            }],
+        )
+        .await?;
+        self.timestamp.updated_at = updated_at;
+        self.code_base64 = value;
+
+        // The hash is generated by the database, so we read it back
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT code_sha256 FROM funcs_v1($1, $2) WHERE id = $3",
+                &[ctx.tenancy(), ctx.visibility(), self.id()],
+            )
+            .await?;
+        let previous_code_sha256 = std::mem::replace(&mut self.code_sha256, row.try_get(0)?);
+        if previous_code_sha256 != self.code_sha256 {
+            FuncExecutionCache::prune(ctx, Some(&previous_code_sha256)).await?;
+        }
+
+        Ok(())
+    }
 }
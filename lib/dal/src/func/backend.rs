@@ -6,7 +6,7 @@ use thiserror::Error;
 use tokio::sync::mpsc;
 use veritech_client::{
     ActionRunResultSuccess, Client as VeritechClient, FunctionResult, OutputStream,
-    ResolverFunctionResponseType,
+    ResolverFunctionResponseType, Runtime,
 };
 
 use crate::{label_list::ToLabelList, DalContext, Func, FuncId, PropKind, StandardModel};
@@ -86,11 +86,40 @@ pub enum FuncBackendKind {
     JsValidation,
     Map,
     Object,
+    /// A Python action, dispatched like a [`JsAction`](Self::JsAction).
+    PythonAction,
+    /// A Python attribute function, dispatched like a [`JsAttribute`](Self::JsAttribute).
+    PythonAttribute,
     String,
     Unset,
     Validation,
 }
 
+impl FuncBackendKind {
+    /// The language [`Runtime`] that executes functions of this kind, or `None` for the kinds
+    /// the dal executes itself.
+    pub fn runtime(&self) -> Option<Runtime> {
+        match self {
+            Self::JsAction
+            | Self::JsAttribute
+            | Self::JsReconciliation
+            | Self::JsSchemaVariantDefinition
+            | Self::JsValidation => Some(Runtime::JavaScript),
+            Self::PythonAction | Self::PythonAttribute => Some(Runtime::Python),
+            Self::Array
+            | Self::Boolean
+            | Self::Diff
+            | Self::Identity
+            | Self::Integer
+            | Self::Map
+            | Self::Object
+            | Self::String
+            | Self::Unset
+            | Self::Validation => None,
+        }
+    }
+}
+
 #[remain::sorted]
 #[derive(
     Deserialize,
@@ -181,6 +210,8 @@ impl ToLabelList for FuncBackendKind {}
 pub struct FuncDispatchContext {
     pub veritech: VeritechClient,
     pub output_tx: mpsc::Sender<OutputStream>,
+    /// The runtime to request from veritech, set from the [`Func`]'s backend kind on dispatch.
+    pub runtime: Runtime,
 }
 
 impl FuncDispatchContext {
@@ -190,6 +221,7 @@ impl FuncDispatchContext {
             Self {
                 veritech: ctx.veritech().clone(),
                 output_tx,
+                runtime: Runtime::default(),
             },
             rx,
         )
@@ -220,11 +252,12 @@ pub trait FuncDispatch: std::fmt::Debug {
     /// This private function creates the "request" to send to veritech in a shape that it
     /// likes. The request's type is [`Self`].
     fn create(
-        context: FuncDispatchContext,
+        mut context: FuncDispatchContext,
         func: &Func,
         args: &serde_json::Value,
     ) -> FuncBackendResult<Box<Self>> {
         let args = Self::Args::deserialize(args)?;
+        context.runtime = func.backend_kind().runtime().unwrap_or_default();
         let code_base64 = func
             .code_base64()
             .ok_or_else(|| FuncBackendError::DispatchMissingBase64(*func.id()))?;
@@ -37,6 +37,9 @@ impl FuncDispatch for FuncBackendJsAction {
             handler: handler.into(),
             code_base64: code_base64.into(),
             args: serde_json::to_value(args).unwrap(),
+            timeout_secs: None,
+            runtime: context.runtime,
+            dry_run: false,
         };
 
         Box::new(Self { context, request })
@@ -38,6 +38,8 @@ impl FuncDispatch for FuncBackendJsAttribute {
             component: args.component,
             response_type: args.response_type,
             code_base64: code_base64.into(),
+            timeout_secs: None,
+            runtime: context.runtime,
         };
 
         Box::new(Self { context, request })
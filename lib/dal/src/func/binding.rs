@@ -1,3 +1,4 @@
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use si_data_nats::NatsError;
@@ -5,7 +6,9 @@ use si_data_pg::PgError;
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::sync::mpsc;
-use veritech_client::{OutputStream, ResolverFunctionComponent};
+use veritech_client::{
+    FunctionResultFailure, FunctionResultFailureError, OutputStream, ResolverFunctionComponent,
+};
 
 use crate::func::execution::FuncExecutionPk;
 use crate::FuncError;
@@ -38,7 +41,8 @@ use crate::{DalContext, Tenancy};
 
 use super::{
     binding_return_value::{FuncBindingReturnValue, FuncBindingReturnValueError},
-    execution::{FuncExecution, FuncExecutionError},
+    execution::{FuncExecution, FuncExecutionError, FuncExecutionSubject},
+    execution_cache::{FuncExecutionCache, FuncExecutionCacheError},
     FuncId,
 };
 
@@ -59,6 +63,8 @@ pub enum FuncBindingError {
     },
     #[error("func backend return value error: {0}")]
     FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
+    #[error("func execution cache error: {0}")]
+    FuncExecutionCache(#[from] FuncExecutionCacheError),
     #[error("func execution tracking error: {0}")]
     FuncExecutionError(#[from] FuncExecutionError),
     #[error("unable to retrieve func for func binding: {0:?}")]
@@ -179,13 +185,24 @@ impl FuncBinding {
         ctx: &DalContext,
         args: serde_json::Value,
         func_id: FuncId,
+    ) -> FuncBindingResult<(Self, FuncBindingReturnValue)> {
+        Self::create_and_execute_for(ctx, args, func_id, FuncExecutionSubject::default()).await
+    }
+
+    /// Like [`Self::create_and_execute()`], but records what the execution ran for.
+    pub async fn create_and_execute_for(
+        ctx: &DalContext,
+        args: serde_json::Value,
+        func_id: FuncId,
+        subject: FuncExecutionSubject,
     ) -> FuncBindingResult<(Self, FuncBindingReturnValue)> {
         let func = Func::get_by_id(ctx, &func_id)
             .await?
             .ok_or(FuncError::NotFound(func_id))?;
         let func_binding = Self::new(ctx, args, func_id, func.backend_kind).await?;
 
-        let func_binding_return_value: FuncBindingReturnValue = func_binding.execute(ctx).await?;
+        let func_binding_return_value: FuncBindingReturnValue =
+            func_binding.execute_for(ctx, subject).await?;
 
         Ok((func_binding, func_binding_return_value))
     }
@@ -206,16 +223,112 @@ impl FuncBinding {
 
     // For a given [`FuncBinding`](Self), execute using veritech.
     pub async fn execute(&self, ctx: &DalContext) -> FuncBindingResult<FuncBindingReturnValue> {
-        let (func, execution, context, mut rx) = self.prepare_execution(ctx).await?;
-        let value = self.execute_critical_section(func.clone(), context).await?;
+        self.execute_for(ctx, FuncExecutionSubject::default()).await
+    }
+
+    /// Like [`Self::execute()`], but records what the execution ran for. If the execution fails,
+    /// the failure and its output are stored on the [`FuncExecution`] before returning the error.
+    ///
+    /// Results of cacheable [`Funcs`](Func) are looked up in the [`FuncExecutionCache`] first,
+    /// in which case nothing is dispatched and the [`FuncExecution`] recorded for the subject
+    /// points to the execution that produced the result.
+    pub async fn execute_for(
+        &self,
+        ctx: &DalContext,
+        subject: FuncExecutionSubject,
+    ) -> FuncBindingResult<FuncBindingReturnValue> {
+        let func: Func = self
+            .func(ctx)
+            .await?
+            .ok_or(FuncBindingError::FuncNotFound(self.pk))?;
+        let cacheable = FuncExecutionCache::is_cacheable(ctx, &func);
+        if cacheable {
+            if let Some(cached) = FuncExecutionCache::get(ctx, &func, &self.args).await? {
+                debug!(
+                    func_id = %func.id(),
+                    cached_from_func_execution_pk = %cached.func_execution_pk,
+                    "func execution cache hit"
+                );
+                let mut execution = FuncExecution::new(ctx, &func, self, subject).await?;
+                execution
+                    .set_cached_from(ctx, cached.func_execution_pk)
+                    .await?;
+
+                let func_binding_return_value = FuncBindingReturnValue::new(
+                    ctx,
+                    cached.unprocessed_value,
+                    cached.value,
+                    *func.id(),
+                    self.id,
+                    execution.pk(),
+                )
+                .await?;
+                execution
+                    .process_return_value(ctx, &func_binding_return_value)
+                    .await?;
+                execution
+                    .set_state(ctx, super::execution::FuncExecutionState::Success)
+                    .await?;
+
+                return Ok(func_binding_return_value);
+            }
+            debug!(func_id = %func.id(), "func execution cache miss");
+        }
+
+        let (func, mut execution, context, mut rx) = self.prepare_execution(ctx, subject).await?;
+        let result = self.execute_critical_section(func.clone(), context).await;
 
         let mut output = Vec::new();
         while let Some(output_stream) = rx.recv().await {
             output.push(output_stream);
         }
 
-        self.postprocess_execution(ctx, output, &func, value, execution)
-            .await
+        let value = match result {
+            Ok(value) => value,
+            Err(err) => {
+                let error = match &err {
+                    FuncBindingError::FuncBackendResultFailure { kind, message, .. } => {
+                        FunctionResultFailureError {
+                            kind: kind.clone(),
+                            message: message.clone(),
+                        }
+                    }
+                    other => FunctionResultFailureError {
+                        kind: "dal".to_owned(),
+                        message: other.to_string(),
+                    },
+                };
+                let failure = FunctionResultFailure {
+                    execution_id: execution.pk().to_string(),
+                    error,
+                    timestamp: u64::try_from(Utc::now().timestamp()).unwrap_or_default(),
+                    redacted: false,
+                };
+                execution.set_failure(ctx, output, failure).await?;
+                return Err(err);
+            }
+        };
+
+        let execution_pk = execution.pk();
+        let func_binding_return_value = self
+            .postprocess_execution(ctx, output, &func, value, execution)
+            .await?;
+
+        if cacheable {
+            FuncExecutionCache::set(
+                ctx,
+                &func,
+                &self.args,
+                (
+                    func_binding_return_value.unprocessed_value(),
+                    func_binding_return_value.value(),
+                ),
+                execution_pk,
+            )
+            .await?;
+        }
+
+        Ok(func_binding_return_value)
     }
 
     /// Perform function execution to veritech for a given [`Func`](crate::Func) and
@@ -230,13 +343,13 @@ impl FuncBinding {
             FuncBackendKind::JsValidation => {
                 FuncBackendJsValidation::create_and_execute(context, &func, &self.args).await
             }
-            FuncBackendKind::JsAction => {
+            FuncBackendKind::JsAction | FuncBackendKind::PythonAction => {
                 FuncBackendJsAction::create_and_execute(context, &func, &self.args).await
             }
             FuncBackendKind::JsReconciliation => {
                 FuncBackendJsReconciliation::create_and_execute(context, &func, &self.args).await
             }
-            FuncBackendKind::JsAttribute => {
+            FuncBackendKind::JsAttribute | FuncBackendKind::PythonAttribute => {
                 let args = FuncBackendJsAttributeArgs {
                     component: ResolverFunctionComponent {
                         data: veritech_client::ComponentView {
@@ -327,6 +440,7 @@ impl FuncBinding {
     pub async fn prepare_execution(
         &self,
         ctx: &DalContext,
+        subject: FuncExecutionSubject,
     ) -> FuncBindingResult<(
         Func,
         FuncExecution,
@@ -338,7 +452,7 @@ impl FuncBinding {
             .await?
             .ok_or(FuncBindingError::FuncNotFound(self.pk))?;
 
-        let mut execution = FuncExecution::new(ctx, &func, self).await?;
+        let mut execution = FuncExecution::new(ctx, &func, self, subject).await?;
 
         match self.backend_kind() {
             FuncBackendKind::Array
@@ -356,7 +470,9 @@ impl FuncBinding {
             | FuncBackendKind::JsAttribute
             | FuncBackendKind::JsReconciliation
             | FuncBackendKind::JsSchemaVariantDefinition
-            | FuncBackendKind::JsValidation => {
+            | FuncBackendKind::JsValidation
+            | FuncBackendKind::PythonAction
+            | FuncBackendKind::PythonAttribute => {
                 execution
                     .set_state(ctx, super::execution::FuncExecutionState::Dispatch)
                     .await?;
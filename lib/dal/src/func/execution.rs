@@ -1,7 +1,9 @@
 use crate::{standard_model_accessor_ro, Tenancy, TransactionsError};
+use chrono::{DateTime, Utc};
+use postgres_types::ToSql;
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
-use si_data_pg::PgError;
+use si_data_pg::{PgError, PgPoolError};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::sync::mpsc::Receiver;
@@ -9,8 +11,8 @@ use veritech_client::{FunctionResultFailure, OutputStream};
 
 use crate::standard_model::object_from_row;
 use crate::{
-    pk, DalContext, Func, FuncBackendKind, FuncBackendResponseType, HistoryEventError,
-    StandardModel, StandardModelError, Timestamp,
+    pk, ComponentId, DalContext, FixId, Func, FuncBackendKind, FuncBackendResponseType,
+    HistoryEventError, StandardModel, StandardModelError, Timestamp,
 };
 
 use super::{
@@ -28,6 +30,8 @@ pub enum FuncExecutionError {
     Nats(#[from] NatsError),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
+    #[error("pg pool error: {0}")]
+    PgPool(#[source] Box<PgPoolError>),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("standard model error: {0}")]
@@ -36,10 +40,60 @@ pub enum FuncExecutionError {
     Transactions(#[from] TransactionsError),
 }
 
+impl From<PgPoolError> for FuncExecutionError {
+    fn from(value: PgPoolError) -> Self {
+        Self::PgPool(Box::new(value))
+    }
+}
+
 pub type FuncExecutionResult<T> = Result<T, FuncExecutionError>;
 
+const FIND_BY_PK: &str = include_str!("../queries/func_execution/find_by_pk.sql");
+const LIST_FUNC_EXECUTIONS: &str = include_str!("../queries/func_execution/list.sql");
+
+/// The number of [`FuncExecutions`](FuncExecution) [`FuncExecution::list`] returns when the
+/// filter doesn't set a limit.
+pub const DEFAULT_FUNC_EXECUTION_LIST_LIMIT: i64 = 50;
+/// The most [`FuncExecutions`](FuncExecution) [`FuncExecution::list`] returns at once.
+pub const MAX_FUNC_EXECUTION_LIST_LIMIT: i64 = 500;
+
 pk!(FuncExecutionPk);
 
+/// What a [`FuncExecution`] ran for, so that it can be found again later.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FuncExecutionSubject {
+    pub component_id: Option<ComponentId>,
+    pub fix_id: Option<FixId>,
+}
+
+impl FuncExecutionSubject {
+    pub fn for_component(component_id: ComponentId) -> Self {
+        Self {
+            component_id: (component_id != ComponentId::NONE).then_some(component_id),
+            fix_id: None,
+        }
+    }
+
+    pub fn with_fix(mut self, fix_id: Option<FixId>) -> Self {
+        self.fix_id = fix_id;
+        self
+    }
+}
+
+/// Narrows down which [`FuncExecutions`](FuncExecution) [`FuncExecution::list`] returns. Every
+/// field is optional; an empty filter returns the most recent executions in the tenancy.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct FuncExecutionFilter {
+    pub func_id: Option<FuncId>,
+    pub component_id: Option<ComponentId>,
+    pub fix_id: Option<FixId>,
+    pub state: Option<FuncExecutionState>,
+    /// Defaults to [`DEFAULT_FUNC_EXECUTION_LIST_LIMIT`] and is capped at
+    /// [`MAX_FUNC_EXECUTION_LIST_LIMIT`].
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
 // Are these the right states? -- Adam
 #[remain::sorted]
 #[derive(
@@ -61,6 +115,10 @@ pub enum FuncExecutionState {
 /// It's not part of the [`standard model`](crate::standard_model) as it doesn't participate in
 /// [`change sets`](crate::ChangeSet), and is only used for reference. Essentially, this is the
 /// [`Func`](crate::Func) equivalent of a [`HistoryEvent`](crate::HistoryEvent).
+///
+/// Executions are written on their own connection rather than in the [`DalContext`]'s
+/// transactions, so that an execution (and in particular its failure) is kept even when the
+/// caller's transaction is rolled back.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct FuncExecution {
     pk: FuncExecutionPk,
@@ -68,6 +126,8 @@ pub struct FuncExecution {
     func_id: FuncId,
     func_binding_id: FuncBindingId,
     func_binding_args: serde_json::Value,
+    /// The sha256 of the args, for finding executions with the same inputs.
+    args_hash: Option<String>,
     backend_kind: FuncBackendKind,
     backend_response_type: FuncBackendResponseType,
     func_binding_return_value_id: Option<FuncBindingReturnValueId>,
@@ -77,6 +137,13 @@ pub struct FuncExecution {
     value: Option<serde_json::Value>,
     output_stream: Option<Vec<OutputStream>>,
     function_failure: Option<FunctionResultFailure>,
+    started_at: Option<DateTime<Utc>>,
+    finished_at: Option<DateTime<Utc>>,
+    component_id: Option<ComponentId>,
+    fix_id: Option<FixId>,
+    /// The execution whose cached result this one reused, if it was answered from the
+    /// [`FuncExecutionCache`](super::execution_cache::FuncExecutionCache) rather than run.
+    cached_from_func_execution_pk: Option<FuncExecutionPk>,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -90,32 +157,41 @@ impl FuncExecution {
         ctx: &DalContext,
         func: &Func,
         func_binding: &FuncBinding,
+        subject: FuncExecutionSubject,
     ) -> FuncExecutionResult<Self> {
-        let row = ctx
-            .txns()
-            .await?
-            .pg()
-            .query_one(
-                "SELECT object FROM func_execution_create_v1($1, $2, $3, $4, $5, $6, $7, $8, $9)",
-                &[
-                    ctx.tenancy(),
-                    &FuncExecutionState::Start.to_string(),
-                    &func.id(),
-                    &func_binding.id(),
-                    &func_binding.args(),
-                    &func_binding.backend_kind().to_string(),
-                    &func.backend_response_type().to_string(),
-                    &func.handler(),
-                    &func.code_base64(),
-                ],
-            )
-            .await?;
+        Self::write(
+            ctx,
+            "SELECT object FROM func_execution_create_v2($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+            &[
+                ctx.tenancy(),
+                &FuncExecutionState::Start.to_string(),
+                &func.id(),
+                &func_binding.id(),
+                &func_binding.args(),
+                &func_binding.backend_kind().to_string(),
+                &func.backend_response_type().to_string(),
+                &func.handler(),
+                &func.code_base64(),
+                &subject.component_id,
+                &subject.fix_id,
+            ],
+        )
+        .await
+    }
+
+    /// Runs a query returning a [`FuncExecution`] on its own connection, which commits it
+    /// immediately, and publishes the result.
+    async fn write(
+        ctx: &DalContext,
+        query: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> FuncExecutionResult<Self> {
+        // This query explicitly uses its own connection to bypass/avoid a ctx's database
+        // transaction--executions live outside of transactions!
+        let row = ctx.pg_pool().get().await?.query_one(query, params).await?;
         let json: serde_json::Value = row.try_get("object")?;
-        // This needs to be some kind of 'immediate mode' publish.
-        ctx.txns()
-            .await?
-            .nats()
-            .publish("funcExecution", &json)
+        ctx.nats_conn()
+            .publish("funcExecution", serde_json::to_vec(&json)?)
             .await?;
         let object: FuncExecution = serde_json::from_value(json)?;
         Ok(object)
@@ -130,23 +206,12 @@ impl FuncExecution {
         ctx: &DalContext,
         state: FuncExecutionState,
     ) -> FuncExecutionResult<()> {
-        let row = ctx
-            .txns()
-            .await?
-            .pg()
-            .query_one(
-                "SELECT object FROM func_execution_set_state_v1($1, $2)",
-                &[&self.pk, &state.to_string()],
-            )
-            .await?;
-        let json: serde_json::Value = row.try_get("object")?;
-        // This needs to be some kind of 'immediate mode' publish.
-        ctx.txns()
-            .await?
-            .nats()
-            .publish("funcExecution", &json)
-            .await?;
-        let mut object: FuncExecution = serde_json::from_value(json)?;
+        let mut object = Self::write(
+            ctx,
+            "SELECT object FROM func_execution_set_state_v1($1, $2)",
+            &[&self.pk, &state.to_string()],
+        )
+        .await?;
         std::mem::swap(self, &mut object);
         Ok(())
     }
@@ -181,22 +246,52 @@ impl FuncExecution {
         output_stream: Vec<OutputStream>,
     ) -> FuncExecutionResult<()> {
         let output_stream_json = serde_json::to_value(&output_stream)?;
-        let row = ctx
-            .txns()
-            .await?
-            .pg()
-            .query_one(
-                "SELECT object FROM func_execution_set_output_stream_v1($1, $2)",
-                &[&self.pk, &output_stream_json],
-            )
-            .await?;
-        let json: serde_json::Value = row.try_get("object")?;
-        ctx.txns()
-            .await?
-            .nats()
-            .publish("funcExecution", &json)
-            .await?;
-        let mut object: FuncExecution = serde_json::from_value(json)?;
+        let mut object = Self::write(
+            ctx,
+            "SELECT object FROM func_execution_set_output_stream_v1($1, $2)",
+            &[&self.pk, &output_stream_json],
+        )
+        .await?;
+        std::mem::swap(self, &mut object);
+        Ok(())
+    }
+
+    /// Stores the output and the failure of an execution that didn't produce a value, and marks it
+    /// as a [`Failure`](FuncExecutionState::Failure). The failure is committed straight away, so it
+    /// outlives the caller's transaction when that is rolled back over the error.
+    pub async fn set_failure(
+        &mut self,
+        ctx: &DalContext,
+        output_stream: Vec<OutputStream>,
+        function_failure: FunctionResultFailure,
+    ) -> FuncExecutionResult<()> {
+        let mut object = Self::write(
+            ctx,
+            "SELECT object FROM func_execution_set_failure_v1($1, $2, $3)",
+            &[
+                &self.pk,
+                &serde_json::to_value(&output_stream)?,
+                &serde_json::to_value(&function_failure)?,
+            ],
+        )
+        .await?;
+        std::mem::swap(self, &mut object);
+        Ok(())
+    }
+
+    /// Records that the execution was answered with the cached result of another one, whose output
+    /// stream it takes on.
+    pub async fn set_cached_from(
+        &mut self,
+        ctx: &DalContext,
+        cached_from_func_execution_pk: FuncExecutionPk,
+    ) -> FuncExecutionResult<()> {
+        let mut object = Self::write(
+            ctx,
+            "SELECT object FROM func_execution_set_cached_from_v1($1, $2)",
+            &[&self.pk, &cached_from_func_execution_pk],
+        )
+        .await?;
         std::mem::swap(self, &mut object);
         Ok(())
     }
@@ -207,27 +302,17 @@ impl FuncExecution {
         ctx: &DalContext,
         func_binding_return_value: &FuncBindingReturnValue,
     ) -> FuncExecutionResult<()> {
-        let row = ctx
-            .txns()
-            .await?
-            .pg()
-            .query_one(
-                "SELECT object FROM func_execution_set_return_value_v1($1, $2, $3, $4)",
-                &[
-                    &self.pk,
-                    &func_binding_return_value.id(),
-                    &func_binding_return_value.value(),
-                    &func_binding_return_value.unprocessed_value(),
-                ],
-            )
-            .await?;
-        let json: serde_json::Value = row.try_get("object")?;
-        ctx.txns()
-            .await?
-            .nats()
-            .publish("funcExecution", &json)
-            .await?;
-        let mut object: FuncExecution = serde_json::from_value(json)?;
+        let mut object = Self::write(
+            ctx,
+            "SELECT object FROM func_execution_set_return_value_v1($1, $2, $3, $4)",
+            &[
+                &self.pk,
+                &func_binding_return_value.id(),
+                &func_binding_return_value.value(),
+                &func_binding_return_value.unprocessed_value(),
+            ],
+        )
+        .await?;
         std::mem::swap(self, &mut object);
 
         Ok(())
@@ -253,6 +338,60 @@ impl FuncExecution {
         Ok(object)
     }
 
+    /// Finds the [`FuncExecution`] for the given [`FuncExecutionPk`] if it is in the tenancy of the
+    /// [`DalContext`].
+    pub async fn find_by_pk(
+        ctx: &DalContext,
+        pk: FuncExecutionPk,
+    ) -> FuncExecutionResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(FIND_BY_PK, &[ctx.tenancy(), &pk])
+            .await?;
+        match row {
+            Some(row) => Ok(Some(object_from_row(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Lists the [`FuncExecutions`](Self) in the tenancy matching the filter, most recent first.
+    pub async fn list(
+        ctx: &DalContext,
+        filter: &FuncExecutionFilter,
+    ) -> FuncExecutionResult<Vec<Self>> {
+        let limit = filter
+            .limit
+            .unwrap_or(DEFAULT_FUNC_EXECUTION_LIST_LIMIT)
+            .clamp(1, MAX_FUNC_EXECUTION_LIST_LIMIT);
+        let offset = filter.offset.unwrap_or(0).max(0);
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FUNC_EXECUTIONS,
+                &[
+                    ctx.tenancy(),
+                    &filter.func_id,
+                    &filter.component_id,
+                    &filter.fix_id,
+                    &filter.state.map(|state| state.to_string()),
+                    &limit,
+                    &offset,
+                ],
+            )
+            .await?;
+
+        let mut executions = Vec::with_capacity(rows.len());
+        for row in rows {
+            executions.push(object_from_row(row)?);
+        }
+        Ok(executions)
+    }
+
     pub async fn get_latest_execution_by_func_id(
         ctx: &DalContext,
         func_id: &FuncId,
@@ -281,6 +420,30 @@ impl FuncExecution {
         self.unprocessed_value.as_ref()
     }
 
+    pub fn args_hash(&self) -> Option<&str> {
+        self.args_hash.as_deref()
+    }
+
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.started_at
+    }
+
+    pub fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.finished_at
+    }
+
+    pub fn component_id(&self) -> Option<ComponentId> {
+        self.component_id
+    }
+
+    pub fn fix_id(&self) -> Option<FixId> {
+        self.fix_id
+    }
+
+    pub fn cached_from_func_execution_pk(&self) -> Option<FuncExecutionPk> {
+        self.cached_from_func_execution_pk
+    }
+
     standard_model_accessor_ro!(func_id, FuncId);
     standard_model_accessor_ro!(function_failure, Option<FunctionResultFailure>);
 }
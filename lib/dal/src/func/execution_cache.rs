@@ -0,0 +1,167 @@
+//! This module contains [`FuncExecutionCache`], which reuses the results of deterministic
+//! [`Func`] executions instead of dispatching identical requests to veritech again.
+//!
+//! Entries are keyed on the [`Func`]'s code hash, handler, backend kind and response type, and on
+//! a hash of the arguments. Changing a [`Func`]'s code changes its key, so stale results are never
+//! returned; the entries for code no [`Func`] has anymore are dropped as it changes.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::func::execution::FuncExecutionPk;
+use crate::{DalContext, Func, FuncBackendKind, TransactionsError};
+
+const STATS: &str = include_str!("../queries/func_execution_cache/stats.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncExecutionCacheError {
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FuncExecutionCacheResult<T> = Result<T, FuncExecutionCacheError>;
+
+/// A cached result of a successful [`Func`] execution.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FuncExecutionCacheEntry {
+    pub unprocessed_value: Option<Value>,
+    pub value: Option<Value>,
+    /// The execution that produced the result, whose output stream belongs to it.
+    pub func_execution_pk: FuncExecutionPk,
+}
+
+/// How much the entries currently cached for the workspace have been used.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncExecutionCacheStats {
+    pub entries: i64,
+    /// Executions that were answered from the cache, counted from the
+    /// [`FuncExecutions`](crate::func::execution::FuncExecution) recorded for them.
+    pub hits: i64,
+    /// Executions whose results were stored, each of which was a cache miss.
+    pub stores: i64,
+}
+
+pub struct FuncExecutionCache;
+
+impl FuncExecutionCache {
+    /// Whether or not executions of the [`Func`] can be cached: it must be an attribute function
+    /// (which includes qualifications and code generation) that has opted in by being marked
+    /// deterministic.
+    pub fn is_cacheable(ctx: &DalContext, func: &Func) -> bool {
+        ctx.tenancy().workspace_pk().is_some()
+            && func.deterministic()
+            && matches!(
+                func.backend_kind(),
+                FuncBackendKind::JsAttribute | FuncBackendKind::PythonAttribute
+            )
+    }
+
+    /// Looks up the result of executing the [`Func`] with the given arguments.
+    ///
+    /// This is a plain read, so concurrent lookups never wait on each other. Hits are counted from
+    /// the [`FuncExecution`](crate::func::execution::FuncExecution) recorded for each one (see
+    /// [`set_cached_from()`](crate::func::execution::FuncExecution::set_cached_from)), which is
+    /// written outside of the [`DalContext`]'s transaction.
+    #[instrument(skip_all, level = "debug", fields(si.func.cache = Empty))]
+    pub async fn get(
+        ctx: &DalContext,
+        func: &Func,
+        args: &Value,
+    ) -> FuncExecutionCacheResult<Option<FuncExecutionCacheEntry>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM func_execution_cache_get_v1($1, $2, $3, $4, $5, $6)",
+                &[
+                    ctx.tenancy(),
+                    &func.backend_kind().as_ref(),
+                    &func.backend_response_type().as_ref(),
+                    &func.code_sha256(),
+                    &func.handler(),
+                    args,
+                ],
+            )
+            .await?;
+        let object: Option<Value> = row.try_get("object")?;
+        let entry = object.map(serde_json::from_value).transpose()?;
+
+        Span::current().record(
+            "si.func.cache",
+            if entry.is_some() { "hit" } else { "miss" },
+        );
+        Ok(entry)
+    }
+
+    /// Stores the result of executing the [`Func`] with the given arguments.
+    pub async fn set(
+        ctx: &DalContext,
+        func: &Func,
+        args: &Value,
+        (unprocessed_value, value): (Option<&Value>, Option<&Value>),
+        func_execution_pk: FuncExecutionPk,
+    ) -> FuncExecutionCacheResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM func_execution_cache_set_v1($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                &[
+                    ctx.tenancy(),
+                    &func.backend_kind().as_ref(),
+                    &func.backend_response_type().as_ref(),
+                    &func.code_sha256(),
+                    &func.handler(),
+                    args,
+                    &unprocessed_value,
+                    &value,
+                    &func_execution_pk,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Drops the entries for code that no [`Func`] in the workspace has anymore, in any change
+    /// set. Only the given code is checked if there is one. Returns how many entries were
+    /// dropped.
+    pub async fn prune(
+        ctx: &DalContext,
+        code_sha256: Option<&str>,
+    ) -> FuncExecutionCacheResult<i64> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT removed FROM func_execution_cache_prune_v1($1, $2)",
+                &[ctx.tenancy(), &code_sha256],
+            )
+            .await?;
+        Ok(row.try_get("removed")?)
+    }
+
+    pub async fn stats(ctx: &DalContext) -> FuncExecutionCacheResult<FuncExecutionCacheStats> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(STATS, &[ctx.tenancy()])
+            .await?;
+        Ok(FuncExecutionCacheStats {
+            entries: row.try_get("entries")?,
+            hits: row.try_get("hits")?,
+            stores: row.try_get("stores")?,
+        })
+    }
+}
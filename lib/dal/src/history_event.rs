@@ -1,4 +1,5 @@
 use crate::{Tenancy, TransactionsError};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use strum::Display as StrumDisplay;
 use thiserror::Error;
@@ -7,7 +8,17 @@ use si_data_nats::NatsError;
 use si_data_pg::PgError;
 use telemetry::prelude::*;
 
-use crate::{pk, DalContext, Timestamp, UserPk};
+use crate::{pk, ChangeSetPk, DalContext, Timestamp, UserPk};
+
+const LIST_HISTORY_EVENTS: &str = include_str!("queries/history_event/list.sql");
+
+/// The number of [`HistoryEvents`](HistoryEvent) returned by [`HistoryEvent::list`] when no
+/// limit is provided.
+pub const DEFAULT_HISTORY_EVENT_LIST_LIMIT: i64 = 100;
+/// The most [`HistoryEvents`](HistoryEvent) [`HistoryEvent::list`] will return at once.
+pub const MAX_HISTORY_EVENT_LIST_LIMIT: i64 = 1000;
+/// The first line of [`HistoryEvent::to_csv`]'s output.
+pub const HISTORY_EVENT_CSV_HEADER: &str = "pk,created_at,actor,label,message,change_set_pk,data\n";
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -48,6 +59,48 @@ impl From<UserPk> for HistoryActor {
 
 pk!(HistoryEventPk);
 
+/// Narrows down which [`HistoryEvents`](HistoryEvent) [`HistoryEvent::list`] returns. Every
+/// field is optional; an empty filter returns the most recent events in the tenancy.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEventFilter {
+    pub actor: Option<HistoryActor>,
+    /// Only events whose label starts with this, e.g. `"change_set."` or `"secret."`.
+    pub label_prefix: Option<String>,
+    /// Only events created at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Only events created before this time.
+    pub to: Option<DateTime<Utc>>,
+    pub change_set_pk: Option<ChangeSetPk>,
+    /// Only events about the object with this id (or pk).
+    pub object_id: Option<String>,
+    /// Defaults to [`DEFAULT_HISTORY_EVENT_LIST_LIMIT`] and is capped at
+    /// [`MAX_HISTORY_EVENT_LIST_LIMIT`]. See [`HistoryEventFilter::effective_limit()`].
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Only events listed after the one at this cursor (i.e. older than it). Unlike `offset`,
+    /// this stays cheap however deep into the history it points. See [`HistoryEvent::cursor()`].
+    pub before: Option<HistoryEventCursor>,
+}
+
+impl HistoryEventFilter {
+    /// The number of events [`HistoryEvent::list`] returns at most for this filter.
+    pub fn effective_limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_HISTORY_EVENT_LIST_LIMIT)
+            .clamp(1, MAX_HISTORY_EVENT_LIST_LIMIT)
+    }
+}
+
+/// The position of a [`HistoryEvent`] in the (newest first) order [`HistoryEvent::list`] returns
+/// events in, for walking through them page by page.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEventCursor {
+    pub created_at: DateTime<Utc>,
+    pub pk: HistoryEventPk,
+}
+
 /// HistoryEvents are the audit trail for things in SI. They track
 /// that a specific actor did something, and optionally store data
 /// associated with the activity for posterity.
@@ -58,6 +111,10 @@ pub struct HistoryEvent {
     pub actor: HistoryActor,
     pub message: String,
     pub data: serde_json::Value,
+    /// The [`ChangeSet`](crate::ChangeSet) the event happened in. Events recorded before change
+    /// sets were tracked have none.
+    #[serde(rename = "visibility_change_set_pk", default)]
+    pub change_set_pk: Option<ChangeSetPk>,
     #[serde(flatten)]
     pub tenancy: Tenancy,
     #[serde(flatten)]
@@ -79,8 +136,15 @@ impl HistoryEvent {
         let row = txns
             .pg()
             .query_one(
-                "SELECT object FROM history_event_create_v1($1, $2, $3, $4, $5)",
-                &[&label.to_string(), &actor, &message, &data, ctx.tenancy()],
+                "SELECT object FROM history_event_create_v1($1, $2, $3, $4, $5, $6)",
+                &[
+                    &label.to_string(),
+                    &actor,
+                    &message,
+                    &data,
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                ],
             )
             .await?;
         let json: serde_json::Value = row.try_get("object")?;
@@ -89,4 +153,98 @@ impl HistoryEvent {
         let object: HistoryEvent = serde_json::from_value(json)?;
         Ok(object)
     }
+    /// Lists the [`HistoryEvents`](HistoryEvent) in the [`DalContext`]'s tenancy matching the
+    /// provided [`filter`](HistoryEventFilter), newest first.
+    #[instrument(skip(ctx))]
+    pub async fn list(
+        ctx: &DalContext,
+        filter: &HistoryEventFilter,
+    ) -> HistoryEventResult<Vec<HistoryEvent>> {
+        let actor = filter
+            .actor
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
+        let limit = filter.effective_limit();
+        let offset = filter.offset.unwrap_or(0).max(0);
+        let before_created_at = filter.before.map(|cursor| cursor.created_at);
+        let before_pk = filter.before.map(|cursor| cursor.pk);
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_HISTORY_EVENTS,
+                &[
+                    ctx.tenancy(),
+                    &actor,
+                    &filter.label_prefix,
+                    &filter.from,
+                    &filter.to,
+                    &filter.change_set_pk,
+                    &filter.object_id,
+                    &limit,
+                    &offset,
+                    &before_created_at,
+                    &before_pk,
+                ],
+            )
+            .await?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let json: serde_json::Value = row.try_get("object")?;
+            events.push(serde_json::from_value(json)?);
+        }
+        Ok(events)
+    }
+
+    /// The position of this event in [`HistoryEvent::list`]'s order. Pass it as
+    /// [`before`](HistoryEventFilter::before) to list the events after it.
+    pub fn cursor(&self) -> HistoryEventCursor {
+        HistoryEventCursor {
+            created_at: self.timestamp.created_at,
+            pk: self.pk,
+        }
+    }
+
+    /// Renders the provided [`HistoryEvents`](HistoryEvent) as CSV, one event per line after a
+    /// header. The event data is embedded as JSON.
+    pub fn to_csv(events: &[HistoryEvent]) -> HistoryEventResult<String> {
+        let mut csv = String::from(HISTORY_EVENT_CSV_HEADER);
+        for event in events {
+            csv.push_str(&event.to_csv_line()?);
+        }
+        Ok(csv)
+    }
+
+    /// Renders this event as a single CSV line, matching [`HISTORY_EVENT_CSV_HEADER`].
+    pub fn to_csv_line(&self) -> HistoryEventResult<String> {
+        let fields = [
+            self.pk.to_string(),
+            self.timestamp.created_at.to_rfc3339(),
+            match self.actor {
+                HistoryActor::User(user_pk) => user_pk.to_string(),
+                HistoryActor::SystemInit => self.actor.to_string(),
+            },
+            self.label.clone(),
+            self.message.clone(),
+            self.change_set_pk
+                .map(|pk| pk.to_string())
+                .unwrap_or_default(),
+            serde_json::to_string(&self.data)?,
+        ];
+        let line: Vec<String> = fields.iter().map(String::as_str).map(csv_escape).collect();
+        Ok(format!("{}\n", line.join(",")))
+    }
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or newline.
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
 }
@@ -1,10 +1,15 @@
-use std::convert::TryFrom;
+use std::{collections::HashMap, convert::TryFrom};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+use tokio::task::JoinSet;
 
 use crate::{
-    fix::FixError,
+    fix::{
+        graph::{FixGraph, FixReadiness},
+        FixError,
+    },
     job::{
         consumer::{
             JobConsumer, JobConsumerError, JobConsumerMetadata, JobConsumerResult, JobInfo,
@@ -12,10 +17,15 @@ use crate::{
         producer::{JobProducer, JobProducerResult},
     },
     AccessBuilder, ActionKind, ActionPrototype, ActionPrototypeId, AttributeValueId, Component,
-    ComponentId, DalContext, DependentValuesUpdate, Fix, FixBatch, FixBatchId, FixCompletionStatus,
-    FixId, FixResolver, RootPropChild, StandardModel, Visibility, WsEvent,
+    ComponentId, DalContext, DependentValuesUpdate, Fix, FixBatch, FixBatchFailurePolicy,
+    FixBatchId, FixCompletionStatus, FixId, FixResolver, RootPropChild, StandardModel, Visibility,
+    WsEvent,
 };
 
+/// The most fixes of a batch that run at once. The rest wait for one of them to finish, even when
+/// nothing they depend on is still running.
+pub const MAX_CONCURRENT_FIXES: usize = 8;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FixItem {
     pub id: FixId,
@@ -24,11 +34,21 @@ pub struct FixItem {
     pub attribute_value_id: AttributeValueId,
 }
 
+impl From<&Fix> for FixItem {
+    fn from(fix: &Fix) -> Self {
+        Self {
+            id: *fix.id(),
+            action_prototype_id: *fix.action_prototype_id(),
+            component_id: *fix.component_id(),
+            attribute_value_id: *fix.attribute_value_id(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct FixesJobArgs {
     fixes: Vec<FixItem>,
     batch_id: FixBatchId,
-    started: bool,
 }
 
 impl From<FixesJob> for FixesJobArgs {
@@ -36,7 +56,6 @@ impl From<FixesJob> for FixesJobArgs {
         Self {
             fixes: value.fixes,
             batch_id: value.batch_id,
-            started: value.started,
         }
     }
 }
@@ -44,7 +63,6 @@ impl From<FixesJob> for FixesJobArgs {
 #[derive(Clone, Debug, Serialize)]
 pub struct FixesJob {
     fixes: Vec<FixItem>,
-    started: bool,
     batch_id: FixBatchId,
     access_builder: AccessBuilder,
     visibility: Visibility,
@@ -53,26 +71,11 @@ pub struct FixesJob {
 
 impl FixesJob {
     pub fn new(ctx: &DalContext, fixes: Vec<FixItem>, batch_id: FixBatchId) -> Box<Self> {
-        Self::new_raw(ctx, fixes, batch_id, false)
-    }
-
-    /// Used for creating another fix job in a "fixes" sequence.
-    fn new_iteration(ctx: &DalContext, fixes: Vec<FixItem>, batch_id: FixBatchId) -> Box<Self> {
-        Self::new_raw(ctx, fixes, batch_id, true)
-    }
-
-    fn new_raw(
-        ctx: &DalContext,
-        fixes: Vec<FixItem>,
-        batch_id: FixBatchId,
-        started: bool,
-    ) -> Box<Self> {
         let access_builder = AccessBuilder::from(ctx.clone());
         let visibility = *ctx.visibility();
 
         Box::new(Self {
             fixes,
-            started,
             batch_id,
             access_builder,
             visibility,
@@ -104,110 +107,300 @@ impl JobConsumerMetadata for FixesJob {
 #[async_trait]
 impl JobConsumer for FixesJob {
     async fn run(&self, ctx: &mut DalContext) -> JobConsumerResult<()> {
-        // Mark the batch as started if it has not been yet.
-        if !self.started {
-            let mut batch = FixBatch::get_by_id(ctx, &self.batch_id)
-                .await?
-                .ok_or(JobConsumerError::MissingFixBatch(self.batch_id))?;
-            batch.stamp_started(ctx).await?;
-        }
+        let mut batch = FixBatch::get_by_id(ctx, &self.batch_id)
+            .await?
+            .ok_or(JobConsumerError::MissingFixBatch(self.batch_id))?;
+        batch.stamp_started(ctx).await?;
+        let failure_policy = *batch.failure_policy();
 
         if self.fixes.is_empty() {
             return finish_batch(ctx, self.batch_id).await;
         }
-        let fix_item = &self.fixes[0];
 
-        let deleted_ctx = &ctx.clone_with_delete_visibility();
-        // Get the workflow for the action we need to run.
-        let component = Component::get_by_id(deleted_ctx, &fix_item.component_id)
-            .await?
-            .ok_or(JobConsumerError::ComponentNotFound(fix_item.component_id))?;
+        let graph = FixGraph::new(ctx, &self.fixes).await?;
+        // Each fix runs with its own context, which has to see the batch as started
+        ctx.commit().await?;
 
-        let action = ActionPrototype::get_by_id(ctx, &fix_item.action_prototype_id)
-            .await?
-            .ok_or_else(|| {
-                JobConsumerError::ActionPrototypeNotFound(fix_item.action_prototype_id)
-            })?;
+        let ctx_builder = ctx.services_context().into_builder(ctx.blocking());
+        let mut pending: Vec<&FixItem> = self.fixes.iter().collect();
+        let mut finished: HashMap<FixId, FixCompletionStatus> = HashMap::new();
+        let mut running = JoinSet::new();
+        let mut failure = None;
 
-        // Run the fix (via the action prototype).
-        let mut fix = Fix::get_by_id(ctx, &fix_item.id)
-            .await?
-            .ok_or(FixError::MissingFix(fix_item.id))?;
-        let resource = fix.run(ctx, &action).await?;
-        let completion_status: FixCompletionStatus = *fix
-            .completion_status()
-            .ok_or(FixError::EmptyCompletionStatus)?;
-
-        // Upsert the fix resolver.
-        FixResolver::upsert(
-            ctx,
-            *action.id(),
-            fix_item.attribute_value_id,
-            Some(matches!(completion_status, FixCompletionStatus::Success)),
-            *fix.id(),
-        )
-        .await?;
+        loop {
+            // Fixes that haven't started don't run once the batch is cancelled, or once a fix
+            // didn't succeed if the batch stops on the first failure
+            let cancelled = FixBatch::get_by_id(ctx, &self.batch_id)
+                .await?
+                .ok_or(JobConsumerError::MissingFixBatch(self.batch_id))?
+                .cancelled_at()
+                .is_some();
+            let stopped_by = match failure_policy {
+                FixBatchFailurePolicy::StopOnFirstFailure => finished
+                    .iter()
+                    .find(|(_, status)| {
+                        !matches!(
+                            status,
+                            FixCompletionStatus::Success | FixCompletionStatus::Unstarted
+                        )
+                    })
+                    .map(|(id, _)| *id),
+                FixBatchFailurePolicy::Continue | FixBatchFailurePolicy::SkipDependents => None,
+            };
+            if cancelled || stopped_by.is_some() {
+                for fix_item in pending.drain(..) {
+                    let (completion_status, message) = match stopped_by {
+                        Some(failed_id) if !cancelled => (
+                            FixCompletionStatus::Unstarted,
+                            format!("Skipped since fix {failed_id} did not succeed"),
+                        ),
+                        _ => (
+                            FixCompletionStatus::Cancelled,
+                            "Cancelled before it started".to_owned(),
+                        ),
+                    };
+                    skip_fix(ctx, fix_item, completion_status, message, self.batch_id).await?;
+                    finished.insert(fix_item.id, completion_status);
+                }
+            }
+
+            // Start every fix whose upstream fixes finished as the failure policy requires, up to
+            // the concurrency limit, and skip the ones where one didn't. Nothing new starts once
+            // running a fix has failed.
+            let mut index = 0;
+            while failure.is_none() && running.len() < MAX_CONCURRENT_FIXES && index < pending.len()
+            {
+                let fix_item = pending[index];
+                match graph.readiness(fix_item.id, &finished, failure_policy) {
+                    FixReadiness::Waiting => index += 1,
+                    FixReadiness::Ready => {
+                        pending.remove(index);
+                        let fix_ctx = ctx_builder
+                            .build(self.access_builder.build(self.visibility))
+                            .await?;
+                        let (fix_item, batch_id) = (fix_item.clone(), self.batch_id);
+                        running.spawn(async move {
+                            let fix_id = fix_item.id;
+                            (fix_id, run_fix(fix_ctx, fix_item, batch_id).await)
+                        });
+                    }
+                    FixReadiness::Blocked(upstream_id) => {
+                        pending.remove(index);
+                        skip_fix(
+                            ctx,
+                            fix_item,
+                            FixCompletionStatus::Unstarted,
+                            format!("Skipped since upstream fix {upstream_id} did not succeed"),
+                            self.batch_id,
+                        )
+                        .await?;
+                        finished.insert(fix_item.id, FixCompletionStatus::Unstarted);
+                        // Fixes that were waiting may depend on the one that was just skipped
+                        index = 0;
+                    }
+                }
+            }
+
+            // Don't hold a transaction open while waiting on the running fixes
+            ctx.commit().await?;
+            match running.join_next().await {
+                Some(Ok((id, Ok(completion_status)))) => {
+                    finished.insert(id, completion_status);
+                }
+                Some(Ok((id, Err(err)))) => {
+                    error!(
+                        error = ?err,
+                        fix_id = %id,
+                        batch_id = %self.batch_id,
+                        "failed to run fix",
+                    );
+                    failure.get_or_insert(err);
+                }
+                Some(Err(err)) => {
+                    error!(error = ?err, batch_id = %self.batch_id, "failed to join fix task");
+                    failure.get_or_insert(err.into());
+                }
+                None => break,
+            }
+        }
 
-        let logs: Vec<_> = match resource {
-            Some(r) => r
-                .logs
-                .iter()
-                .flat_map(|l| l.split('\n'))
-                .map(|l| l.to_owned())
-                .collect(),
-            None => vec![],
-        };
+        // Every fix that was running when one failed has finished by now
+        if let Some(err) = failure {
+            fail_batch(ctx, &self.fixes, &pending, &finished, self.batch_id, &err).await;
+            return Err(err);
+        }
 
-        let attribute_value = Component::root_prop_child_attribute_value_for_component(
-            ctx,
-            *component.id(),
-            RootPropChild::Resource,
-        )
-        .await?;
+        finish_batch(ctx, self.batch_id).await
+    }
+}
 
-        // Always retriggers confirmations, and propagates resource if it changed.
-        ctx.enqueue_job(DependentValuesUpdate::new(
-            ctx.access_builder(),
-            *ctx.visibility(),
-            vec![*attribute_value.id()],
-        ))
-        .await?;
+/// Runs a [`Fix`] with its own [`DalContext`], which is committed once the fix's resource has
+/// been refreshed.
+async fn run_fix(
+    ctx: DalContext,
+    fix_item: FixItem,
+    batch_id: FixBatchId,
+) -> JobConsumerResult<FixCompletionStatus> {
+    let deleted_ctx = &ctx.clone_with_delete_visibility();
+    // Get the workflow for the action we need to run.
+    let component = Component::get_by_id(deleted_ctx, &fix_item.component_id)
+        .await?
+        .ok_or(JobConsumerError::ComponentNotFound(fix_item.component_id))?;
 
-        // Commit progress so far, and wait for dependent values propagation so we can run
-        // consecutive fixes that depend on the /root/resource from the previous fix.
-        // `blocking_commit()` will wait for any jobs that have ben created through
-        // `enqueue_job(...)` to finish before moving on.
-        ctx.blocking_commit().await?;
-
-        component.act(ctx, ActionKind::Refresh).await?;
-
-        ctx.blocking_commit().await?;
-
-        WsEvent::fix_return(
-            ctx,
-            *fix.id(),
-            self.batch_id,
-            fix_item.attribute_value_id,
-            *action.kind(),
-            completion_status,
-            logs,
-        )
+    let action = ActionPrototype::get_by_id(&ctx, &fix_item.action_prototype_id)
         .await?
-        .publish_on_commit(ctx)
+        .ok_or_else(|| JobConsumerError::ActionPrototypeNotFound(fix_item.action_prototype_id))?;
+
+    // Run the fix (via the action prototype).
+    let mut fix = Fix::get_by_id(&ctx, &fix_item.id)
+        .await?
+        .ok_or(FixError::MissingFix(fix_item.id))?;
+    let resource = fix.run(&ctx, &action).await?;
+    let completion_status: FixCompletionStatus = *fix
+        .completion_status()
+        .ok_or(FixError::EmptyCompletionStatus)?;
+
+    // Upsert the fix resolver.
+    FixResolver::upsert(
+        &ctx,
+        *action.id(),
+        fix_item.attribute_value_id,
+        Some(matches!(completion_status, FixCompletionStatus::Success)),
+        *fix.id(),
+    )
+    .await?;
+
+    let logs: Vec<_> = match resource {
+        Some(r) => r
+            .logs
+            .iter()
+            .flat_map(|l| l.split('\n'))
+            .map(|l| l.to_owned())
+            .collect(),
+        None => vec![],
+    };
+
+    let attribute_value = Component::root_prop_child_attribute_value_for_component(
+        &ctx,
+        *component.id(),
+        RootPropChild::Resource,
+    )
+    .await?;
+
+    // Always retriggers confirmations, and propagates resource if it changed.
+    ctx.enqueue_job(DependentValuesUpdate::new(
+        ctx.access_builder(),
+        *ctx.visibility(),
+        vec![*attribute_value.id()],
+    ))
+    .await?;
+
+    // Commit progress so far, and wait for dependent values propagation so we can run
+    // downstream fixes that depend on the /root/resource from this fix.
+    // `blocking_commit()` will wait for any jobs that have ben created through
+    // `enqueue_job(...)` to finish before moving on.
+    ctx.blocking_commit().await?;
+
+    component.act(&ctx, ActionKind::Refresh).await?;
+
+    ctx.blocking_commit().await?;
+
+    WsEvent::fix_return(
+        &ctx,
+        *fix.id(),
+        batch_id,
+        fix_item.attribute_value_id,
+        *action.kind(),
+        completion_status,
+        logs,
+    )
+    .await?
+    .publish_on_commit(&ctx)
+    .await?;
+
+    ctx.commit().await?;
+
+    Ok(completion_status)
+}
+
+/// Finishes a [`Fix`] without running it, either since a fix it waited on didn't succeed or since
+/// the batch no longer runs fixes. Also finishes fixes that failed while running, unless they
+/// got as far as finishing.
+async fn skip_fix(
+    ctx: &DalContext,
+    fix_item: &FixItem,
+    completion_status: FixCompletionStatus,
+    message: String,
+    batch_id: FixBatchId,
+) -> JobConsumerResult<()> {
+    let mut fix = Fix::get_by_id(ctx, &fix_item.id)
+        .await?
+        .ok_or(FixError::MissingFix(fix_item.id))?;
+    if fix.finished_at().is_some() {
+        return Ok(());
+    }
+    if fix.started_at().is_none() {
+        fix.stamp_started(ctx).await?;
+    }
+    fix.stamp_finished(ctx, completion_status, Some(message), None)
         .await?;
 
-        if self.fixes.len() == 1 {
-            finish_batch(ctx, self.batch_id).await?;
+    WsEvent::fix_return(
+        ctx,
+        fix_item.id,
+        batch_id,
+        fix_item.attribute_value_id,
+        *fix.action_kind(),
+        completion_status,
+        vec![],
+    )
+    .await?
+    .publish_on_commit(ctx)
+    .await?;
+
+    ctx.commit().await?;
+    Ok(())
+}
+
+/// Finishes the batch once running one of its fixes failed: fixes that never started are skipped,
+/// the ones that ran without reporting back are marked as failed, and the batch is stamped
+/// finished. Errors are logged rather than returned, so that the caller can return the one that
+/// stopped the batch.
+async fn fail_batch(
+    ctx: &DalContext,
+    fixes: &[FixItem],
+    pending: &[&FixItem],
+    finished: &HashMap<FixId, FixCompletionStatus>,
+    batch_id: FixBatchId,
+    failure: &JobConsumerError,
+) {
+    for fix_item in fixes
+        .iter()
+        .filter(|fix_item| !finished.contains_key(&fix_item.id))
+    {
+        let (completion_status, message) = if pending.iter().any(|p| p.id == fix_item.id) {
+            (
+                FixCompletionStatus::Unstarted,
+                "Skipped since running another fix in the batch failed".to_owned(),
+            )
         } else {
-            ctx.enqueue_job(FixesJob::new_iteration(
-                ctx,
-                self.fixes.iter().skip(1).cloned().collect(),
-                self.batch_id,
-            ))
-            .await?;
+            (
+                FixCompletionStatus::Failure,
+                format!("Failed to run: {failure}"),
+            )
+        };
+        if let Err(err) = skip_fix(ctx, fix_item, completion_status, message, batch_id).await {
+            error!(error = ?err, fix_id = %fix_item.id, %batch_id, "failed to finish fix");
         }
+    }
 
-        Ok(())
+    let finished = async {
+        finish_batch(ctx, batch_id).await?;
+        ctx.commit().await?;
+        Ok::<_, JobConsumerError>(())
+    };
+    if let Err(err) = finished.await {
+        error!(error = ?err, %batch_id, "failed to finish fix batch");
     }
 }
 
@@ -220,7 +413,6 @@ impl TryFrom<JobInfo> for FixesJob {
         Ok(Self {
             fixes: args.fixes,
             batch_id: args.batch_id,
-            started: args.started,
             access_builder: job.access_builder,
             visibility: job.visibility,
             job: Some(job),
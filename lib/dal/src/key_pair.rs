@@ -1,14 +1,18 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
 use base64::{engine::general_purpose, Engine};
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
 use sodiumoxide::crypto::box_::{self, PublicKey as BoxPublicKey, SecretKey as BoxSecretKey};
+use sodiumoxide::crypto::sealedbox;
 use telemetry::prelude::*;
 use thiserror::Error;
 
 use crate::{
-    pk, standard_model_accessor_ro, DalContext, HistoryEvent, HistoryEventError, Timestamp,
-    TransactionsError, Workspace, WorkspaceError, WorkspacePk,
+    pk, standard_model_accessor_ro, DalContext, HistoryEvent, HistoryEventError, SecretPk,
+    Timestamp, TransactionsError, Workspace, WorkspaceError, WorkspacePk,
 };
 
 mod key_pair_box_public_key_serde;
@@ -16,10 +20,15 @@ mod key_pair_box_secret_key_serde;
 
 const PUBLIC_KEY_GET_CURRENT: &str = include_str!("./queries/public_key_get_current.sql");
 const KEY_PAIR_GET_BY_PK: &str = include_str!("queries/key_pair_get_by_pk.sql");
+const KEY_PAIR_LIST_SECRETS_FOR_REENCRYPTION: &str =
+    include_str!("queries/key_pair_list_secrets_for_reencryption.sql");
+const KEY_PAIR_REENCRYPT_SECRET: &str = include_str!("queries/key_pair_reencrypt_secret.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum KeyPairError {
+    #[error("error decoding crypted secret: {0}")]
+    Decode(#[from] base64::DecodeError),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("Invalid workspace: {0}")]
@@ -30,6 +39,8 @@ pub enum KeyPairError {
     NoCurrentKeyPair,
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
+    #[error("could not re-encrypt secret {0} with the new key pair")]
+    ReencryptionFailed(SecretPk),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("transactions error: {0}")]
@@ -120,6 +131,76 @@ impl KeyPair {
         Ok(serde_json::from_value(json)?)
     }
 
+    /// Replaces the current key pair of the [`DalContext`]'s workspace with a newly generated
+    /// one and re-encrypts every secret stored in SI (in every change set) with it. Previous key
+    /// pairs are kept so that archived versions of secrets can still be decrypted.
+    #[instrument(skip_all)]
+    pub async fn rotate(ctx: &DalContext) -> KeyPairResult<Self> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(KeyPairError::NoCurrentKeyPair)?;
+        let current = Self::get_current(ctx).await?;
+        let rotated = Self::new(ctx, &current.name).await?;
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                KEY_PAIR_LIST_SECRETS_FOR_REENCRYPTION,
+                &[&workspace_pk, &rotated.pk],
+            )
+            .await?;
+
+        let mut previous_key_pairs: HashMap<KeyPairPk, Self> = HashMap::new();
+        for row in &rows {
+            let secret_pk: SecretPk = row.try_get("pk")?;
+            let key_pair_pk: KeyPairPk = row.try_get("key_pair_pk")?;
+            let crypted: String = row.try_get("crypted")?;
+
+            let previous = match previous_key_pairs.entry(key_pair_pk) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(Self::get_by_pk(ctx, key_pair_pk).await?),
+            };
+
+            let message = sealedbox::open(
+                &general_purpose::STANDARD_NO_PAD.decode(crypted)?,
+                &previous.public_key,
+                &previous.secret_key,
+            )
+            .map_err(|_| KeyPairError::ReencryptionFailed(secret_pk))?;
+            let recrypted = sealedbox::seal(&message, &rotated.public_key);
+
+            ctx.txns()
+                .await?
+                .pg()
+                .execute(
+                    KEY_PAIR_REENCRYPT_SECRET,
+                    &[
+                        &secret_pk,
+                        &general_purpose::STANDARD_NO_PAD.encode(recrypted),
+                        &rotated.pk,
+                    ],
+                )
+                .await?;
+        }
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "key_pair.rotate",
+            "Key Pair rotated",
+            &serde_json::json![{
+                "previous_key_pair_pk": current.pk,
+                "key_pair_pk": rotated.pk,
+                "reencrypted_secrets": rows.len(),
+            }],
+        )
+        .await?;
+
+        Ok(rotated)
+    }
+
     standard_model_accessor_ro!(name, String);
     standard_model_accessor_ro!(workspace_pk, WorkspacePk);
     standard_model_accessor_ro!(public_key, BoxPublicKey);
@@ -89,7 +89,10 @@ pub use attribute::{
     },
 };
 pub use builtins::{BuiltinsError, BuiltinsResult};
-pub use change_set::{ChangeSet, ChangeSetError, ChangeSetPk, ChangeSetStatus};
+pub use change_set::{
+    ChangeSet, ChangeSetConflict, ChangeSetError, ChangeSetPk, ChangeSetRebaseReport,
+    ChangeSetStatus,
+};
 pub use code_view::{CodeLanguage, CodeView};
 pub use component::{
     resource::ResourceView, status::ComponentStatus, status::HistoryActorTimestamp, Component,
@@ -104,8 +107,9 @@ pub use diagram::{
     connection::Connection, connection::DiagramEdgeView, Diagram, DiagramError, DiagramKind,
 };
 pub use edge::{Edge, EdgeError, EdgeResult};
-pub use fix::batch::{FixBatch, FixBatchId};
+pub use fix::batch::{FixBatch, FixBatchFailurePolicy, FixBatchId};
 pub use fix::resolver::{FixResolver, FixResolverError, FixResolverId};
+pub use fix::schedule::{FixSchedule, FixScheduleId, FixScheduleItem};
 pub use fix::{Fix, FixCompletionStatus, FixError, FixId};
 pub use func::argument::FuncArgument;
 pub use func::binding_return_value::{FuncBindingReturnValue, FuncBindingReturnValueError};
@@ -116,7 +120,9 @@ pub use func::{
     binding::{FuncBinding, FuncBindingError, FuncBindingId},
     Func, FuncError, FuncId, FuncResult,
 };
-pub use history_event::{HistoryActor, HistoryEvent, HistoryEventError};
+pub use history_event::{
+    HistoryActor, HistoryEvent, HistoryEventCursor, HistoryEventError, HistoryEventFilter,
+};
 pub use index_map::IndexMap;
 pub use job::definition::DependentValuesUpdate;
 pub use job::processor::{JobQueueProcessor, NatsProcessor};
@@ -148,8 +154,11 @@ pub use schema::variant::root_prop::RootPropChild;
 pub use schema::variant::SchemaVariantError;
 pub use schema::{Schema, SchemaError, SchemaId, SchemaPk, SchemaVariant, SchemaVariantId};
 pub use secret::{
-    DecryptedSecret, EncryptedSecret, Secret, SecretAlgorithm, SecretError, SecretId, SecretKind,
-    SecretObjectType, SecretPk, SecretResult, SecretVersion,
+    DecryptedSecret, EncryptedSecret, Secret, SecretAlgorithm, SecretBackend, SecretBackendError,
+    SecretBackendKind, SecretBackends, SecretComponentConsumer, SecretConsumers, SecretDefinition,
+    SecretDefinitionField, SecretDefinitionId, SecretDefinitionPk, SecretDefinitionView,
+    SecretError, SecretFuncConsumer, SecretId, SecretKind, SecretObjectType, SecretPk,
+    SecretResult, SecretVersion,
 };
 pub use socket::{Socket, SocketArity, SocketId};
 pub use standard_model::{StandardModel, StandardModelError, StandardModelResult};
@@ -158,7 +167,9 @@ pub use status::{
 };
 pub use tenancy::{Tenancy, TenancyError};
 pub use timestamp::{Timestamp, TimestampError};
-pub use user::{User, UserClaim, UserError, UserPk, UserResult};
+pub use user::{
+    User, UserClaim, UserError, UserPk, UserResult, WorkspacePermission, WorkspaceRole,
+};
 pub use validation::prototype::{
     context::ValidationPrototypeContext, ValidationPrototype, ValidationPrototypeError,
     ValidationPrototypeId,
@@ -167,6 +178,7 @@ pub use validation::resolver::{
     ValidationResolver, ValidationResolverError, ValidationResolverId, ValidationStatus,
 };
 pub use visibility::{Visibility, VisibilityError};
+pub use workspace::member::{WorkspaceInvitation, WorkspaceInvitationPk, WorkspaceMember};
 pub use workspace::{Workspace, WorkspaceError, WorkspacePk, WorkspaceResult, WorkspaceSignup};
 pub use ws_event::{WsEvent, WsEventError, WsEventResult, WsPayload};
 
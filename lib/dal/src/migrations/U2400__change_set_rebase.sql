@@ -0,0 +1 @@
+ALTER TABLE change_sets ADD COLUMN rebased_at timestamp with time zone;
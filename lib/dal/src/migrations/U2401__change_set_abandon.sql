@@ -0,0 +1,43 @@
+CREATE OR REPLACE FUNCTION change_set_abandon_v1(this_change_set_pk ident,
+                                                 this_actor jsonb,
+                                                 this_tenancy jsonb,
+                                                 OUT timestamp_updated_at timestamp with time zone) AS
+$$
+DECLARE
+    standard_model  standard_models%ROWTYPE;
+    this_table_name regclass;
+    deleted_count   bigint;
+BEGIN
+    UPDATE change_sets
+    SET status     = 'Abandoned',
+        updated_at = clock_timestamp()
+    WHERE pk = this_change_set_pk
+      AND in_tenancy_v1(this_tenancy, change_sets.tenancy_workspace_pk)
+    RETURNING updated_at INTO timestamp_updated_at;
+
+    -- Nothing in an abandoned change set can ever be applied, so every row scoped to it (across
+    -- all standard model tables) is removed.
+    FOR standard_model IN SELECT * FROM standard_models
+        LOOP
+            this_table_name := standard_model.table_name::regclass;
+
+            EXECUTE format('DELETE FROM %1$I ' ||
+                           'WHERE visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, tenancy_workspace_pk)',
+                           this_table_name, this_change_set_pk, this_tenancy);
+            GET DIAGNOSTICS deleted_count = ROW_COUNT;
+
+            IF deleted_count > 0 THEN
+                PERFORM history_event_create_v1(standard_model.history_event_label_base || '.change_set.abandon',
+                                                this_actor,
+                                                standard_model.history_event_message_name ||
+                                                ' rows discarded by abandoned change set',
+                                                jsonb_build_object(
+                                                        'change_set_pk', this_change_set_pk,
+                                                        'count', deleted_count
+                                                    ),
+                                                this_tenancy);
+            END IF;
+        END LOOP;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
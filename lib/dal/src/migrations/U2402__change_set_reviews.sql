@@ -0,0 +1,38 @@
+ALTER TABLE workspaces ADD COLUMN required_change_set_approvals integer NOT NULL DEFAULT 0;
+
+ALTER TABLE change_sets ADD COLUMN review_requested_at timestamp with time zone;
+ALTER TABLE change_sets ADD COLUMN review_requested_by_user_pk ident;
+
+CREATE TABLE change_set_reviews
+(
+    pk                   ident primary key default ident_create_v1(),
+    tenancy_workspace_pk ident,
+    change_set_pk        ident                    NOT NULL,
+    reviewer_user_pk     ident                    NOT NULL,
+    status               text                     NOT NULL,
+    comment              text,
+    created_at           timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at           timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE INDEX ON change_set_reviews (change_set_pk);
+CREATE INDEX ON change_set_reviews (reviewer_user_pk);
+
+CREATE OR REPLACE FUNCTION change_set_review_create_v1(this_change_set_pk ident,
+                                                       this_reviewer_user_pk ident,
+                                                       this_status text,
+                                                       this_comment text,
+                                                       this_tenancy jsonb,
+                                                       OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_set_reviews%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+    INSERT INTO change_set_reviews (tenancy_workspace_pk, change_set_pk, reviewer_user_pk, status, comment)
+    VALUES (this_tenancy_record.tenancy_workspace_pk, this_change_set_pk, this_reviewer_user_pk, this_status,
+            this_comment)
+    RETURNING * INTO this_new_row;
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
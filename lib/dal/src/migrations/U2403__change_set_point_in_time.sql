@@ -0,0 +1,184 @@
+ALTER TABLE change_sets
+    ADD COLUMN applied_at timestamp with time zone,
+    ADD COLUMN as_of      timestamp with time zone;
+
+-- The version of each head object that an applied change set replaced. A NULL previous_object
+-- means the object did not exist on head before the change set was applied.
+CREATE TABLE change_set_head_archive
+(
+    pk                   ident primary key                 default ident_create_v1(),
+    change_set_pk        ident                    NOT NULL,
+    tenancy_workspace_pk ident,
+    table_name           text                     NOT NULL,
+    object_id            ident                    NOT NULL,
+    previous_object      jsonb,
+    created_at           timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE INDEX change_set_head_archive_change_set_pk_idx ON change_set_head_archive (change_set_pk);
+CREATE INDEX change_set_head_archive_object_idx ON change_set_head_archive (table_name, object_id);
+
+CREATE OR REPLACE FUNCTION change_set_apply_v1(this_change_set_pk ident,
+                                               this_actor jsonb,
+                                               this_tenancy jsonb,
+                                               OUT timestamp_updated_at timestamp with time zone) AS
+$$
+DECLARE
+    standard_model      standard_models%ROWTYPE;
+    this_table_name     regclass;
+    insert_column_names text;
+    update_set_names    text;
+    query               text;
+    updated_model       change_set_update_type_v1;
+BEGIN
+    timestamp_updated_at := clock_timestamp();
+    UPDATE change_sets
+    SET status     = 'Applied',
+        applied_at = timestamp_updated_at,
+        updated_at = timestamp_updated_at
+    WHERE pk = this_change_set_pk;
+
+    FOR standard_model IN SELECT * FROM standard_models
+        LOOP
+            this_table_name := standard_model.table_name::regclass;
+
+            SELECT string_agg(information_schema.columns.column_name::text, ',')
+            FROM information_schema.columns
+            WHERE information_schema.columns.table_name = standard_model.table_name
+              AND information_schema.columns.column_name NOT IN
+                  ('visibility_change_set_pk', 'pk', 'created_at', 'updated_at')
+              AND information_schema.columns.is_generated = 'NEVER'
+            INTO insert_column_names;
+
+            SELECT string_agg(information_schema.columns.column_name::text || ' = EXCLUDED.' ||
+                              information_schema.columns.column_name::text, ', ')
+            FROM information_schema.columns
+            WHERE information_schema.columns.table_name = standard_model.table_name
+              AND information_schema.columns.column_name NOT IN
+                  ('pk', 'id', 'tenancy_workspace_pk', 'visibility_change_set_pk', 'created_at', 'updated_at')
+              AND information_schema.columns.is_generated = 'NEVER'
+            INTO update_set_names;
+
+            -- Before head is overwritten, archive the current head version of every object the
+            -- change set touches (or NULL, if it does not exist on head yet) so that head can be
+            -- reconstructed as it was before this apply. See change_set_create_as_of_v1.
+            EXECUTE format('INSERT INTO change_set_head_archive (change_set_pk, tenancy_workspace_pk, table_name, object_id, previous_object) ' ||
+                           'SELECT DISTINCT ON (cs.id) %2$L, cs.tenancy_workspace_pk, %4$L, cs.id, ' ||
+                           '       (SELECT row_to_json(head.*)::jsonb ' ||
+                           '        FROM %1$I head ' ||
+                           '        WHERE head.id = cs.id ' ||
+                           '          AND head.visibility_change_set_pk = ident_nil_v1() ' ||
+                           '          AND head.visibility_deleted_at IS NULL ' ||
+                           '          AND head.tenancy_workspace_pk IS NOT DISTINCT FROM cs.tenancy_workspace_pk) ' ||
+                           'FROM %1$I cs ' ||
+                           'WHERE cs.visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, cs.tenancy_workspace_pk)',
+                           this_table_name, this_change_set_pk, this_tenancy, standard_model.table_name);
+
+            -- Ok, this looks neat, huh? What's going on?
+            --
+            -- If we've deleted something in a change set, then we want those
+            -- rows to conflict in head when we try to insert into head in the
+            -- next query below (i.e. we're looking to trigger the ON CONFLICT
+            -- behavior).
+            --
+            -- This will likely not do the correct thing if we have a deleted
+            -- and a not-deleted version of a record in a changeset
+            EXECUTE format('UPDATE %1$I ' ||
+                           '  SET visibility_deleted_at = clock_timestamp(), updated_at = clock_timestamp() ' ||
+                           'WHERE visibility_change_set_pk = ident_nil_v1() ' ||
+                           '  AND visibility_deleted_at IS NULL ' ||
+                           '  AND in_tenancy_v1(%3$L, tenancy_workspace_pk) ' ||
+                           '  AND id IN ( ' ||
+                           '      SELECT id ' ||
+                           '      FROM %1$I ' ||
+                           '      WHERE visibility_change_set_pk = %2$L ' ||
+                           '        AND in_tenancy_v1(%3$L, tenancy_workspace_pk) ' ||
+                           '        AND visibility_deleted_at IS NOT NULL ' ||
+                           '  )', this_table_name, this_change_set_pk, this_tenancy);
+
+            query := format('INSERT INTO %1$I (%2$s) ' ||
+                            'SELECT %2$s FROM %1$I WHERE %1$I.visibility_change_set_pk = %3$L ' ||
+                            '                            AND in_tenancy_v1(%5$L, tenancy_workspace_pk) ' ||
+                            'ON CONFLICT (id, ' ||
+                            '              tenancy_workspace_pk, ' ||
+                            '              visibility_change_set_pk) ' ||
+                            'DO UPDATE SET updated_at = clock_timestamp(), %4$s ' ||
+                            'RETURNING pk, id, tenancy_workspace_pk',
+                            this_table_name, insert_column_names, this_change_set_pk, update_set_names, this_tenancy);
+
+            FOR updated_model IN EXECUTE query
+                LOOP
+                    PERFORM history_event_create_v1(standard_model.history_event_label_base || '.change_set.apply',
+                                                    this_actor,
+                                                    standard_model.history_event_message_name ||
+                                                    ' update applied by change set',
+                                                    jsonb_build_object(
+                                                            'pk', updated_model.pk,
+                                                            'id', updated_model.id,
+                                                            'change_set_pk', this_change_set_pk
+                                                        ),
+                                                    jsonb_build_object('tenancy_workspace_pk', updated_model.tenancy_workspace_pk)
+                        );
+                END LOOP;
+        END LOOP;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+
+-- Creates a read-only "Historical" change set whose rows overlay head so that reading through it
+-- shows head as it was at this_as_of: every object touched by a change set applied after that
+-- moment is restored to the version it replaced (or hidden, if that change set created it).
+CREATE OR REPLACE FUNCTION change_set_create_as_of_v1(this_name text,
+                                                      this_as_of timestamp with time zone,
+                                                      this_tenancy jsonb,
+                                                      OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_sets%ROWTYPE;
+    archived            change_set_head_archive%ROWTYPE;
+    this_object         jsonb;
+    column_names        text;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+    INSERT INTO change_sets (name, status, tenancy_workspace_pk, as_of)
+    VALUES (this_name, 'Historical', this_tenancy_record.tenancy_workspace_pk, this_as_of)
+    RETURNING * INTO this_new_row;
+
+    FOR archived IN SELECT DISTINCT ON (a.table_name, a.object_id, a.tenancy_workspace_pk) a.*
+                    FROM change_set_head_archive a
+                             INNER JOIN change_sets ON change_sets.pk = a.change_set_pk
+                    WHERE in_tenancy_v1(this_tenancy, a.tenancy_workspace_pk)
+                      AND change_sets.applied_at > this_as_of
+                    ORDER BY a.table_name, a.object_id, a.tenancy_workspace_pk, change_sets.applied_at
+        LOOP
+            IF archived.previous_object IS NOT NULL THEN
+                this_object := archived.previous_object;
+            ELSE
+                EXECUTE format('SELECT row_to_json(t.*)::jsonb || jsonb_build_object(''visibility_deleted_at'', clock_timestamp()) ' ||
+                               'FROM %1$I t ' ||
+                               'WHERE t.id = %2$L ' ||
+                               '  AND t.visibility_change_set_pk = ident_nil_v1() ' ||
+                               '  AND t.visibility_deleted_at IS NULL ' ||
+                               '  AND t.tenancy_workspace_pk IS NOT DISTINCT FROM %3$L',
+                               archived.table_name, archived.object_id, archived.tenancy_workspace_pk)
+                    INTO this_object;
+                -- Already gone from head, so there is nothing to hide.
+                CONTINUE WHEN this_object IS NULL;
+            END IF;
+            this_object := this_object || jsonb_build_object('visibility_change_set_pk', this_new_row.pk);
+
+            SELECT string_agg(information_schema.columns.column_name::text, ',')
+            FROM information_schema.columns
+            WHERE information_schema.columns.table_name = archived.table_name
+              AND information_schema.columns.column_name != 'pk'
+              AND information_schema.columns.is_generated = 'NEVER'
+            INTO column_names;
+
+            EXECUTE format('INSERT INTO %1$I (%2$s) SELECT %2$s FROM jsonb_populate_record(NULL::%1$I, %3$L)',
+                           archived.table_name, column_names, this_object);
+        END LOOP;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,103 @@
+ALTER TABLE change_sets
+    ADD COLUMN reverts_change_set_pk ident;
+
+-- Writes the archived (pre-apply) version of an object into the target change set. If the object
+-- did not exist before the apply, the current head version is written as deleted instead so that
+-- the object is hidden when reading through the target change set.
+CREATE OR REPLACE FUNCTION change_set_head_archive_overlay_v1(this_target_change_set_pk ident,
+                                                              archived change_set_head_archive)
+    RETURNS void AS
+$$
+DECLARE
+    this_object  jsonb;
+    column_names text;
+BEGIN
+    IF archived.previous_object IS NOT NULL THEN
+        this_object := archived.previous_object;
+    ELSE
+        EXECUTE format('SELECT row_to_json(t.*)::jsonb || jsonb_build_object(''visibility_deleted_at'', clock_timestamp()) ' ||
+                       'FROM %1$I t ' ||
+                       'WHERE t.id = %2$L ' ||
+                       '  AND t.visibility_change_set_pk = ident_nil_v1() ' ||
+                       '  AND t.visibility_deleted_at IS NULL ' ||
+                       '  AND t.tenancy_workspace_pk IS NOT DISTINCT FROM %3$L',
+                       archived.table_name, archived.object_id, archived.tenancy_workspace_pk)
+            INTO this_object;
+        -- Already gone from head, so there is nothing to hide.
+        IF this_object IS NULL THEN
+            RETURN;
+        END IF;
+    END IF;
+    this_object := this_object || jsonb_build_object('visibility_change_set_pk', this_target_change_set_pk);
+
+    SELECT string_agg(information_schema.columns.column_name::text, ',')
+    FROM information_schema.columns
+    WHERE information_schema.columns.table_name = archived.table_name
+      AND information_schema.columns.column_name != 'pk'
+      AND information_schema.columns.is_generated = 'NEVER'
+    INTO column_names;
+
+    EXECUTE format('INSERT INTO %1$I (%2$s) SELECT %2$s FROM jsonb_populate_record(NULL::%1$I, %3$L)',
+                   archived.table_name, column_names, this_object);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION change_set_create_as_of_v1(this_name text,
+                                                      this_as_of timestamp with time zone,
+                                                      this_tenancy jsonb,
+                                                      OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_sets%ROWTYPE;
+    archived            change_set_head_archive%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+    INSERT INTO change_sets (name, status, tenancy_workspace_pk, as_of)
+    VALUES (this_name, 'Historical', this_tenancy_record.tenancy_workspace_pk, this_as_of)
+    RETURNING * INTO this_new_row;
+
+    FOR archived IN SELECT DISTINCT ON (a.table_name, a.object_id, a.tenancy_workspace_pk) a.*
+                    FROM change_set_head_archive a
+                             INNER JOIN change_sets ON change_sets.pk = a.change_set_pk
+                    WHERE in_tenancy_v1(this_tenancy, a.tenancy_workspace_pk)
+                      AND change_sets.applied_at > this_as_of
+                    ORDER BY a.table_name, a.object_id, a.tenancy_workspace_pk, change_sets.applied_at
+        LOOP
+            PERFORM change_set_head_archive_overlay_v1(this_new_row.pk, archived);
+        END LOOP;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Creates an open change set containing the inverse of everything the applied change set did:
+-- objects it modified or deleted are restored to the version it replaced, and objects it created
+-- are deleted.
+CREATE OR REPLACE FUNCTION change_set_revert_v1(this_change_set_pk ident,
+                                                this_name text,
+                                                this_tenancy jsonb,
+                                                OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_sets%ROWTYPE;
+    archived            change_set_head_archive%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+    INSERT INTO change_sets (name, status, tenancy_workspace_pk, reverts_change_set_pk)
+    VALUES (this_name, 'Open', this_tenancy_record.tenancy_workspace_pk, this_change_set_pk)
+    RETURNING * INTO this_new_row;
+
+    FOR archived IN SELECT DISTINCT ON (a.table_name, a.object_id, a.tenancy_workspace_pk) a.*
+                    FROM change_set_head_archive a
+                    WHERE a.change_set_pk = this_change_set_pk
+                      AND in_tenancy_v1(this_tenancy, a.tenancy_workspace_pk)
+                    ORDER BY a.table_name, a.object_id, a.tenancy_workspace_pk
+        LOOP
+            PERFORM change_set_head_archive_overlay_v1(this_new_row.pk, archived);
+        END LOOP;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
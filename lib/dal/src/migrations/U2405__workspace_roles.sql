@@ -0,0 +1,4 @@
+-- Everyone associated with a workspace before roles existed could do everything, so they keep
+-- doing so as owners.
+ALTER TABLE user_belongs_to_workspaces
+    ADD COLUMN role text NOT NULL DEFAULT 'Owner';
@@ -0,0 +1,56 @@
+CREATE TABLE workspace_invitations
+(
+    pk                  ident primary key default ident_create_v1(),
+    workspace_pk        ident                    NOT NULL,
+    email               text                     NOT NULL,
+    role                text                     NOT NULL,
+    invited_by_user_pk  ident,
+    accepted_by_user_pk ident,
+    accepted_at         timestamp with time zone,
+    created_at          timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at          timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE INDEX ON workspace_invitations (workspace_pk);
+-- Only one pending invitation per email address and workspace.
+CREATE UNIQUE INDEX ON workspace_invitations (workspace_pk, lower(email)) WHERE accepted_at IS NULL;
+
+CREATE OR REPLACE FUNCTION workspace_invitation_create_v1(this_workspace_pk ident,
+                                                          this_email text,
+                                                          this_role text,
+                                                          this_invited_by_user_pk ident,
+                                                          OUT object json) AS
+$$
+DECLARE
+    this_new_row workspace_invitations%ROWTYPE;
+BEGIN
+    -- Re-inviting someone replaces their pending invitation.
+    DELETE
+    FROM workspace_invitations
+    WHERE workspace_pk = this_workspace_pk
+      AND lower(email) = lower(this_email)
+      AND accepted_at IS NULL;
+
+    INSERT INTO workspace_invitations (workspace_pk, email, role, invited_by_user_pk)
+    VALUES (this_workspace_pk, this_email, this_role, this_invited_by_user_pk)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Associates the user with the workspace (restoring them if they were removed) with the given
+-- role.
+CREATE OR REPLACE FUNCTION user_associate_workspace_with_role_v1(
+    this_user_pk ident,
+    this_workspace_pk ident,
+    this_role text
+) RETURNS void AS
+$$
+BEGIN
+    INSERT INTO user_belongs_to_workspaces (user_pk, workspace_pk, role)
+    VALUES (this_user_pk, this_workspace_pk, this_role)
+    ON CONFLICT (user_pk, workspace_pk) DO UPDATE SET role                  = EXCLUDED.role,
+                                                      visibility_deleted_at = NULL,
+                                                      updated_at            = clock_timestamp();
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,41 @@
+ALTER TABLE history_events
+    ADD COLUMN visibility_change_set_pk ident;
+CREATE INDEX history_events_tenancy_created_at_idx ON history_events (tenancy_workspace_pk, created_at DESC);
+
+CREATE OR REPLACE FUNCTION history_event_create_v1(this_label text,
+                                                   this_actor jsonb,
+                                                   this_message text,
+                                                   this_data jsonb,
+                                                   this_tenancy jsonb,
+                                                   this_visibility jsonb,
+                                                   OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        history_events%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    INSERT INTO history_events (label, actor, message, data, tenancy_workspace_pk, visibility_change_set_pk)
+    VALUES (this_label, this_actor, this_message, this_data, this_tenancy_record.tenancy_workspace_pk,
+            (this_visibility ->> 'visibility_change_set_pk')::ident)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Events recorded from within the database (e.g. by change set apply) have no visibility of their
+-- own, but record the change set they belong to in their data.
+CREATE OR REPLACE FUNCTION history_event_create_v1(this_label text,
+                                                   this_actor jsonb,
+                                                   this_message text,
+                                                   this_data jsonb,
+                                                   this_tenancy jsonb,
+                                                   OUT object json) AS
+$$
+BEGIN
+    object := history_event_create_v1(this_label, this_actor, this_message, this_data, this_tenancy,
+                                      jsonb_build_object('visibility_change_set_pk', this_data ->> 'change_set_pk'));
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
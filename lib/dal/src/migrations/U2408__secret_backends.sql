@@ -0,0 +1,71 @@
+-- Secrets whose backend is not 'database' are references to a message stored outside of SI. Their
+-- crypted column is empty.
+ALTER TABLE encrypted_secrets
+    ADD COLUMN backend           text NOT NULL DEFAULT 'database',
+    ADD COLUMN backend_reference text;
+
+CREATE OR REPLACE VIEW secrets AS
+SELECT pk,
+       id,
+       tenancy_workspace_pk,
+       visibility_change_set_pk,
+       visibility_deleted_at,
+       key_pair_pk,
+       created_at,
+       updated_at,
+       name,
+       object_type,
+       kind,
+       backend,
+       backend_reference
+FROM encrypted_secrets;
+
+CREATE OR REPLACE FUNCTION encrypted_secret_create_external_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_name text,
+    this_object_type text,
+    this_kind text,
+    this_backend text,
+    this_backend_reference text,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           encrypted_secrets%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO encrypted_secrets (tenancy_workspace_pk,
+                                   visibility_change_set_pk,
+                                   name,
+                                   object_type,
+                                   kind,
+                                   crypted,
+                                   version,
+                                   algorithm,
+                                   key_pair_pk,
+                                   backend,
+                                   backend_reference)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_name,
+            this_object_type,
+            this_kind,
+            '',
+            'v1',
+            'sealedbox',
+            ident_nil_v1(),
+            this_backend,
+            this_backend_reference)
+    RETURNING * INTO this_new_row;
+
+    this_new_row.crypted = null;
+    this_new_row.version = null;
+    this_new_row.algorithm = null;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,20 @@
+-- Bumped each time a secret's message is replaced.
+ALTER TABLE encrypted_secrets
+    ADD COLUMN message_version integer NOT NULL DEFAULT 1;
+
+CREATE OR REPLACE VIEW secrets AS
+SELECT pk,
+       id,
+       tenancy_workspace_pk,
+       visibility_change_set_pk,
+       visibility_deleted_at,
+       key_pair_pk,
+       created_at,
+       updated_at,
+       name,
+       object_type,
+       kind,
+       backend,
+       backend_reference,
+       message_version
+FROM encrypted_secrets;
@@ -0,0 +1,115 @@
+-- Secret definitions describe the fields of a kind of secret. A secret's "kind" is the name of
+-- its definition.
+CREATE TABLE secret_definitions
+(
+    pk                          ident primary key default ident_create_v1(),
+    id                          ident not null default ident_create_v1(),
+    tenancy_workspace_pk        ident,
+    visibility_change_set_pk    ident                   NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    name                        text                     NOT NULL,
+    display_name                text,
+    description                 text,
+    fields                      jsonb                    NOT NULL DEFAULT '[]'::jsonb
+);
+CREATE UNIQUE INDEX unique_secret_definition_name_live ON secret_definitions (
+        name,
+        tenancy_workspace_pk,
+        visibility_change_set_pk)
+    WHERE visibility_deleted_at IS NULL;
+SELECT standard_model_table_constraints_v1('secret_definitions');
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('secret_definitions', 'model', 'secret_definition', 'Secret Definition');
+
+CREATE OR REPLACE FUNCTION secret_definition_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_name text,
+    this_display_name text,
+    this_description text,
+    this_fields jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           secret_definitions%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO secret_definitions (tenancy_workspace_pk,
+                                    visibility_change_set_pk,
+                                    name,
+                                    display_name,
+                                    description,
+                                    fields)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_name,
+            this_display_name,
+            this_description,
+            this_fields)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- The definitions for the kinds of secrets that used to be hardcoded. Creates whichever of them
+-- are missing on HEAD for the given tenancy.
+CREATE OR REPLACE FUNCTION secret_definition_create_builtins_v1(this_tenancy jsonb)
+RETURNS VOID AS
+$$
+DECLARE
+    this_builtin record;
+BEGIN
+    FOR this_builtin IN
+        SELECT *
+        FROM (VALUES ('awsAccessKey',
+                      'AWS Access Key',
+                      'An AWS access key',
+                      '[{"name": "accessKeyId", "displayName": "Access Key Id", "sensitive": false, "required": true, "pattern": null},
+                        {"name": "secretAccessKey", "displayName": "Secret Access Key", "sensitive": true, "required": true, "pattern": null}]'::jsonb),
+                     ('azureServicePrincipal',
+                      'Azure Service Principal',
+                      'An Azure service principal',
+                      '[{"name": "tenantId", "displayName": "Tenant Id", "sensitive": false, "required": true, "pattern": null},
+                        {"name": "clientId", "displayName": "Client Id", "sensitive": false, "required": true, "pattern": null},
+                        {"name": "clientSecret", "displayName": "Client Secret", "sensitive": true, "required": true, "pattern": null}]'::jsonb),
+                     ('dockerHub',
+                      'Docker Hub',
+                      'A Docker Hub credential',
+                      '[{"name": "username", "displayName": "Username", "sensitive": false, "required": true, "pattern": null},
+                        {"name": "password", "displayName": "Password", "sensitive": true, "required": true, "pattern": null}]'::jsonb),
+                     ('helmRepo',
+                      'Helm Repository',
+                      'A Helm repository credential',
+                      '[{"name": "username", "displayName": "Username", "sensitive": false, "required": true, "pattern": null},
+                        {"name": "password", "displayName": "Password", "sensitive": true, "required": true, "pattern": null}]'::jsonb))
+                 AS builtins (name, display_name, description, fields)
+        LOOP
+            IF NOT EXISTS(SELECT 1
+                          FROM secret_definitions
+                          WHERE name = this_builtin.name
+                            AND visibility_change_set_pk = ident_nil_v1()
+                            AND visibility_deleted_at IS NULL
+                            AND in_tenancy_v1(this_tenancy, secret_definitions.tenancy_workspace_pk)) THEN
+                PERFORM secret_definition_create_v1(this_tenancy,
+                                                    jsonb_build_object('visibility_change_set_pk', ident_nil_v1(),
+                                                                       'visibility_deleted_at', NULL),
+                                                    this_builtin.name,
+                                                    this_builtin.display_name,
+                                                    this_builtin.description,
+                                                    this_builtin.fields);
+            END IF;
+        END LOOP;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Workspaces that already exist won't import the builtin definitions, so create them here.
+SELECT secret_definition_create_builtins_v1(jsonb_build_object('tenancy_workspace_pk', workspaces.pk))
+FROM workspaces;
@@ -0,0 +1,102 @@
+-- Keeps enough about every function execution to inspect it after the fact: a hash of its args,
+-- when it ran, and the component and fix it ran for.
+ALTER TABLE func_executions
+    ADD COLUMN args_hash    text GENERATED ALWAYS AS (ENCODE(DIGEST(func_binding_args::text, 'sha256'), 'hex')) STORED,
+    ADD COLUMN started_at   timestamp with time zone,
+    ADD COLUMN finished_at  timestamp with time zone,
+    ADD COLUMN component_id ident,
+    ADD COLUMN fix_id       ident;
+
+CREATE INDEX ON func_executions (component_id);
+CREATE INDEX ON func_executions (fix_id);
+CREATE INDEX ON func_executions (tenancy_workspace_pk, created_at);
+
+CREATE OR REPLACE FUNCTION func_execution_create_v2(
+    this_tenancy jsonb,
+    this_state text,
+    this_func_id ident,
+    this_func_binding_id ident,
+    this_func_binding_args jsonb,
+    this_backend_kind text,
+    this_backend_response_type text,
+    this_handler text,
+    this_code_base64 text,
+    this_component_id ident,
+    this_fix_id ident,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        func_executions%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+
+    INSERT INTO func_executions (tenancy_workspace_pk,
+                                 state,
+                                 func_id,
+                                 func_binding_id,
+                                 func_binding_args,
+                                 backend_kind,
+                                 backend_response_type,
+                                 handler,
+                                 code_base64,
+                                 component_id,
+                                 fix_id)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_state,
+            this_func_id,
+            this_func_binding_id,
+            this_func_binding_args,
+            this_backend_kind,
+            this_backend_response_type,
+            this_handler,
+            this_code_base64,
+            this_component_id,
+            this_fix_id)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Stamps the start time when the execution starts running and the finish time when it succeeds or
+-- fails.
+CREATE OR REPLACE FUNCTION func_execution_set_state_v1(
+    this_pk ident,
+    this_state text,
+    OUT object json) AS
+$$
+BEGIN
+    UPDATE func_executions
+    SET state       = this_state,
+        started_at  = CASE
+                          WHEN this_state = 'Run' THEN COALESCE(started_at, clock_timestamp())
+                          ELSE started_at
+                      END,
+        finished_at = CASE
+                          WHEN this_state IN ('Success', 'Failure') THEN clock_timestamp()
+                          ELSE finished_at
+                      END,
+        updated_at  = clock_timestamp()
+    WHERE pk = this_pk
+    RETURNING row_to_json(func_executions.*) INTO object;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION func_execution_set_failure_v1(
+    this_pk ident,
+    this_output_stream jsonb,
+    this_function_failure jsonb,
+    OUT object json) AS
+$$
+BEGIN
+    UPDATE func_executions
+    SET state            = 'Failure',
+        output_stream    = this_output_stream,
+        function_failure = this_function_failure,
+        finished_at      = clock_timestamp(),
+        updated_at       = clock_timestamp()
+    WHERE pk = this_pk
+    RETURNING row_to_json(func_executions.*) INTO object;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
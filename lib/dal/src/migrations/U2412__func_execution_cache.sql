@@ -0,0 +1,136 @@
+-- Funcs whose result depends on more than their code and arguments (the clock, the network,
+-- randomness...) opt out of result caching.
+ALTER TABLE funcs ADD COLUMN deterministic bool NOT NULL DEFAULT TRUE;
+
+-- The results of successful executions, keyed on everything that determines them. Entries are
+-- shared by every change set of a workspace, since the same code run with the same arguments
+-- returns the same value regardless of where it ran.
+CREATE TABLE func_execution_cache
+(
+    pk                    ident primary key default ident_create_v1(),
+    tenancy_workspace_pk  ident,
+    created_at            timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at            timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    backend_kind          text                     NOT NULL,
+    backend_response_type text                     NOT NULL,
+    code_sha256           text                     NOT NULL,
+    handler               text                     NOT NULL,
+    args_hash             text                     NOT NULL,
+    unprocessed_value     jsonb,
+    value                 jsonb,
+    func_execution_pk     ident,
+    store_count           bigint                   NOT NULL DEFAULT 1,
+    hit_count             bigint                   NOT NULL DEFAULT 0,
+    last_hit_at           timestamp with time zone
+);
+CREATE UNIQUE INDEX unique_func_execution_cache_key ON func_execution_cache (
+        tenancy_workspace_pk,
+        backend_kind,
+        backend_response_type,
+        code_sha256,
+        handler,
+        args_hash);
+CREATE INDEX ON func_execution_cache (tenancy_workspace_pk, code_sha256);
+
+-- jsonb normalizes key order and whitespace, so equal arguments always hash the same.
+CREATE OR REPLACE FUNCTION func_execution_cache_args_hash_v1(this_args jsonb)
+RETURNS text AS
+$$
+    SELECT ENCODE(DIGEST(this_args::text, 'sha256'), 'hex');
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+-- Returns the cached result for the key, counting the hit, or nothing on a miss.
+CREATE OR REPLACE FUNCTION func_execution_cache_get_v1(
+    this_tenancy jsonb,
+    this_backend_kind text,
+    this_backend_response_type text,
+    this_code_sha256 text,
+    this_handler text,
+    this_args jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+
+    UPDATE func_execution_cache
+    SET hit_count   = hit_count + 1,
+        last_hit_at = clock_timestamp()
+    WHERE tenancy_workspace_pk IS NOT DISTINCT FROM this_tenancy_record.tenancy_workspace_pk
+      AND backend_kind = this_backend_kind
+      AND backend_response_type = this_backend_response_type
+      AND code_sha256 = this_code_sha256
+      AND handler = COALESCE(this_handler, '')
+      AND args_hash = func_execution_cache_args_hash_v1(this_args)
+    RETURNING row_to_json(func_execution_cache.*) INTO object;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION func_execution_cache_set_v1(
+    this_tenancy jsonb,
+    this_backend_kind text,
+    this_backend_response_type text,
+    this_code_sha256 text,
+    this_handler text,
+    this_args jsonb,
+    this_unprocessed_value jsonb,
+    this_value jsonb,
+    this_func_execution_pk ident,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+
+    INSERT INTO func_execution_cache (tenancy_workspace_pk,
+                                      backend_kind,
+                                      backend_response_type,
+                                      code_sha256,
+                                      handler,
+                                      args_hash,
+                                      unprocessed_value,
+                                      value,
+                                      func_execution_pk)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_backend_kind,
+            this_backend_response_type,
+            this_code_sha256,
+            COALESCE(this_handler, ''),
+            func_execution_cache_args_hash_v1(this_args),
+            this_unprocessed_value,
+            this_value,
+            this_func_execution_pk)
+    ON CONFLICT (tenancy_workspace_pk, backend_kind, backend_response_type, code_sha256, handler, args_hash)
+        DO UPDATE SET unprocessed_value = EXCLUDED.unprocessed_value,
+                      value             = EXCLUDED.value,
+                      func_execution_pk = EXCLUDED.func_execution_pk,
+                      store_count       = func_execution_cache.store_count + 1,
+                      updated_at        = clock_timestamp()
+    RETURNING row_to_json(func_execution_cache.*) INTO object;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Drops the entries for code that no func in the tenancy has anymore, in any change set, and
+-- returns how many were dropped. Only the given code is checked unless it's NULL.
+CREATE OR REPLACE FUNCTION func_execution_cache_prune_v1(
+    this_tenancy jsonb,
+    this_code_sha256 text,
+    OUT removed bigint) AS
+$$
+BEGIN
+    WITH deleted AS (
+        DELETE FROM func_execution_cache
+        WHERE in_tenancy_v1(this_tenancy, func_execution_cache.tenancy_workspace_pk)
+          AND (this_code_sha256 IS NULL OR func_execution_cache.code_sha256 = this_code_sha256)
+          AND NOT EXISTS(SELECT 1
+                         FROM funcs
+                         WHERE funcs.code_sha256 = func_execution_cache.code_sha256
+                           AND funcs.visibility_deleted_at IS NULL
+                           AND in_tenancy_v1(this_tenancy, funcs.tenancy_workspace_pk))
+        RETURNING 1
+    )
+    SELECT count(*) INTO removed FROM deleted;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
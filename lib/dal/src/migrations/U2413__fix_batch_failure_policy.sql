@@ -0,0 +1,3 @@
+-- How a batch reacts to a fix that doesn't succeed, and when it was asked to stop running fixes.
+ALTER TABLE fix_batches ADD COLUMN failure_policy text NOT NULL DEFAULT 'skipDependents';
+ALTER TABLE fix_batches ADD COLUMN cancelled_at text;
@@ -0,0 +1,3 @@
+-- Action funcs opt in to dry runs, where they describe the changes they would make instead of
+-- making them. Funcs that haven't are never invoked when planning.
+ALTER TABLE funcs ADD COLUMN supports_dry_run bool NOT NULL DEFAULT FALSE;
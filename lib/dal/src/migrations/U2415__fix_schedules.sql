@@ -0,0 +1,82 @@
+-- Fix batches to start at a future time, once or on a recurring interval. Schedules only exist on
+-- head, since fixes only run there.
+CREATE TABLE fix_schedules
+(
+    pk                       ident primary key default ident_create_v1(),
+    id                       ident not null default ident_create_v1(),
+    tenancy_workspace_pk     ident,
+    visibility_change_set_pk ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at    timestamp with time zone,
+    created_at               timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at               timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    author                   text                     NOT NULL,
+    user_pk                  ident                    NOT NULL,
+    fixes                    jsonb                    NOT NULL,
+    failure_policy           text                     NOT NULL,
+    next_run_at              timestamp with time zone,
+    interval_secs            bigint,
+    last_run_at              timestamp with time zone,
+    last_fix_batch_id        ident,
+    cancelled_at             timestamp with time zone
+);
+
+SELECT standard_model_table_constraints_v1('fix_schedules');
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('fix_schedules', 'model', 'fix_schedule', 'Fix Schedule');
+
+CREATE INDEX ON fix_schedules (next_run_at) WHERE cancelled_at IS NULL;
+
+CREATE OR REPLACE FUNCTION fix_schedule_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_author text,
+    this_user_pk ident,
+    this_fixes jsonb,
+    this_failure_policy text,
+    this_next_run_at timestamp with time zone,
+    this_interval_secs bigint,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           fix_schedules%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO fix_schedules (tenancy_workspace_pk, visibility_change_set_pk, author, user_pk, fixes,
+                               failure_policy, next_run_at, interval_secs)
+    VALUES (this_tenancy_record.tenancy_workspace_pk, this_visibility_record.visibility_change_set_pk,
+            this_author, this_user_pk, this_fixes, this_failure_policy, this_next_run_at,
+            this_interval_secs)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Claims the schedules that are due in every workspace, moving recurring ones to their next run
+-- after now (skipping the runs that were missed) and finishing the others. Schedules claimed by
+-- another scheduler are skipped.
+CREATE OR REPLACE FUNCTION fix_schedules_claim_due_v1(OUT object json) RETURNS SETOF json AS
+$$
+WITH due AS (SELECT pk
+             FROM fix_schedules
+             WHERE next_run_at <= CLOCK_TIMESTAMP()
+               AND cancelled_at IS NULL
+               AND visibility_deleted_at IS NULL
+             FOR UPDATE SKIP LOCKED)
+UPDATE fix_schedules
+SET last_run_at = CLOCK_TIMESTAMP(),
+    updated_at  = CLOCK_TIMESTAMP(),
+    next_run_at = CASE
+                      WHEN interval_secs IS NULL THEN NULL
+                      ELSE next_run_at + make_interval(secs => interval_secs *
+                                                               (floor(extract(EPOCH FROM CLOCK_TIMESTAMP() - next_run_at) /
+                                                                      interval_secs) + 1))
+        END
+FROM due
+WHERE fix_schedules.pk = due.pk
+RETURNING row_to_json(fix_schedules.*) AS object
+$$ LANGUAGE SQL VOLATILE;
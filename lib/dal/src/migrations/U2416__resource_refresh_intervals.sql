@@ -0,0 +1,5 @@
+-- How often the resource scheduler refreshes the resources of a schema variant's components, and
+-- of a single component, overriding its schema variant. The scheduler's default is used when
+-- neither is set.
+ALTER TABLE schema_variants ADD COLUMN refresh_interval_secs bigint;
+ALTER TABLE components ADD COLUMN refresh_interval_secs bigint;
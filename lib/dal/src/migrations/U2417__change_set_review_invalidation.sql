@@ -0,0 +1,28 @@
+ALTER TABLE workspaces
+    ADD CONSTRAINT workspaces_check_required_change_set_approvals
+        CHECK (required_change_set_approvals >= 0);
+
+-- When anything scoped to the change set (across all standard model tables) was last written.
+-- Reviews left before then no longer describe what would be applied.
+CREATE OR REPLACE FUNCTION change_set_last_written_at_v1(this_tenancy jsonb,
+                                                         this_change_set_pk ident,
+                                                         OUT last_written_at timestamp with time zone) AS
+$$
+DECLARE
+    standard_model   standard_models%ROWTYPE;
+    table_written_at timestamp with time zone;
+BEGIN
+    FOR standard_model IN SELECT * FROM standard_models
+        LOOP
+            EXECUTE format('SELECT max(updated_at) FROM %1$I ' ||
+                           'WHERE visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, tenancy_workspace_pk)',
+                           standard_model.table_name::regclass, this_change_set_pk, this_tenancy)
+                INTO table_written_at;
+
+            IF table_written_at > last_written_at OR last_written_at IS NULL THEN
+                last_written_at := table_written_at;
+            END IF;
+        END LOOP;
+END;
+$$ LANGUAGE PLPGSQL STABLE;
@@ -0,0 +1,178 @@
+-- Rows scoped to a Historical change set are only ever written by change_set_create_as_of_v1 (before
+-- the change set itself exists) and removed by change_set_historical_purge_v1 (after it is no longer
+-- Historical). Anything else writing through one is trying to edit the past, so it is refused.
+CREATE OR REPLACE FUNCTION change_set_historical_read_only_v1() RETURNS trigger AS
+$$
+DECLARE
+    this_change_set_pk ident;
+BEGIN
+    FOREACH this_change_set_pk IN ARRAY ARRAY [
+        CASE WHEN TG_OP != 'INSERT' THEN OLD.visibility_change_set_pk END,
+        CASE WHEN TG_OP != 'DELETE' THEN NEW.visibility_change_set_pk END
+        ]
+        LOOP
+            IF this_change_set_pk IS NOT NULL
+                AND this_change_set_pk != ident_nil_v1()
+                AND EXISTS(SELECT 1
+                           FROM change_sets
+                           WHERE pk = this_change_set_pk
+                             AND status = 'Historical') THEN
+                RAISE EXCEPTION 'change set % is a read-only historical view', this_change_set_pk
+                    USING ERRCODE = 'read_only_sql_transaction';
+            END IF;
+        END LOOP;
+
+    IF TG_OP = 'DELETE' THEN
+        RETURN OLD;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION standard_model_historical_read_only_create_v1(this_table_name text) RETURNS VOID AS
+$$
+BEGIN
+    EXECUTE format('CREATE TRIGGER %1$s_historical_read_only ' ||
+                   'BEFORE INSERT OR UPDATE OR DELETE ON %1$I ' ||
+                   'FOR EACH ROW EXECUTE FUNCTION change_set_historical_read_only_v1()',
+                   this_table_name);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Every standard model table registered from now on gets the trigger as well.
+CREATE OR REPLACE FUNCTION standard_models_registered_v1() RETURNS trigger AS
+$$
+BEGIN
+    PERFORM standard_model_historical_read_only_create_v1(NEW.table_name);
+    RETURN NEW;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE TRIGGER standard_models_registered
+    AFTER INSERT
+    ON standard_models
+    FOR EACH ROW
+EXECUTE FUNCTION standard_models_registered_v1();
+
+SELECT standard_model_historical_read_only_create_v1(table_name)
+FROM standard_models;
+
+-- Historical change sets are derived entirely from change_set_head_archive, so ones nobody has
+-- asked for since this_older_than are removed outright rather than left to pile up.
+CREATE OR REPLACE FUNCTION change_set_historical_purge_v1(this_tenancy jsonb,
+                                                          this_older_than timestamp with time zone) RETURNS VOID AS
+$$
+DECLARE
+    standard_model standard_models%ROWTYPE;
+    purged_pks     ident[];
+BEGIN
+    WITH purged AS (
+        UPDATE change_sets
+            SET status = 'Abandoned',
+                updated_at = clock_timestamp()
+            WHERE status = 'Historical'
+                AND updated_at < this_older_than
+                AND in_tenancy_v1(this_tenancy, change_sets.tenancy_workspace_pk)
+            RETURNING pk)
+    SELECT array_agg(pk)
+    FROM purged
+    INTO purged_pks;
+
+    IF purged_pks IS NULL THEN
+        RETURN;
+    END IF;
+
+    FOR standard_model IN SELECT * FROM standard_models
+        LOOP
+            EXECUTE format('DELETE FROM %1$I WHERE visibility_change_set_pk = ANY(%2$L)',
+                           standard_model.table_name::regclass, purged_pks);
+        END LOOP;
+
+    DELETE FROM change_sets WHERE pk = ANY (purged_pks);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Creates a read-only "Historical" change set whose rows overlay head so that reading through it
+-- shows head as it was at this_as_of: every object touched by a change set applied after that
+-- moment is restored to the version it replaced (or hidden, if that change set created it).
+--
+-- Asking for the same point in time again returns the existing change set, and ones that have not
+-- been asked for in a day are purged.
+CREATE OR REPLACE FUNCTION change_set_create_as_of_v1(this_name text,
+                                                      this_as_of timestamp with time zone,
+                                                      this_tenancy jsonb,
+                                                      OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_sets%ROWTYPE;
+    this_new_pk         ident;
+    archived            change_set_head_archive%ROWTYPE;
+    this_object         jsonb;
+    column_names        text;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    UPDATE change_sets
+    SET updated_at = clock_timestamp()
+    WHERE pk = (SELECT pk
+                FROM change_sets
+                WHERE status = 'Historical'
+                  AND as_of = this_as_of
+                  AND tenancy_workspace_pk IS NOT DISTINCT FROM this_tenancy_record.tenancy_workspace_pk
+                ORDER BY created_at DESC
+                LIMIT 1)
+    RETURNING * INTO this_new_row;
+
+    PERFORM change_set_historical_purge_v1(this_tenancy, clock_timestamp() - interval '1 day');
+
+    IF this_new_row.pk IS NOT NULL THEN
+        object := row_to_json(this_new_row);
+        RETURN;
+    END IF;
+
+    -- The overlay rows are written before the change set row exists, since once it does (and is
+    -- Historical) nothing can be written through it.
+    this_new_pk := ident_create_v1();
+
+    FOR archived IN SELECT DISTINCT ON (a.table_name, a.object_id, a.tenancy_workspace_pk) a.*
+                    FROM change_set_head_archive a
+                             INNER JOIN change_sets ON change_sets.pk = a.change_set_pk
+                    WHERE in_tenancy_v1(this_tenancy, a.tenancy_workspace_pk)
+                      AND change_sets.applied_at > this_as_of
+                    ORDER BY a.table_name, a.object_id, a.tenancy_workspace_pk, change_sets.applied_at
+        LOOP
+            IF archived.previous_object IS NOT NULL THEN
+                this_object := archived.previous_object;
+            ELSE
+                EXECUTE format('SELECT row_to_json(t.*)::jsonb || jsonb_build_object(''visibility_deleted_at'', clock_timestamp()) ' ||
+                               'FROM %1$I t ' ||
+                               'WHERE t.id = %2$L ' ||
+                               '  AND t.visibility_change_set_pk = ident_nil_v1() ' ||
+                               '  AND t.visibility_deleted_at IS NULL ' ||
+                               '  AND t.tenancy_workspace_pk IS NOT DISTINCT FROM %3$L',
+                               archived.table_name, archived.object_id, archived.tenancy_workspace_pk)
+                    INTO this_object;
+                -- Already gone from head, so there is nothing to hide.
+                CONTINUE WHEN this_object IS NULL;
+            END IF;
+            this_object := this_object || jsonb_build_object('visibility_change_set_pk', this_new_pk);
+
+            SELECT string_agg(information_schema.columns.column_name::text, ',')
+            FROM information_schema.columns
+            WHERE information_schema.columns.table_name = archived.table_name
+              AND information_schema.columns.column_name != 'pk'
+              AND information_schema.columns.is_generated = 'NEVER'
+            INTO column_names;
+
+            EXECUTE format('INSERT INTO %1$I (%2$s) SELECT %2$s FROM jsonb_populate_record(NULL::%1$I, %3$L)',
+                           archived.table_name, column_names, this_object);
+        END LOOP;
+
+    INSERT INTO change_sets (pk, name, status, tenancy_workspace_pk, as_of)
+    VALUES (this_new_pk, this_name, 'Historical', this_tenancy_record.tenancy_workspace_pk, this_as_of)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
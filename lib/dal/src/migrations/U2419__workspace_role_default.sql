@@ -0,0 +1,5 @@
+-- Every existing membership was made an Owner when roles were added (see U2405). From now on,
+-- only the creator of a workspace is made an Owner (explicitly); anyone else associated with it
+-- without an invitation can only read.
+ALTER TABLE user_belongs_to_workspaces
+    ALTER COLUMN role SET DEFAULT 'Viewer';
@@ -0,0 +1,4 @@
+-- Paging through history events uses (created_at, pk) as the key, so that the pk breaks ties
+-- between events created at the same moment without a sort.
+DROP INDEX history_events_tenancy_created_at_idx;
+CREATE INDEX history_events_tenancy_created_at_pk_idx ON history_events (tenancy_workspace_pk, created_at DESC, pk DESC);
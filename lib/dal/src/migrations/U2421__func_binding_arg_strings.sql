@@ -0,0 +1,9 @@
+-- Every string found anywhere in a func binding's args, as a jsonb array. Finding the bindings
+-- that were given a particular value (such as a secret id) is then a containment check against
+-- this, which the GIN index below can answer without reading every binding's args.
+CREATE OR REPLACE FUNCTION func_binding_arg_strings_v1(this_args json) RETURNS jsonb AS
+$$
+SELECT jsonb_path_query_array(this_args::jsonb, 'strict $.** ? (@.type() == "string")')
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+CREATE INDEX func_bindings_arg_strings_idx ON func_bindings USING gin (func_binding_arg_strings_v1(args) jsonb_path_ops);
@@ -0,0 +1,33 @@
+-- Most funcs read the clock or call out to the network, so caching their results has to be opted
+-- into rather than out of. Everything cached so far assumed otherwise, so it is dropped.
+ALTER TABLE funcs ALTER COLUMN deterministic SET DEFAULT FALSE;
+
+UPDATE funcs
+SET deterministic = FALSE
+WHERE deterministic
+  AND visibility_change_set_pk NOT IN (SELECT pk FROM change_sets WHERE status = 'Historical');
+
+DELETE FROM func_execution_cache;
+
+-- Executions answered from the cache are recorded like any other, pointing at the execution whose
+-- result they reused.
+ALTER TABLE func_executions
+    ADD COLUMN cached_from_func_execution_pk ident;
+
+-- Marks the execution as answered by the cached result of another, taking its output stream.
+CREATE OR REPLACE FUNCTION func_execution_set_cached_from_v1(
+    this_pk ident,
+    this_cached_from_func_execution_pk ident,
+    OUT object json) AS
+$$
+BEGIN
+    UPDATE func_executions
+    SET cached_from_func_execution_pk = this_cached_from_func_execution_pk,
+        output_stream                 = (SELECT cached_from.output_stream
+                                         FROM func_executions cached_from
+                                         WHERE cached_from.pk = this_cached_from_func_execution_pk),
+        updated_at                    = clock_timestamp()
+    WHERE pk = this_pk
+    RETURNING row_to_json(func_executions.*) INTO object;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
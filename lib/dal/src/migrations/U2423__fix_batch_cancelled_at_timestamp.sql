@@ -0,0 +1,4 @@
+-- Unlike the older started_at and finished_at columns, nothing requires cancellations to be stored
+-- as text.
+ALTER TABLE fix_batches
+    ALTER COLUMN cancelled_at TYPE timestamp with time zone USING cancelled_at::timestamp with time zone;
@@ -0,0 +1,27 @@
+-- Schedules only run on head, so schedules that were created in a change set (or copied into one)
+-- are never claimed. Runs that fail after being claimed record why on the schedule, since a
+-- one-off schedule has no next run to try again at.
+ALTER TABLE fix_schedules ADD COLUMN last_run_error text;
+
+CREATE OR REPLACE FUNCTION fix_schedules_claim_due_v1(OUT object json) RETURNS SETOF json AS
+$$
+WITH due AS (SELECT pk
+             FROM fix_schedules
+             WHERE next_run_at <= CLOCK_TIMESTAMP()
+               AND cancelled_at IS NULL
+               AND visibility_change_set_pk = ident_nil_v1()
+               AND visibility_deleted_at IS NULL
+             FOR UPDATE SKIP LOCKED)
+UPDATE fix_schedules
+SET last_run_at = CLOCK_TIMESTAMP(),
+    updated_at  = CLOCK_TIMESTAMP(),
+    next_run_at = CASE
+                      WHEN interval_secs IS NULL THEN NULL
+                      ELSE next_run_at + make_interval(secs => interval_secs *
+                                                               (floor(extract(EPOCH FROM CLOCK_TIMESTAMP() - next_run_at) /
+                                                                      interval_secs) + 1))
+        END
+FROM due
+WHERE fix_schedules.pk = due.pk
+RETURNING row_to_json(fix_schedules.*) AS object
+$$ LANGUAGE SQL VOLATILE;
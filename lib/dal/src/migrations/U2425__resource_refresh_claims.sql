@@ -0,0 +1,36 @@
+-- Schema variant definitions carry the refresh interval of the variants they create.
+ALTER TABLE schema_variant_definitions ADD COLUMN refresh_interval_secs bigint;
+
+-- When the resource scheduler last enqueued a refresh of a component's resource. It's kept in the
+-- database rather than by each scheduler, so that every sdf instance can run a scheduler without
+-- refreshing a resource more than once.
+ALTER TABLE components ADD COLUMN resource_refresh_enqueued_at timestamp with time zone;
+
+-- Claims the refresh of a component's resource on head, unless another scheduler has claimed it
+-- since the given time (when this scheduler last saw it claimed) or is claiming it now. Returns
+-- when it was claimed, or NULL if it wasn't.
+CREATE OR REPLACE FUNCTION component_claim_resource_refresh_v1(
+    this_tenancy jsonb,
+    this_component_id ident,
+    this_last_enqueued_at timestamp with time zone,
+    OUT enqueued_at timestamp with time zone) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+
+    WITH claimable AS (SELECT pk
+                       FROM components
+                       WHERE id = this_component_id
+                         AND tenancy_workspace_pk = this_tenancy_record.tenancy_workspace_pk
+                         AND visibility_change_set_pk = ident_nil_v1()
+                         AND resource_refresh_enqueued_at IS NOT DISTINCT FROM this_last_enqueued_at
+                       FOR UPDATE SKIP LOCKED)
+    UPDATE components
+    SET resource_refresh_enqueued_at = CLOCK_TIMESTAMP()
+    FROM claimable
+    WHERE components.pk = claimable.pk
+    RETURNING components.resource_refresh_enqueued_at INTO enqueued_at;
+END
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,78 @@
+-- Historical change sets are kept read-only by the dal (see standard_model::require_writable)
+-- rather than by a trigger on every standard model table, which cost a lookup per row written and
+-- refused maintenance updates that run across change sets.
+CREATE OR REPLACE FUNCTION standard_model_historical_read_only_drop_v1(this_table_name text) RETURNS VOID AS
+$$
+BEGIN
+    EXECUTE format('DROP TRIGGER IF EXISTS %1$s_historical_read_only ON %1$I', this_table_name);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+SELECT standard_model_historical_read_only_drop_v1(table_name)
+FROM standard_models;
+
+DROP FUNCTION standard_model_historical_read_only_drop_v1(text);
+DROP TRIGGER standard_models_registered ON standard_models;
+DROP FUNCTION standard_models_registered_v1();
+DROP FUNCTION standard_model_historical_read_only_create_v1(text);
+DROP FUNCTION change_set_historical_read_only_v1();
+
+-- Creates a read-only "Historical" change set whose rows overlay head so that reading through it
+-- shows head as it was at this_as_of: every object touched by a change set applied after that
+-- moment is restored to the version it replaced (or hidden, if that change set created it).
+--
+-- Asking for the same point in time again returns the existing change set, as long as nothing has
+-- been applied since it was created (its overlay would be missing that apply). Ones that have not
+-- been asked for in a day are purged.
+CREATE OR REPLACE FUNCTION change_set_create_as_of_v1(this_name text,
+                                                      this_as_of timestamp with time zone,
+                                                      this_tenancy jsonb,
+                                                      OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_sets%ROWTYPE;
+    archived            change_set_head_archive%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    UPDATE change_sets
+    SET updated_at = clock_timestamp()
+    WHERE pk = (SELECT historical.pk
+                FROM change_sets historical
+                WHERE historical.status = 'Historical'
+                  AND historical.as_of = this_as_of
+                  AND historical.tenancy_workspace_pk IS NOT DISTINCT FROM this_tenancy_record.tenancy_workspace_pk
+                  AND NOT EXISTS(SELECT 1
+                                 FROM change_sets applied
+                                 WHERE applied.applied_at >= historical.created_at
+                                   AND applied.tenancy_workspace_pk IS NOT DISTINCT FROM
+                                       this_tenancy_record.tenancy_workspace_pk)
+                ORDER BY historical.created_at DESC
+                LIMIT 1)
+    RETURNING * INTO this_new_row;
+
+    PERFORM change_set_historical_purge_v1(this_tenancy, clock_timestamp() - interval '1 day');
+
+    IF this_new_row.pk IS NOT NULL THEN
+        object := row_to_json(this_new_row);
+        RETURN;
+    END IF;
+
+    INSERT INTO change_sets (name, status, tenancy_workspace_pk, as_of)
+    VALUES (this_name, 'Historical', this_tenancy_record.tenancy_workspace_pk, this_as_of)
+    RETURNING * INTO this_new_row;
+
+    FOR archived IN SELECT DISTINCT ON (a.table_name, a.object_id, a.tenancy_workspace_pk) a.*
+                    FROM change_set_head_archive a
+                             INNER JOIN change_sets ON change_sets.pk = a.change_set_pk
+                    WHERE in_tenancy_v1(this_tenancy, a.tenancy_workspace_pk)
+                      AND change_sets.applied_at > this_as_of
+                    ORDER BY a.table_name, a.object_id, a.tenancy_workspace_pk, change_sets.applied_at
+        LOOP
+            PERFORM change_set_head_archive_overlay_v1(this_new_row.pk, archived);
+        END LOOP;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,200 @@
+-- A revert is based on head as it was right after the change set it reverts was applied, so that
+-- anything changed on head since then shows up as a conflict rather than being overwritten.
+ALTER TABLE change_sets
+    ADD COLUMN reverts_applied_at timestamp with time zone;
+
+-- Restores a row deleted in a change set. If head still has the object, the change set's copy is
+-- dropped so that head's version shows through again. Otherwise (the object was created in the
+-- change set, or is deleted on head as well) the change set's copy is brought back instead.
+CREATE OR REPLACE FUNCTION change_set_restore_by_pk_v1(this_table_text text,
+                                                       this_tenancy jsonb,
+                                                       this_pk ident) RETURNS VOID AS
+$$
+DECLARE
+    this_table     regclass;
+    exists_on_head bool;
+BEGIN
+    this_table := this_table_text::regclass;
+    EXECUTE format('SELECT EXISTS(SELECT 1 ' ||
+                   '              FROM %1$I cs ' ||
+                   '                       INNER JOIN %1$I head ' ||
+                   '                                  ON head.id = cs.id ' ||
+                   '                                      AND head.tenancy_workspace_pk IS NOT DISTINCT FROM cs.tenancy_workspace_pk ' ||
+                   '                                      AND head.visibility_change_set_pk = ident_nil_v1() ' ||
+                   '                                      AND head.visibility_deleted_at IS NULL ' ||
+                   '              WHERE cs.pk = %2$L)',
+                   this_table, this_pk)
+        INTO exists_on_head;
+
+    IF exists_on_head THEN
+        PERFORM hard_delete_by_pk_v1(this_table_text, this_pk);
+    ELSE
+        PERFORM undelete_by_pk_v1(this_table_text, this_tenancy, this_pk);
+    END IF;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Unchanged from U0963, except that rows are restored with change_set_restore_by_pk_v1 rather than
+-- always being hard deleted, so that components deleted on head can be restored in a change set.
+CREATE OR REPLACE FUNCTION component_restore_and_propagate_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_component_id ident
+)
+    RETURNS TABLE
+            (
+                object json
+            )
+AS
+$$
+DECLARE
+    table_name                   text;
+    target_pk                    ident;
+    peer_component_id            ident;
+    internal_provider_id         ident;
+    external_provider_id         ident;
+    this_visibility_with_deleted jsonb;
+
+BEGIN
+    this_visibility_with_deleted := this_visibility || jsonb_build_object('visibility_deleted_at', now());
+
+    -- Outgoing Edges
+    FOR target_pk, peer_component_id, internal_provider_id, external_provider_id IN
+        SELECT e.pk, e.head_object_id, sbtip.belongs_to_id, sbtep.belongs_to_id
+        FROM edges_v1(this_tenancy, this_visibility_with_deleted) e
+                 LEFT JOIN socket_belongs_to_internal_provider sbtip ON sbtip.object_id = e.head_socket_id
+                 LEFT JOIN socket_belongs_to_external_provider sbtep ON sbtep.object_id = e.head_socket_id
+        WHERE tail_object_id = this_component_id
+          AND e.visibility_deleted_at IS NOT NULL
+          AND e.visibility_change_set_pk = (this_visibility ->> 'visibility_change_set_pk')::ident
+    LOOP
+        PERFORM change_set_restore_by_pk_v1('edges', this_tenancy, target_pk);
+
+        -- We have to get the edge head values so we can make update them after edge deletion
+        RETURN QUERY SELECT row_to_json(av.*) AS object
+                     FROM attribute_values_v1(this_tenancy, this_visibility) av
+                     WHERE attribute_context_component_id = peer_component_id
+                       AND (attribute_context_internal_provider_id = internal_provider_id OR
+                            attribute_context_external_provider_id = external_provider_id);
+    END LOOP;
+
+    -- Incoming Edges
+    FOR target_pk, internal_provider_id, external_provider_id IN
+        SELECT e.pk, sbtip.belongs_to_id, sbtep.belongs_to_id
+        FROM edges_v1(this_tenancy, this_visibility_with_deleted) e
+                 LEFT JOIN socket_belongs_to_internal_provider sbtip ON sbtip.object_id = e.head_socket_id
+                 LEFT JOIN socket_belongs_to_external_provider sbtep ON sbtep.object_id = e.head_socket_id
+        WHERE head_object_id = this_component_id
+          AND e.visibility_deleted_at IS NOT NULL
+          AND e.visibility_change_set_pk = (this_visibility ->> 'visibility_change_set_pk')::ident
+    LOOP
+        PERFORM change_set_restore_by_pk_v1('edges', this_tenancy, target_pk);
+
+        -- We have to get the edge head values so we can make update them after edge deletion
+        RETURN QUERY SELECT row_to_json(av.*) AS object
+                     FROM attribute_values_v1(this_tenancy, this_visibility) av
+                     WHERE attribute_context_component_id = this_component_id
+                       AND (attribute_context_internal_provider_id = internal_provider_id OR
+                            attribute_context_external_provider_id = external_provider_id);
+    END LOOP;
+
+    -- Belongs to queries are a bit more complicated (and should be gone pretty soon)
+    FOR target_pk, table_name IN
+        SELECT nbtc.pk, 'node_belongs_to_component' as table_name
+        FROM node_belongs_to_component_v1(this_tenancy, this_visibility_with_deleted) nbtc
+        WHERE nbtc.belongs_to_id = this_component_id
+          AND nbtc.visibility_deleted_at IS NOT NULL
+          AND nbtc.visibility_change_set_pk = (this_visibility ->> 'visibility_change_set_pk')::ident
+        UNION
+        SELECT n.pk, 'nodes' as table_name
+        FROM node_belongs_to_component_v1(this_tenancy, this_visibility_with_deleted) nbtc
+                 INNER JOIN nodes_v1(this_tenancy, this_visibility_with_deleted) n ON n.id = nbtc.object_id
+            AND n.visibility_deleted_at IS NOT NULL
+            AND n.visibility_change_set_pk = (this_visibility ->> 'visibility_change_set_pk')::ident
+        WHERE nbtc.belongs_to_id = this_component_id
+          AND nbtc.visibility_deleted_at IS NOT NULL
+          AND nbtc.visibility_change_set_pk = (this_visibility ->> 'visibility_change_set_pk')::ident
+    LOOP
+        PERFORM change_set_restore_by_pk_v1(table_name, this_tenancy, target_pk);
+    END LOOP;
+
+    SELECT pk
+    INTO target_pk
+    FROM components_v1(this_tenancy, this_visibility_with_deleted)
+    WHERE id = this_component_id
+      AND visibility_deleted_at IS NOT NULL
+      AND visibility_change_set_pk = (this_visibility ->> 'visibility_change_set_pk')::ident;
+
+    PERFORM change_set_restore_by_pk_v1('components', this_tenancy, target_pk);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- The tables whose rows make up components and the connections between them. A revert leaves
+-- these to the dal, which restores and deletes components, edges and attribute values the same
+-- way a user would; every other object the applied change set touched is overlaid as it was.
+CREATE OR REPLACE FUNCTION change_set_revert_through_dal_v1(this_table_name text) RETURNS bool AS
+$$
+SELECT this_table_name IN ('components', 'nodes', 'edges', 'component_statuses')
+           OR this_table_name LIKE 'component\_%'
+           OR this_table_name LIKE 'node\_%'
+           OR this_table_name LIKE 'attribute\_value%'
+           OR this_table_name LIKE 'attribute\_prototype%'
+           OR this_table_name LIKE 'func\_binding%';
+$$ LANGUAGE SQL IMMUTABLE;
+
+-- Creates an open change set that will revert the applied change set. Objects outside of
+-- components (funcs, schemas and the like) are restored to the version the change set replaced,
+-- or hidden if it created them. For the rows of components and edges the change set deleted, the
+-- deletion is copied into the new change set, so that they can be brought back with
+-- Component::restore_and_propagate and Edge::restore_by_id.
+CREATE OR REPLACE FUNCTION change_set_revert_v1(this_change_set_pk ident,
+                                                this_name text,
+                                                this_tenancy jsonb,
+                                                OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_sets%ROWTYPE;
+    archived            change_set_head_archive%ROWTYPE;
+    column_names        text;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+    INSERT INTO change_sets (name, status, tenancy_workspace_pk, reverts_change_set_pk, reverts_applied_at)
+    SELECT this_name, 'Open', this_tenancy_record.tenancy_workspace_pk, this_change_set_pk, applied_at
+    FROM change_sets
+    WHERE pk = this_change_set_pk
+    RETURNING * INTO this_new_row;
+
+    FOR archived IN SELECT DISTINCT ON (a.table_name, a.object_id, a.tenancy_workspace_pk) a.*
+                    FROM change_set_head_archive a
+                    WHERE a.change_set_pk = this_change_set_pk
+                      AND in_tenancy_v1(this_tenancy, a.tenancy_workspace_pk)
+                    ORDER BY a.table_name, a.object_id, a.tenancy_workspace_pk
+        LOOP
+            IF NOT change_set_revert_through_dal_v1(archived.table_name) THEN
+                PERFORM change_set_head_archive_overlay_v1(this_new_row.pk, archived);
+                CONTINUE;
+            END IF;
+            CONTINUE WHEN archived.previous_object IS NULL;
+
+            SELECT string_agg(information_schema.columns.column_name::text, ',')
+            FROM information_schema.columns
+            WHERE information_schema.columns.table_name = archived.table_name
+              AND information_schema.columns.column_name NOT IN ('pk', 'visibility_change_set_pk')
+              AND information_schema.columns.is_generated = 'NEVER'
+            INTO column_names;
+
+            EXECUTE format('INSERT INTO %1$I (%2$s, visibility_change_set_pk) ' ||
+                           'SELECT %2$s, %3$L ' ||
+                           'FROM %1$I ' ||
+                           'WHERE id = %4$L ' ||
+                           '  AND visibility_change_set_pk = ident_nil_v1() ' ||
+                           '  AND visibility_deleted_at IS NOT NULL ' ||
+                           '  AND tenancy_workspace_pk IS NOT DISTINCT FROM %5$L',
+                           archived.table_name, column_names, this_new_row.pk, archived.object_id,
+                           archived.tenancy_workspace_pk);
+        END LOOP;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
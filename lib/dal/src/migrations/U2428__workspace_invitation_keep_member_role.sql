@@ -0,0 +1,17 @@
+-- Associates the user with the workspace with the given role. Users who are already members keep
+-- the role they have; users who were removed are restored with the given role.
+CREATE OR REPLACE FUNCTION user_associate_workspace_with_role_v1(
+    this_user_pk ident,
+    this_workspace_pk ident,
+    this_role text
+) RETURNS void AS
+$$
+BEGIN
+    INSERT INTO user_belongs_to_workspaces (user_pk, workspace_pk, role)
+    VALUES (this_user_pk, this_workspace_pk, this_role)
+    ON CONFLICT (user_pk, workspace_pk) DO UPDATE SET role                  = EXCLUDED.role,
+                                                      visibility_deleted_at = NULL,
+                                                      updated_at            = clock_timestamp()
+    WHERE user_belongs_to_workspaces.visibility_deleted_at IS NOT NULL;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
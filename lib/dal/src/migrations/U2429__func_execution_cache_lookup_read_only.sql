@@ -0,0 +1,35 @@
+-- Counting hits on the entry made every lookup take a row lock in the caller's transaction, which
+-- serialized (and could deadlock) concurrent jobs reading the same entry. Hits are now counted
+-- from the executions recorded for them, which are written on their own connection.
+ALTER TABLE func_execution_cache
+    DROP COLUMN hit_count,
+    DROP COLUMN last_hit_at;
+
+CREATE INDEX ON func_executions (cached_from_func_execution_pk);
+
+-- Returns the cached result for the key, or nothing on a miss.
+CREATE OR REPLACE FUNCTION func_execution_cache_get_v1(
+    this_tenancy jsonb,
+    this_backend_kind text,
+    this_backend_response_type text,
+    this_code_sha256 text,
+    this_handler text,
+    this_args jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+
+    SELECT row_to_json(func_execution_cache.*)
+    INTO object
+    FROM func_execution_cache
+    WHERE tenancy_workspace_pk IS NOT DISTINCT FROM this_tenancy_record.tenancy_workspace_pk
+      AND backend_kind = this_backend_kind
+      AND backend_response_type = this_backend_response_type
+      AND code_sha256 = this_code_sha256
+      AND handler = COALESCE(this_handler, '')
+      AND args_hash = func_execution_cache_args_hash_v1(this_args);
+END;
+$$ LANGUAGE PLPGSQL STABLE;
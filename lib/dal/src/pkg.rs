@@ -25,7 +25,7 @@ use crate::{
     AttributeReadContext, AttributeValueError, ExternalProviderError, ExternalProviderId,
     FuncBackendKind, FuncBackendResponseType, FuncError, FuncId, InternalProviderError,
     InternalProviderId, PropError, PropId, PropKind, SchemaError, SchemaId, SchemaVariantError,
-    SchemaVariantId, StandardModelError, ValidationPrototypeError,
+    SchemaVariantId, SecretError, StandardModelError, ValidationPrototypeError,
 };
 
 #[remain::sorted]
@@ -135,6 +135,8 @@ pub enum PkgError {
     SchemaVariantDefinition(#[from] SchemaVariantDefinitionError),
     #[error("schema variant not found: {0}")]
     SchemaVariantNotFound(SchemaVariantId),
+    #[error(transparent)]
+    Secret(#[from] SecretError),
     #[error("json serialization error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
@@ -178,6 +180,8 @@ impl From<FuncBackendKind> for FuncSpecBackendKind {
             FuncBackendKind::JsValidation => Self::JsValidation,
             FuncBackendKind::Map => Self::Map,
             FuncBackendKind::Object => Self::Object,
+            FuncBackendKind::PythonAction => Self::PythonAction,
+            FuncBackendKind::PythonAttribute => Self::PythonAttribute,
             FuncBackendKind::String => Self::String,
             FuncBackendKind::Unset => Self::Unset,
             FuncBackendKind::Validation => Self::Validation,
@@ -200,6 +204,8 @@ impl From<FuncSpecBackendKind> for FuncBackendKind {
             FuncSpecBackendKind::JsValidation => Self::JsValidation,
             FuncSpecBackendKind::Map => Self::Map,
             FuncSpecBackendKind::Object => Self::Object,
+            FuncSpecBackendKind::PythonAction => Self::PythonAction,
+            FuncSpecBackendKind::PythonAttribute => Self::PythonAttribute,
             FuncSpecBackendKind::String => Self::String,
             FuncSpecBackendKind::Unset => Self::Unset,
             FuncSpecBackendKind::Validation => Self::Validation,
@@ -6,23 +6,24 @@ use si_pkg::{
     ActionFuncSpec, AttrFuncInputSpec, AttrFuncInputSpecKind, FuncArgumentSpec,
     FuncDescriptionSpec, FuncSpec, FuncUniqueId, LeafFunctionSpec, MapKeyFuncSpec, PkgSpec,
     PropSpec, PropSpecBuilder, PropSpecKind, SchemaSpec, SchemaVariantSpec,
-    SchemaVariantSpecBuilder, SchemaVariantSpecComponentType, SchemaVariantSpecPropRoot, SiPkg,
-    SiPropFuncSpec, SiPropFuncSpecKind, SocketSpec, SocketSpecKind, SpecError, ValidationSpec,
-    ValidationSpecKind,
+    SchemaVariantSpecBuilder, SchemaVariantSpecComponentType, SchemaVariantSpecPropRoot,
+    SecretDefinitionFieldSpec, SecretDefinitionSpec, SiPkg, SiPropFuncSpec, SiPropFuncSpecKind,
+    SocketSpec, SocketSpecKind, SpecError, ValidationSpec, ValidationSpecKind,
 };
 
 use crate::schema::variant::definition::SchemaVariantDefinition;
 use crate::{
     func::{argument::FuncArgument, backend::validation::FuncBackendValidationArgs},
     prop_tree::{PropTree, PropTreeNode},
+    property_editor::schema::WidgetKind,
     socket::SocketKind,
     validation::Validation,
     ActionPrototype, ActionPrototypeContext, AttributeContextBuilder, AttributePrototype,
     AttributePrototypeArgument, AttributeReadContext, AttributeValue, ComponentType, DalContext,
     ExternalProvider, ExternalProviderId, Func, FuncDescription, FuncId, InternalProvider,
     InternalProviderId, LeafInputLocation, LeafKind, Prop, PropId, PropKind, Schema, SchemaVariant,
-    SchemaVariantError, SchemaVariantId, Socket, StandardModel, StandardModelError,
-    ValidationPrototype,
+    SchemaVariantError, SchemaVariantId, SecretDefinition, SecretKind, Socket, StandardModel,
+    StandardModelError, ValidationPrototype,
 };
 
 use super::{PkgError, PkgResult};
@@ -75,7 +76,15 @@ async fn build_pkg(
         pkg_spec_builder.func(intrinsic_spec);
     }
 
+    let mut secret_kinds: Vec<SecretKind> = vec![];
+
     for variant_id in variant_ids {
+        for kind in secret_kinds_for_variant(ctx, variant_id).await? {
+            if !secret_kinds.contains(&kind) {
+                secret_kinds.push(kind);
+            }
+        }
+
         let related_funcs = SchemaVariant::all_funcs(ctx, variant_id).await?;
         for func in &related_funcs {
             if !func_specs.contains_key(func.id()) {
@@ -89,6 +98,12 @@ async fn build_pkg(
         pkg_spec_builder.schema(schema_spec);
     }
 
+    for kind in secret_kinds {
+        if let Some(definition) = SecretDefinition::find_for_kind(ctx, &kind).await? {
+            pkg_spec_builder.secret_definition(build_secret_definition_spec(&definition)?);
+        }
+    }
+
     let spec = pkg_spec_builder.build()?;
 
     let pkg = SiPkg::load_from_spec(spec)?;
@@ -96,6 +111,56 @@ async fn build_pkg(
     Ok(pkg)
 }
 
+/// Finds the [`SecretKinds`](SecretKind) that the [`SecretSelect`](WidgetKind::SecretSelect)
+/// [`Props`](Prop) of a [`SchemaVariant`] are restricted to.
+async fn secret_kinds_for_variant(
+    ctx: &DalContext,
+    variant_id: SchemaVariantId,
+) -> PkgResult<Vec<SecretKind>> {
+    let mut kinds = vec![];
+    for prop in SchemaVariant::all_props(ctx, variant_id).await? {
+        if *prop.widget_kind() != WidgetKind::SecretSelect {
+            continue;
+        }
+        if let Some(kind) = prop
+            .widget_options()
+            .and_then(|options| options.get("secretKind"))
+            .and_then(|kind| kind.as_str())
+        {
+            kinds.push(kind.parse()?);
+        }
+    }
+    Ok(kinds)
+}
+
+fn build_secret_definition_spec(definition: &SecretDefinition) -> PkgResult<SecretDefinitionSpec> {
+    let mut builder = SecretDefinitionSpec::builder();
+    builder.name(definition.name());
+    if let Some(display_name) = definition.display_name() {
+        builder.display_name(display_name);
+    }
+    if let Some(description) = definition.description() {
+        builder.description(description);
+    }
+
+    for field in definition.fields() {
+        let mut field_builder = SecretDefinitionFieldSpec::builder();
+        field_builder
+            .name(&field.name)
+            .sensitive(field.sensitive)
+            .required(field.required);
+        if let Some(display_name) = &field.display_name {
+            field_builder.display_name(display_name);
+        }
+        if let Some(pattern) = &field.pattern {
+            field_builder.pattern(pattern);
+        }
+        builder.field(field_builder.build()?);
+    }
+
+    Ok(builder.build()?)
+}
+
 fn build_func_spec(func: &Func, args: &[FuncArgument]) -> PkgResult<FuncSpec> {
     let mut func_spec_builder = FuncSpec::builder();
 
@@ -120,6 +185,8 @@ fn build_func_spec(func: &Func, args: &[FuncArgument]) -> PkgResult<FuncSpec> {
     func_spec_builder.backend_kind(*func.backend_kind());
 
     func_spec_builder.hidden(func.hidden());
+    func_spec_builder.deterministic(func.deterministic());
+    func_spec_builder.supports_dry_run(func.supports_dry_run());
 
     for arg in args {
         func_spec_builder.argument(
@@ -499,6 +566,9 @@ async fn build_variant_spec(
     }
 
     variant_spec_builder.component_type(get_component_type(ctx, &variant).await?);
+    if let Some(refresh_interval_secs) = variant.refresh_interval_secs() {
+        variant_spec_builder.refresh_interval_secs(*refresh_interval_secs);
+    }
 
     set_variant_spec_prop_data(
         ctx,
@@ -5,7 +5,7 @@ use tokio::sync::Mutex;
 use si_pkg::{
     FuncUniqueId, SchemaVariantSpecPropRoot, SiPkg, SiPkgActionFunc, SiPkgAttrFuncInputView,
     SiPkgError, SiPkgFunc, SiPkgFuncDescription, SiPkgLeafFunction, SiPkgProp, SiPkgSchema,
-    SiPkgSchemaVariant, SiPkgSocket, SiPkgValidation, SocketSpecKind,
+    SiPkgSchemaVariant, SiPkgSecretDefinition, SiPkgSocket, SiPkgValidation, SocketSpecKind,
 };
 
 use crate::{
@@ -27,7 +27,8 @@ use crate::{
     AttributeReadContext, AttributeValue, AttributeValueError, DalContext, ExternalProvider,
     ExternalProviderId, Func, FuncArgument, FuncDescription, FuncDescriptionContents, FuncError,
     FuncId, InternalProvider, Prop, PropId, PropKind, Schema, SchemaId, SchemaVariant,
-    SchemaVariantError, SchemaVariantId, StandardModel,
+    SchemaVariantError, SchemaVariantId, SecretDefinition, SecretDefinitionField, SecretKind,
+    StandardModel,
 };
 
 use super::{PkgError, PkgResult};
@@ -106,6 +107,15 @@ pub async fn import_pkg_from_pkg(
         funcs_by_unique_id.insert(unique_id, func);
     }
 
+    for secret_definition_spec in pkg.secret_definitions()? {
+        info!(
+            "installing secret definition '{}' from {}",
+            secret_definition_spec.name(),
+            file_name
+        );
+        create_secret_definition(ctx, secret_definition_spec).await?;
+    }
+
     let mut installed_schema_variant_ids = vec![];
 
     for schema_spec in pkg.schemas()? {
@@ -143,6 +153,41 @@ pub async fn import_pkg(ctx: &DalContext, pkg_file_path: impl AsRef<Path>) -> Pk
     Ok(pkg)
 }
 
+/// Creates the [`SecretDefinition`] unless one already exists for its kind, in which case the
+/// existing definition wins.
+async fn create_secret_definition(
+    ctx: &DalContext,
+    secret_definition_spec: SiPkgSecretDefinition<'_>,
+) -> PkgResult<()> {
+    let kind: SecretKind = secret_definition_spec.name().parse()?;
+    if SecretDefinition::find_for_kind(ctx, &kind).await?.is_some() {
+        return Ok(());
+    }
+
+    let fields = secret_definition_spec
+        .fields()?
+        .into_iter()
+        .map(|field| SecretDefinitionField {
+            name: field.name().to_owned(),
+            display_name: field.display_name().map(ToOwned::to_owned),
+            sensitive: field.sensitive(),
+            required: field.required(),
+            pattern: field.pattern().map(ToOwned::to_owned),
+        })
+        .collect();
+
+    SecretDefinition::new(
+        ctx,
+        &kind,
+        secret_definition_spec.display_name().map(ToOwned::to_owned),
+        secret_definition_spec.description().map(ToOwned::to_owned),
+        fields,
+    )
+    .await?;
+
+    Ok(())
+}
+
 async fn create_func(
     ctx: &DalContext,
     func_spec: SiPkgFunc<'_>,
@@ -180,6 +225,10 @@ async fn create_func(
             func.set_description(ctx, func_spec.description()).await?;
             func.set_handler(ctx, Some(func_spec.handler())).await?;
             func.set_hidden(ctx, func.hidden()).await?;
+            func.set_deterministic(ctx, func_spec.deterministic())
+                .await?;
+            func.set_supports_dry_run(ctx, func_spec.supports_dry_run())
+                .await?;
             func.set_link(ctx, func_spec.link().map(|l| l.to_string()))
                 .await?;
 
@@ -308,7 +357,7 @@ async fn create_schema_variant_definition(
                     let spec = schema_spec.to_spec().await?;
                     let metadata = SchemaVariantDefinitionJson::metadata_from_spec(spec)?;
 
-                    SchemaVariantDefinition::new(
+                    let mut schema_variant_definition = SchemaVariantDefinition::new(
                         ctx,
                         metadata.name,
                         metadata.menu_name,
@@ -319,7 +368,11 @@ async fn create_schema_variant_definition(
                         metadata.description,
                         *asset_func.id(),
                     )
-                    .await?
+                    .await?;
+                    schema_variant_definition
+                        .set_refresh_interval_secs(ctx, metadata.refresh_interval_secs)
+                        .await?;
+                    schema_variant_definition
                 }
                 Some(schema_variant_definition) => schema_variant_definition,
             };
@@ -636,6 +689,9 @@ async fn create_schema_variant(
             if let Some(color) = variant_spec.color() {
                 schema_variant.set_color(ctx, color.to_owned()).await?;
             }
+            schema_variant
+                .set_refresh_interval_secs(ctx, variant_spec.refresh_interval_secs())
+                .await?;
 
             let (domain_attr_funcs, domain_default_values, map_key_funcs) = create_props(
                 ctx,
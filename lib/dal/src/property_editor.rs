@@ -8,7 +8,8 @@ use thiserror::Error;
 
 use crate::{
     pk, schema::variant::SchemaVariantError, AttributeValueError, AttributeValueId, ComponentError,
-    PropId, SchemaVariantId, StandardModelError, TransactionsError, ValidationResolverError,
+    PropId, SchemaVariantId, SecretError, StandardModelError, TransactionsError,
+    ValidationResolverError,
 };
 
 pub mod schema;
@@ -38,6 +39,8 @@ pub enum PropertyEditorError {
     SchemaVariant(#[from] SchemaVariantError),
     #[error("schema variant not found: {0}")]
     SchemaVariantNotFound(SchemaVariantId),
+    #[error("secret error: {0}")]
+    Secret(#[from] SecretError),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("standard model error: {0}")]
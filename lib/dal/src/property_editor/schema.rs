@@ -11,7 +11,7 @@ use si_pkg::PropSpecWidgetKind;
 use crate::property_editor::{PropertyEditorError, PropertyEditorPropId, PropertyEditorResult};
 use crate::{
     DalContext, LabelEntry, LabelList, Prop, PropKind, SchemaVariant, SchemaVariantId, Secret,
-    SecretId, StandardModel,
+    SecretDefinition, SecretDefinitionView, SecretId, SecretKind, StandardModel,
 };
 
 const PROPERTY_EDITOR_SCHEMA_FOR_SCHEMA_VARIANT: &str =
@@ -133,11 +133,20 @@ pub enum PropertyEditorPropWidgetKind {
     Array,
     Checkbox,
     Color,
-    ComboBox { options: Option<Value> },
+    ComboBox {
+        options: Option<Value>,
+    },
     Header,
     Map,
-    SecretSelect { options: LabelList<SecretId> },
-    Select { options: Option<Value> },
+    SecretSelect {
+        options: LabelList<SecretId>,
+        /// The definition of the kind of secret the widget selects from, if it is restricted to
+        /// one (via the "secretKind" widget option).
+        definition: Option<SecretDefinitionView>,
+    },
+    Select {
+        options: Option<Value>,
+    },
     Text,
     TextArea,
 }
@@ -216,15 +225,32 @@ impl PropertyEditorPropWidgetKind {
                 options: widget_options,
             },
             WidgetKind::Color => Self::Color,
-            WidgetKind::SecretSelect => Self::SecretSelect {
-                options: LabelList::new(
-                    Secret::list(ctx)
+            WidgetKind::SecretSelect => {
+                let kind: Option<SecretKind> = widget_options
+                    .as_ref()
+                    .and_then(|options| options.get("secretKind"))
+                    .and_then(Value::as_str)
+                    .map(str::parse)
+                    .transpose()?;
+                let definition = match &kind {
+                    Some(kind) => SecretDefinition::find_for_kind(ctx, kind)
                         .await?
-                        .into_iter()
-                        .map(|s| LabelEntry::new(s.name(), *s.id()))
-                        .collect(),
-                ),
-            },
+                        .map(Into::into),
+                    None => None,
+                };
+
+                Self::SecretSelect {
+                    options: LabelList::new(
+                        Secret::list(ctx)
+                            .await?
+                            .into_iter()
+                            .filter(|s| kind.as_ref().map_or(true, |kind| s.kind() == kind))
+                            .map(|s| LabelEntry::new(s.name(), *s.id()))
+                            .collect(),
+                    ),
+                    definition,
+                }
+            }
             WidgetKind::Text => Self::Text,
             WidgetKind::TextArea => Self::TextArea,
             WidgetKind::ComboBox => Self::ComboBox {
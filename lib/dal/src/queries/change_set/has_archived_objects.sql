@@ -0,0 +1,4 @@
+SELECT EXISTS(SELECT 1
+              FROM change_set_head_archive
+              WHERE change_set_pk = $2
+                AND in_tenancy_v1($1, tenancy_workspace_pk)) AS has_archived_objects
@@ -0,0 +1,2 @@
+SELECT last_written_at
+FROM change_set_last_written_at_v1($1, $2)
@@ -3,5 +3,6 @@ SELECT
     change_sets.pk AS value
 FROM change_sets
 WHERE
-    status = 'Open'
+    (status = $2::text OR ($2::text IS NULL AND status != 'Historical'))
     AND in_tenancy_v1($1, change_sets.tenancy_workspace_pk)
+ORDER BY change_sets.updated_at DESC
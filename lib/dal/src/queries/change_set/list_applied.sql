@@ -0,0 +1,7 @@
+SELECT row_to_json(change_sets.*) AS object
+FROM change_sets
+WHERE status = 'Applied'
+  AND applied_at IS NOT NULL
+  AND ($2::timestamp with time zone IS NULL OR applied_at <= $2)
+  AND in_tenancy_v1($1, change_sets.tenancy_workspace_pk)
+ORDER BY change_sets.applied_at DESC
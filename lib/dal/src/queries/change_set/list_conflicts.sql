@@ -0,0 +1,99 @@
+-- Attribute values that were directly set (via an "si:set*" func) both in the change set and on
+-- HEAD since the change set's base, to different values.
+SELECT 'attributeValue'                     AS object_kind,
+       cs_av.id                             AS object_id,
+       'bothModified'                       AS conflict_kind,
+       cs_av.attribute_context_component_id AS component_id,
+       props.path                           AS prop_path
+FROM attribute_values AS cs_av
+         INNER JOIN attribute_values AS head_av
+                    ON head_av.id = cs_av.id
+                        AND head_av.visibility_change_set_pk = ident_nil_v1()
+                        AND head_av.visibility_deleted_at IS NULL
+                        AND in_tenancy_v1($1, head_av.tenancy_workspace_pk)
+         INNER JOIN func_binding_return_values_v1($1, $2) AS cs_fbrv
+                    ON cs_fbrv.id = cs_av.func_binding_return_value_id
+         INNER JOIN func_binding_return_values_v1($1, $2) AS head_fbrv
+                    ON head_fbrv.id = head_av.func_binding_return_value_id
+         INNER JOIN attribute_value_belongs_to_attribute_prototype_v1($1, $2) AS avbtap
+                    ON avbtap.object_id = cs_av.id
+         INNER JOIN attribute_prototypes_v1($1, $2) AS attribute_prototypes
+                    ON attribute_prototypes.id = avbtap.belongs_to_id
+         INNER JOIN funcs_v1($1, $2) AS funcs
+                    ON funcs.id = attribute_prototypes.func_id
+         INNER JOIN props_v1($1, $2) AS props
+                    ON props.id = cs_av.attribute_context_prop_id
+WHERE cs_av.visibility_change_set_pk = $3
+  AND cs_av.visibility_deleted_at IS NULL
+  AND in_tenancy_v1($1, cs_av.tenancy_workspace_pk)
+  AND cs_av.attribute_context_component_id != ident_nil_v1()
+  AND head_av.updated_at > $4
+  AND funcs.name LIKE 'si:set%'
+  AND cs_fbrv.value IS DISTINCT FROM head_fbrv.value
+
+UNION ALL
+
+-- Components deleted in the change set that have been edited on HEAD since the change set's base.
+SELECT 'component'          AS object_kind,
+       cs_components.id     AS object_id,
+       'deletedInChangeSet' AS conflict_kind,
+       cs_components.id     AS component_id,
+       NULL                 AS prop_path
+FROM components AS cs_components
+WHERE cs_components.visibility_change_set_pk = $3
+  AND cs_components.visibility_deleted_at IS NOT NULL
+  AND in_tenancy_v1($1, cs_components.tenancy_workspace_pk)
+  AND EXISTS(SELECT 1
+             FROM attribute_values AS head_av
+             WHERE head_av.attribute_context_component_id = cs_components.id
+               AND head_av.visibility_change_set_pk = ident_nil_v1()
+               AND head_av.visibility_deleted_at IS NULL
+               AND in_tenancy_v1($1, head_av.tenancy_workspace_pk)
+               AND head_av.updated_at > $4)
+
+UNION ALL
+
+-- Components deleted on HEAD since the change set's base that have been edited in the change set.
+SELECT 'component'            AS object_kind,
+       head_components.id     AS object_id,
+       'deletedOnHead'        AS conflict_kind,
+       head_components.id     AS component_id,
+       NULL                   AS prop_path
+FROM components AS head_components
+WHERE head_components.visibility_change_set_pk = ident_nil_v1()
+  AND head_components.visibility_deleted_at IS NOT NULL
+  AND head_components.updated_at > $4
+  AND in_tenancy_v1($1, head_components.tenancy_workspace_pk)
+  AND NOT EXISTS(SELECT 1
+                 FROM components AS cs_components
+                 WHERE cs_components.id = head_components.id
+                   AND cs_components.visibility_change_set_pk = $3
+                   AND in_tenancy_v1($1, cs_components.tenancy_workspace_pk))
+  AND EXISTS(SELECT 1
+             FROM attribute_values AS cs_av
+             WHERE cs_av.attribute_context_component_id = head_components.id
+               AND cs_av.visibility_change_set_pk = $3
+               AND cs_av.visibility_deleted_at IS NULL
+               AND in_tenancy_v1($1, cs_av.tenancy_workspace_pk))
+
+UNION ALL
+
+-- Edges created in the change set whose head or tail component was deleted on HEAD since the
+-- change set's base.
+SELECT 'edge'              AS object_kind,
+       cs_edges.id         AS object_id,
+       'deletedOnHead'     AS conflict_kind,
+       head_components.id  AS component_id,
+       NULL                AS prop_path
+FROM edges AS cs_edges
+         INNER JOIN components AS head_components
+                    ON head_components.id IN (cs_edges.head_object_id, cs_edges.tail_object_id)
+                        AND head_components.visibility_change_set_pk = ident_nil_v1()
+                        AND head_components.visibility_deleted_at IS NOT NULL
+                        AND head_components.updated_at > $4
+                        AND in_tenancy_v1($1, head_components.tenancy_workspace_pk)
+WHERE cs_edges.visibility_change_set_pk = $3
+  AND cs_edges.visibility_deleted_at IS NULL
+  AND in_tenancy_v1($1, cs_edges.tenancy_workspace_pk)
+
+ORDER BY object_kind, object_id
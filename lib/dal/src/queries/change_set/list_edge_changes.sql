@@ -0,0 +1,19 @@
+SELECT e.id                                                                       AS edge_id,
+       CASE WHEN e.visibility_deleted_at IS NULL THEN 'added' ELSE 'deleted' END AS change_status,
+       e.head_object_id                                                           AS head_component_id,
+       head_socket.name                                                           AS head_socket_name,
+       e.tail_object_id                                                           AS tail_component_id,
+       tail_socket.name                                                           AS tail_socket_name
+FROM edges e
+         LEFT JOIN sockets_v1($1, $3) AS head_socket
+                   ON head_socket.id = e.head_socket_id
+         LEFT JOIN sockets_v1($1, $3) AS tail_socket
+                   ON tail_socket.id = e.tail_socket_id
+WHERE e.visibility_change_set_pk = $2
+  AND in_tenancy_v1($1, e.tenancy_workspace_pk)
+  AND (e.visibility_deleted_at IS NULL) = (e.id NOT IN (SELECT id
+                                                         FROM edges
+                                                         WHERE visibility_change_set_pk = ident_nil_v1()
+                                                           AND visibility_deleted_at IS NULL
+                                                           AND in_tenancy_v1($1, tenancy_workspace_pk)))
+ORDER BY e.id
@@ -0,0 +1,22 @@
+SELECT cs_func.id                                     AS func_id,
+       cs_func.name                                   AS name,
+       CASE
+           WHEN cs_func.visibility_deleted_at IS NOT NULL THEN 'deleted'
+           WHEN head_func.id IS NULL THEN 'added'
+           ELSE 'modified'
+           END                                        AS change_status,
+       head_func.code_base64                          AS before_code_base64,
+       CASE
+           WHEN cs_func.visibility_deleted_at IS NULL THEN cs_func.code_base64
+           END                                        AS after_code_base64
+FROM funcs cs_func
+         LEFT JOIN funcs head_func
+                   ON head_func.id = cs_func.id
+                       AND head_func.visibility_change_set_pk = ident_nil_v1()
+                       AND head_func.visibility_deleted_at IS NULL
+                       AND in_tenancy_v1($1, head_func.tenancy_workspace_pk)
+WHERE cs_func.visibility_change_set_pk = $2
+  AND in_tenancy_v1($1, cs_func.tenancy_workspace_pk)
+  -- Funcs created and deleted within the change set never existed as far as head is concerned.
+  AND (cs_func.visibility_deleted_at IS NULL OR head_func.id IS NOT NULL)
+ORDER BY cs_func.name
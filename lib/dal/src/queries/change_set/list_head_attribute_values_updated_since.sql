@@ -0,0 +1,8 @@
+SELECT DISTINCT ON (head_av.id) head_av.id
+FROM attribute_values AS head_av
+WHERE head_av.visibility_change_set_pk = ident_nil_v1()
+  AND head_av.visibility_deleted_at IS NULL
+  AND in_tenancy_v1($1, head_av.tenancy_workspace_pk)
+  AND head_av.attribute_context_component_id != ident_nil_v1()
+  AND head_av.updated_at > $2
+ORDER BY head_av.id
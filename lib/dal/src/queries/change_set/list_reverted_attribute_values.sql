@@ -0,0 +1,24 @@
+-- Attribute values that an applied change set ($3) directly set (via an "si:set*" func), along
+-- with the value they had before it was applied, if that differs from the value they have now.
+SELECT av.id                AS attribute_value_id,
+       previous_fbrv.value AS previous_value
+FROM change_set_head_archive AS archive
+         INNER JOIN attribute_values_v1($1, $2) AS av
+                    ON av.id = archive.object_id
+         INNER JOIN func_binding_return_values_v1($1, $2) AS previous_fbrv
+                    ON previous_fbrv.id = (archive.previous_object ->> 'func_binding_return_value_id')::ident
+         INNER JOIN func_binding_return_values_v1($1, $2) AS fbrv
+                    ON fbrv.id = av.func_binding_return_value_id
+         INNER JOIN attribute_value_belongs_to_attribute_prototype_v1($1, $2) AS avbtap
+                    ON avbtap.object_id = av.id
+         INNER JOIN attribute_prototypes_v1($1, $2) AS attribute_prototypes
+                    ON attribute_prototypes.id = avbtap.belongs_to_id
+         INNER JOIN funcs_v1($1, $2) AS funcs
+                    ON funcs.id = attribute_prototypes.func_id
+WHERE archive.change_set_pk = $3
+  AND archive.table_name = 'attribute_values'
+  AND archive.previous_object IS NOT NULL
+  AND in_tenancy_v1($1, archive.tenancy_workspace_pk)
+  AND funcs.name LIKE 'si:set%'
+  AND previous_fbrv.value IS DISTINCT FROM fbrv.value
+ORDER BY av.id
@@ -0,0 +1,10 @@
+-- The components and edges an applied change set touched, and whether each existed on HEAD before
+-- it was applied.
+SELECT DISTINCT ON (archive.table_name, archive.object_id) archive.table_name,
+                                                           archive.object_id,
+                                                           archive.previous_object IS NOT NULL AS existed_before_apply
+FROM change_set_head_archive AS archive
+WHERE archive.change_set_pk = $2
+  AND in_tenancy_v1($1, archive.tenancy_workspace_pk)
+  AND archive.table_name IN ('components', 'edges')
+ORDER BY archive.table_name, archive.object_id
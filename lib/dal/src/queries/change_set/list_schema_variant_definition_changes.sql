@@ -0,0 +1,21 @@
+SELECT cs_svd.id                                     AS schema_variant_definition_id,
+       cs_svd.name                                   AS name,
+       CASE
+           WHEN cs_svd.visibility_deleted_at IS NOT NULL THEN 'deleted'
+           WHEN head_svd.id IS NULL THEN 'added'
+           ELSE 'modified'
+           END                                       AS change_status,
+       head_svd.definition                           AS before_definition,
+       CASE
+           WHEN cs_svd.visibility_deleted_at IS NULL THEN cs_svd.definition
+           END                                       AS after_definition
+FROM schema_variant_definitions cs_svd
+         LEFT JOIN schema_variant_definitions head_svd
+                   ON head_svd.id = cs_svd.id
+                       AND head_svd.visibility_change_set_pk = ident_nil_v1()
+                       AND head_svd.visibility_deleted_at IS NULL
+                       AND in_tenancy_v1($1, head_svd.tenancy_workspace_pk)
+WHERE cs_svd.visibility_change_set_pk = $2
+  AND in_tenancy_v1($1, cs_svd.tenancy_workspace_pk)
+  AND (cs_svd.visibility_deleted_at IS NULL OR head_svd.id IS NOT NULL)
+ORDER BY cs_svd.name
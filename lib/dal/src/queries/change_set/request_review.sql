@@ -0,0 +1,7 @@
+UPDATE change_sets
+SET review_requested_at         = clock_timestamp(),
+    review_requested_by_user_pk = $3,
+    updated_at                  = clock_timestamp()
+WHERE pk = $2
+  AND in_tenancy_v1($1, change_sets.tenancy_workspace_pk)
+RETURNING review_requested_at, updated_at
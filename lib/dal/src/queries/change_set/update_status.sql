@@ -0,0 +1,6 @@
+UPDATE change_sets
+SET status     = $3,
+    updated_at = clock_timestamp()
+WHERE pk = $2
+  AND in_tenancy_v1($1, change_sets.tenancy_workspace_pk)
+RETURNING updated_at
@@ -0,0 +1,9 @@
+SELECT row_to_json(func_executions.*) AS object
+FROM func_executions
+WHERE in_tenancy_v1($1, func_executions.tenancy_workspace_pk)
+  AND ($2::ident IS NULL OR func_executions.func_id = $2::ident)
+  AND ($3::ident IS NULL OR func_executions.component_id = $3::ident)
+  AND ($4::ident IS NULL OR func_executions.fix_id = $4::ident)
+  AND ($5::text IS NULL OR func_executions.state = $5::text)
+ORDER BY func_executions.created_at DESC, func_executions.pk DESC
+LIMIT $6 OFFSET $7
@@ -0,0 +1,9 @@
+SELECT count(*)                              AS entries,
+       COALESCE(sum(hits.count), 0)::bigint  AS hits,
+       COALESCE(sum(store_count), 0)::bigint AS stores
+FROM func_execution_cache
+         LEFT JOIN LATERAL (SELECT count(*) AS count
+                            FROM func_executions
+                            WHERE func_executions.cached_from_func_execution_pk =
+                                  func_execution_cache.func_execution_pk) AS hits ON TRUE
+WHERE in_tenancy_v1($1, func_execution_cache.tenancy_workspace_pk)
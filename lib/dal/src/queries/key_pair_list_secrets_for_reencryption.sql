@@ -0,0 +1,7 @@
+SELECT encrypted_secrets.pk,
+       encrypted_secrets.key_pair_pk,
+       encrypted_secrets.crypted
+FROM encrypted_secrets
+WHERE encrypted_secrets.tenancy_workspace_pk = $1
+  AND encrypted_secrets.backend = 'database'
+  AND encrypted_secrets.key_pair_pk != $2
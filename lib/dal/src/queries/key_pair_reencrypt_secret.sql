@@ -0,0 +1,4 @@
+UPDATE encrypted_secrets
+SET crypted     = $2,
+    key_pair_pk = $3
+WHERE pk = $1
@@ -0,0 +1,15 @@
+-- Component attribute values for "SecretSelect" props that are set to the given secret.
+SELECT DISTINCT attribute_values.attribute_context_component_id AS component_id,
+                props.id                                        AS prop_id,
+                props.path                                      AS prop_path
+FROM attribute_values_v1($1, $2) AS attribute_values
+         INNER JOIN props_v1($1, $2) AS props
+                    ON props.id = attribute_values.attribute_context_prop_id
+         INNER JOIN func_binding_return_values_v1($1, $2) AS func_binding_return_values
+                    ON func_binding_return_values.id = attribute_values.func_binding_return_value_id
+         INNER JOIN components_v1($1, $2) AS components
+                    ON components.id = attribute_values.attribute_context_component_id
+WHERE attribute_values.attribute_context_component_id != ident_nil_v1()
+  AND props.widget_kind = 'SecretSelect'
+  AND func_binding_return_values.value = to_jsonb($3::text)
+ORDER BY component_id, prop_path;
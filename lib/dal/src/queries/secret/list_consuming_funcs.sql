@@ -0,0 +1,17 @@
+-- Action and qualification funcs that were executed with the given secret somewhere in their
+-- arguments, along with when they last ran.
+SELECT funcs.id                                  AS func_id,
+       funcs.name                                AS func_name,
+       funcs.backend_response_type               AS backend_response_type,
+       max(func_binding_return_values.updated_at) AS last_ran_at
+FROM func_binding_return_values_v1($1, $2) AS func_binding_return_values
+         INNER JOIN func_bindings_v1($1, $2) AS func_bindings
+                    ON func_bindings.id = func_binding_return_values.func_binding_id
+         INNER JOIN func_binding_belongs_to_func_v1($1, $2) AS func_binding_belongs_to_func
+                    ON func_binding_belongs_to_func.object_id = func_bindings.id
+         INNER JOIN funcs_v1($1, $2) AS funcs
+                    ON funcs.id = func_binding_belongs_to_func.belongs_to_id
+WHERE funcs.backend_response_type IN ('Action', 'Qualification')
+  AND func_binding_arg_strings_v1(func_bindings.args) @> jsonb_build_array($3::text)
+GROUP BY funcs.id, funcs.name, funcs.backend_response_type
+ORDER BY func_name;
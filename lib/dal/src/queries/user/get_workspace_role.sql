@@ -0,0 +1,5 @@
+SELECT role
+FROM user_belongs_to_workspaces
+WHERE user_pk = $1
+  AND workspace_pk = $2
+  AND visibility_deleted_at IS NULL
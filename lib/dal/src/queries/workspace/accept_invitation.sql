@@ -0,0 +1,6 @@
+UPDATE workspace_invitations
+SET accepted_at         = clock_timestamp(),
+    accepted_by_user_pk = $2,
+    updated_at          = clock_timestamp()
+WHERE pk = $1
+RETURNING accepted_at, updated_at
@@ -0,0 +1,11 @@
+SELECT users.pk                            AS user_pk,
+       users.name                          AS name,
+       users.email                         AS email,
+       user_belongs_to_workspaces.role     AS role,
+       user_belongs_to_workspaces.created_at AS joined_at
+FROM user_belongs_to_workspaces
+         INNER JOIN users ON users.pk = user_belongs_to_workspaces.user_pk
+WHERE user_belongs_to_workspaces.workspace_pk = $1
+  AND user_belongs_to_workspaces.visibility_deleted_at IS NULL
+  AND users.visibility_deleted_at IS NULL
+ORDER BY users.name
@@ -0,0 +1,6 @@
+SELECT row_to_json(workspace_invitations.*) AS object
+FROM workspace_invitations
+WHERE workspace_pk = $1
+  AND accepted_at IS NULL
+  AND ($2::text IS NULL OR lower(email) = lower($2::text))
+ORDER BY created_at
@@ -0,0 +1,7 @@
+UPDATE user_belongs_to_workspaces
+SET visibility_deleted_at = clock_timestamp(),
+    updated_at            = clock_timestamp()
+WHERE user_pk = $1
+  AND workspace_pk = $2
+  AND visibility_deleted_at IS NULL
+RETURNING pk
@@ -0,0 +1,6 @@
+DELETE
+FROM workspace_invitations
+WHERE pk = $1
+  AND workspace_pk = $2
+  AND accepted_at IS NULL
+RETURNING pk
@@ -0,0 +1,5 @@
+UPDATE workspaces
+SET required_change_set_approvals = $2,
+    updated_at                    = clock_timestamp()
+WHERE pk = $1
+RETURNING updated_at
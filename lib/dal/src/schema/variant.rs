@@ -105,7 +105,7 @@ pub enum SchemaVariantError {
     InvalidSchemaVariant,
     #[error("leaf function response type ({0}) must match leaf kind ({0})")]
     LeafFunctionMismatch(FuncBackendResponseType, LeafKind),
-    #[error("leaf function ({0}) must be JsAttribute")]
+    #[error("leaf function ({0}) must be JsAttribute or PythonAttribute")]
     LeafFunctionMustBeJsAttribute(FuncId),
     #[error("link not found in doc links map for doc link ref: {0}")]
     LinkNotFoundForDocLinkRef(String),
@@ -185,6 +185,9 @@ pub struct SchemaVariant {
     // NOTE(nick): we may want to replace this with a better solution. We use this to ensure
     // components are not created unless the variant has been finalized at least once.
     finalized_once: bool,
+    /// How often the resource scheduler refreshes the resources of the variant's
+    /// [`Components`](crate::Component), unless they set their own interval.
+    refresh_interval_secs: Option<i64>,
 }
 
 impl_standard_model! {
@@ -411,6 +414,11 @@ impl SchemaVariant {
     standard_model_accessor!(root_prop_id, Option<Pk(PropId)>, SchemaVariantResult);
     standard_model_accessor!(link, Option<String>, SchemaVariantResult);
     standard_model_accessor!(finalized_once, bool, SchemaVariantResult);
+    standard_model_accessor!(
+        refresh_interval_secs,
+        OptionBigInt<i64>,
+        SchemaVariantResult
+    );
     standard_model_accessor!(
         schema_variant_definition_id,
         Option<Pk(SchemaVariantDefinitionId)>,
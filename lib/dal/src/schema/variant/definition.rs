@@ -136,6 +136,8 @@ pub struct SchemaVariantDefinition {
     func_id: FuncId,
     description: Option<String>,
     schema_variant_id: Option<SchemaVariantId>,
+    /// How often the resources of the variant's components are refreshed, in seconds.
+    refresh_interval_secs: Option<i64>,
 }
 
 impl_standard_model! {
@@ -268,6 +270,11 @@ impl SchemaVariantDefinition {
         Enum(ComponentType),
         SchemaVariantDefinitionResult
     );
+    standard_model_accessor!(
+        refresh_interval_secs,
+        OptionBigInt<i64>,
+        SchemaVariantDefinitionResult
+    );
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -289,6 +296,9 @@ pub struct SchemaVariantDefinitionMetadataJson {
     pub component_type: ComponentType,
     pub link: Option<String>,
     pub description: Option<String>,
+    /// How often the resources of the variant's components are refreshed, in seconds.
+    #[serde(alias = "refresh_interval_secs", default)]
+    pub refresh_interval_secs: Option<i64>,
 }
 
 impl SchemaVariantDefinitionMetadataJson {
@@ -316,6 +326,7 @@ impl From<SchemaVariantDefinition> for SchemaVariantDefinitionMetadataJson {
             component_type: value.component_type,
             link: value.link,
             description: value.description,
+            refresh_interval_secs: value.refresh_interval_secs,
         }
     }
 }
@@ -342,6 +353,7 @@ impl SchemaVariantDefinitionMetadataJson {
             component_type,
             link: link.map(|l| l.to_string()),
             description: description.map(|d| d.to_string()),
+            refresh_interval_secs: None,
         }
     }
 
@@ -381,6 +393,7 @@ impl SchemaVariantDefinitionMetadataJson {
                 .await
                 .map_err(Box::new)?
                 .into(),
+            refresh_interval_secs: variant.refresh_interval_secs().copied(),
         })
     }
 }
@@ -439,6 +452,9 @@ impl SchemaVariantDefinitionJson {
         if let Some(link) = metadata.link {
             builder.try_link(link.as_str())?;
         }
+        if let Some(refresh_interval_secs) = metadata.refresh_interval_secs {
+            builder.refresh_interval_secs(refresh_interval_secs);
+        }
         for input_socket in &self.input_sockets {
             builder.socket(input_socket.to_spec(true, identity_func_unique_id)?);
         }
@@ -472,6 +488,7 @@ impl SchemaVariantDefinitionJson {
             component_type: variant_spec.component_type.into(),
             link: variant_spec.link.as_ref().map(|l| l.to_string()),
             description: None, // XXX - does this exist?
+            refresh_interval_secs: variant_spec.refresh_interval_secs,
         };
 
         Ok(metadata)
@@ -206,7 +206,10 @@ impl SchemaVariant {
         let func = Func::get_by_id(ctx, &func_id)
             .await?
             .ok_or(FuncError::NotFound(func_id))?;
-        if func.backend_kind() != &FuncBackendKind::JsAttribute {
+        if !matches!(
+            func.backend_kind(),
+            FuncBackendKind::JsAttribute | FuncBackendKind::PythonAttribute
+        ) {
             return Err(SchemaVariantError::LeafFunctionMustBeJsAttribute(
                 *func.id(),
             ));
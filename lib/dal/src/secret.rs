@@ -1,5 +1,7 @@
 use crate::{Tenancy, TransactionsError};
+use std::borrow::Cow;
 use std::fmt;
+use std::str::FromStr;
 
 use base64::{engine::general_purpose, Engine};
 use serde::{Deserialize, Serialize};
@@ -14,36 +16,71 @@ use telemetry::prelude::*;
 use thiserror::Error;
 use veritech_client::SensitiveContainer;
 
+pub mod backend;
+pub mod definition;
+pub mod usage;
+
+pub use backend::{
+    SecretBackend, SecretBackendError, SecretBackendKind, SecretBackendResult, SecretBackends,
+};
+pub use definition::{
+    SecretDefinition, SecretDefinitionField, SecretDefinitionId, SecretDefinitionPk,
+    SecretDefinitionView,
+};
+pub use usage::{SecretComponentConsumer, SecretConsumers, SecretFuncConsumer};
+
 use crate::{
     impl_standard_model,
     key_pair::KeyPairPk,
     pk,
     standard_model::{self, TypeHint},
     standard_model_accessor, standard_model_accessor_ro, DalContext, HistoryEvent,
-    HistoryEventError, KeyPair, KeyPairError, StandardModel, StandardModelError, Timestamp,
-    Visibility,
+    HistoryEventError, KeyPair, KeyPairError, StandardModel, StandardModelError, Timestamp, User,
+    UserError, Visibility, WorkspacePermission,
 };
 
 /// Error type for Secrets.
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum SecretError {
+    #[error("secret backend error: {0}")]
+    Backend(#[from] SecretBackendError),
     #[error("error when decrypting crypted secret")]
     DecryptionFailed,
+    #[error("secret definition not found for kind: {0}")]
+    DefinitionNotFound(SecretKind),
     #[error("error deserializing message: {0}")]
     DeserializeMessage(#[source] serde_json::Error),
+    #[error("secret {0} is stored in the {1} backend; its message cannot be updated in SI")]
+    ExternalMessage(SecretId, SecretBackendKind),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
+    #[error("secret {0} is in use by {1} component(s); delete with force to delete it anyway")]
+    InUse(SecretId, usize),
+    #[error("invalid secret definition for {0}: {1}")]
+    InvalidDefinition(SecretKind, String),
+    #[error("invalid secret kind: {0}")]
+    InvalidKind(String),
+    #[error("invalid {0} secret message: {1}")]
+    InvalidMessage(SecretKind, String),
     #[error("key pair error: {0}")]
     KeyPair(#[from] KeyPairError),
     #[error("key pair not found for secret")]
     KeyPairNotFound,
+    #[error("secret {0} does not belong to a workspace")]
+    NoWorkspace(SecretId),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
     #[error("standard model error: {0}")]
     StandardModelError(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("unknown func backend response type: {0}")]
+    UnknownFuncBackendResponseType(String),
+    #[error("user error: {0}")]
+    User(#[from] UserError),
 }
 
 /// Result type for Secrets.
@@ -64,6 +101,13 @@ pub struct Secret {
     object_type: SecretObjectType,
     key_pair_pk: KeyPairPk,
     kind: SecretKind,
+    #[serde(default)]
+    backend: SecretBackendKind,
+    /// Where the secret's message can be found in its [`backend`](SecretBackendKind), unless it
+    /// is stored in SI.
+    backend_reference: Option<String>,
+    /// Bumped each time the secret's message is [updated](Secret::update_message).
+    message_version: i32,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -117,10 +161,130 @@ impl Secret {
     // Once created, these object fields are to be considered immutable
     standard_model_accessor_ro!(object_type, SecretObjectType);
     standard_model_accessor_ro!(kind, SecretKind);
+    standard_model_accessor_ro!(backend, SecretBackendKind);
+    standard_model_accessor_ro!(message_version, i32);
+
+    pub fn backend_reference(&self) -> Option<&str> {
+        self.backend_reference.as_deref()
+    }
+
+    /// Replaces the secret's message in place, so that everything referencing the secret picks
+    /// up the new value. The provided message must be sealed with the [`KeyPair`] for
+    /// `key_pair_pk`; it is re-sealed with the workspace's current [`KeyPair`] before being
+    /// stored, and the secret's [`message_version`](Self::message_version) is bumped.
+    pub async fn update_message(
+        &mut self,
+        ctx: &DalContext,
+        crypted: &[u8],
+        key_pair_pk: KeyPairPk,
+        version: SecretVersion,
+        algorithm: SecretAlgorithm,
+    ) -> SecretResult<()> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::ManageSecrets).await?;
+        if self.backend != SecretBackendKind::Database {
+            return Err(SecretError::ExternalMessage(self.id, self.backend));
+        }
+
+        let provided_key_pair = KeyPair::get_by_pk(ctx, key_pair_pk).await?;
+        let message = match (version, algorithm) {
+            (SecretVersion::V1, SecretAlgorithm::Sealedbox) => sealedbox::open(
+                crypted,
+                provided_key_pair.public_key(),
+                provided_key_pair.secret_key(),
+            )
+            .map_err(|_| SecretError::DecryptionFailed)?,
+        };
+        let value =
+            serde_json::from_slice::<Value>(&message).map_err(SecretError::DeserializeMessage)?;
+        SecretDefinition::get_for_kind(ctx, &self.kind)
+            .await?
+            .validate_message(&value)?;
+
+        let current_key_pair = KeyPair::get_current(ctx).await?;
+        let crypted = sealedbox::seal(&message, current_key_pair.public_key());
+        let message_version = self.message_version + 1;
+
+        for (column, value, hint) in [
+            ("crypted", encode_crypted(&crypted), TypeHint::Text),
+            (
+                "key_pair_pk",
+                current_key_pair.pk().to_string(),
+                TypeHint::Ident,
+            ),
+            ("version", SecretVersion::V1.to_string(), TypeHint::Text),
+            (
+                "algorithm",
+                SecretAlgorithm::Sealedbox.to_string(),
+                TypeHint::Text,
+            ),
+        ] {
+            standard_model::update(ctx, "encrypted_secrets", column, self.id(), &value, hint)
+                .await?;
+        }
+        let updated_at = standard_model::update(
+            ctx,
+            "encrypted_secrets",
+            "message_version",
+            self.id(),
+            &message_version,
+            TypeHint::Integer,
+        )
+        .await?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            Self::history_event_label(vec!["update_message"]),
+            Self::history_event_message("message updated"),
+            &serde_json::json!({
+                "pk": self.pk,
+                "id": self.id,
+                "message_version": message_version,
+            }),
+        )
+        .await?;
+
+        self.key_pair_pk = current_key_pair.pk();
+        self.message_version = message_version;
+        self.timestamp.updated_at = updated_at;
+
+        Ok(())
+    }
 
     pub async fn key_pair(&self, ctx: &DalContext) -> SecretResult<KeyPair> {
         Ok(KeyPair::get_by_pk(ctx, self.key_pair_pk).await?)
     }
+
+    /// Lists the [`Components`](crate::Component) and [`Funcs`](crate::Func) consuming this
+    /// secret. See [`SecretConsumers`].
+    pub async fn list_consumers(&self, ctx: &DalContext) -> SecretResult<SecretConsumers> {
+        SecretConsumers::list(ctx, self.id).await
+    }
+
+    /// Deletes the secret. Secrets still referenced by a [`Component`](crate::Component) are
+    /// only deleted when `force` is set.
+    pub async fn delete(&mut self, ctx: &DalContext, force: bool) -> SecretResult<()> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::ManageSecrets).await?;
+        if !force {
+            let consumers = self.list_consumers(ctx).await?;
+            if consumers.in_use() {
+                return Err(SecretError::InUse(self.id, consumers.components.len()));
+            }
+        }
+
+        // Delete from the underlying `encrypted_secrets` table rather than the `secrets` view
+        let deleted_at = standard_model::delete_by_id(ctx, "encrypted_secrets", self.id).await?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            Self::history_event_label(vec!["deleted"]),
+            Self::history_event_message("deleted"),
+            &serde_json::json!({"pk": self.pk, "id": self.id, "force": force}),
+        )
+        .await?;
+        self.visibility.deleted_at = Some(deleted_at);
+        self.timestamp.updated_at = deleted_at;
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -130,6 +294,8 @@ pub struct SecretView {
     pub name: String,
     pub object_type: SecretObjectType,
     pub kind: SecretKind,
+    pub backend: SecretBackendKind,
+    pub backend_reference: Option<String>,
 }
 
 impl From<Secret> for SecretView {
@@ -138,7 +304,9 @@ impl From<Secret> for SecretView {
             id: *secret.id(),
             name: secret.name().to_owned(),
             object_type: *secret.object_type(),
-            kind: *secret.kind(),
+            kind: secret.kind().clone(),
+            backend: secret.backend,
+            backend_reference: secret.backend_reference,
         }
     }
 }
@@ -162,6 +330,10 @@ pub struct EncryptedSecret {
     crypted: Vec<u8>,
     version: SecretVersion,
     algorithm: SecretAlgorithm,
+    #[serde(default)]
+    backend: SecretBackendKind,
+    backend_reference: Option<String>,
+    message_version: i32,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -180,6 +352,8 @@ impl fmt::Debug for EncryptedSecret {
             .field("kind", &self.kind)
             .field("version", &self.version)
             .field("algorithm", &self.algorithm)
+            .field("backend", &self.backend)
+            .field("backend_reference", &self.backend_reference)
             .field("tenancy", &self.tenancy)
             .field("timestamp", &self.timestamp)
             .field("visibility", &self.visibility)
@@ -209,7 +383,10 @@ impl EncryptedSecret {
         version: SecretVersion,
         algorithm: SecretAlgorithm,
     ) -> SecretResult<Secret> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::ManageSecrets).await?;
+
         let name = name.as_ref();
+        SecretDefinition::get_for_kind(ctx, &kind).await?;
 
         let row = ctx
             .txns()
@@ -235,6 +412,49 @@ impl EncryptedSecret {
         Ok(object)
     }
 
+    /// Creates a new secret whose message is stored outside of SI, in the provided
+    /// [`backend`](SecretBackendKind), and returns a corresponding [`Secret`] representation.
+    /// The message is resolved from the `backend_reference` each time the secret is decrypted.
+    #[allow(clippy::new_ret_no_self)]
+    pub async fn new_external(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        object_type: SecretObjectType,
+        kind: SecretKind,
+        backend: SecretBackendKind,
+        backend_reference: impl AsRef<str>,
+    ) -> SecretResult<Secret> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::ManageSecrets).await?;
+
+        let name = name.as_ref();
+        let backend_reference = backend_reference.as_ref();
+        if backend == SecretBackendKind::Database || backend_reference.is_empty() {
+            return Err(SecretBackendError::ReferenceRequired(backend).into());
+        }
+        SecretDefinition::get_for_kind(ctx, &kind).await?;
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM encrypted_secret_create_external_v1($1, $2, $3, $4, $5, $6, $7)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &name,
+                    &object_type.as_ref(),
+                    &kind.as_ref(),
+                    &backend.as_ref(),
+                    &backend_reference,
+                ],
+            )
+            .await?;
+        let object: Secret = standard_model::finish_create_from_row(ctx, row).await?;
+
+        Ok(object)
+    }
+
     standard_model_accessor!(name, String, SecretResult);
 
     // Once created, these object fields are to be considered immutable
@@ -242,10 +462,33 @@ impl EncryptedSecret {
     standard_model_accessor_ro!(kind, SecretKind);
     standard_model_accessor_ro!(version, SecretVersion);
     standard_model_accessor_ro!(algorithm, SecretAlgorithm);
+    standard_model_accessor_ro!(backend, SecretBackendKind);
 
     /// Decrypts the encrypted secret with its associated [`KeyPair`] and returns a
-    /// [`DecryptedSecret`].
+    /// [`DecryptedSecret`]. Secrets stored outside of SI are instead resolved with the
+    /// [`SecretBackend`] registered for their [`backend`](SecretBackendKind).
     pub async fn decrypt(self, ctx: &DalContext) -> SecretResult<DecryptedSecret> {
+        if self.backend != SecretBackendKind::Database {
+            let reference = self
+                .backend_reference
+                .as_deref()
+                .ok_or(SecretBackendError::ReferenceRequired(self.backend))?;
+            let workspace_pk = self
+                .tenancy
+                .workspace_pk()
+                .ok_or(SecretError::NoWorkspace(self.id))?;
+            let message = ctx
+                .secret_backends()
+                .resolve(workspace_pk, self.backend, reference)
+                .await?;
+            return Ok(DecryptedSecret {
+                name: self.name,
+                object_type: self.object_type,
+                secret_kind: self.kind,
+                message,
+            });
+        }
+
         let key_pair = self.key_pair(ctx).await?;
         self.into_decrypted(key_pair.public_key(), key_pair.secret_key())
     }
@@ -270,6 +513,18 @@ impl EncryptedSecret {
     pub async fn key_pair(&self, ctx: &DalContext) -> SecretResult<KeyPair> {
         Ok(KeyPair::get_by_pk(ctx, self.key_pair_pk).await?)
     }
+
+    /// Checks the decrypted message against the [`SecretDefinition`] for the secret's
+    /// [`kind`](SecretKind). Secrets stored outside of SI are not resolved, and so not checked.
+    pub async fn validate_message(self, ctx: &DalContext) -> SecretResult<()> {
+        if self.backend != SecretBackendKind::Database {
+            return Ok(());
+        }
+
+        let definition = SecretDefinition::get_for_kind(ctx, &self.kind).await?;
+        let decrypted = self.decrypt(ctx).await?;
+        definition.validate_message(&decrypted.message)
+    }
 }
 
 /// A secret that has been decrypted.
@@ -305,7 +560,7 @@ impl DecryptedSecret {
 
     /// Gets the decrypted secret's kind.
     pub fn kind(&self) -> SecretKind {
-        self.secret_kind
+        self.secret_kind.clone()
     }
 }
 
@@ -367,22 +622,65 @@ pub enum SecretObjectType {
     Credential,
 }
 
-/// The kind of a secret.
-#[remain::sorted]
-#[derive(
-    AsRefStr, Clone, Copy, Debug, Deserialize, Display, EnumString, Eq, PartialEq, Serialize,
-)]
-#[serde(rename_all = "camelCase")]
-#[strum(serialize_all = "camelCase")]
-pub enum SecretKind {
+/// The kind of a secret: the name of the [`SecretDefinition`] describing its fields. Kinds start
+/// with a letter and contain only letters, digits, `-` and `_`.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SecretKind(Cow<'static, str>);
+
+impl SecretKind {
     /// An AWS access key
-    AwsAccessKey,
+    pub const AWS_ACCESS_KEY: Self = Self(Cow::Borrowed("awsAccessKey"));
     /// An Azure service principal
-    AzureServicePrincipal,
+    pub const AZURE_SERVICE_PRINCIPAL: Self = Self(Cow::Borrowed("azureServicePrincipal"));
     /// A Docker Hub credential
-    DockerHub,
+    pub const DOCKER_HUB: Self = Self(Cow::Borrowed("dockerHub"));
     /// A Helm repository credential
-    HelmRepo,
+    pub const HELM_REPO: Self = Self(Cow::Borrowed("helmRepo"));
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SecretKind {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SecretKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for SecretKind {
+    type Err = SecretError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.to_owned())
+    }
+}
+
+impl TryFrom<String> for SecretKind {
+    type Error = SecretError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut chars = value.chars();
+        let valid = chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(SecretError::InvalidKind(value));
+        }
+        Ok(Self(Cow::Owned(value)))
+    }
+}
+
+impl From<SecretKind> for String {
+    fn from(value: SecretKind) -> Self {
+        value.0.into_owned()
+    }
 }
 
 fn encode_crypted(crypted: &[u8]) -> String {
@@ -445,6 +743,9 @@ mod tests {
                 crypted,
                 version: Default::default(),
                 algorithm: Default::default(),
+                backend: Default::default(),
+                backend_reference: None,
+                message_version: 1,
                 tenancy: Tenancy::new(wid),
                 timestamp: Timestamp::now(),
                 visibility: Visibility::new_head(false),
@@ -473,7 +774,7 @@ mod tests {
             let encrypted = encrypted_secret(
                 "the-cadillac-three",
                 SecretObjectType::Credential,
-                SecretKind::DockerHub,
+                SecretKind::DOCKER_HUB,
                 crypted,
                 WorkspacePk::NONE,
             );
@@ -483,7 +784,7 @@ mod tests {
 
             assert_eq!("the-cadillac-three", decrypted.name);
             assert_eq!(SecretObjectType::Credential, decrypted.object_type);
-            assert_eq!(SecretKind::DockerHub, decrypted.secret_kind);
+            assert_eq!(SecretKind::DOCKER_HUB, decrypted.secret_kind);
             assert_eq!(message, decrypted.message);
         }
     }
@@ -549,12 +850,12 @@ mod tests {
         }
 
         fn invalid() -> &'static str {
-            r#"{"kind":"nope"}"#
+            r#"{"kind":"not a kind"}"#
         }
 
         fn object() -> Object {
             Object {
-                kind: SecretKind::DockerHub,
+                kind: SecretKind::DOCKER_HUB,
             }
         }
 
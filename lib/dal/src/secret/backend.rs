@@ -0,0 +1,347 @@
+//! This module contains [`SecretBackend`], which resolves the message of a
+//! [`Secret`](crate::Secret) that is stored outside of SI, and the [`SecretBackends`] registry
+//! consulted when decrypting secrets for function execution.
+//!
+//! Secrets stored in SI itself (the [`Database`](SecretBackendKind::Database) backend) are sealed
+//! with the workspace [`KeyPair`](crate::KeyPair) and never go through a [`SecretBackend`].
+//!
+//! The backends are shared by every workspace, so each one only resolves references beneath the
+//! prefix of the [`Workspace`](crate::Workspace) the secret belongs to (e.g. the
+//! `<workspace pk>/` directory of the [`FileSecretBackend`]). A workspace cannot read another
+//! workspace's secrets by guessing their references.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::{AsRefStr, Display, EnumString};
+use thiserror::Error;
+
+use crate::WorkspacePk;
+
+/// The environment variable that enables the [`EnvSecretBackend`] when set to `true`. It is off
+/// by default since it reads the environment of the process executing functions.
+pub const ENV_SECRET_BACKEND_ENABLED_ENV_VAR: &str = "SI_SECRET_BACKEND_ENV_ENABLED";
+/// The environment variable holding the root directory of the [`FileSecretBackend`].
+pub const FILE_SECRET_BACKEND_ROOT_ENV_VAR: &str = "SI_SECRET_BACKEND_FILE_ROOT";
+/// The prefix prepended to references resolved by the [`EnvSecretBackend`].
+pub const ENV_SECRET_BACKEND_PREFIX: &str = "SI_SECRET_";
+/// The environment variable holding the address of the Vault server.
+pub const VAULT_ADDR_ENV_VAR: &str = "SI_VAULT_ADDR";
+/// The environment variable holding the token used to authenticate with Vault.
+pub const VAULT_TOKEN_ENV_VAR: &str = "SI_VAULT_TOKEN";
+/// The environment variable holding the mount path of the Vault KV (version 2) engine.
+pub const VAULT_KV_MOUNT_ENV_VAR: &str = "SI_VAULT_KV_MOUNT";
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum SecretBackendError {
+    #[error("environment variable not found: {0}")]
+    EnvVarNotFound(String),
+    #[error("invalid secret backend reference: {0}")]
+    InvalidReference(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("secret message is not valid json: {0}")]
+    Message(#[source] serde_json::Error),
+    #[error("secret backend not configured: {0}")]
+    NotConfigured(SecretBackendKind),
+    #[error("secret backend reference required for the {0} backend")]
+    ReferenceRequired(SecretBackendKind),
+    #[error("http request error: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("unexpected response from vault for {0}")]
+    VaultResponse(String),
+}
+
+pub type SecretBackendResult<T> = Result<T, SecretBackendError>;
+
+/// Where the message of a [`Secret`](crate::Secret) is stored.
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumString,
+    Eq,
+    Hash,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum SecretBackendKind {
+    /// Sealed with the workspace [`KeyPair`](crate::KeyPair) and stored in SI.
+    #[default]
+    Database,
+    /// An environment variable of the process executing functions, named
+    /// [`ENV_SECRET_BACKEND_PREFIX`]`<workspace pk>_<reference>`. Intended for CI.
+    Env,
+    /// A JSON file at `<workspace pk>/<reference>` beneath a configured root directory. Intended
+    /// for local development.
+    File,
+    /// A HashiCorp Vault KV (version 2) secret at `<workspace pk>/<reference>`.
+    Vault,
+}
+
+/// Resolves the message of a [`Secret`](crate::Secret) stored outside of SI from its
+/// reference, within the prefix of the [`Workspace`](crate::Workspace) it belongs to.
+#[async_trait]
+pub trait SecretBackend: fmt::Debug + Send + Sync {
+    fn kind(&self) -> SecretBackendKind;
+
+    async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &str,
+    ) -> SecretBackendResult<Value>;
+}
+
+/// The [`SecretBackends`](SecretBackend) available when decrypting secrets, by
+/// [`SecretBackendKind`].
+#[derive(Clone, Debug, Default)]
+pub struct SecretBackends {
+    backends: HashMap<SecretBackendKind, Arc<dyn SecretBackend>>,
+}
+
+impl SecretBackends {
+    /// Registers the [`EnvSecretBackend`], [`FileSecretBackend`] and [`VaultSecretBackend`] that
+    /// are enabled or configured in the process environment.
+    pub fn from_env() -> Self {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Like [`SecretBackends::from_env()`], but reads the provided variables instead of the
+    /// process environment.
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let vars: HashMap<String, String> = vars.into_iter().collect();
+        let mut backends = Self::default();
+        if vars
+            .get(ENV_SECRET_BACKEND_ENABLED_ENV_VAR)
+            .map_or(false, |enabled| enabled == "true")
+        {
+            backends.register(EnvSecretBackend::new(
+                ENV_SECRET_BACKEND_PREFIX,
+                vars.clone(),
+            ));
+        }
+        if let Some(root) = vars.get(FILE_SECRET_BACKEND_ROOT_ENV_VAR) {
+            backends.register(FileSecretBackend::new(root));
+        }
+        if let (Some(address), Some(token)) =
+            (vars.get(VAULT_ADDR_ENV_VAR), vars.get(VAULT_TOKEN_ENV_VAR))
+        {
+            let mount = vars
+                .get(VAULT_KV_MOUNT_ENV_VAR)
+                .cloned()
+                .unwrap_or_else(|| "secret".to_owned());
+            backends.register(VaultSecretBackend::new(address, token, mount));
+        }
+        backends
+    }
+
+    /// Registers a [`SecretBackend`], replacing any backend already registered for its kind.
+    pub fn register(&mut self, backend: impl SecretBackend + 'static) {
+        self.backends.insert(backend.kind(), Arc::new(backend));
+    }
+
+    pub fn get(&self, kind: SecretBackendKind) -> SecretBackendResult<Arc<dyn SecretBackend>> {
+        self.backends
+            .get(&kind)
+            .cloned()
+            .ok_or(SecretBackendError::NotConfigured(kind))
+    }
+
+    /// Resolves `reference` for the [`Workspace`](crate::Workspace) for the provided
+    /// [`WorkspacePk`] with the backend registered for `kind`.
+    pub async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        kind: SecretBackendKind,
+        reference: &str,
+    ) -> SecretBackendResult<Value> {
+        self.get(kind)?.resolve(workspace_pk, reference).await
+    }
+}
+
+/// Reads secret messages as JSON from environment variables named by the reference, with a
+/// fixed prefix (followed by the workspace pk) so that references cannot read the rest of the
+/// environment. Only the variables starting with the prefix are kept.
+#[derive(Clone)]
+pub struct EnvSecretBackend {
+    prefix: String,
+    vars: HashMap<String, String>,
+}
+
+impl EnvSecretBackend {
+    pub fn new(
+        prefix: impl Into<String>,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        let prefix = prefix.into();
+        let vars = vars
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .collect();
+        Self { prefix, vars }
+    }
+}
+
+impl fmt::Debug for EnvSecretBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnvSecretBackend")
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl SecretBackend for EnvSecretBackend {
+    fn kind(&self) -> SecretBackendKind {
+        SecretBackendKind::Env
+    }
+
+    async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &str,
+    ) -> SecretBackendResult<Value> {
+        let name = format!("{}{}_{}", self.prefix, workspace_pk, reference);
+        let raw = self
+            .vars
+            .get(&name)
+            .ok_or(SecretBackendError::EnvVarNotFound(name))?;
+        serde_json::from_str(raw).map_err(SecretBackendError::Message)
+    }
+}
+
+/// Reads secret messages from JSON files beneath a root directory. References are paths
+/// relative to the workspace's directory in the root and may not escape it.
+#[derive(Clone, Debug)]
+pub struct FileSecretBackend {
+    root: PathBuf,
+}
+
+impl FileSecretBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl SecretBackend for FileSecretBackend {
+    fn kind(&self) -> SecretBackendKind {
+        SecretBackendKind::File
+    }
+
+    async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &str,
+    ) -> SecretBackendResult<Value> {
+        if Path::new(reference).is_absolute() {
+            return Err(SecretBackendError::InvalidReference(reference.to_owned()));
+        }
+        let root = tokio::fs::canonicalize(self.root.join(workspace_pk.to_string())).await?;
+        let path = tokio::fs::canonicalize(root.join(reference)).await?;
+        if !path.starts_with(&root) {
+            return Err(SecretBackendError::InvalidReference(reference.to_owned()));
+        }
+
+        let raw = tokio::fs::read(path).await?;
+        serde_json::from_slice(&raw).map_err(SecretBackendError::Message)
+    }
+}
+
+/// Reads secret messages from a HashiCorp Vault KV (version 2) engine. References are secret
+/// paths relative to the workspace's path in the engine's mount, made up of `/`-separated
+/// segments of ASCII letters, digits, `.`, `_` and `-` (other than `.` and `..`).
+#[derive(Clone)]
+pub struct VaultSecretBackend {
+    address: String,
+    token: String,
+    mount: String,
+    client: reqwest::Client,
+}
+
+impl VaultSecretBackend {
+    pub fn new(
+        address: impl Into<String>,
+        token: impl Into<String>,
+        mount: impl Into<String>,
+    ) -> Self {
+        Self {
+            address: address.into(),
+            token: token.into(),
+            mount: mount.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl fmt::Debug for VaultSecretBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VaultSecretBackend")
+            .field("address", &self.address)
+            .field("mount", &self.mount)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl SecretBackend for VaultSecretBackend {
+    fn kind(&self) -> SecretBackendKind {
+        SecretBackendKind::Vault
+    }
+
+    async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &str,
+    ) -> SecretBackendResult<Value> {
+        // The reference ends up in the request URL, so anything that could be decoded into a
+        // different path (e.g. "%2e%2e") or split off into a query or fragment is refused.
+        let reference = reference.trim_matches('/');
+        let is_valid_segment = |segment: &str| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        };
+        if reference.is_empty() || !reference.split('/').all(is_valid_segment) {
+            return Err(SecretBackendError::InvalidReference(reference.to_owned()));
+        }
+
+        let url = format!(
+            "{}/v1/{}/data/{}/{}",
+            self.address.trim_end_matches('/'),
+            self.mount.trim_matches('/'),
+            workspace_pk,
+            reference
+        );
+        let mut response: Value = self
+            .client
+            .get(url)
+            .header("X-Vault-Token", &self.token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        response
+            .pointer_mut("/data/data")
+            .map(Value::take)
+            .ok_or_else(|| SecretBackendError::VaultResponse(reference.to_owned()))
+    }
+}
@@ -0,0 +1,220 @@
+//! This module contains [`SecretDefinition`], which describes the fields of a
+//! [`SecretKind`]. Definitions are data: the builtin kinds are created for every
+//! [`Workspace`](crate::Workspace), and more can be created directly or installed from a
+//! package.
+
+use std::collections::HashSet;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use telemetry::prelude::*;
+
+use crate::secret::{SecretError, SecretKind, SecretResult};
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor_ro, DalContext, StandardModel,
+    Tenancy, Timestamp, Visibility,
+};
+
+pk!(SecretDefinitionPk);
+pk!(SecretDefinitionId);
+
+/// A field of a [`SecretDefinition`]. Every field is a string in the secret's message.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretDefinitionField {
+    pub name: String,
+    pub display_name: Option<String>,
+    /// Whether or not the field should be masked when entered and redacted from output.
+    #[serde(default = "default_true")]
+    pub sensitive: bool,
+    #[serde(default = "default_true")]
+    pub required: bool,
+    /// A regular expression the field's value must match.
+    pub pattern: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Describes the fields of the secrets of a given [`SecretKind`]. The definition's name is the
+/// kind.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SecretDefinition {
+    pk: SecretDefinitionPk,
+    id: SecretDefinitionId,
+    name: String,
+    display_name: Option<String>,
+    description: Option<String>,
+    fields: Vec<SecretDefinitionField>,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: SecretDefinition,
+    pk: SecretDefinitionPk,
+    id: SecretDefinitionId,
+    table_name: "secret_definitions",
+    history_event_label_base: "secret_definition",
+    history_event_message_name: "Secret Definition"
+}
+
+impl SecretDefinition {
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        kind: &SecretKind,
+        display_name: Option<String>,
+        description: Option<String>,
+        fields: Vec<SecretDefinitionField>,
+    ) -> SecretResult<Self> {
+        let mut field_names = HashSet::new();
+        for field in &fields {
+            if !field_names.insert(field.name.as_str()) {
+                return Err(SecretError::InvalidDefinition(
+                    kind.clone(),
+                    format!("duplicate field {}", field.name),
+                ));
+            }
+            if let Some(pattern) = &field.pattern {
+                Regex::new(pattern).map_err(|err| {
+                    SecretError::InvalidDefinition(
+                        kind.clone(),
+                        format!("invalid pattern for field {}: {err}", field.name),
+                    )
+                })?;
+            }
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM secret_definition_create_v1($1, $2, $3, $4, $5, $6)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &kind.as_ref(),
+                    &display_name,
+                    &description,
+                    &serde_json::to_value(&fields)?,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+
+        Ok(object)
+    }
+
+    /// Creates the definitions for the builtin [`SecretKinds`](SecretKind) on _head_ for the
+    /// tenancy of the [`DalContext`], unless they already exist.
+    pub async fn create_builtins(ctx: &DalContext) -> SecretResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "SELECT secret_definition_create_builtins_v1($1)",
+                &[ctx.tenancy()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_for_kind(ctx: &DalContext, kind: &SecretKind) -> SecretResult<Option<Self>> {
+        let mut definitions: Vec<Self> =
+            standard_model::find_by_attr(ctx, Self::table_name(), "name", &kind.to_string())
+                .await?;
+        Ok(definitions.pop())
+    }
+
+    pub async fn get_for_kind(ctx: &DalContext, kind: &SecretKind) -> SecretResult<Self> {
+        Self::find_for_kind(ctx, kind)
+            .await?
+            .ok_or_else(|| SecretError::DefinitionNotFound(kind.clone()))
+    }
+
+    pub fn kind(&self) -> SecretKind {
+        SecretKind(self.name.clone().into())
+    }
+
+    standard_model_accessor_ro!(name, str);
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn fields(&self) -> &[SecretDefinitionField] {
+        &self.fields
+    }
+
+    /// Checks a secret's decrypted message against the definition's fields. Every required field
+    /// must be set, and every field that is set must be a string matching the field's pattern.
+    /// Fields the definition doesn't know about are allowed.
+    pub fn validate_message(&self, message: &Value) -> SecretResult<()> {
+        let invalid = |reason: String| SecretError::InvalidMessage(self.kind(), reason);
+        let object = message
+            .as_object()
+            .ok_or_else(|| invalid("message must be an object".to_owned()))?;
+
+        for field in &self.fields {
+            let value = match object.get(&field.name) {
+                None | Some(Value::Null) if field.required => {
+                    return Err(invalid(format!("missing required field {}", field.name)));
+                }
+                None | Some(Value::Null) => continue,
+                Some(Value::String(value)) => value,
+                Some(_) => return Err(invalid(format!("field {} must be a string", field.name))),
+            };
+            if let Some(pattern) = &field.pattern {
+                let regex = Regex::new(pattern).map_err(|err| {
+                    SecretError::InvalidDefinition(
+                        self.kind(),
+                        format!("invalid pattern for field {}: {err}", field.name),
+                    )
+                })?;
+                if !regex.is_match(value) {
+                    return Err(invalid(format!(
+                        "field {} does not match {pattern}",
+                        field.name
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The shape of a [`SecretDefinition`] exposed to the property editor and external API.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretDefinitionView {
+    pub id: SecretDefinitionId,
+    pub kind: SecretKind,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub fields: Vec<SecretDefinitionField>,
+}
+
+impl From<SecretDefinition> for SecretDefinitionView {
+    fn from(definition: SecretDefinition) -> Self {
+        Self {
+            id: definition.id,
+            kind: definition.kind(),
+            display_name: definition.display_name,
+            description: definition.description,
+            fields: definition.fields,
+        }
+    }
+}
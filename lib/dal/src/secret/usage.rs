@@ -0,0 +1,100 @@
+//! This module contains [`SecretConsumers`], which answers "what will break if this
+//! [`Secret`](crate::Secret) is deleted or rotated?".
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgRow;
+
+use crate::secret::{SecretError, SecretResult};
+use crate::{ComponentId, DalContext, FuncBackendResponseType, FuncId, PropId, SecretId};
+
+const LIST_CONSUMING_COMPONENTS: &str =
+    include_str!("../queries/secret/list_consuming_components.sql");
+const LIST_CONSUMING_FUNCS: &str = include_str!("../queries/secret/list_consuming_funcs.sql");
+
+/// A [`Component`](crate::Component) whose [`SecretSelect`](crate::property_editor::schema::WidgetKind::SecretSelect)
+/// [`Prop`](crate::Prop) is set to the [`Secret`](crate::Secret).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretComponentConsumer {
+    pub component_id: ComponentId,
+    pub prop_id: PropId,
+    pub prop_path: String,
+}
+
+/// An action or qualification [`Func`](crate::Func) that was executed with the
+/// [`Secret`](crate::Secret) in its arguments.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretFuncConsumer {
+    pub func_id: FuncId,
+    pub func_name: String,
+    pub backend_response_type: FuncBackendResponseType,
+    pub last_ran_at: DateTime<Utc>,
+}
+
+/// Everything that consumes a [`Secret`](crate::Secret) in the current visibility.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretConsumers {
+    pub components: Vec<SecretComponentConsumer>,
+    pub funcs: Vec<SecretFuncConsumer>,
+}
+
+impl SecretConsumers {
+    /// Lists the [`Components`](crate::Component) referencing the [`Secret`](crate::Secret) for
+    /// the provided [`SecretId`] and the action and qualification [`Funcs`](crate::Func) that
+    /// have run with it.
+    pub async fn list(ctx: &DalContext, secret_id: SecretId) -> SecretResult<Self> {
+        let txns = ctx.txns().await?;
+        let secret_id = secret_id.to_string();
+
+        let component_rows = txns
+            .pg()
+            .query(
+                LIST_CONSUMING_COMPONENTS,
+                &[ctx.tenancy(), ctx.visibility(), &secret_id],
+            )
+            .await?;
+        let mut components = Vec::with_capacity(component_rows.len());
+        for row in component_rows {
+            components.push(SecretComponentConsumer {
+                component_id: row.try_get("component_id")?,
+                prop_id: row.try_get("prop_id")?,
+                prop_path: row.try_get("prop_path")?,
+            });
+        }
+
+        let func_rows = txns
+            .pg()
+            .query(
+                LIST_CONSUMING_FUNCS,
+                &[ctx.tenancy(), ctx.visibility(), &secret_id],
+            )
+            .await?;
+        let mut funcs = Vec::with_capacity(func_rows.len());
+        for row in func_rows {
+            funcs.push(Self::func_consumer_from_row(row)?);
+        }
+
+        Ok(Self { components, funcs })
+    }
+
+    /// Whether or not any [`Component`](crate::Component) currently references the
+    /// [`Secret`](crate::Secret). Past [`Func`](crate::Func) executions do not count.
+    pub fn in_use(&self) -> bool {
+        !self.components.is_empty()
+    }
+
+    fn func_consumer_from_row(row: PgRow) -> SecretResult<SecretFuncConsumer> {
+        let backend_response_type: String = row.try_get("backend_response_type")?;
+        Ok(SecretFuncConsumer {
+            func_id: row.try_get("func_id")?,
+            func_name: row.try_get("func_name")?,
+            backend_response_type: backend_response_type
+                .parse()
+                .map_err(|_| SecretError::UnknownFuncBackendResponseType(backend_response_type))?,
+            last_ran_at: row.try_get("last_ran_at")?,
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Tenancy, TransactionsError, UserError, UserPk};
+use crate::{ChangeSetPk, Tenancy, TransactionsError, UserError, UserPk};
 use chrono::{DateTime, Utc};
 use postgres_types::ToSql;
 use serde::{de::DeserializeOwned, Serialize};
@@ -14,6 +14,8 @@ use crate::{DalContext, HistoryEvent, HistoryEventError, Timestamp, Visibility};
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum StandardModelError {
+    #[error("change set {0} is a read-only historical view")]
+    HistoricalChangeSet(ChangeSetPk),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("{0} id {1} is missing when one was expected; it does not exist, is not visible, or is not valid for this tenancy")]
@@ -53,6 +55,31 @@ pub enum TypeHint {
     TimestampWithTimeZone,
 }
 
+/// Returns [`StandardModelError::HistoricalChangeSet`] if the [`DalContext`] is reading through a
+/// [`Historical`](crate::ChangeSetStatus::Historical) change set, which nothing can be written
+/// through. Every write in this module checks this first.
+#[instrument(level = "trace", skip(ctx))]
+pub async fn require_writable(ctx: &DalContext) -> StandardModelResult<()> {
+    if ctx.visibility().is_head() {
+        return Ok(());
+    }
+    let change_set_pk = ctx.visibility().change_set_pk;
+
+    let row = ctx
+        .txns()
+        .await?
+        .pg()
+        .query_opt(
+            "SELECT pk FROM change_sets WHERE pk = $1 AND status = 'Historical'",
+            &[&change_set_pk],
+        )
+        .await?;
+    match row {
+        Some(_) => Err(StandardModelError::HistoricalChangeSet(change_set_pk)),
+        None => Ok(()),
+    }
+}
+
 #[instrument(level = "trace", skip(ctx))]
 pub async fn get_by_pk<PK: Send + Sync + ToSql, OBJECT: DeserializeOwned>(
     ctx: &DalContext,
@@ -229,6 +256,8 @@ pub async fn set_belongs_to<ObjectId: Send + Sync + ToSql, BelongsToId: Send + S
     object_id: &ObjectId,
     belongs_to_id: &BelongsToId,
 ) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
     ctx.txns()
         .await?
         .pg()
@@ -252,6 +281,8 @@ pub async fn unset_belongs_to<ObjectId: Send + Sync + ToSql>(
     table: &str,
     object_id: &ObjectId,
 ) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
     ctx.txns()
         .await?
         .pg()
@@ -269,6 +300,8 @@ pub async fn hard_unset_belongs_to_in_change_set<ObjectId: Send + Sync + ToSql>(
     table: &str,
     object_id: &ObjectId,
 ) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
     ctx.txns()
         .await?
         .pg()
@@ -286,6 +319,8 @@ pub async fn unset_all_belongs_to<BelongsToId: Send + Sync + ToSql>(
     table: &str,
     belongs_to_id: &BelongsToId,
 ) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
     ctx.txns()
         .await?
         .pg()
@@ -303,6 +338,8 @@ pub async fn hard_unset_all_belongs_to_in_change_set<BelongsToId: Send + Sync +
     table: &str,
     belongs_to_id: &BelongsToId,
 ) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
     ctx.txns()
         .await?
         .pg()
@@ -380,6 +417,8 @@ pub async fn associate_many_to_many<LeftId: Send + Sync + ToSql, RightId: Send +
     left_object_id: &LeftId,
     right_object_id: &RightId,
 ) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
     ctx.txns()
         .await?
         .pg()
@@ -407,6 +446,8 @@ pub async fn disassociate_many_to_many<
     left_object_id: &LeftId,
     right_object_id: &RightId,
 ) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
     ctx.txns()
         .await?
         .pg()
@@ -430,6 +471,8 @@ pub async fn disassociate_all_many_to_many<LeftId: Send + Sync + ToSql>(
     table: &str,
     left_object_id: &LeftId,
 ) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
     ctx.txns()
         .await?
         .pg()
@@ -483,6 +526,8 @@ where
     ID: Send + Sync + ToSql + std::fmt::Display,
     VALUE: Send + Sync + ToSql,
 {
+    require_writable(ctx).await?;
+
     let query = format!(
         "SELECT updated_at FROM update_by_id_v1($1, $2, $3, $4, $5, $6::{})",
         hint.as_ref()
@@ -531,6 +576,8 @@ pub async fn delete_by_id<ID: Send + Sync + ToSql + std::fmt::Display>(
     table: &str,
     id: ID,
 ) -> StandardModelResult<DateTime<Utc>> {
+    require_writable(ctx).await?;
+
     let row = ctx
         .txns()
         .await?
@@ -550,6 +597,8 @@ pub async fn delete_by_pk<PK: Send + Sync + ToSql + std::fmt::Display>(
     table: &str,
     pk: PK,
 ) -> StandardModelResult<DateTime<Utc>> {
+    require_writable(ctx).await?;
+
     let row = ctx
         .txns()
         .await?
@@ -569,6 +618,8 @@ pub async fn undelete<PK: Send + Sync + ToSql + std::fmt::Display>(
     table: &str,
     pk: PK,
 ) -> StandardModelResult<DateTime<Utc>> {
+    require_writable(ctx).await?;
+
     let row = ctx
         .txns()
         .await?
@@ -582,12 +633,36 @@ pub async fn undelete<PK: Send + Sync + ToSql + std::fmt::Display>(
         .map_err(|_| StandardModelError::ModelMissing(table.to_string(), pk.to_string()))
 }
 
+/// Restores an object deleted in the [`DalContext`]'s change set. The change set's copy is dropped
+/// if _head_ still has the object, so that _head_'s version shows through again, and is brought
+/// back otherwise.
+#[instrument(level = "trace", skip(ctx))]
+pub async fn restore_by_pk<PK: Send + Sync + ToSql + std::fmt::Display>(
+    ctx: &DalContext,
+    table: &str,
+    pk: &PK,
+) -> StandardModelResult<()> {
+    require_writable(ctx).await?;
+
+    ctx.txns()
+        .await?
+        .pg()
+        .execute(
+            "SELECT change_set_restore_by_pk_v1($1, $2, $3)",
+            &[&table, ctx.tenancy(), &pk],
+        )
+        .await?;
+    Ok(())
+}
+
 #[instrument(level = "trace", skip(ctx))]
 pub async fn hard_delete<PK: Send + Sync + ToSql + std::fmt::Display, OBJECT: DeserializeOwned>(
     ctx: &DalContext,
     table: &str,
     pk: &PK,
 ) -> StandardModelResult<OBJECT> {
+    require_writable(ctx).await?;
+
     let row = ctx
         .txns()
         .await?
@@ -606,12 +681,18 @@ pub async fn finish_create_from_row<Object: Send + Sync + DeserializeOwned + Sta
     ctx: &DalContext,
     row: PgRow,
 ) -> StandardModelResult<Object> {
+    require_writable(ctx).await?;
+
     let json: serde_json::Value = row.try_get("object")?;
     let _history_event = HistoryEvent::new(
         ctx,
         Object::history_event_label(vec!["create"]),
         Object::history_event_message("created"),
-        &serde_json::json![{ "visibility": ctx.visibility() }],
+        &serde_json::json![{
+            "pk": json.get("pk"),
+            "id": json.get("id"),
+            "visibility": ctx.visibility(),
+        }],
     )
     .await?;
     let object: Object = serde_json::from_value(json)?;
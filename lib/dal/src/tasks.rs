@@ -2,9 +2,11 @@
 //! SI binaries that are dependent on the [`dal`](crate).
 
 // This modules should remain private! Add "pub use" statements to use their contents.
+mod fix_scheduler;
 mod resource_scheduler;
 mod status_receiver;
 
-pub use resource_scheduler::{ResourceScheduler, ResourceSchedulerError};
+pub use fix_scheduler::{FixScheduler, FixSchedulerError};
+pub use resource_scheduler::{ResourceScheduler, ResourceSchedulerConfig, ResourceSchedulerError};
 pub use status_receiver::client::StatusReceiverClient;
 pub use status_receiver::{StatusReceiver, StatusReceiverError, StatusReceiverRequest};
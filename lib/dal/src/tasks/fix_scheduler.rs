@@ -0,0 +1,146 @@
+//! This module contains [`FixScheduler`], which is a "long-running" task that starts the
+//! [`FixBatches`](crate::FixBatch) of [`FixSchedules`](FixSchedule) when they're due.
+
+use std::time::Duration;
+
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{sync::broadcast, time};
+
+use crate::{
+    AccessBuilder, DalContextBuilder, FixError, FixSchedule, HistoryActor, RequestContext,
+    ServicesContext, StandardModel, TransactionsError, Visibility,
+};
+
+/// How often the scheduler looks for due schedules.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FixSchedulerError {
+    #[error(transparent)]
+    Fix(#[from] FixError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FixSchedulerResult<T> = Result<T, FixSchedulerError>;
+
+/// The fix scheduler claims the [`FixSchedules`](FixSchedule) that are due, in every workspace,
+/// and starts their batches on head as the users who scheduled them. Schedules are claimed in the
+/// database, so every sdf instance can run a scheduler without running a schedule twice.
+#[derive(Debug, Clone)]
+pub struct FixScheduler {
+    services_context: ServicesContext,
+}
+
+impl FixScheduler {
+    pub fn new(services_context: ServicesContext) -> FixScheduler {
+        FixScheduler { services_context }
+    }
+
+    /// Starts the scheduler, which runs until the shutdown broadcast is received.
+    pub fn start(self, mut shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown_broadcast_rx.recv() => {
+                    info!("Fix Scheduler received shutdown request, bailing out");
+                },
+                _ = self.start_task() => {}
+            }
+            info!("Fix Scheduler stopped");
+        });
+    }
+
+    #[instrument(name = "fix_scheduler.start_task", skip_all, level = "debug")]
+    async fn start_task(&self) {
+        let mut interval = time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.run().await {
+                error!(error = ?err, "failed to run due fix schedules");
+            }
+        }
+    }
+
+    /// Runs every schedule that is due. A schedule that fails to run records why, and is skipped
+    /// until its next run; the other schedules that were claimed still run.
+    #[instrument(name = "fix_scheduler.run", skip_all, level = "debug")]
+    async fn run(&self) -> FixSchedulerResult<()> {
+        let builder = self.services_context.clone().into_builder(false);
+
+        // Schedules are claimed across workspaces, so this bypasses tenancy
+        let ctx = builder.build_default().await?;
+        let schedules = FixSchedule::claim_due(&ctx).await?;
+        ctx.commit().await?;
+
+        for mut schedule in schedules {
+            let access_builder =
+                AccessBuilder::new(*schedule.tenancy(), HistoryActor::User(*schedule.user_pk()));
+            let request_context = access_builder.build(Visibility::new_head(false));
+
+            if let Err(err) =
+                Self::run_schedule(&builder, request_context.clone(), &mut schedule).await
+            {
+                error!(
+                    error = ?err,
+                    fix_schedule_id = %schedule.id(),
+                    "failed to run fix schedule",
+                );
+                if let Err(record_err) =
+                    Self::record_run_error(&builder, request_context, &mut schedule, &err).await
+                {
+                    error!(
+                        error = ?record_err,
+                        fix_schedule_id = %schedule.id(),
+                        "failed to record fix schedule run error",
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn run_schedule(
+        builder: &DalContextBuilder,
+        request_context: RequestContext,
+        schedule: &mut FixSchedule,
+    ) -> FixSchedulerResult<()> {
+        let ctx = builder.build(request_context).await?;
+        let batch_id = match schedule.run(&ctx).await {
+            Ok(batch_id) => batch_id,
+            Err(err) => {
+                ctx.rollback().await?;
+                return Err(err.into());
+            }
+        };
+        ctx.commit().await?;
+
+        match batch_id {
+            Some(batch_id) => info!(
+                fix_schedule_id = %schedule.id(),
+                %batch_id,
+                "started scheduled fix batch",
+            ),
+            None => warn!(fix_schedule_id = %schedule.id(), "no scheduled fixes left to run"),
+        }
+        Ok(())
+    }
+
+    /// Records why the schedule failed to run. The run's transaction was rolled back (or never
+    /// committed), so this uses its own.
+    async fn record_run_error(
+        builder: &DalContextBuilder,
+        request_context: RequestContext,
+        schedule: &mut FixSchedule,
+        err: &FixSchedulerError,
+    ) -> FixSchedulerResult<()> {
+        let ctx = builder.build(request_context).await?;
+        schedule
+            .set_last_run_error(&ctx, Some(err.to_string()))
+            .await?;
+        ctx.commit().await?;
+        Ok(())
+    }
+}
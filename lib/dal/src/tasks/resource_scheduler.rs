@@ -1,19 +1,31 @@
 //! This module contains [`ResourceScheduler`], which is a "long-running" tasks that performs
 //! [`resource`](crate::component::resource) syncing on a cadence.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+use rand::Rng;
 use si_data_nats::NatsError;
 use si_data_pg::{PgError, PgPoolError};
 use telemetry::prelude::*;
 use thiserror::Error;
-use tokio::{sync::broadcast, time};
+use tokio::{sync::broadcast, task::JoinSet, time};
 
-use crate::{standard_model, Component, ServicesContext, StandardModelError, TransactionsError};
+use crate::{
+    job::definition::RefreshJob, AccessBuilder, ActionKind, ActionPrototype,
+    ActionPrototypeContext, ActionPrototypeError, Component, ComponentError, DalContext,
+    HistoryActor, SchemaVariantId, ServicesContext, StandardModel, StandardModelError, Tenancy,
+    TransactionsError, Visibility, WorkspacePk,
+};
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ResourceSchedulerError {
+    #[error(transparent)]
+    ActionPrototype(#[from] ActionPrototypeError),
+    #[error(transparent)]
+    Component(#[from] ComponentError),
     #[error(transparent)]
     Nats(#[from] NatsError),
     #[error(transparent)]
@@ -30,19 +42,72 @@ pub enum ResourceSchedulerError {
 
 pub type ResourceSchedulerResult<T> = Result<T, ResourceSchedulerError>;
 
-/// The resource scheduler handles looking up all the components, and scheduling
-/// their resources to refresh. Eventually, it should become smart enough to parallelize,
-/// it might be extracted to a fully separate service, etc etc. For now,
-/// it is the dumbest thing that could possibly work - no more often than every 30
-/// seconds, it will ask a resource to refresh
+/// How the [`ResourceScheduler`] paces refreshes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceSchedulerConfig {
+    /// How often the scheduler looks for resources that are due for a refresh. Resources are
+    /// never refreshed more often than this.
+    pub poll_interval: Duration,
+    /// How often resources are refreshed when neither their [`Component`] nor its
+    /// [`SchemaVariant`](crate::SchemaVariant) set an interval.
+    pub default_refresh_interval: Duration,
+    /// Up to how long a refresh is randomly pushed back past its interval, so that resources
+    /// refreshed together once don't keep being refreshed together.
+    pub jitter: Duration,
+    /// How many workspaces are scheduled at the same time.
+    pub max_concurrent_workspaces: usize,
+    /// How many components a single [`RefreshJob`] refreshes. A workspace with more components
+    /// due gets several jobs, which run concurrently.
+    pub max_components_per_job: usize,
+}
+
+impl Default for ResourceSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(30),
+            default_refresh_interval: Duration::from_secs(300),
+            jitter: Duration::from_secs(30),
+            max_concurrent_workspaces: 4,
+            max_components_per_job: 10,
+        }
+    }
+}
+
+impl ResourceSchedulerConfig {
+    /// The refresh interval for a resource, given the one set on its [`Component`] or
+    /// [`SchemaVariant`](crate::SchemaVariant). Negative intervals are ignored.
+    fn refresh_interval(&self, refresh_interval_secs: Option<i64>) -> Duration {
+        refresh_interval_secs
+            .and_then(|secs| u64::try_from(secs).ok())
+            .map(Duration::from_secs)
+            .unwrap_or(self.default_refresh_interval)
+    }
+}
+
+/// The resource scheduler handles looking up all the components on head that have a refresh
+/// [`ActionPrototype`], and scheduling their resources to refresh once their refresh interval has
+/// passed. Every workspace is scheduled in its own tenancy, and the refreshes themselves run in
+/// [`RefreshJobs`](RefreshJob). Refreshes are claimed in the database, so every sdf instance can
+/// run a scheduler without refreshing a resource twice.
 #[derive(Debug, Clone)]
 pub struct ResourceScheduler {
     services_context: ServicesContext,
+    config: ResourceSchedulerConfig,
 }
 
 impl ResourceScheduler {
     pub fn new(services_context: ServicesContext) -> ResourceScheduler {
-        ResourceScheduler { services_context }
+        Self::with_config(services_context, ResourceSchedulerConfig::default())
+    }
+
+    pub fn with_config(
+        services_context: ServicesContext,
+        config: ResourceSchedulerConfig,
+    ) -> ResourceScheduler {
+        ResourceScheduler {
+            services_context,
+            config,
+        }
     }
 
     /// Starts the scheduler. It returns the join handle to the spawned scheduler, and
@@ -60,34 +125,170 @@ impl ResourceScheduler {
         });
     }
 
+    /// Schedules the resources that are due in every workspace, a few workspaces at a time. A
+    /// workspace that fails to be scheduled is logged and retried on the next run.
     #[instrument(name = "resource_scheduler.run", skip_all, level = "debug")]
-    async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-        /*
-        let components = self.components().await?;
-        info!("Refresh {} resources", components.len());
+    async fn run(&self) -> ResourceSchedulerResult<()> {
+        let workspace_pks = self.workspaces().await?;
+        debug!(
+            "Scheduling resource refreshes in {} workspaces",
+            workspace_pks.len()
+        );
 
-        for component in components {
-            // First we're building a ctx with no tenancy at head, then updating it with a
-            // workspace head request context
+        let max_concurrent_workspaces = self.config.max_concurrent_workspaces.max(1);
+        let mut running = JoinSet::new();
+        for workspace_pk in workspace_pks {
+            if running.len() >= max_concurrent_workspaces {
+                Self::join_next(&mut running).await;
+            }
 
-            let builder = self.services_context.clone().into_builder(false);
-            let mut ctx = builder.build_default().await?;
+            let scheduler = self.clone();
+            running.spawn(async move {
+                let result = scheduler.run_for_workspace(workspace_pk).await;
+                (workspace_pk, result)
+            });
+        }
+        while !running.is_empty() {
+            Self::join_next(&mut running).await;
+        }
 
-            ctx.update_tenancy(*component.tenancy());
+        Ok(())
+    }
 
-            component.act(&ctx, "refresh").await?;
-            ctx.commit().await?;
+    async fn join_next(running: &mut JoinSet<(WorkspacePk, ResourceSchedulerResult<usize>)>) {
+        match running.join_next().await {
+            Some(Ok((workspace_pk, Ok(count)))) if count > 0 => {
+                info!(%workspace_pk, "Scheduled {count} resource refreshes");
+            }
+            Some(Ok((workspace_pk, Err(err)))) => {
+                error!(error = ?err, %workspace_pk, "failed to schedule resource refreshes");
+            }
+            Some(Err(err)) => error!(error = ?err, "resource scheduling task failed"),
+            Some(Ok(_)) | None => {}
         }
-        */
-        Ok(())
     }
 
-    /// The internal task spawned by `start`. No more frequently than every 30
-    /// seconds, it will iterate over all the components on head in the database and
-    /// schedule them to refresh.
+    /// Enqueues [`RefreshJobs`](RefreshJob) for the components of a workspace that are due,
+    /// returning how many were enqueued. Components whose refresh was claimed by another scheduler
+    /// in the meantime are left to it.
+    pub async fn run_for_workspace(
+        &self,
+        workspace_pk: WorkspacePk,
+    ) -> ResourceSchedulerResult<usize> {
+        let builder = self.services_context.clone().into_builder(false);
+        let access_builder =
+            AccessBuilder::new(Tenancy::new(workspace_pk), HistoryActor::SystemInit);
+        let mut ctx = builder
+            .build(access_builder.build(Visibility::new_head(false)))
+            .await?;
+        // Components that need to be destroyed still have resources to refresh
+        ctx.update_with_deleted_visibility();
+
+        let mut component_ids = Vec::new();
+        for mut component in self.due_components(&ctx).await? {
+            if component.claim_resource_refresh(&ctx).await? {
+                component_ids.push(*component.id());
+            }
+        }
+        for chunk in component_ids.chunks(self.config.max_components_per_job.max(1)) {
+            ctx.enqueue_job(RefreshJob::new(
+                access_builder,
+                Visibility::new_head(false),
+                chunk.to_vec(),
+            ))
+            .await?;
+        }
+        ctx.commit().await?;
+
+        Ok(component_ids.len())
+    }
+
+    /// Lists the components, in the ctx's workspace, whose resources are due for a refresh: the
+    /// ones with a resource and a refresh [`ActionPrototype`] that weren't refreshed, or enqueued
+    /// for a refresh, within their interval. Resources that were never created aren't refreshed.
+    pub async fn due_components(
+        &self,
+        ctx: &DalContext,
+    ) -> ResourceSchedulerResult<Vec<Component>> {
+        let now = Utc::now();
+        // Only components that need to be destroyed are listed with deleted visibility, not
+        // action prototypes
+        let head_ctx = &ctx.clone_without_deleted_visibility();
+
+        // The interval of every schema variant, if it has a refresh action prototype
+        let mut variants: HashMap<SchemaVariantId, Option<Option<i64>>> = HashMap::new();
+        let mut due = Vec::new();
+        for component in Component::list(ctx).await? {
+            if component.is_destroyed() {
+                continue;
+            }
+            let resource = component.resource(ctx).await?;
+            if resource.payload.is_none() {
+                continue;
+            }
+            let schema_variant = match component.schema_variant(ctx).await? {
+                Some(schema_variant) => schema_variant,
+                None => {
+                    warn!(component_id = %component.id(), "component has no schema variant");
+                    continue;
+                }
+            };
+
+            let variant_interval = match variants.get(schema_variant.id()) {
+                Some(variant_interval) => *variant_interval,
+                None => {
+                    let refreshable = !ActionPrototype::find_for_context_and_kind(
+                        head_ctx,
+                        ActionKind::Refresh,
+                        ActionPrototypeContext {
+                            schema_variant_id: *schema_variant.id(),
+                        },
+                    )
+                    .await?
+                    .is_empty();
+                    let variant_interval =
+                        refreshable.then(|| schema_variant.refresh_interval_secs().copied());
+                    variants.insert(*schema_variant.id(), variant_interval);
+                    variant_interval
+                }
+            };
+            let variant_interval = match variant_interval {
+                Some(variant_interval) => variant_interval,
+                None => continue,
+            };
+            let interval = self.config.refresh_interval(
+                component
+                    .refresh_interval_secs()
+                    .copied()
+                    .or(variant_interval),
+            );
+
+            let last_synced = resource
+                .last_synced
+                .and_then(|last_synced| DateTime::parse_from_rfc3339(&last_synced).ok())
+                .map(|last_synced| last_synced.with_timezone(&Utc));
+            // A refresh that was enqueued counts as done, even if it's still pending
+            let last_refreshed = last_synced.max(*component.resource_refresh_enqueued_at());
+
+            let jitter = self.jitter();
+            if is_due(last_refreshed, interval, jitter, now) {
+                due.push(component);
+            }
+        }
+
+        Ok(due)
+    }
+
+    fn jitter(&self) -> Duration {
+        let max_millis = self.config.jitter.as_millis() as u64;
+        Duration::from_millis(rand::thread_rng().gen_range(0..=max_millis))
+    }
+
+    /// The internal task spawned by `start`. Every poll interval, it will iterate over all the
+    /// components on head in the database and schedule the ones that are due to refresh.
     #[instrument(name = "resource_scheduler.start_task", skip_all, level = "debug")]
     async fn start_task(&self) {
-        let mut interval = time::interval(Duration::from_secs(300));
+        let mut interval = time::interval(self.config.poll_interval);
         loop {
             interval.tick().await;
             match self.run().await {
@@ -97,30 +298,95 @@ impl ResourceScheduler {
         }
     }
 
-    /// Gets a list of all the resources in the database.
+    /// Gets a list of all the workspaces with components on head in the database.
     #[instrument(skip_all, level = "debug")]
-    pub async fn components(&self) -> ResourceSchedulerResult<Vec<Component>> {
+    pub async fn workspaces(&self) -> ResourceSchedulerResult<Vec<WorkspacePk>> {
         let builder = self.services_context.clone().into_builder(false);
-        let mut ctx = builder.build_default().await?;
-        ctx.update_with_deleted_visibility();
+        let ctx = builder.build_default().await?;
 
-        // We need to bypass tenancy checks, only lists components on head as they are the only ones refreshed
+        // We need to bypass tenancy checks, only lists workspaces with components on head as
+        // they are the only ones refreshed
         let rows = ctx
             .txns()
             .await?
             .pg()
             .query(
-                "SELECT DISTINCT ON (id) id, row_to_json(components.*) as object
+                "SELECT DISTINCT tenancy_workspace_pk
                  FROM components
-                 WHERE is_visible_v1($1, visibility_change_set_pk, visibility_deleted_at)
-                       AND (visibility_deleted_at IS NULL OR needs_destroy)
-                 ORDER BY id",
-                &[ctx.visibility()],
+                 WHERE visibility_change_set_pk = ident_nil_v1()
+                       AND tenancy_workspace_pk IS NOT NULL
+                       AND (visibility_deleted_at IS NULL OR needs_destroy)",
+                &[],
             )
             .await?;
-        let components: Vec<Component> = standard_model::objects_from_rows(rows)?;
+        let mut workspace_pks = Vec::with_capacity(rows.len());
+        for row in rows {
+            workspace_pks.push(row.try_get("tenancy_workspace_pk")?);
+        }
 
         ctx.commit().await?;
-        Ok(components)
+        Ok(workspace_pks)
+    }
+}
+
+/// A resource is due once its interval, pushed back by the jitter, has passed since it was last
+/// refreshed. Resources that were never refreshed are due right away.
+fn is_due(
+    last_refreshed: Option<DateTime<Utc>>,
+    interval: Duration,
+    jitter: Duration,
+    now: DateTime<Utc>,
+) -> bool {
+    match last_refreshed {
+        Some(last_refreshed) => match chrono::Duration::from_std(interval + jitter) {
+            Ok(wait) => last_refreshed + wait <= now,
+            // Intervals too long to represent are never due
+            Err(_) => false,
+        },
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn never_refreshed_resources_are_due() {
+        assert!(is_due(
+            None,
+            Duration::from_secs(300),
+            Duration::ZERO,
+            Utc::now()
+        ));
+    }
+
+    #[test]
+    fn resources_are_due_after_their_interval_and_jitter() {
+        let now = Utc::now();
+        let interval = Duration::from_secs(300);
+        let jitter = Duration::from_secs(30);
+
+        let recent = now - chrono::Duration::seconds(310);
+        assert!(is_due(Some(recent), interval, Duration::ZERO, now));
+        assert!(!is_due(Some(recent), interval, jitter, now));
+
+        let stale = now - chrono::Duration::seconds(330);
+        assert!(is_due(Some(stale), interval, jitter, now));
+    }
+
+    #[test]
+    fn refresh_interval_falls_back_to_the_default() {
+        let config = ResourceSchedulerConfig::default();
+
+        assert_eq!(Duration::from_secs(60), config.refresh_interval(Some(60)));
+        assert_eq!(
+            config.default_refresh_interval,
+            config.refresh_interval(None)
+        );
+        assert_eq!(
+            config.default_refresh_interval,
+            config.refresh_interval(Some(-1))
+        );
     }
 }
@@ -1,16 +1,20 @@
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
+use strum::{AsRefStr, Display, EnumString};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::task::JoinError;
 
 use crate::{
-    jwt_key::JwtKeyError, pk, standard_model_accessor_ro, DalContext, HistoryEvent,
+    jwt_key::JwtKeyError, pk, standard_model_accessor_ro, DalContext, HistoryActor, HistoryEvent,
     HistoryEventError, JwtPublicSigningKey, Tenancy, Timestamp, TransactionsError, WorkspacePk,
 };
 
 const USER_GET_BY_PK: &str = include_str!("queries/user/get_by_pk.sql");
+const USER_IS_MEMBER_OF_WORKSPACE: &str = include_str!("queries/user/is_member_of_workspace.sql");
+const USER_GET_WORKSPACE_ROLE: &str = include_str!("queries/user/get_workspace_role.sql");
+const USER_SET_WORKSPACE_ROLE: &str = include_str!("queries/user/set_workspace_role.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -25,20 +29,80 @@ pub enum UserError {
     Nats(#[from] NatsError),
     #[error("user not found in tenancy: {0} {1:?}")]
     NotFoundInTenancy(UserPk, Tenancy),
+    #[error("user {0} is not a member of workspace {1}")]
+    NotMemberOfWorkspace(UserPk, WorkspacePk),
     #[error("no workspace in tenancy")]
     NoWorkspaceInTenancy,
+    #[error("user {0} does not have the {1} permission in workspace {2}")]
+    PermissionDenied(UserPk, WorkspacePermission, WorkspacePk),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
+    #[error("unknown workspace role: {0}")]
+    UnknownWorkspaceRole(String),
 }
 
 pub type UserResult<T> = Result<T, UserError>;
 
 pk!(UserPk);
 
+/// Something a [`User`] can do in a [`Workspace`](crate::Workspace), granted by their
+/// [`WorkspaceRole`].
+#[remain::sorted]
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, AsRefStr,
+)]
+pub enum WorkspacePermission {
+    /// Apply change sets and run fixes against real infrastructure.
+    Apply,
+    /// Modify the model: components, connections, funcs, schemas and change sets.
+    Edit,
+    /// Manage who belongs to the workspace, their roles and workspace-wide policies.
+    ManageMembers,
+    /// Create and update secrets.
+    ManageSecrets,
+    /// Read anything in the workspace.
+    Read,
+}
+
+/// The role a [`User`] holds in a [`Workspace`](crate::Workspace). Each role grants every
+/// [`WorkspacePermission`] of the roles below it.
+#[remain::sorted]
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, AsRefStr,
+)]
+pub enum WorkspaceRole {
+    /// Can edit the model, apply change sets and run fixes.
+    Applier,
+    /// Can edit the model, but cannot apply change sets or run fixes.
+    Editor,
+    /// Can do everything, including managing secrets and members.
+    Owner,
+    /// Read-only access.
+    Viewer,
+}
+
+impl WorkspaceRole {
+    /// Returns whether or not this role grants the provided [`WorkspacePermission`].
+    pub fn grants(&self, permission: WorkspacePermission) -> bool {
+        match self {
+            Self::Owner => true,
+            Self::Applier => matches!(
+                permission,
+                WorkspacePermission::Read | WorkspacePermission::Edit | WorkspacePermission::Apply
+            ),
+            Self::Editor => matches!(
+                permission,
+                WorkspacePermission::Read | WorkspacePermission::Edit
+            ),
+            Self::Viewer => permission == WorkspacePermission::Read,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct User {
     pk: UserPk,
@@ -117,9 +181,121 @@ impl User {
         }
     }
 
-    pub async fn authorize(_ctx: &DalContext, _user_pk: &UserPk) -> UserResult<bool> {
-        // TODO(paulo,theo): implement capabilities through auth0
-        Ok(true)
+    /// Returns whether or not the [`User`] for the provided [`UserPk`] holds a
+    /// [`WorkspaceRole`] granting the provided [`WorkspacePermission`] in the
+    /// [`Workspace`](crate::Workspace) of the [`DalContext`]'s tenancy. Users not associated with
+    /// that workspace are never authorized.
+    pub async fn authorize(
+        ctx: &DalContext,
+        user_pk: &UserPk,
+        permission: WorkspacePermission,
+    ) -> UserResult<bool> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(UserError::NoWorkspaceInTenancy)?;
+        Ok(Self::role_for_workspace(ctx, *user_pk, workspace_pk)
+            .await?
+            .map(|role| role.grants(permission))
+            .unwrap_or(false))
+    }
+
+    /// Like [`User::authorize()`], but returns [`UserError::PermissionDenied`] if the [`User`] is
+    /// not authorized.
+    pub async fn require_permission(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        permission: WorkspacePermission,
+    ) -> UserResult<()> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(UserError::NoWorkspaceInTenancy)?;
+        if !Self::authorize(ctx, &user_pk, permission).await? {
+            return Err(UserError::PermissionDenied(
+                user_pk,
+                permission,
+                workspace_pk,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Requires the provided [`WorkspacePermission`] of the [`DalContext`]'s
+    /// [`HistoryActor`] (see [`User::require_permission()`]). The system is always authorized.
+    pub async fn require_permission_for_actor(
+        ctx: &DalContext,
+        permission: WorkspacePermission,
+    ) -> UserResult<()> {
+        match ctx.history_actor() {
+            HistoryActor::User(user_pk) => {
+                Self::require_permission(ctx, *user_pk, permission).await
+            }
+            HistoryActor::SystemInit => Ok(()),
+        }
+    }
+
+    /// Returns the [`WorkspaceRole`] the [`User`] for the provided [`UserPk`] holds in the
+    /// [`Workspace`](crate::Workspace) for the provided [`WorkspacePk`], if they are a member.
+    pub async fn role_for_workspace(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+    ) -> UserResult<Option<WorkspaceRole>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(USER_GET_WORKSPACE_ROLE, &[&user_pk, &workspace_pk])
+            .await?;
+        match row {
+            Some(row) => {
+                let role: String = row.try_get("role")?;
+                Ok(Some(
+                    role.parse()
+                        .map_err(|_| UserError::UnknownWorkspaceRole(role))?,
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Changes the [`WorkspaceRole`] of the [`User`] for the provided [`UserPk`] in the
+    /// [`Workspace`](crate::Workspace) for the provided [`WorkspacePk`].
+    pub async fn set_role_for_workspace(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+        role: WorkspaceRole,
+    ) -> UserResult<()> {
+        Self::require_permission_for_actor(ctx, WorkspacePermission::ManageMembers).await?;
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                USER_SET_WORKSPACE_ROLE,
+                &[&user_pk, &workspace_pk, &role.to_string()],
+            )
+            .await?;
+        if row.is_none() {
+            return Err(UserError::NotMemberOfWorkspace(user_pk, workspace_pk));
+        }
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "user.set_workspace_role",
+            "User workspace role changed",
+            &serde_json::json![{
+                "user_pk": user_pk,
+                "workspace_pk": workspace_pk,
+                "role": role,
+            }],
+        )
+        .await?;
+
+        Ok(())
     }
 
     pub async fn associate_workspace(
@@ -137,6 +313,43 @@ impl User {
             .await?;
         Ok(())
     }
+
+    /// Associates the [`User`] with the [`Workspace`](crate::Workspace) for the provided
+    /// [`WorkspacePk`] with the provided [`WorkspaceRole`], restoring their membership if they
+    /// were previously removed. Existing members keep their current role; use
+    /// [`set_role_for_workspace()`](Self::set_role_for_workspace) to change it.
+    pub async fn associate_workspace_with_role(
+        &self,
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        role: WorkspaceRole,
+    ) -> UserResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "SELECT user_associate_workspace_with_role_v1($1, $2, $3)",
+                &[&self.pk, &workspace_pk, &role.to_string()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Returns whether or not the [`User`] for the provided [`UserPk`] is associated with the
+    /// [`Workspace`](crate::Workspace) for the provided [`WorkspacePk`].
+    pub async fn is_member_of_workspace(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+    ) -> UserResult<bool> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(USER_IS_MEMBER_OF_WORKSPACE, &[&user_pk, &workspace_pk])
+            .await?;
+        Ok(row.try_get("is_member")?)
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, Copy)]
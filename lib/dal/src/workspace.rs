@@ -4,24 +4,50 @@ use si_data_pg::PgError;
 use telemetry::prelude::*;
 use thiserror::Error;
 
+use crate::standard_model::objects_from_rows;
 use crate::{
     pk, standard_model, standard_model_accessor_ro, DalContext, HistoryActor, HistoryEvent,
     HistoryEventError, KeyPair, KeyPairError, StandardModelError, Tenancy, Timestamp,
-    TransactionsError, User, UserError, UserPk,
+    TransactionsError, User, UserError, UserPk, WorkspacePermission, WorkspaceRole, WsEvent,
+    WsEventError,
 };
 
+pub mod member;
+
+use member::{WorkspaceInvitation, WorkspaceInvitationPk, WorkspaceMember};
+
 const WORKSPACE_GET_BY_PK: &str = include_str!("queries/workspace/get_by_pk.sql");
 const WORKSPACE_FIND_BY_NAME: &str = include_str!("queries/workspace/find_by_name.sql");
+const WORKSPACE_UPDATE_REQUIRED_CHANGE_SET_APPROVALS: &str =
+    include_str!("queries/workspace/update_required_change_set_approvals.sql");
+const WORKSPACE_LIST_MEMBERS: &str = include_str!("queries/workspace/list_members.sql");
+const WORKSPACE_LIST_PENDING_INVITATIONS: &str =
+    include_str!("queries/workspace/list_pending_invitations.sql");
+const WORKSPACE_ACCEPT_INVITATION: &str = include_str!("queries/workspace/accept_invitation.sql");
+const WORKSPACE_REVOKE_INVITATION: &str = include_str!("queries/workspace/revoke_invitation.sql");
+const WORKSPACE_REMOVE_MEMBER: &str = include_str!("queries/workspace/remove_member.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum WorkspaceError {
+    #[error("{0} is already a member of workspace {1}")]
+    AlreadyMember(String, WorkspacePk),
+    #[error("cannot remove user {0}: they are the last owner of workspace {1}")]
+    CannotRemoveLastOwner(UserPk, WorkspacePk),
     #[error(transparent)]
     HistoryEvent(#[from] HistoryEventError),
+    #[error("required change set approvals cannot be negative: {0}")]
+    InvalidRequiredChangeSetApprovals(i32),
+    #[error("workspace invitation not found: {0}")]
+    InvitationNotFound(WorkspaceInvitationPk),
     #[error(transparent)]
     KeyPair(#[from] KeyPairError),
     #[error(transparent)]
     Nats(#[from] NatsError),
+    #[error("user {0} is not a member of workspace {1}")]
+    NotMemberOfWorkspace(UserPk, WorkspacePk),
+    #[error("user {0} is not an owner of workspace {1}")]
+    NotOwner(UserPk, WorkspacePk),
     #[error(transparent)]
     Pg(#[from] PgError),
     #[error(transparent)]
@@ -30,8 +56,12 @@ pub enum WorkspaceError {
     StandardModel(#[from] StandardModelError),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
+    #[error("unknown workspace role: {0}")]
+    UnknownWorkspaceRole(String),
     #[error(transparent)]
     User(#[from] UserError),
+    #[error(transparent)]
+    WsEvent(#[from] WsEventError),
 }
 
 pub type WorkspaceResult<T> = Result<T, WorkspaceError>;
@@ -49,6 +79,10 @@ pub struct WorkspaceSignup {
 pub struct Workspace {
     pk: WorkspacePk,
     name: String,
+    /// How many approvals a [`ChangeSet`](crate::ChangeSet) needs before it can be applied. No
+    /// review is required when this is zero.
+    #[serde(default)]
+    required_change_set_approvals: i32,
     #[serde(flatten)]
     timestamp: Timestamp,
 }
@@ -127,6 +161,10 @@ impl Workspace {
         .await?;
         ctx.update_history_actor(HistoryActor::User(user.pk()));
 
+        // The user signing up owns the workspace.
+        user.associate_workspace_with_role(ctx, workspace.pk, WorkspaceRole::Owner)
+            .await?;
+
         ctx.import_builtins().await?;
 
         Ok(WorkspaceSignup {
@@ -166,4 +204,287 @@ impl Workspace {
     }
 
     standard_model_accessor_ro!(name, String);
+    standard_model_accessor_ro!(required_change_set_approvals, i32);
+
+    /// Configures the approval policy for [`ChangeSets`](crate::ChangeSet) in this workspace.
+    pub async fn set_required_change_set_approvals(
+        &mut self,
+        ctx: &DalContext,
+        required_change_set_approvals: i32,
+    ) -> WorkspaceResult<()> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::ManageMembers).await?;
+
+        if required_change_set_approvals < 0 {
+            return Err(WorkspaceError::InvalidRequiredChangeSetApprovals(
+                required_change_set_approvals,
+            ));
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                WORKSPACE_UPDATE_REQUIRED_CHANGE_SET_APPROVALS,
+                &[&self.pk, &required_change_set_approvals],
+            )
+            .await?;
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+        self.required_change_set_approvals = required_change_set_approvals;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "workspace.update_approval_policy",
+            "Workspace change set approval policy updated",
+            &serde_json::json![{
+                "pk": &self.pk,
+                "required_change_set_approvals": required_change_set_approvals,
+            }],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Invites whoever signs in with the provided email address to join this workspace with the
+    /// provided [`WorkspaceRole`]. Inviting the same email address again replaces their pending
+    /// invitation. Existing members cannot be invited; change their role instead.
+    #[instrument(skip(ctx, email))]
+    pub async fn invite_member(
+        &self,
+        ctx: &DalContext,
+        email: impl AsRef<str>,
+        role: WorkspaceRole,
+    ) -> WorkspaceResult<WorkspaceInvitation> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::ManageMembers).await?;
+
+        let email = email.as_ref();
+        if self
+            .list_members(ctx)
+            .await?
+            .iter()
+            .any(|member| member.email.eq_ignore_ascii_case(email))
+        {
+            return Err(WorkspaceError::AlreadyMember(email.to_owned(), self.pk));
+        }
+
+        let invited_by_user_pk = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => Some(*user_pk),
+            HistoryActor::SystemInit => None,
+        };
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM workspace_invitation_create_v1($1, $2, $3, $4)",
+                &[&self.pk, &email, &role.to_string(), &invited_by_user_pk],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "workspace.invite_member",
+            "Workspace member invited",
+            &json,
+        )
+        .await?;
+        let invitation: WorkspaceInvitation = serde_json::from_value(json)?;
+
+        WsEvent::workspace_members_updated(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(invitation)
+    }
+
+    /// Lists every [`User`] associated with this workspace, along with their [`WorkspaceRole`].
+    pub async fn list_members(&self, ctx: &DalContext) -> WorkspaceResult<Vec<WorkspaceMember>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(WORKSPACE_LIST_MEMBERS, &[&self.pk])
+            .await?;
+        rows.into_iter().map(WorkspaceMember::from_row).collect()
+    }
+
+    /// Lists the invitations to this workspace that have not been accepted yet, oldest first.
+    pub async fn list_invitations(
+        &self,
+        ctx: &DalContext,
+    ) -> WorkspaceResult<Vec<WorkspaceInvitation>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                WORKSPACE_LIST_PENDING_INVITATIONS,
+                &[&self.pk, &None::<String>],
+            )
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+
+    /// Revokes a pending invitation to this workspace.
+    pub async fn revoke_invitation(
+        &self,
+        ctx: &DalContext,
+        invitation_pk: WorkspaceInvitationPk,
+    ) -> WorkspaceResult<()> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::ManageMembers).await?;
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(WORKSPACE_REVOKE_INVITATION, &[&invitation_pk, &self.pk])
+            .await?;
+        if row.is_none() {
+            return Err(WorkspaceError::InvitationNotFound(invitation_pk));
+        }
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "workspace.revoke_invitation",
+            "Workspace invitation revoked",
+            &serde_json::json![{ "pk": invitation_pk, "workspace_pk": self.pk }],
+        )
+        .await?;
+        WsEvent::workspace_members_updated(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Accepts the pending invitation to this workspace for the provided [`User's`](User) email
+    /// address, if there is one, associating them with the workspace with the invited
+    /// [`WorkspaceRole`]. Users who are already members keep the role they have.
+    pub async fn accept_invitation(
+        &self,
+        ctx: &DalContext,
+        user: &User,
+    ) -> WorkspaceResult<Option<WorkspaceInvitation>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                WORKSPACE_LIST_PENDING_INVITATIONS,
+                &[&self.pk, &Some(user.email().as_str())],
+            )
+            .await?;
+        let mut invitation: WorkspaceInvitation = match objects_from_rows(rows)?.pop() {
+            Some(invitation) => invitation,
+            None => return Ok(None),
+        };
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(WORKSPACE_ACCEPT_INVITATION, &[&invitation.pk, &user.pk()])
+            .await?;
+        invitation.accepted_at = row.try_get("accepted_at")?;
+        invitation.accepted_by_user_pk = Some(user.pk());
+        invitation.timestamp.updated_at = row.try_get("updated_at")?;
+
+        user.associate_workspace_with_role(ctx, self.pk, invitation.role)
+            .await?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "workspace.accept_invitation",
+            "Workspace invitation accepted",
+            &serde_json::to_value(&invitation)?,
+        )
+        .await?;
+
+        Ok(Some(invitation))
+    }
+
+    /// Removes the [`User`] for the provided [`UserPk`] from this workspace. The last
+    /// [`Owner`](WorkspaceRole::Owner) of a workspace cannot be removed.
+    pub async fn remove_member(&self, ctx: &DalContext, user_pk: UserPk) -> WorkspaceResult<()> {
+        User::require_permission_for_actor(ctx, WorkspacePermission::ManageMembers).await?;
+
+        let role = User::role_for_workspace(ctx, user_pk, self.pk)
+            .await?
+            .ok_or(WorkspaceError::NotMemberOfWorkspace(user_pk, self.pk))?;
+        if role == WorkspaceRole::Owner {
+            let owners = self
+                .list_members(ctx)
+                .await?
+                .into_iter()
+                .filter(|member| member.role == WorkspaceRole::Owner)
+                .count();
+            if owners <= 1 {
+                return Err(WorkspaceError::CannotRemoveLastOwner(user_pk, self.pk));
+            }
+        }
+
+        ctx.txns()
+            .await?
+            .pg()
+            .query_one(WORKSPACE_REMOVE_MEMBER, &[&user_pk, &self.pk])
+            .await?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "workspace.remove_member",
+            "Workspace member removed",
+            &serde_json::json![{ "user_pk": user_pk, "workspace_pk": self.pk }],
+        )
+        .await?;
+        WsEvent::workspace_members_updated(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Makes the [`User`] for `to_user_pk` an [`Owner`](WorkspaceRole::Owner) of this workspace,
+    /// demoting the current owner (`from_user_pk`) to an [`Applier`](WorkspaceRole::Applier).
+    pub async fn transfer_ownership(
+        &self,
+        ctx: &DalContext,
+        from_user_pk: UserPk,
+        to_user_pk: UserPk,
+    ) -> WorkspaceResult<()> {
+        if User::role_for_workspace(ctx, from_user_pk, self.pk).await? != Some(WorkspaceRole::Owner)
+        {
+            return Err(WorkspaceError::NotOwner(from_user_pk, self.pk));
+        }
+        if !User::is_member_of_workspace(ctx, to_user_pk, self.pk).await? {
+            return Err(WorkspaceError::NotMemberOfWorkspace(to_user_pk, self.pk));
+        }
+        if from_user_pk == to_user_pk {
+            return Ok(());
+        }
+
+        User::set_role_for_workspace(ctx, to_user_pk, self.pk, WorkspaceRole::Owner).await?;
+        User::set_role_for_workspace(ctx, from_user_pk, self.pk, WorkspaceRole::Applier).await?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "workspace.transfer_ownership",
+            "Workspace ownership transferred",
+            &serde_json::json![{
+                "workspace_pk": self.pk,
+                "from_user_pk": from_user_pk,
+                "to_user_pk": to_user_pk,
+            }],
+        )
+        .await?;
+        WsEvent::workspace_members_updated(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,64 @@
+//! This module contains [`WorkspaceMember`] and [`WorkspaceInvitation`], which describe who
+//! belongs to (or has been invited to) a [`Workspace`](crate::Workspace).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgRow;
+
+use crate::workspace::{WorkspaceError, WorkspaceResult};
+use crate::{
+    pk, DalContext, Timestamp, UserPk, WorkspacePk, WorkspaceRole, WsEvent, WsEventResult,
+    WsPayload,
+};
+
+pk!(WorkspaceInvitationPk);
+
+/// An invitation for whoever signs in with `email` to join a [`Workspace`](crate::Workspace)
+/// with the given [`WorkspaceRole`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceInvitation {
+    pub pk: WorkspaceInvitationPk,
+    pub workspace_pk: WorkspacePk,
+    pub email: String,
+    pub role: WorkspaceRole,
+    pub invited_by_user_pk: Option<UserPk>,
+    pub accepted_by_user_pk: Option<UserPk>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+/// A [`User`](crate::User) associated with a [`Workspace`](crate::Workspace).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMember {
+    pub user_pk: UserPk,
+    pub name: String,
+    pub email: String,
+    pub role: WorkspaceRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl WorkspaceMember {
+    pub(crate) fn from_row(row: PgRow) -> WorkspaceResult<Self> {
+        let role: String = row.try_get("role")?;
+        Ok(Self {
+            user_pk: row.try_get("user_pk")?,
+            name: row.try_get("name")?,
+            email: row.try_get("email")?,
+            role: role
+                .parse()
+                .map_err(|_| WorkspaceError::UnknownWorkspaceRole(role))?,
+            joined_at: row.try_get("joined_at")?,
+        })
+    }
+}
+
+impl WsEvent {
+    pub async fn workspace_members_updated(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::WorkspaceMembersUpdated(workspace_pk)).await
+    }
+}
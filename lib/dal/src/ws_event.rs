@@ -40,7 +40,12 @@ pub type WsEventResult<T> = Result<T, WsEventError>;
 pub enum WsPayload {
     ChangeSetApplied(ChangeSetPk),
     ChangeSetCanceled(ChangeSetPk),
+    ChangeSetClosed(ChangeSetPk),
     ChangeSetCreated(ChangeSetPk),
+    ChangeSetRebased(ChangeSetPk),
+    ChangeSetReopened(ChangeSetPk),
+    ChangeSetReviewed(ChangeSetPk),
+    ChangeSetReviewRequested(ChangeSetPk),
     ChangeSetWritten(ChangeSetPk),
     CheckedQualifications(QualificationCheckPayload),
     CodeGenerated(CodeGeneratedPayload),
@@ -51,6 +56,7 @@ pub enum WsPayload {
     ResourceRefreshed(ResourceRefreshedPayload),
     SchemaCreated(SchemaPk),
     StatusUpdate(StatusMessage),
+    WorkspaceMembersUpdated(WorkspacePk),
 }
 
 #[remain::sorted]
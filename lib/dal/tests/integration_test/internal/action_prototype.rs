@@ -1,8 +1,12 @@
 use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
 
 use dal::action_prototype::ActionKind;
-use dal::{ActionPrototype, ActionPrototypeContext, DalContext, FuncId};
+use dal::{
+    ActionPrototype, ActionPrototypeContext, Component, DalContext, FuncId, Schema, StandardModel,
+};
 use dal_test::test;
+use veritech_client::ResourceStatus;
 
 #[test]
 async fn new(ctx: &DalContext) {
@@ -13,3 +17,63 @@ async fn new(ctx: &DalContext) {
     assert_eq!(*prototype.kind(), ActionKind::Create);
     assert_eq!(prototype.func_id(), FuncId::NONE);
 }
+
+/// Recommendation: run this test with the following environment variable:
+/// ```shell
+/// SI_TEST_BUILTIN_SCHEMAS=test
+/// ```
+#[test]
+async fn plan(ctx: &DalContext) {
+    let schema = Schema::find_by_name(ctx, "starfield")
+        .await
+        .expect("could not find schema");
+    let schema_variant_id = *schema
+        .default_schema_variant_id()
+        .expect("could not get default variant id");
+    let (component, _) = Component::new(ctx, "constellation", schema_variant_id)
+        .await
+        .expect("could not create component");
+    let resource = component
+        .resource(ctx)
+        .await
+        .expect("could not get resource");
+
+    let context = ActionPrototypeContext { schema_variant_id };
+    let create = ActionPrototype::find_for_context_and_kind(ctx, ActionKind::Create, context)
+        .await
+        .expect("could not find create action")
+        .pop()
+        .expect("no create action");
+    let refresh = ActionPrototype::find_for_context_and_kind(ctx, ActionKind::Refresh, context)
+        .await
+        .expect("could not find refresh action")
+        .pop()
+        .expect("no refresh action");
+
+    // The create action honours dry runs, describing what it would create
+    let plan = create
+        .plan(ctx, *component.id())
+        .await
+        .expect("could not plan create action");
+    assert!(plan.supported);
+    assert_eq!(Some(ResourceStatus::Ok), plan.status);
+    assert_eq!(Some(json!({ "create": { "poop": true } })), plan.changes);
+
+    // The refresh action doesn't, so it isn't invoked
+    let plan = refresh
+        .plan(ctx, *component.id())
+        .await
+        .expect("could not plan refresh action");
+    assert!(!plan.supported);
+    assert_eq!(None, plan.status);
+    assert_eq!(None, plan.changes);
+
+    // Planning leaves the resource as it was
+    assert_eq!(
+        resource, // expected
+        component
+            .resource(ctx)
+            .await
+            .expect("could not get resource")  // actual
+    );
+}
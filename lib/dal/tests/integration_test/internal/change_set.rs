@@ -1,6 +1,17 @@
-use dal::{ChangeSet, ChangeSetStatus, DalContext, Visibility};
+use dal::change_set::{
+    ChangeSetApprovalStatus, ChangeSetDiff, ChangeSetReview, ChangeSetReviewStatus,
+};
+use dal::change_status::ChangeStatus;
+use dal::{
+    ChangeSet, ChangeSetStatus, DalContext, Func, FuncBackendKind, FuncBackendResponseType,
+    FuncError, HistoryActor, StandardModel, StandardModelError, User, UserPk, Visibility,
+    Workspace,
+};
 use dal_test::{helpers::create_change_set, test, DalContextHeadMutRef, DalContextHeadRef};
 
+mod rebase;
+mod revert;
+
 #[test]
 async fn new(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
     let change_set = ChangeSet::new(
@@ -84,3 +95,348 @@ async fn get_by_pk(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
         .expect("change set pk should exist");
     assert_eq!(&change_set, &result);
 }
+
+#[test]
+async fn rebase_without_conflicts(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let mut change_set = create_change_set(ctx).await;
+    assert!(change_set.rebased_at.is_none());
+
+    let conflicts = change_set
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts");
+    assert!(conflicts.is_empty());
+
+    let report = change_set
+        .rebase(ctx, &[])
+        .await
+        .expect("could not rebase change set");
+    assert!(report.rebased);
+    assert!(report.conflicts.is_empty());
+    assert!(change_set.rebased_at.is_some());
+    assert_eq!(Some(change_set.base()), change_set.rebased_at);
+}
+
+#[test]
+async fn abandon(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let mut change_set = create_change_set(ctx).await;
+    change_set
+        .abandon(ctx)
+        .await
+        .expect("cannot abandon change set");
+    assert_eq!(&change_set.status, &ChangeSetStatus::Abandoned);
+
+    let open = ChangeSet::list_open(ctx)
+        .await
+        .expect("cannot get list of open change sets");
+    assert!(open.is_empty());
+    let abandoned = ChangeSet::list_with_status(ctx, Some(ChangeSetStatus::Abandoned))
+        .await
+        .expect("cannot get list of abandoned change sets");
+    assert_eq!(abandoned.len(), 1);
+
+    assert!(change_set.abandon(ctx).await.is_err());
+    assert!(change_set.reopen(ctx).await.is_err());
+}
+
+#[test]
+async fn close_and_reopen(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let mut change_set = create_change_set(ctx).await;
+    change_set
+        .close(ctx)
+        .await
+        .expect("cannot close change set");
+    assert_eq!(&change_set.status, &ChangeSetStatus::Closed);
+    assert!(ChangeSet::list_open(ctx)
+        .await
+        .expect("cannot get list of open change sets")
+        .is_empty());
+
+    change_set
+        .reopen(ctx)
+        .await
+        .expect("cannot reopen change set");
+    assert_eq!(&change_set.status, &ChangeSetStatus::Open);
+    let all = ChangeSet::list_with_status(ctx, None)
+        .await
+        .expect("cannot get list of change sets");
+    assert_eq!(all.len(), 1);
+}
+
+#[test]
+async fn review_and_approval_policy(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .expect("no workspace in tenancy");
+    let mut workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("could not get workspace")
+        .expect("workspace not found");
+    workspace
+        .set_required_change_set_approvals(ctx, 1)
+        .await
+        .expect("could not set approval policy");
+
+    let mut change_set = create_change_set(ctx).await;
+    let approval = ChangeSetApprovalStatus::new(ctx, &change_set)
+        .await
+        .expect("could not evaluate approval policy");
+    assert!(!approval.satisfied);
+
+    let reviewer = User::new(
+        ctx,
+        UserPk::generate(),
+        "reviewer",
+        "reviewer@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    reviewer
+        .associate_workspace(ctx, workspace_pk)
+        .await
+        .expect("cannot associate user with workspace");
+    let reviewer_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(reviewer.pk()));
+
+    assert!(ChangeSetReview::new(
+        &reviewer_ctx,
+        &change_set,
+        ChangeSetReviewStatus::Approved,
+        None
+    )
+    .await
+    .is_err());
+
+    change_set
+        .request_review(ctx)
+        .await
+        .expect("could not request review");
+    assert!(change_set.review_requested_at.is_some());
+
+    let review = ChangeSetReview::new(
+        &reviewer_ctx,
+        &change_set,
+        ChangeSetReviewStatus::Approved,
+        Some("looks good".to_owned()),
+    )
+    .await
+    .expect("could not review change set");
+    assert_eq!(review.reviewer_user_pk, reviewer.pk());
+
+    let approval = ChangeSetApprovalStatus::new(ctx, &change_set)
+        .await
+        .expect("could not evaluate approval policy");
+    assert_eq!(approval.approved_by, vec![reviewer.pk()]);
+    assert!(approval.satisfied);
+
+    // Writing to the change set after it was approved invalidates the approval
+    let change_set_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(change_set.pk, false));
+    Func::new(
+        &change_set_ctx,
+        "after-approval",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Object,
+    )
+    .await
+    .expect("could not create func");
+    let approval = ChangeSetApprovalStatus::new(ctx, &change_set)
+        .await
+        .expect("could not evaluate approval policy");
+    assert!(approval.last_written_at.is_some());
+    assert!(approval.approved_by.is_empty());
+    assert!(!approval.satisfied);
+
+    assert!(workspace
+        .set_required_change_set_approvals(ctx, -1)
+        .await
+        .is_err());
+    assert_eq!(1, *workspace.required_change_set_approvals());
+}
+
+#[test]
+async fn diff_includes_func_changes(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let change_set = create_change_set(ctx).await;
+    let change_set_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(change_set.pk, false));
+
+    let empty = ChangeSetDiff::new(ctx, change_set.pk)
+        .await
+        .expect("could not diff change set");
+    assert!(empty.components.is_empty());
+    assert!(empty.edges.is_empty());
+    assert!(empty.funcs.is_empty());
+
+    let mut func = Func::new(
+        &change_set_ctx,
+        "under-review",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Object,
+    )
+    .await
+    .expect("could not create func");
+    func.set_code_plaintext(&change_set_ctx, Some("function review() {}"))
+        .await
+        .expect("could not set code");
+
+    let diff = ChangeSetDiff::new(ctx, change_set.pk)
+        .await
+        .expect("could not diff change set");
+    assert_eq!(diff.funcs.len(), 1);
+    let func_diff = diff.funcs.first().expect("no func diff");
+    assert_eq!(func_diff.func_id, *func.id());
+    assert_eq!(func_diff.status, ChangeStatus::Added);
+    assert_eq!(func_diff.before, None);
+    assert_eq!(func_diff.after.as_deref(), Some("function review() {}"));
+    assert!(func_diff.code_diff.is_some());
+}
+
+#[test]
+async fn view_as_of(DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>) {
+    let mut change_set = create_change_set(ctx).await;
+    let change_set_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(change_set.pk, false));
+    let func = Func::new(
+        &change_set_ctx,
+        "from-the-past",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Object,
+    )
+    .await
+    .expect("could not create func");
+
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.update_visibility(Visibility::new_head(false));
+    assert!(change_set.applied_at.is_some());
+    assert!(Func::get_by_id(ctx, func.id())
+        .await
+        .expect("could not get func")
+        .is_some());
+
+    let timeline = ChangeSet::list_applied(ctx, None)
+        .await
+        .expect("could not list applied change sets");
+    assert_eq!(
+        timeline.first().map(|applied| applied.pk),
+        Some(change_set.pk)
+    );
+
+    let historical = ChangeSet::new_as_of(ctx, change_set.timestamp.created_at)
+        .await
+        .expect("could not create historical change set");
+    assert_eq!(historical.status, ChangeSetStatus::Historical);
+    let historical_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(historical.pk, false));
+    assert!(Func::get_by_id(&historical_ctx, func.id())
+        .await
+        .expect("could not get func")
+        .is_none());
+
+    let after_apply = ChangeSet::new_as_of_change_set(ctx, change_set.pk)
+        .await
+        .expect("could not create historical change set");
+    let after_apply_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(after_apply.pk, false));
+    assert!(Func::get_by_id(&after_apply_ctx, func.id())
+        .await
+        .expect("could not get func")
+        .is_some());
+
+    // Asking for the same point in time again reuses the historical change set
+    let again = ChangeSet::new_as_of(ctx, change_set.timestamp.created_at)
+        .await
+        .expect("could not create historical change set");
+    assert_eq!(historical.pk, again.pk);
+
+    let all = ChangeSet::list_with_status(ctx, None)
+        .await
+        .expect("cannot get list of change sets");
+    assert!(!all.iter().any(|entry| entry.value == historical.pk));
+    let historicals = ChangeSet::list_with_status(ctx, Some(ChangeSetStatus::Historical))
+        .await
+        .expect("cannot get list of historical change sets");
+    assert_eq!(historicals.len(), 2);
+
+    // Historical change sets are read-only
+    let result = Func::new(
+        &historical_ctx,
+        "rewriting-history",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Object,
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(FuncError::StandardModelError(
+            StandardModelError::HistoricalChangeSet(change_set_pk)
+        )) if change_set_pk == historical.pk
+    ));
+
+    // Once something else is applied, the view no longer shows what head looked like back then,
+    // so it isn't reused
+    let mut later = create_change_set(ctx).await;
+    let later_ctx = ctx.clone_with_new_visibility(Visibility::new_change_set(later.pk, false));
+    let later_func = Func::new(
+        &later_ctx,
+        "from-the-future",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Object,
+    )
+    .await
+    .expect("could not create func");
+    later.apply(ctx).await.expect("cannot apply change set");
+    ctx.update_visibility(Visibility::new_head(false));
+
+    let rebuilt = ChangeSet::new_as_of(ctx, change_set.timestamp.created_at)
+        .await
+        .expect("could not create historical change set");
+    assert_ne!(historical.pk, rebuilt.pk);
+    let rebuilt_ctx = ctx.clone_with_new_visibility(Visibility::new_change_set(rebuilt.pk, false));
+    assert!(Func::get_by_id(&rebuilt_ctx, later_func.id())
+        .await
+        .expect("could not get func")
+        .is_none());
+}
+
+#[test]
+async fn revert(DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>) {
+    let mut change_set = create_change_set(ctx).await;
+    let change_set_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(change_set.pk, false));
+    let func = Func::new(
+        &change_set_ctx,
+        "regrettable",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Object,
+    )
+    .await
+    .expect("could not create func");
+
+    assert!(ChangeSet::revert(ctx, change_set.pk).await.is_err());
+
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.update_visibility(Visibility::new_head(false));
+
+    let revert = ChangeSet::revert(ctx, change_set.pk)
+        .await
+        .expect("could not revert change set");
+    assert_eq!(revert.status, ChangeSetStatus::Open);
+    assert_eq!(revert.reverts_change_set_pk, Some(change_set.pk));
+
+    let revert_ctx = ctx.clone_with_new_visibility(Visibility::new_change_set(revert.pk, false));
+    assert!(Func::get_by_id(&revert_ctx, func.id())
+        .await
+        .expect("could not get func")
+        .is_none());
+    assert!(Func::get_by_id(ctx, func.id())
+        .await
+        .expect("could not get func")
+        .is_some());
+}
@@ -0,0 +1,245 @@
+use dal::change_set::{
+    ChangeSetConflictKind, ChangeSetConflictObject, ChangeSetConflictResolution,
+    ChangeSetConflictStrategy,
+};
+use dal::prop::PropPath;
+use dal::{
+    AttributeValue, ChangeSet, ChangeSetError, ChangeSetStatus, Component, DalContext,
+    StandardModel, Visibility,
+};
+use dal_test::helpers::component_bag::{ComponentBag, ComponentBagger};
+use dal_test::helpers::{create_change_set, generate_fake_name};
+use dal_test::{test, DalContextHeadMutRef, DalContextHeadRef};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::Value;
+
+const FREESTAR: &[&str] = &["root", "domain", "freestar"];
+
+pub(super) async fn set_freestar(ctx: &DalContext, bag: &ComponentBag, value: &str) {
+    let prop = bag.find_prop(ctx, FREESTAR).await;
+    bag.update_attribute_value_for_prop(ctx, *prop.id(), Some(Value::from(value)))
+        .await;
+}
+
+pub(super) async fn freestar(ctx: &DalContext, bag: &ComponentBag) -> Option<Value> {
+    let prop = bag.find_prop(ctx, FREESTAR).await;
+    AttributeValue::find_for_context(ctx, bag.attribute_read_context_with_prop(*prop.id()))
+        .await
+        .expect("could not find attribute value")
+        .expect("attribute value not found")
+        .get_value(ctx)
+        .await
+        .expect("could not get value")
+}
+
+pub(super) async fn component_exists(ctx: &DalContext, bag: &ComponentBag) -> bool {
+    Component::get_by_id(ctx, &bag.component_id)
+        .await
+        .expect("could not get component")
+        .is_some()
+}
+
+/// Creates a component on head with "freestar" set, and a change set after it.
+pub(super) async fn setup(ctx: &DalContext) -> (ComponentBag, ChangeSet, DalContext) {
+    let bag = ComponentBagger::new()
+        .create_component(ctx, &generate_fake_name(), "starfield")
+        .await;
+    set_freestar(ctx, &bag, "before").await;
+
+    let change_set = create_change_set(ctx).await;
+    let change_set_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(change_set.pk, false));
+    (bag, change_set, change_set_ctx)
+}
+
+/// Sets "freestar" to different values in the change set and then on head.
+async fn setup_both_modified(ctx: &DalContext) -> (ComponentBag, ChangeSet, DalContext) {
+    let (bag, change_set, change_set_ctx) = setup(ctx).await;
+    set_freestar(&change_set_ctx, &bag, "change set").await;
+    set_freestar(ctx, &bag, "head").await;
+    (bag, change_set, change_set_ctx)
+}
+
+/// Deletes the component in the change set and then edits it on head.
+async fn setup_deleted_in_change_set(ctx: &DalContext) -> (ComponentBag, ChangeSet, DalContext) {
+    let (bag, change_set, change_set_ctx) = setup(ctx).await;
+    bag.component(&change_set_ctx)
+        .await
+        .delete_and_propagate(&change_set_ctx)
+        .await
+        .expect("could not delete component in change set");
+    set_freestar(ctx, &bag, "head").await;
+    (bag, change_set, change_set_ctx)
+}
+
+/// Edits the component in the change set and then deletes it on head.
+async fn setup_deleted_on_head(ctx: &DalContext) -> (ComponentBag, ChangeSet, DalContext) {
+    let (bag, change_set, change_set_ctx) = setup(ctx).await;
+    set_freestar(&change_set_ctx, &bag, "change set").await;
+    bag.component(ctx)
+        .await
+        .delete_and_propagate(ctx)
+        .await
+        .expect("could not delete component on head");
+    (bag, change_set, change_set_ctx)
+}
+
+async fn rebase(
+    ctx: &DalContext,
+    change_set: &mut ChangeSet,
+    strategy: ChangeSetConflictStrategy,
+) -> ChangeSetConflictKind {
+    let conflicts = change_set
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts");
+    assert_eq!(1, conflicts.len());
+    let conflict = conflicts.first().expect("no conflict");
+
+    // Without a resolution, nothing is rebased
+    let report = change_set
+        .rebase(ctx, &[])
+        .await
+        .expect("could not rebase change set");
+    assert!(!report.rebased);
+    assert!(change_set.rebased_at.is_none());
+
+    let report = change_set
+        .rebase(
+            ctx,
+            &[ChangeSetConflictResolution {
+                object: conflict.object,
+                strategy,
+            }],
+        )
+        .await
+        .expect("could not rebase change set");
+    assert!(report.rebased);
+    assert!(change_set
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts")
+        .is_empty());
+
+    conflict.kind
+}
+
+#[test]
+async fn both_modified_conflict(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let (bag, change_set, _) = setup_both_modified(ctx).await;
+
+    let conflicts = change_set
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts");
+    assert_eq!(1, conflicts.len());
+    let conflict = conflicts.first().expect("no conflict");
+    assert_eq!(ChangeSetConflictKind::BothModified, conflict.kind);
+    assert_eq!(bag.component_id, conflict.component_id);
+    assert!(matches!(
+        conflict.object,
+        ChangeSetConflictObject::AttributeValue(_)
+    ));
+    assert_eq!(
+        Some(PropPath::new(FREESTAR).as_str()),
+        conflict.prop_path.as_deref()
+    );
+}
+
+#[test]
+async fn both_modified_take_head(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup_both_modified(ctx).await;
+
+    let kind = rebase(ctx, &mut change_set, ChangeSetConflictStrategy::TakeHead).await;
+    assert_eq!(ChangeSetConflictKind::BothModified, kind);
+    assert_eq!(
+        Some(Value::from("head")),
+        freestar(&change_set_ctx, &bag).await
+    );
+}
+
+#[test]
+async fn both_modified_keep_change_set(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup_both_modified(ctx).await;
+
+    let kind = rebase(
+        ctx,
+        &mut change_set,
+        ChangeSetConflictStrategy::KeepChangeSet,
+    )
+    .await;
+    assert_eq!(ChangeSetConflictKind::BothModified, kind);
+    assert_eq!(
+        Some(Value::from("change set")),
+        freestar(&change_set_ctx, &bag).await
+    );
+}
+
+#[test]
+async fn deleted_in_change_set_take_head(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup_deleted_in_change_set(ctx).await;
+    assert!(!component_exists(&change_set_ctx, &bag).await);
+
+    let kind = rebase(ctx, &mut change_set, ChangeSetConflictStrategy::TakeHead).await;
+    assert_eq!(ChangeSetConflictKind::DeletedInChangeSet, kind);
+    assert!(component_exists(&change_set_ctx, &bag).await);
+}
+
+#[test]
+async fn deleted_in_change_set_keep_change_set(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup_deleted_in_change_set(ctx).await;
+
+    let kind = rebase(
+        ctx,
+        &mut change_set,
+        ChangeSetConflictStrategy::KeepChangeSet,
+    )
+    .await;
+    assert_eq!(ChangeSetConflictKind::DeletedInChangeSet, kind);
+    assert!(!component_exists(&change_set_ctx, &bag).await);
+}
+
+#[test]
+async fn deleted_on_head_take_head(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup_deleted_on_head(ctx).await;
+
+    let kind = rebase(ctx, &mut change_set, ChangeSetConflictStrategy::TakeHead).await;
+    assert_eq!(ChangeSetConflictKind::DeletedOnHead, kind);
+    assert!(!component_exists(&change_set_ctx, &bag).await);
+}
+
+#[test]
+async fn deleted_on_head_keep_change_set(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup_deleted_on_head(ctx).await;
+
+    let kind = rebase(
+        ctx,
+        &mut change_set,
+        ChangeSetConflictStrategy::KeepChangeSet,
+    )
+    .await;
+    assert_eq!(ChangeSetConflictKind::DeletedOnHead, kind);
+    assert!(component_exists(&change_set_ctx, &bag).await);
+    assert!(!component_exists(ctx, &bag).await);
+}
+
+#[test]
+async fn apply_is_refused_with_unresolved_conflicts(
+    DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>,
+) {
+    let (_, mut change_set, _) = setup_both_modified(ctx).await;
+
+    let result = change_set.apply(ctx).await;
+    assert!(matches!(
+        result,
+        Err(ChangeSetError::UnresolvedConflicts(pk, 1)) if pk == change_set.pk
+    ));
+    assert_eq!(
+        ChangeSetStatus::Open,
+        ChangeSet::get_by_pk(ctx, &change_set.pk)
+            .await
+            .expect("could not get change set")
+            .expect("change set not found")
+            .status
+    );
+}
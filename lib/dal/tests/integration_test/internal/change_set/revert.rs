@@ -0,0 +1,184 @@
+use dal::change_set::ChangeSetConflictKind;
+use dal::edge::EdgeKind;
+use dal::socket::SocketEdgeKind;
+use dal::{
+    ChangeSet, ChangeSetError, Connection, DalContext, Edge, Socket, StandardModel, Visibility,
+};
+use dal_test::helpers::component_bag::{ComponentBag, ComponentBagger};
+use dal_test::helpers::{create_change_set, generate_fake_name};
+use dal_test::{test, DalContextHeadMutRef};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::Value;
+
+use super::rebase::{component_exists, freestar, set_freestar, setup};
+
+/// Applies the change set, leaving the ctx on head.
+async fn apply(ctx: &mut DalContext, change_set: &mut ChangeSet) {
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.update_visibility(Visibility::new_head(false));
+}
+
+/// Reverts the applied change set, returning the revert and a ctx for it.
+async fn revert(ctx: &DalContext, change_set: &ChangeSet) -> (ChangeSet, DalContext) {
+    let revert = ChangeSet::revert(ctx, change_set.pk)
+        .await
+        .expect("could not revert change set");
+    assert_eq!(Some(change_set.pk), revert.reverts_change_set_pk);
+    assert_eq!(change_set.applied_at, revert.reverts_applied_at);
+    let revert_ctx = ctx.clone_with_new_visibility(Visibility::new_change_set(revert.pk, false));
+    (revert, revert_ctx)
+}
+
+async fn connect(ctx: &DalContext, from: &ComponentBag, to: &ComponentBag) -> Connection {
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        from.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        to.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+
+    Connection::new(
+        ctx,
+        from.node_id,
+        *output_socket.id(),
+        to.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+    )
+    .await
+    .expect("could not create connection")
+}
+
+async fn edge_exists(ctx: &DalContext, connection: &Connection) -> bool {
+    Edge::get_by_id(ctx, &connection.id)
+        .await
+        .expect("could not get edge")
+        .is_some()
+}
+
+#[test]
+async fn revert_created_component(DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>) {
+    let mut change_set = create_change_set(ctx).await;
+    let change_set_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(change_set.pk, false));
+    let bag = ComponentBagger::new()
+        .create_component(&change_set_ctx, &generate_fake_name(), "starfield")
+        .await;
+    apply(ctx, &mut change_set).await;
+
+    let (revert, revert_ctx) = revert(ctx, &change_set).await;
+    assert!(!component_exists(&revert_ctx, &bag).await);
+    assert!(component_exists(ctx, &bag).await);
+    assert!(revert
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts")
+        .is_empty());
+}
+
+#[test]
+async fn revert_deleted_component(DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup(ctx).await;
+    bag.component(&change_set_ctx)
+        .await
+        .delete_and_propagate(&change_set_ctx)
+        .await
+        .expect("could not delete component in change set");
+    apply(ctx, &mut change_set).await;
+    assert!(!component_exists(ctx, &bag).await);
+
+    let (_, revert_ctx) = revert(ctx, &change_set).await;
+    assert!(component_exists(&revert_ctx, &bag).await);
+    assert_eq!(
+        Some(Value::from("before")),
+        freestar(&revert_ctx, &bag).await
+    );
+    assert!(!component_exists(ctx, &bag).await);
+}
+
+#[test]
+async fn revert_edges(DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>) {
+    let bagger = ComponentBagger::new();
+    let to_starfield = bagger.create_component(ctx, "to", "starfield").await;
+    let from_fallout = bagger.create_component(ctx, "from", "fallout").await;
+    let from_other_fallout = bagger.create_component(ctx, "other", "fallout").await;
+    let deleted_connection = connect(ctx, &from_fallout, &to_starfield).await;
+
+    let mut change_set = create_change_set(ctx).await;
+    let change_set_ctx =
+        ctx.clone_with_new_visibility(Visibility::new_change_set(change_set.pk, false));
+    Edge::get_by_id(&change_set_ctx, &deleted_connection.id)
+        .await
+        .expect("could not get edge")
+        .expect("edge not found")
+        .delete_and_propagate(&change_set_ctx)
+        .await
+        .expect("could not delete edge in change set");
+    let created_connection = connect(&change_set_ctx, &from_other_fallout, &to_starfield).await;
+    apply(ctx, &mut change_set).await;
+
+    let (_, revert_ctx) = revert(ctx, &change_set).await;
+    assert!(edge_exists(&revert_ctx, &deleted_connection).await);
+    assert!(!edge_exists(&revert_ctx, &created_connection).await);
+    assert!(!edge_exists(ctx, &deleted_connection).await);
+    assert!(edge_exists(ctx, &created_connection).await);
+}
+
+#[test]
+async fn revert_attribute_value(DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup(ctx).await;
+    set_freestar(&change_set_ctx, &bag, "change set").await;
+    apply(ctx, &mut change_set).await;
+
+    let (revert, revert_ctx) = revert(ctx, &change_set).await;
+    assert_eq!(
+        Some(Value::from("before")),
+        freestar(&revert_ctx, &bag).await
+    );
+    assert_eq!(Some(Value::from("change set")), freestar(ctx, &bag).await);
+    assert!(revert
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts")
+        .is_empty());
+}
+
+#[test]
+async fn revert_after_head_changed(DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>) {
+    let (bag, mut change_set, change_set_ctx) = setup(ctx).await;
+    set_freestar(&change_set_ctx, &bag, "change set").await;
+    apply(ctx, &mut change_set).await;
+    set_freestar(ctx, &bag, "head").await;
+
+    let (mut revert, _) = revert(ctx, &change_set).await;
+    let conflicts = revert
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts");
+    assert_eq!(1, conflicts.len());
+    let conflict = conflicts.first().expect("no conflict");
+    assert_eq!(ChangeSetConflictKind::BothModified, conflict.kind);
+    assert_eq!(bag.component_id, conflict.component_id);
+
+    let result = revert.apply(ctx).await;
+    assert!(matches!(
+        result,
+        Err(ChangeSetError::UnresolvedConflicts(pk, 1)) if pk == revert.pk
+    ));
+    assert_eq!(Some(Value::from("head")), freestar(ctx, &bag).await);
+}
@@ -0,0 +1,445 @@
+use dal::job::definition::{FixItem, FixesJob};
+use dal::{
+    generate_name, ActionKind, ActionPrototype, ActionPrototypeContext, ActionPrototypeId,
+    AttributeValueId, ChangeSet, Component, ComponentId, DalContext, Fix, FixBatch,
+    FixBatchFailurePolicy, FixCompletionStatus, FixError, Func, FuncBackendKind,
+    FuncBackendResponseType, Schema, SchemaVariantId, StandardModel, Visibility,
+};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+const AUTHOR: &str = "toddhoward@systeminit.com";
+
+/// A component whose resource doesn't exist yet, along with the create action its confirmation
+/// recommends.
+struct Recommended {
+    component_id: ComponentId,
+    attribute_value_id: AttributeValueId,
+    create_action_prototype_id: ActionPrototypeId,
+}
+
+async fn starfield_variant_id(ctx: &DalContext) -> SchemaVariantId {
+    *Schema::find_by_name(ctx, "starfield")
+        .await
+        .expect("could not find schema")
+        .default_schema_variant_id()
+        .expect("could not get default variant id")
+}
+
+/// Creates starfield components with the given names in a change set, applies it and returns
+/// what their confirmations recommend, in the order of the names.
+async fn create_applied_components(ctx: &mut DalContext, names: &[&str]) -> Vec<Recommended> {
+    ctx.update_to_head();
+    let schema_variant_id = starfield_variant_id(ctx).await;
+
+    let change_set = ChangeSet::new(ctx, generate_name(), None)
+        .await
+        .expect("could not create new change set");
+    ctx.update_visibility(Visibility::new(change_set.pk, None));
+    let mut component_ids = Vec::new();
+    for name in names {
+        let (component, _) = Component::new(ctx, name, schema_variant_id)
+            .await
+            .expect("cannot create component");
+        component_ids.push(*component.id());
+    }
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let mut change_set = ChangeSet::get_by_pk(ctx, &change_set.pk)
+        .await
+        .expect("could not fetch change set by pk")
+        .expect("no change set found for pk");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+    ctx.update_visibility(Visibility::new_head(false));
+
+    let (_, recommendations) = Component::list_confirmations(ctx)
+        .await
+        .expect("could not list confirmations");
+    component_ids
+        .into_iter()
+        .map(|component_id| {
+            let recommendation = recommendations
+                .iter()
+                .find(|recommendation| recommendation.component_id == component_id)
+                .expect("no recommendation for component");
+            assert_eq!(ActionKind::Create, recommendation.action_kind);
+            Recommended {
+                component_id,
+                attribute_value_id: recommendation.confirmation_attribute_value_id,
+                create_action_prototype_id: recommendation.action_prototype_id,
+            }
+        })
+        .collect()
+}
+
+/// Adds an action to starfield that runs the given code, whose handler is `run`.
+async fn create_action(ctx: &DalContext, name: &str, code: &str) -> ActionPrototypeId {
+    let mut func = Func::new(
+        ctx,
+        name,
+        FuncBackendKind::JsAction,
+        FuncBackendResponseType::Action,
+    )
+    .await
+    .expect("could not create func");
+    func.set_handler(ctx, Some("run"))
+        .await
+        .expect("could not set func handler");
+    func.set_code_plaintext(ctx, Some(code))
+        .await
+        .expect("could not set func code");
+
+    let schema_variant_id = starfield_variant_id(ctx).await;
+    let prototype = ActionPrototype::new(
+        ctx,
+        *func.id(),
+        ActionKind::Other,
+        ActionPrototypeContext { schema_variant_id },
+    )
+    .await
+    .expect("could not create action prototype");
+    *prototype.id()
+}
+
+async fn add_fix(
+    ctx: &DalContext,
+    batch: &FixBatch,
+    recommended: &Recommended,
+    action_prototype_id: ActionPrototypeId,
+) -> FixItem {
+    let fix = Fix::new(
+        ctx,
+        *batch.id(),
+        recommended.attribute_value_id,
+        recommended.component_id,
+        action_prototype_id,
+    )
+    .await
+    .expect("could not create fix");
+    FixItem::from(&fix)
+}
+
+/// Runs the batch through a [`FixesJob`] and returns it once it has finished.
+async fn run_batch(ctx: &DalContext, batch: &FixBatch, fixes: Vec<FixItem>) -> FixBatch {
+    ctx.enqueue_job(FixesJob::new(ctx, fixes, *batch.id()))
+        .await
+        .expect("failed to enqueue job");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    FixBatch::get_by_id(ctx, batch.id())
+        .await
+        .expect("could not get fix batch")
+        .expect("fix batch not found")
+}
+
+async fn completion_statuses(ctx: &DalContext, fixes: &[FixItem]) -> Vec<FixCompletionStatus> {
+    let mut statuses = Vec::new();
+    for fix_item in fixes {
+        let fix = Fix::get_by_id(ctx, &fix_item.id)
+            .await
+            .expect("could not get fix")
+            .expect("fix not found");
+        statuses.push(
+            *fix.completion_status()
+                .expect("fix has no completion status"),
+        );
+    }
+    statuses
+}
+
+/// Recommendation: run this test with the following environment variable:
+/// ```shell
+/// SI_TEST_BUILTIN_SCHEMAS=test
+/// ```
+#[test]
+async fn fixes_job_runs_batch(mut octx: DalContext) {
+    let ctx = &mut octx;
+    let recommended = create_applied_components(ctx, &["nuka", "cola"]).await;
+
+    let batch = FixBatch::new(ctx, AUTHOR)
+        .await
+        .expect("could not create fix batch");
+    let mut fixes = Vec::new();
+    for recommended in &recommended {
+        fixes.push(
+            add_fix(
+                ctx,
+                &batch,
+                recommended,
+                recommended.create_action_prototype_id,
+            )
+            .await,
+        );
+    }
+
+    let batch = run_batch(ctx, &batch, fixes.clone()).await;
+    assert!(batch.started_at().is_some());
+    assert!(batch.finished_at().is_some());
+    assert_eq!(
+        Some(&FixCompletionStatus::Success), // expected
+        batch.completion_status()            // actual
+    );
+    assert_eq!(
+        vec![FixCompletionStatus::Success, FixCompletionStatus::Success], // expected
+        completion_statuses(ctx, &fixes).await                            // actual
+    );
+
+    // Both resources now exist, so nothing is recommended anymore
+    let (_, recommendations) = Component::list_confirmations(ctx)
+        .await
+        .expect("could not list confirmations");
+    assert!(recommendations.is_empty());
+}
+
+/// Recommendation: run this test with the following environment variable:
+/// ```shell
+/// SI_TEST_BUILTIN_SCHEMAS=test
+/// ```
+#[test]
+async fn cancel_and_retry(mut octx: DalContext) {
+    let ctx = &mut octx;
+    let recommended = create_applied_components(ctx, &["vault"]).await;
+
+    let mut batch = FixBatch::new(ctx, AUTHOR)
+        .await
+        .expect("could not create fix batch");
+    batch
+        .set_failure_policy(ctx, FixBatchFailurePolicy::StopOnFirstFailure)
+        .await
+        .expect("could not set failure policy");
+    let fixes = vec![
+        add_fix(
+            ctx,
+            &batch,
+            &recommended[0],
+            recommended[0].create_action_prototype_id,
+        )
+        .await,
+    ];
+
+    // Cancelling again keeps the original cancellation
+    batch.cancel(ctx).await.expect("could not cancel batch");
+    let cancelled_at = *batch.cancelled_at();
+    assert!(cancelled_at.is_some());
+    batch.cancel(ctx).await.expect("could not cancel batch");
+    assert_eq!(cancelled_at, *batch.cancelled_at());
+
+    let retry_result = batch.retry(ctx, AUTHOR).await;
+    assert!(matches!(retry_result, Err(FixError::BatchNotFinished(_))));
+
+    // Nothing runs in a batch cancelled before it started
+    let mut batch = run_batch(ctx, &batch, fixes.clone()).await;
+    assert_eq!(
+        Some(&FixCompletionStatus::Cancelled), // expected
+        batch.completion_status()              // actual
+    );
+    assert_eq!(
+        vec![FixCompletionStatus::Cancelled],   // expected
+        completion_statuses(ctx, &fixes).await  // actual
+    );
+    let cancel_result = batch.cancel(ctx).await;
+    assert!(matches!(
+        cancel_result,
+        Err(FixError::CannotCancelFinishedBatch(_))
+    ));
+
+    // The retry has the fixes that didn't succeed, and the same failure policy
+    let (retry, retry_fixes) = batch
+        .retry(ctx, AUTHOR)
+        .await
+        .expect("could not retry batch");
+    assert_ne!(batch.id(), retry.id());
+    assert_eq!(
+        FixBatchFailurePolicy::StopOnFirstFailure, // expected
+        *retry.failure_policy()                    // actual
+    );
+    assert_eq!(1, retry_fixes.len());
+    assert_eq!(
+        recommended[0].create_action_prototype_id, // expected
+        *retry_fixes[0].action_prototype_id()      // actual
+    );
+    assert_eq!(
+        recommended[0].component_id,    // expected
+        *retry_fixes[0].component_id()  // actual
+    );
+
+    let retry_fixes: Vec<FixItem> = retry_fixes.iter().map(FixItem::from).collect();
+    let retry = run_batch(ctx, &retry, retry_fixes.clone()).await;
+    assert_eq!(
+        Some(&FixCompletionStatus::Success), // expected
+        retry.completion_status()            // actual
+    );
+    assert_eq!(
+        vec![FixCompletionStatus::Success],           // expected
+        completion_statuses(ctx, &retry_fixes).await  // actual
+    );
+
+    let retry_result = retry.retry(ctx, AUTHOR).await;
+    assert!(matches!(retry_result, Err(FixError::NoFixesToRetry(_))));
+}
+
+/// Recommendation: run this test with the following environment variable:
+/// ```shell
+/// SI_TEST_BUILTIN_SCHEMAS=test
+/// ```
+#[test]
+async fn failure_policies(mut octx: DalContext) {
+    let ctx = &mut octx;
+    ctx.update_to_head();
+    let fail = create_action(
+        ctx,
+        "test:failStarfield",
+        "async function run() { return { status: 'error', message: 'it just works' }; }",
+    )
+    .await;
+    // Slow enough for the failing action to finish first when both start together
+    let slow = create_action(
+        ctx,
+        "test:slowStarfield",
+        "async function run() {
+            const until = Date.now() + 3000;
+            while (Date.now() < until) {}
+            return { status: 'ok' };
+        }",
+    )
+    .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    use FixCompletionStatus::{Failure, Success, Unstarted};
+    for (failure_policy, expected) in [
+        (
+            FixBatchFailurePolicy::Continue,
+            vec![Failure, Success, Success, Success],
+        ),
+        // The create action of the component whose first fix failed is skipped
+        (
+            FixBatchFailurePolicy::SkipDependents,
+            vec![Failure, Unstarted, Success, Success],
+        ),
+        // Every fix that hadn't started when the first one failed is skipped
+        (
+            FixBatchFailurePolicy::StopOnFirstFailure,
+            vec![Failure, Unstarted, Success, Unstarted],
+        ),
+    ] {
+        let first = format!("{failure_policy}-first");
+        let second = format!("{failure_policy}-second");
+        let recommended = create_applied_components(ctx, &[&first, &second]).await;
+
+        let mut batch = FixBatch::new(ctx, AUTHOR)
+            .await
+            .expect("could not create fix batch");
+        batch
+            .set_failure_policy(ctx, failure_policy)
+            .await
+            .expect("could not set failure policy");
+        // Fixes of the same component run in the order they were requested, while the two
+        // components' fixes are independent
+        let fixes = vec![
+            add_fix(ctx, &batch, &recommended[0], fail).await,
+            add_fix(
+                ctx,
+                &batch,
+                &recommended[0],
+                recommended[0].create_action_prototype_id,
+            )
+            .await,
+            add_fix(ctx, &batch, &recommended[1], slow).await,
+            add_fix(
+                ctx,
+                &batch,
+                &recommended[1],
+                recommended[1].create_action_prototype_id,
+            )
+            .await,
+        ];
+
+        let batch = run_batch(ctx, &batch, fixes.clone()).await;
+        assert_eq!(
+            expected,                               // expected
+            completion_statuses(ctx, &fixes).await, // actual
+            "{failure_policy}"
+        );
+        assert_eq!(
+            Some(&Failure),            // expected
+            batch.completion_status(), // actual
+            "{failure_policy}"
+        );
+    }
+}
+
+/// Recommendation: run this test with the following environment variable:
+/// ```shell
+/// SI_TEST_BUILTIN_SCHEMAS=test
+/// ```
+#[test]
+async fn cancellations_take_precedence_over_failures(mut octx: DalContext) {
+    let ctx = &mut octx;
+    let recommended = create_applied_components(ctx, &["mister", "handy", "codsworth"]).await;
+
+    // Stamps the batch's fixes with the given statuses and returns the status of the batch
+    async fn finish_with(
+        ctx: &DalContext,
+        recommended: &[Recommended],
+        statuses: &[FixCompletionStatus],
+    ) -> FixCompletionStatus {
+        let mut batch = FixBatch::new(ctx, AUTHOR)
+            .await
+            .expect("could not create fix batch");
+        for (recommended, status) in recommended.iter().zip(statuses) {
+            let fix_item = add_fix(
+                ctx,
+                &batch,
+                recommended,
+                recommended.create_action_prototype_id,
+            )
+            .await;
+            let mut fix = Fix::get_by_id(ctx, &fix_item.id)
+                .await
+                .expect("could not get fix")
+                .expect("fix not found");
+            fix.stamp_started(ctx).await.expect("could not start fix");
+            fix.stamp_finished(ctx, *status, None, None)
+                .await
+                .expect("could not finish fix");
+        }
+        batch
+            .stamp_started(ctx)
+            .await
+            .expect("could not start batch");
+        batch
+            .stamp_finished(ctx)
+            .await
+            .expect("could not finish batch")
+    }
+
+    use FixCompletionStatus::{Cancelled, Error, Failure, Success, Unstarted};
+    assert_eq!(
+        Cancelled,
+        finish_with(ctx, &recommended, &[Failure, Cancelled, Success]).await
+    );
+    assert_eq!(
+        Cancelled,
+        finish_with(ctx, &recommended, &[Cancelled, Failure, Unstarted]).await
+    );
+    assert_eq!(
+        Error,
+        finish_with(ctx, &recommended, &[Cancelled, Error, Failure]).await
+    );
+    assert_eq!(
+        Failure,
+        finish_with(ctx, &recommended, &[Success, Unstarted, Failure]).await
+    );
+}
@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use dal::{
+    ActionPrototypeId, AttributeValueId, ComponentId, DalContext, FixBatchFailurePolicy, FixError,
+    FixSchedule, FixScheduleItem, StandardModel, User, UserError, UserPk, WorkspacePermission,
+    WorkspaceSignup,
+};
+use dal_test::{test, DalContextHeadRef};
+use pretty_assertions_sorted::assert_eq;
+
+fn item() -> FixScheduleItem {
+    FixScheduleItem {
+        attribute_value_id: AttributeValueId::generate(),
+        component_id: ComponentId::generate(),
+        action_prototype_id: ActionPrototypeId::generate(),
+    }
+}
+
+#[test]
+async fn new_validates_fixes_and_interval(
+    DalContextHeadRef(ctx): DalContextHeadRef<'_>,
+    nw: &WorkspaceSignup,
+) {
+    let result = FixSchedule::new(
+        ctx,
+        nw.user.email(),
+        nw.user.pk(),
+        vec![],
+        FixBatchFailurePolicy::default(),
+        Utc::now(),
+        None,
+    )
+    .await;
+    assert!(matches!(result, Err(FixError::EmptyFixSchedule)));
+
+    let result = FixSchedule::new(
+        ctx,
+        nw.user.email(),
+        nw.user.pk(),
+        vec![item()],
+        FixBatchFailurePolicy::default(),
+        Utc::now(),
+        Some(Duration::from_secs(1)),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(FixError::FixScheduleIntervalTooShort(_))
+    ));
+}
+
+#[test]
+async fn new_rejects_change_sets(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let result = FixSchedule::new(
+        ctx,
+        nw.user.email(),
+        nw.user.pk(),
+        vec![item()],
+        FixBatchFailurePolicy::default(),
+        Utc::now(),
+        None,
+    )
+    .await;
+    assert!(matches!(result, Err(FixError::FixScheduleNotOnHead)));
+}
+
+#[test]
+async fn claim_due_moves_schedules_to_their_next_run(
+    DalContextHeadRef(ctx): DalContextHeadRef<'_>,
+    nw: &WorkspaceSignup,
+) {
+    let hour_ago = Utc::now() - chrono::Duration::hours(1);
+    let once = FixSchedule::new(
+        ctx,
+        nw.user.email(),
+        nw.user.pk(),
+        vec![item()],
+        FixBatchFailurePolicy::StopOnFirstFailure,
+        hour_ago,
+        None,
+    )
+    .await
+    .expect("could not create fix schedule");
+    let recurring = FixSchedule::new(
+        ctx,
+        nw.user.email(),
+        nw.user.pk(),
+        vec![item()],
+        FixBatchFailurePolicy::default(),
+        hour_ago,
+        Some(Duration::from_secs(25 * 60)),
+    )
+    .await
+    .expect("could not create fix schedule");
+    let mut cancelled = FixSchedule::new(
+        ctx,
+        nw.user.email(),
+        nw.user.pk(),
+        vec![item()],
+        FixBatchFailurePolicy::default(),
+        hour_ago,
+        None,
+    )
+    .await
+    .expect("could not create fix schedule");
+    cancelled
+        .cancel(ctx)
+        .await
+        .expect("could not cancel fix schedule");
+
+    let ours = [*once.id(), *recurring.id(), *cancelled.id()];
+    let mut claimed: Vec<FixSchedule> = FixSchedule::claim_due(ctx)
+        .await
+        .expect("could not claim due fix schedules")
+        .into_iter()
+        .filter(|schedule| ours.contains(schedule.id()))
+        .collect();
+    claimed.sort_by_key(|schedule| schedule.interval_secs().is_some());
+    assert_eq!(
+        vec![*once.id(), *recurring.id()],
+        claimed
+            .iter()
+            .map(|schedule| *schedule.id())
+            .collect::<Vec<_>>()
+    );
+
+    // One-off schedules are done, and recurring ones skip the runs they missed
+    assert_eq!(None, *claimed[0].next_run_at());
+    let next_run_at = claimed[1]
+        .next_run_at()
+        .expect("recurring schedule has no next run");
+    assert!(next_run_at > Utc::now());
+    assert!(next_run_at <= Utc::now() + chrono::Duration::minutes(25));
+    assert!(claimed[1].last_run_at().is_some());
+
+    let claimed_again = FixSchedule::claim_due(ctx)
+        .await
+        .expect("could not claim due fix schedules");
+    assert!(!claimed_again
+        .iter()
+        .any(|schedule| ours.contains(schedule.id())));
+}
+
+#[test]
+async fn run_leaves_out_fixes_that_no_longer_exist(
+    DalContextHeadRef(ctx): DalContextHeadRef<'_>,
+    nw: &WorkspaceSignup,
+) {
+    let mut schedule = FixSchedule::new(
+        ctx,
+        nw.user.email(),
+        nw.user.pk(),
+        vec![item()],
+        FixBatchFailurePolicy::default(),
+        Utc::now(),
+        None,
+    )
+    .await
+    .expect("could not create fix schedule");
+
+    let batch_id = schedule.run(ctx).await.expect("could not run fix schedule");
+    assert_eq!(None, batch_id);
+    assert_eq!(None, schedule.last_fix_batch_id());
+}
+
+#[test]
+async fn run_requires_the_user_to_still_be_able_to_apply(
+    DalContextHeadRef(ctx): DalContextHeadRef<'_>,
+    nw: &WorkspaceSignup,
+) {
+    let viewer = User::new(
+        ctx,
+        UserPk::generate(),
+        "onlooker",
+        "onlooker@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    viewer
+        .associate_workspace(ctx, *nw.workspace.pk())
+        .await
+        .expect("cannot associate user with workspace");
+
+    let mut schedule = FixSchedule::new(
+        ctx,
+        viewer.email(),
+        viewer.pk(),
+        vec![item()],
+        FixBatchFailurePolicy::default(),
+        Utc::now(),
+        None,
+    )
+    .await
+    .expect("could not create fix schedule");
+
+    let result = schedule.run(ctx).await;
+    assert!(matches!(
+        result,
+        Err(FixError::User(UserError::PermissionDenied(user_pk, WorkspacePermission::Apply, _)))
+            if user_pk == viewer.pk()
+    ));
+
+    // The scheduler records why the run failed, since a one-off schedule won't run again
+    schedule
+        .set_last_run_error(ctx, Some(result.expect_err("run succeeded").to_string()))
+        .await
+        .expect("could not record run error");
+    let schedule = FixSchedule::get_by_id(ctx, schedule.id())
+        .await
+        .expect("could not get fix schedule")
+        .expect("fix schedule not found");
+    assert!(schedule.last_run_error().is_some());
+    assert_eq!(None, schedule.last_fix_batch_id());
+}
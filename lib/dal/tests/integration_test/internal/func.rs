@@ -4,7 +4,7 @@ use dal::{
         backend::string::FuncBackendStringArgs,
         binding::FuncBinding,
         binding_return_value::FuncBindingReturnValue,
-        execution::FuncExecution,
+        execution::{FuncExecution, FuncExecutionSubject},
     },
     generate_name, ChangeSetPk, DalContext, Func, FuncBackendKind, FuncBackendResponseType, FuncId,
     StandardModel, Visibility,
@@ -16,6 +16,7 @@ use dal_test::{
 use strum::IntoEnumIterator;
 
 mod description;
+mod execution_cache;
 mod reconciliation;
 mod schema_variant_definition;
 
@@ -49,7 +50,7 @@ async fn func_binding_return_value_new(ctx: &DalContext) {
 
     let func_binding = create_func_binding(ctx, args_json, *func.id(), *func.backend_kind()).await;
 
-    let execution = FuncExecution::new(ctx, &func, &func_binding)
+    let execution = FuncExecution::new(ctx, &func, &func_binding, FuncExecutionSubject::default())
         .await
         .expect("cannot create a new func execution");
 
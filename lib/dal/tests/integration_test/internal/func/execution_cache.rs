@@ -0,0 +1,128 @@
+use dal::func::execution::{FuncExecution, FuncExecutionPk};
+use dal::func::execution_cache::FuncExecutionCache;
+use dal::{DalContext, Func, FuncBackendKind, FuncBackendResponseType, FuncBinding, StandardModel};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+async fn create_attribute_func(ctx: &DalContext, code: &str, deterministic: bool) -> Func {
+    let mut func = Func::new(
+        ctx,
+        "truth",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::String,
+    )
+    .await
+    .expect("cannot create func");
+    func.set_handler(ctx, Some("truth"))
+        .await
+        .expect("unable to set func handler");
+    func.set_code_plaintext(ctx, Some(code))
+        .await
+        .expect("unable to set func code plaintext");
+    func.set_deterministic(ctx, deterministic)
+        .await
+        .expect("unable to set func deterministic");
+    func
+}
+
+async fn execute(ctx: &DalContext, func: &Func) -> (Option<serde_json::Value>, FuncExecutionPk) {
+    let (_, return_value) =
+        FuncBinding::create_and_execute(ctx, serde_json::json!({ "name": "lunchbox" }), *func.id())
+            .await
+            .expect("unable to execute func");
+    (
+        return_value.value().cloned(),
+        return_value.func_execution_pk(),
+    )
+}
+
+#[test]
+async fn reuses_deterministic_results(ctx: &DalContext) {
+    let func = create_attribute_func(
+        ctx,
+        "function truth(input) { return `hello ${input.name}`; }",
+        true,
+    )
+    .await;
+
+    let (first_value, first_execution) = execute(ctx, &func).await;
+    let (second_value, second_execution) = execute(ctx, &func).await;
+    assert_eq!(first_value, Some(serde_json::json!("hello lunchbox")));
+    assert_eq!(first_value, second_value);
+
+    // The hit is recorded as an execution of its own, pointing at the one it reused
+    assert_ne!(first_execution, second_execution);
+    let second_execution = FuncExecution::get_by_pk(ctx, &second_execution)
+        .await
+        .expect("unable to get func execution");
+    assert_eq!(
+        second_execution.cached_from_func_execution_pk(),
+        Some(first_execution)
+    );
+    assert_eq!(
+        second_execution.value(),
+        Some(&serde_json::json!("hello lunchbox"))
+    );
+
+    let stats = FuncExecutionCache::stats(ctx)
+        .await
+        .expect("unable to get cache stats");
+    assert_eq!(stats.entries, 1);
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.stores, 1);
+}
+
+#[test]
+async fn skips_non_deterministic_funcs(ctx: &DalContext) {
+    let func = create_attribute_func(
+        ctx,
+        "function truth() { return `${Math.random()}`; }",
+        false,
+    )
+    .await;
+
+    let (_, first_execution) = execute(ctx, &func).await;
+    let (_, second_execution) = execute(ctx, &func).await;
+    assert_ne!(first_execution, second_execution);
+
+    let stats = FuncExecutionCache::stats(ctx)
+        .await
+        .expect("unable to get cache stats");
+    assert_eq!(stats.entries, 0);
+    assert_eq!(stats.hits, 0);
+}
+
+#[test]
+async fn funcs_are_not_deterministic_by_default(ctx: &DalContext) {
+    let func = Func::new(
+        ctx,
+        "lookup",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::String,
+    )
+    .await
+    .expect("cannot create func");
+    assert!(!func.deterministic());
+    assert!(!FuncExecutionCache::is_cacheable(ctx, &func));
+}
+
+#[test]
+async fn invalidates_on_code_change(ctx: &DalContext) {
+    let mut func = create_attribute_func(ctx, "function truth() { return 'before'; }", true).await;
+    let (value, _) = execute(ctx, &func).await;
+    assert_eq!(value, Some(serde_json::json!("before")));
+
+    func.set_code_plaintext(ctx, Some("function truth() { return 'after'; }"))
+        .await
+        .expect("unable to set func code plaintext");
+    let (value, _) = execute(ctx, &func).await;
+    assert_eq!(value, Some(serde_json::json!("after")));
+
+    // The result of the previous code was dropped along with it
+    let stats = FuncExecutionCache::stats(ctx)
+        .await
+        .expect("unable to get cache stats");
+    assert_eq!(stats.entries, 1);
+    assert_eq!(stats.hits, 0);
+    assert_eq!(stats.stores, 1);
+}
@@ -1,15 +1,16 @@
 use dal::{
     func::{
         backend::string::FuncBackendStringArgs,
-        execution::{FuncExecution, FuncExecutionState},
+        binding::FuncBinding,
+        execution::{FuncExecution, FuncExecutionFilter, FuncExecutionState, FuncExecutionSubject},
     },
-    DalContext, StandardModel,
+    ComponentId, DalContext, Func, FuncBackendKind, FuncBackendResponseType, StandardModel,
 };
 use dal_test::{
     test,
-    test_harness::{create_func, create_func_binding},
+    test_harness::{create_func, create_func_binding, generate_fake_name},
 };
-use veritech_client::OutputStream;
+use veritech_client::{FunctionResultFailure, FunctionResultFailureError, OutputStream};
 
 #[test]
 async fn new(ctx: &DalContext) {
@@ -17,7 +18,7 @@ async fn new(ctx: &DalContext) {
     let args = FuncBackendStringArgs::new("slayer".to_string());
     let args_json = serde_json::to_value(args).expect("cannot serialize args to json");
     let func_binding = create_func_binding(ctx, args_json, *func.id(), *func.backend_kind()).await;
-    let execution = FuncExecution::new(ctx, &func, &func_binding)
+    let execution = FuncExecution::new(ctx, &func, &func_binding, FuncExecutionSubject::default())
         .await
         .expect("cannot create a new func execution");
     assert_eq!(execution.state(), FuncExecutionState::Start);
@@ -29,9 +30,10 @@ async fn set_state(ctx: &DalContext) {
     let args = FuncBackendStringArgs::new("slayer".to_string());
     let args_json = serde_json::to_value(args).expect("cannot serialize args to json");
     let func_binding = create_func_binding(ctx, args_json, *func.id(), *func.backend_kind()).await;
-    let mut execution = FuncExecution::new(ctx, &func, &func_binding)
-        .await
-        .expect("cannot create a new func execution");
+    let mut execution =
+        FuncExecution::new(ctx, &func, &func_binding, FuncExecutionSubject::default())
+            .await
+            .expect("cannot create a new func execution");
     assert_eq!(execution.state(), FuncExecutionState::Start);
     execution
         .set_state(ctx, FuncExecutionState::Dispatch)
@@ -46,9 +48,10 @@ async fn set_output_stream(ctx: &DalContext) {
     let args = FuncBackendStringArgs::new("slayer".to_string());
     let args_json = serde_json::to_value(args).expect("cannot serialize args to json");
     let func_binding = create_func_binding(ctx, args_json, *func.id(), *func.backend_kind()).await;
-    let mut execution = FuncExecution::new(ctx, &func, &func_binding)
-        .await
-        .expect("cannot create a new func execution");
+    let mut execution =
+        FuncExecution::new(ctx, &func, &func_binding, FuncExecutionSubject::default())
+            .await
+            .expect("cannot create a new func execution");
 
     execution
         .set_output_stream(
@@ -81,9 +84,10 @@ async fn process_return_value(ctx: &DalContext) {
         .await
         .expect("cannot execute binding");
 
-    let mut execution = FuncExecution::new(ctx, &func, &func_binding)
-        .await
-        .expect("cannot create a new func execution");
+    let mut execution =
+        FuncExecution::new(ctx, &func, &func_binding, FuncExecutionSubject::default())
+            .await
+            .expect("cannot create a new func execution");
 
     execution
         .process_return_value(ctx, &func_binding_return_value)
@@ -100,6 +104,146 @@ async fn process_return_value(ctx: &DalContext) {
     );
 }
 
+#[test]
+async fn set_failure(ctx: &DalContext) {
+    let func = create_func(ctx).await;
+    let args = FuncBackendStringArgs::new("slayer".to_string());
+    let args_json = serde_json::to_value(args).expect("cannot serialize args to json");
+    let func_binding = create_func_binding(ctx, args_json, *func.id(), *func.backend_kind()).await;
+    let mut execution =
+        FuncExecution::new(ctx, &func, &func_binding, FuncExecutionSubject::default())
+            .await
+            .expect("cannot create a new func execution");
+    execution
+        .set_state(ctx, FuncExecutionState::Run)
+        .await
+        .expect("cannot set state");
+    assert!(execution.started_at().is_some());
+    assert!(execution.finished_at().is_none());
+
+    execution
+        .set_failure(
+            ctx,
+            Vec::new(),
+            FunctionResultFailure {
+                execution_id: execution.pk().to_string(),
+                error: FunctionResultFailureError {
+                    kind: "timeout".to_string(),
+                    message: "too slow".to_string(),
+                },
+                timestamp: 1865,
+                redacted: false,
+            },
+        )
+        .await
+        .expect("cannot set failure");
+    assert_eq!(execution.state(), FuncExecutionState::Failure);
+    assert!(execution.finished_at().is_some());
+    assert_eq!(
+        execution
+            .function_failure()
+            .as_ref()
+            .map(|failure| failure.error.kind.as_str()),
+        Some("timeout")
+    );
+}
+
+#[test]
+async fn failure_outlives_rolled_back_transaction(ctx: &DalContext) {
+    let func = Func::new(
+        ctx,
+        generate_fake_name(),
+        FuncBackendKind::String,
+        FuncBackendResponseType::String,
+    )
+    .await
+    .expect("cannot create func");
+    let func_id = *func.id();
+
+    // Not string args, so the execution fails
+    let result =
+        FuncBinding::create_and_execute(ctx, serde_json::json!({ "value": 1865 }), func_id).await;
+    assert!(result.is_err());
+
+    // The caller gives up on everything it did because of the failure...
+    ctx.rollback().await.expect("cannot roll back");
+    assert!(Func::get_by_id(ctx, &func_id)
+        .await
+        .expect("cannot get func")
+        .is_none());
+
+    // ...but the failed execution is still there to explain why
+    let executions = FuncExecution::list(
+        ctx,
+        &FuncExecutionFilter {
+            func_id: Some(func_id),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list func executions");
+    assert_eq!(executions.len(), 1);
+    let execution = executions.first().expect("no func execution");
+    assert_eq!(execution.state(), FuncExecutionState::Failure);
+    assert!(execution.function_failure().is_some());
+    assert_eq!(
+        FuncExecution::find_by_pk(ctx, execution.pk())
+            .await
+            .expect("cannot find func execution")
+            .as_ref(),
+        Some(execution)
+    );
+}
+
+#[test]
+async fn list_and_find(ctx: &DalContext) {
+    let func = create_func(ctx).await;
+    let args = FuncBackendStringArgs::new("slayer".to_string());
+    let args_json = serde_json::to_value(args).expect("cannot serialize args to json");
+    let func_binding = create_func_binding(ctx, args_json, *func.id(), *func.backend_kind()).await;
+
+    let component_id = ComponentId::generate();
+    let for_component = FuncExecution::new(
+        ctx,
+        &func,
+        &func_binding,
+        FuncExecutionSubject::for_component(component_id),
+    )
+    .await
+    .expect("cannot create a new func execution");
+    let _unrelated = FuncExecution::new(ctx, &func, &func_binding, FuncExecutionSubject::default())
+        .await
+        .expect("cannot create a new func execution");
+    assert!(for_component.args_hash().is_some());
+
+    let executions = FuncExecution::list(
+        ctx,
+        &FuncExecutionFilter {
+            component_id: Some(component_id),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list func executions");
+    assert_eq!(executions, vec![for_component.clone()]);
+
+    let executions = FuncExecution::list(
+        ctx,
+        &FuncExecutionFilter {
+            func_id: Some(*func.id()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list func executions");
+    assert_eq!(executions.len(), 2);
+
+    let found = FuncExecution::find_by_pk(ctx, for_component.pk())
+        .await
+        .expect("cannot find func execution");
+    assert_eq!(found, Some(for_component));
+}
+
 // FIXME(nick,fletcher): re-add test once upsert is added.
 // #[test]
 // async fn execution_upserts_return_value() {
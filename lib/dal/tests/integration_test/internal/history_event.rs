@@ -1,4 +1,5 @@
-use dal::{DalContext, HistoryActor, HistoryEvent};
+use dal::history_event::{HistoryEventPk, MAX_HISTORY_EVENT_LIST_LIMIT};
+use dal::{DalContext, HistoryActor, HistoryEvent, HistoryEventFilter};
 use dal_test::test;
 
 #[test]
@@ -17,3 +18,99 @@ async fn new(ctx: &DalContext) {
     assert_eq!(&history_event.data, &serde_json::json!({}));
     assert_eq!(&history_event.tenancy, ctx.tenancy());
 }
+
+#[test]
+async fn list(ctx: &DalContext) {
+    for id in ["sg-1", "sg-2", "sg-1"] {
+        HistoryEvent::new(
+            ctx,
+            "security_group.updated",
+            "security group updated",
+            &serde_json::json!({ "id": id }),
+        )
+        .await
+        .expect("cannot create a new history event");
+    }
+    HistoryEvent::new(ctx, "unrelated.event", "unrelated", &serde_json::json!({}))
+        .await
+        .expect("cannot create a new history event");
+
+    let events = HistoryEvent::list(
+        ctx,
+        &HistoryEventFilter {
+            label_prefix: Some("security_group.".to_owned()),
+            object_id: Some("sg-1".to_owned()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("could not list history events");
+    assert_eq!(2, events.len());
+    assert!(events
+        .iter()
+        .all(|event| event.label == "security_group.updated"
+            && event.change_set_pk == Some(ctx.visibility().change_set_pk)));
+    assert!(events[0].timestamp.created_at >= events[1].timestamp.created_at);
+
+    let page = HistoryEvent::list(
+        ctx,
+        &HistoryEventFilter {
+            label_prefix: Some("security_group.".to_owned()),
+            limit: Some(1),
+            offset: Some(1),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("could not list history events");
+    assert_eq!(1, page.len());
+
+    // Walking the pages with cursors visits every event once, in order
+    let all: Vec<HistoryEventPk> = HistoryEvent::list(
+        ctx,
+        &HistoryEventFilter {
+            label_prefix: Some("security_group.".to_owned()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("could not list history events")
+    .iter()
+    .map(|event| event.pk)
+    .collect();
+    assert_eq!(3, all.len());
+    let mut filter = HistoryEventFilter {
+        label_prefix: Some("security_group.".to_owned()),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let mut walked = Vec::new();
+    loop {
+        let page = HistoryEvent::list(ctx, &filter)
+            .await
+            .expect("could not list history events");
+        match page.last() {
+            Some(last) => filter.before = Some(last.cursor()),
+            None => break,
+        }
+        walked.extend(page.iter().map(|event| event.pk));
+    }
+    assert_eq!(all, walked);
+
+    assert_eq!(
+        MAX_HISTORY_EVENT_LIST_LIMIT,
+        HistoryEventFilter {
+            limit: Some(MAX_HISTORY_EVENT_LIST_LIMIT * 10),
+            ..Default::default()
+        }
+        .effective_limit()
+    );
+
+    let csv = HistoryEvent::to_csv(&events).expect("could not render csv");
+    let mut lines = csv.lines();
+    assert_eq!(
+        Some("pk,created_at,actor,label,message,change_set_pk,data"),
+        lines.next()
+    );
+    assert!(lines.all(|line| line.ends_with(r#""{""id"":""sg-1""}""#)));
+}
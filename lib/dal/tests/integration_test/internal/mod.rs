@@ -4,6 +4,8 @@ mod change_set;
 mod component;
 mod diagram;
 mod edge;
+mod fix_batch;
+mod fix_schedule;
 mod func;
 mod func_execution;
 mod graph;
@@ -16,6 +18,7 @@ mod prop;
 mod prop_tree;
 mod property_editor;
 mod provider;
+mod resource_scheduler;
 mod schema;
 mod secret;
 mod socket;
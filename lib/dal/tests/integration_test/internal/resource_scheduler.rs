@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use dal::tasks::{ResourceScheduler, ResourceSchedulerConfig};
+use dal::{
+    func::backend::js_action::ActionRunResult, generate_name, ChangeSet, Component, ComponentId,
+    DalContext, Schema, StandardModel, Visibility,
+};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use veritech_client::ResourceStatus;
+
+/// Creates starfield components with the given names in a change set and applies it, leaving the
+/// ctx on head.
+async fn create_applied_components(ctx: &mut DalContext, names: &[&str]) -> Vec<ComponentId> {
+    ctx.update_to_head();
+    let schema_variant_id = *Schema::find_by_name(ctx, "starfield")
+        .await
+        .expect("could not find schema")
+        .default_schema_variant_id()
+        .expect("could not get default variant id");
+
+    let change_set = ChangeSet::new(ctx, generate_name(), None)
+        .await
+        .expect("could not create new change set");
+    ctx.update_visibility(Visibility::new(change_set.pk, None));
+    let mut component_ids = Vec::new();
+    for name in names {
+        let (component, _) = Component::new(ctx, name, schema_variant_id)
+            .await
+            .expect("cannot create component");
+        component_ids.push(*component.id());
+    }
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let mut change_set = ChangeSet::get_by_pk(ctx, &change_set.pk)
+        .await
+        .expect("could not fetch change set by pk")
+        .expect("no change set found for pk");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+    ctx.update_visibility(Visibility::new_head(false));
+
+    component_ids
+}
+
+async fn get_component(ctx: &DalContext, component_id: ComponentId) -> Component {
+    Component::get_by_id(ctx, &component_id)
+        .await
+        .expect("could not fetch component")
+        .expect("component not found")
+}
+
+async fn set_resource(ctx: &DalContext, component_id: ComponentId, last_synced: Option<String>) {
+    get_component(ctx, component_id)
+        .await
+        .set_resource(
+            ctx,
+            ActionRunResult {
+                status: ResourceStatus::Ok,
+                payload: Some(serde_json::json![{ "poop": true }]),
+                logs: Default::default(),
+                message: Default::default(),
+                last_synced,
+            },
+            true,
+        )
+        .await
+        .expect("could not set resource");
+}
+
+/// Recommendation: run this test with the following environment variable:
+/// ```shell
+/// SI_TEST_BUILTIN_SCHEMAS=test
+/// ```
+#[test]
+async fn schedules_stale_resources_once(mut octx: DalContext) {
+    let ctx = &mut octx;
+    let component_ids = create_applied_components(ctx, &["stale", "fresh", "uncreated"]).await;
+    let (stale_id, fresh_id) = (component_ids[0], component_ids[1]);
+
+    set_resource(ctx, stale_id, None).await;
+    set_resource(ctx, fresh_id, Some(Utc::now().to_rfc3339())).await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let scheduler = ResourceScheduler::with_config(
+        ctx.services_context(),
+        ResourceSchedulerConfig {
+            jitter: Duration::ZERO,
+            ..Default::default()
+        },
+    );
+    let due: Vec<ComponentId> = scheduler
+        .due_components(ctx)
+        .await
+        .expect("could not list due components")
+        .iter()
+        .map(|component| *component.id())
+        .collect();
+    assert_eq!(vec![stale_id], due);
+
+    // Seen before the refresh is enqueued, as another scheduler would have
+    let mut seen_by_another_scheduler = get_component(ctx, stale_id).await;
+    assert_eq!(
+        None,
+        *seen_by_another_scheduler.resource_refresh_enqueued_at()
+    );
+
+    let workspace_pk = ctx.tenancy().workspace_pk().expect("ctx has no workspace");
+    let enqueued = scheduler
+        .run_for_workspace(workspace_pk)
+        .await
+        .expect("could not schedule workspace");
+    assert_eq!(1, enqueued);
+
+    let stale = get_component(ctx, stale_id).await;
+    assert!(stale.resource_refresh_enqueued_at().is_some());
+    assert!(scheduler
+        .due_components(ctx)
+        .await
+        .expect("could not list due components")
+        .is_empty());
+    assert!(!seen_by_another_scheduler
+        .claim_resource_refresh(ctx)
+        .await
+        .expect("could not claim resource refresh"));
+}
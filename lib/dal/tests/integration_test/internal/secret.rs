@@ -1,10 +1,23 @@
+use dal::func::binding::FuncBinding;
+use dal::func::binding_return_value::FuncBindingReturnValue;
+use dal::func::execution::{FuncExecution, FuncExecutionSubject};
+use dal::property_editor::schema::WidgetKind;
+use dal::secret::backend::{
+    FileSecretBackend, VaultSecretBackend, ENV_SECRET_BACKEND_ENABLED_ENV_VAR,
+    ENV_SECRET_BACKEND_PREFIX,
+};
 use dal::{
-    DalContext, EncryptedSecret, Secret, SecretAlgorithm, SecretKind, SecretObjectType,
-    SecretVersion, StandardModel, WorkspaceSignup,
+    AttributeContext, AttributeValue, Component, DalContext, EncryptedSecret, Func,
+    FuncBackendKind, FuncBackendResponseType, KeyPair, Prop, PropKind, Secret, SecretAlgorithm,
+    SecretBackend, SecretBackendError, SecretBackendKind, SecretBackends, SecretDefinition,
+    SecretDefinitionField, SecretError, SecretKind, SecretObjectType, SecretVersion, StandardModel,
+    WorkspacePk, WorkspaceSignup,
 };
 use dal_test::{
     test,
-    test_harness::{create_secret, generate_fake_name},
+    test_harness::{
+        create_schema, create_schema_variant_with_root, create_secret, generate_fake_name,
+    },
 };
 
 #[test]
@@ -15,7 +28,7 @@ async fn new_encrypted_secret(ctx: &DalContext, nw: &WorkspaceSignup) {
         ctx,
         &name,
         SecretObjectType::Credential,
-        SecretKind::DockerHub,
+        SecretKind::DOCKER_HUB,
         "im-crypted-bytes-maybe".as_bytes(),
         nw.key_pair.pk(),
         SecretVersion::V1,
@@ -26,7 +39,7 @@ async fn new_encrypted_secret(ctx: &DalContext, nw: &WorkspaceSignup) {
 
     assert_eq!(secret.name(), name);
     assert_eq!(secret.object_type(), &SecretObjectType::Credential);
-    assert_eq!(secret.kind(), &SecretKind::DockerHub);
+    assert_eq!(secret.kind(), &SecretKind::DOCKER_HUB);
 
     let key_pair = secret
         .key_pair(ctx)
@@ -90,7 +103,7 @@ async fn encrypt_decrypt_round_trip(ctx: &DalContext, nw: &WorkspaceSignup) {
         ctx,
         &name,
         SecretObjectType::Credential,
-        SecretKind::DockerHub,
+        SecretKind::DOCKER_HUB,
         &crypted,
         nw.key_pair.pk(),
         Default::default(),
@@ -117,3 +130,429 @@ async fn encrypt_decrypt_round_trip(ctx: &DalContext, nw: &WorkspaceSignup) {
         serde_json::to_value(&decrypted).expect("failed to serial decrypted into Value");
     assert_eq!(decrypted_value["message"], message);
 }
+
+#[test]
+async fn env_backend_round_trip(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let workspace_pk = *nw.workspace.pk();
+    let message = serde_json::json!({"accessKeyId": "ci", "secretAccessKey": "from-the-env"});
+    let mut vars = vec![(
+        format!("{ENV_SECRET_BACKEND_PREFIX}{workspace_pk}_DAL_TEST_ENV_BACKEND"),
+        message.to_string(),
+    )];
+
+    // The env backend is only available when explicitly enabled
+    let disabled = SecretBackends::from_vars(vars.clone());
+    assert!(matches!(
+        disabled
+            .resolve(workspace_pk, SecretBackendKind::Env, "DAL_TEST_ENV_BACKEND")
+            .await,
+        Err(SecretBackendError::NotConfigured(SecretBackendKind::Env))
+    ));
+    vars.push((
+        ENV_SECRET_BACKEND_ENABLED_ENV_VAR.to_owned(),
+        "true".to_owned(),
+    ));
+    let backends = SecretBackends::from_vars(vars);
+
+    let secret = EncryptedSecret::new_external(
+        ctx,
+        generate_fake_name(),
+        SecretObjectType::Credential,
+        SecretKind::AWS_ACCESS_KEY,
+        SecretBackendKind::Env,
+        "DAL_TEST_ENV_BACKEND",
+    )
+    .await
+    .expect("failed to create external secret");
+    assert_eq!(SecretBackendKind::Env, *secret.backend());
+    let reference = secret
+        .backend_reference()
+        .expect("external secret has no reference");
+    assert_eq!("DAL_TEST_ENV_BACKEND", reference);
+
+    assert_eq!(
+        message,
+        backends
+            .resolve(workspace_pk, SecretBackendKind::Env, reference)
+            .await
+            .expect("failed to resolve external secret")
+    );
+
+    // Other workspaces cannot read it
+    assert!(matches!(
+        backends
+            .resolve(WorkspacePk::generate(), SecretBackendKind::Env, reference)
+            .await,
+        Err(SecretBackendError::EnvVarNotFound(_))
+    ));
+}
+
+#[test]
+async fn file_backend_stays_beneath_workspace_root() {
+    let workspace_pk = WorkspacePk::generate();
+    let root = tempfile::tempdir().expect("failed to create temp dir");
+    let message = serde_json::json!({"username": "local", "password": "dev"});
+    std::fs::create_dir(root.path().join(workspace_pk.to_string()))
+        .expect("failed to create workspace dir");
+    std::fs::write(
+        root.path()
+            .join(workspace_pk.to_string())
+            .join("docker.json"),
+        message.to_string(),
+    )
+    .expect("failed to write secret file");
+    std::fs::write(root.path().join("shared.json"), message.to_string())
+        .expect("failed to write secret file");
+
+    let backend = FileSecretBackend::new(root.path());
+    assert_eq!(
+        message,
+        backend
+            .resolve(workspace_pk, "docker.json")
+            .await
+            .expect("failed to resolve secret file")
+    );
+    assert!(matches!(
+        backend.resolve(workspace_pk, "../shared.json").await,
+        Err(SecretBackendError::InvalidReference(_))
+    ));
+    assert!(matches!(
+        backend
+            .resolve(WorkspacePk::generate(), "docker.json")
+            .await,
+        Err(SecretBackendError::Io(_))
+    ));
+    assert!(matches!(
+        backend.resolve(workspace_pk, "../../../etc/passwd").await,
+        Err(SecretBackendError::InvalidReference(_)) | Err(SecretBackendError::Io(_))
+    ));
+    assert!(matches!(
+        backend.resolve(workspace_pk, "/etc/passwd").await,
+        Err(SecretBackendError::InvalidReference(_))
+    ));
+}
+
+#[test]
+async fn vault_backend_refuses_references_outside_workspace_path() {
+    // Nothing listens here: references must be refused before a request is made.
+    let backend = VaultSecretBackend::new("http://127.0.0.1:1", "token", "secret");
+    for reference in [
+        "",
+        "..",
+        "../other-workspace/docker",
+        "docker/../../other-workspace/docker",
+        "./docker",
+        "docker//credentials",
+        "%2e%2e/other-workspace/docker",
+        "%2E%2E%2Fother-workspace",
+        "docker?version=1",
+        "docker#fragment",
+        "docker credentials",
+        "docker\\..\\credentials",
+    ] {
+        assert!(
+            matches!(
+                backend.resolve(WorkspacePk::generate(), reference).await,
+                Err(SecretBackendError::InvalidReference(_))
+            ),
+            "reference was not refused: {reference}"
+        );
+    }
+}
+
+#[test]
+async fn update_message_and_rotate_key_pair(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let seal = |message: &serde_json::Value| {
+        sodiumoxide::crypto::sealedbox::seal(
+            &serde_json::to_vec(message).expect("failed to serialize message"),
+            nw.key_pair.public_key(),
+        )
+    };
+    let decrypt = |secret: &Secret| {
+        let id = *secret.id();
+        async move {
+            let decrypted = EncryptedSecret::get_by_id(ctx, &id)
+                .await
+                .expect("failed to fetch encrypted secret")
+                .expect("failed to find encrypted secret for tenancy and/or visibility")
+                .decrypt(ctx)
+                .await
+                .expect("failed to decrypt encrypted secret");
+            serde_json::to_value(&decrypted).expect("failed to serial decrypted into Value")
+                ["message"]
+                .clone()
+        }
+    };
+
+    let mut secret = EncryptedSecret::new(
+        ctx,
+        generate_fake_name(),
+        SecretObjectType::Credential,
+        SecretKind::AWS_ACCESS_KEY,
+        &seal(&serde_json::json!({"accessKeyId": "ci", "secretAccessKey": "old"})),
+        nw.key_pair.pk(),
+        Default::default(),
+        Default::default(),
+    )
+    .await
+    .expect("failed to create encrypted secret");
+    assert_eq!(1, *secret.message_version());
+
+    let rotated_message = serde_json::json!({"accessKeyId": "ci", "secretAccessKey": "rotated"});
+    secret
+        .update_message(
+            ctx,
+            &seal(&rotated_message),
+            nw.key_pair.pk(),
+            Default::default(),
+            Default::default(),
+        )
+        .await
+        .expect("failed to update secret message");
+    assert_eq!(2, *secret.message_version());
+    assert_eq!(rotated_message, decrypt(&secret).await);
+
+    let key_pair = KeyPair::rotate(ctx)
+        .await
+        .expect("failed to rotate key pair");
+    assert_ne!(nw.key_pair.pk(), key_pair.pk());
+    assert_eq!(
+        key_pair.pk(),
+        KeyPair::get_current(ctx)
+            .await
+            .expect("failed to get current key pair")
+            .pk()
+    );
+
+    let secret = Secret::get_by_id(ctx, secret.id())
+        .await
+        .expect("failed to get secret")
+        .expect("failed to find secret");
+    assert_eq!(
+        key_pair.pk(),
+        secret
+            .key_pair(ctx)
+            .await
+            .expect("failed to fetch key pair")
+            .pk()
+    );
+    assert_eq!(rotated_message, decrypt(&secret).await);
+}
+
+#[test]
+async fn list_consumers_and_delete(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let secret_prop = Prop::new(
+        ctx,
+        "credential",
+        PropKind::String,
+        Some((WidgetKind::SecretSelect, None)),
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    let (component, _) = Component::new(ctx, "consumer", *schema_variant.id())
+        .await
+        .expect("unable to create component");
+    let mut secret = create_secret(ctx, nw.key_pair.pk()).await;
+
+    let consumers = secret
+        .list_consumers(ctx)
+        .await
+        .expect("failed to list consumers");
+    assert!(!consumers.in_use());
+    assert!(consumers.components.is_empty());
+
+    let mut base_attribute_context = AttributeContext::builder();
+    base_attribute_context.set_component_id(*component.id());
+    let domain_value = AttributeValue::find_for_context(
+        ctx,
+        base_attribute_context
+            .clone()
+            .set_prop_id(root.domain_prop_id)
+            .to_context()
+            .expect("cannot create domain AttributeContext")
+            .into(),
+    )
+    .await
+    .expect("could not fetch domain AttributeValue")
+    .expect("could not find domain AttributeValue");
+    let secret_context = base_attribute_context
+        .clone()
+        .set_prop_id(*secret_prop.id())
+        .to_context()
+        .expect("cannot create secret AttributeContext");
+    let secret_value = AttributeValue::find_for_context(ctx, secret_context.into())
+        .await
+        .expect("could not fetch secret AttributeValue")
+        .expect("could not find secret AttributeValue");
+    AttributeValue::update_for_context(
+        ctx,
+        *secret_value.id(),
+        Some(*domain_value.id()),
+        secret_context,
+        Some(serde_json::json!(secret.id().to_string())),
+        None,
+    )
+    .await
+    .expect("could not update secret prop value");
+
+    let consumers = secret
+        .list_consumers(ctx)
+        .await
+        .expect("failed to list consumers");
+    assert!(consumers.in_use());
+    assert_eq!(1, consumers.components.len());
+    assert_eq!(*component.id(), consumers.components[0].component_id);
+    assert_eq!(*secret_prop.id(), consumers.components[0].prop_id);
+    assert!(consumers.funcs.is_empty());
+
+    // An action that ran with the secret nested in its args is a consumer too, while one that ran
+    // with an id merely containing the secret id is not.
+    let func = Func::new(
+        ctx,
+        generate_fake_name(),
+        FuncBackendKind::JsAction,
+        FuncBackendResponseType::Action,
+    )
+    .await
+    .expect("could not create func");
+    for args in [
+        serde_json::json!({ "properties": { "domain": { "credential": secret.id().to_string() } } }),
+        serde_json::json!({ "credential": format!("{}-not-it", secret.id()) }),
+    ] {
+        let func_binding = FuncBinding::new(ctx, args, *func.id(), *func.backend_kind())
+            .await
+            .expect("could not create func binding");
+        let execution =
+            FuncExecution::new(ctx, &func, &func_binding, FuncExecutionSubject::default())
+                .await
+                .expect("could not create func execution");
+        FuncBindingReturnValue::new(
+            ctx,
+            None,
+            None,
+            *func.id(),
+            *func_binding.id(),
+            execution.pk(),
+        )
+        .await
+        .expect("could not create func binding return value");
+    }
+
+    let consumers = secret
+        .list_consumers(ctx)
+        .await
+        .expect("failed to list consumers");
+    assert_eq!(1, consumers.funcs.len());
+    assert_eq!(*func.id(), consumers.funcs[0].func_id);
+    assert_eq!(
+        FuncBackendResponseType::Action,
+        consumers.funcs[0].backend_response_type
+    );
+
+    let result = secret.delete(ctx, false).await;
+    assert!(matches!(result, Err(SecretError::InUse(id, 1)) if id == *secret.id()));
+    assert!(Secret::get_by_id(ctx, secret.id())
+        .await
+        .expect("failed to get secret")
+        .is_some());
+
+    secret
+        .delete(ctx, true)
+        .await
+        .expect("failed to force delete secret");
+    assert!(Secret::get_by_id(ctx, secret.id())
+        .await
+        .expect("failed to get secret")
+        .is_none());
+}
+
+#[test]
+async fn secret_definition_validates_message(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let seal = |message: &serde_json::Value| {
+        sodiumoxide::crypto::sealedbox::seal(
+            &serde_json::to_vec(message).expect("failed to serialize message"),
+            nw.key_pair.public_key(),
+        )
+    };
+    let kind: SecretKind = "githubToken".parse().expect("failed to parse secret kind");
+
+    let definition = SecretDefinition::new(
+        ctx,
+        &kind,
+        Some("GitHub Token".to_owned()),
+        None,
+        vec![SecretDefinitionField {
+            name: "token".to_owned(),
+            display_name: None,
+            sensitive: true,
+            required: true,
+            pattern: Some("^ghp_[A-Za-z0-9]+$".to_owned()),
+        }],
+    )
+    .await
+    .expect("failed to create secret definition");
+    assert_eq!(kind, definition.kind());
+    assert_eq!(
+        definition,
+        SecretDefinition::get_for_kind(ctx, &kind)
+            .await
+            .expect("failed to find secret definition")
+    );
+
+    for (message, valid) in [
+        (serde_json::json!({"token": "ghp_abc123"}), true),
+        (serde_json::json!({"token": "not-a-token"}), false),
+        (serde_json::json!({"user": "octocat"}), false),
+    ] {
+        let secret = EncryptedSecret::new(
+            ctx,
+            generate_fake_name(),
+            SecretObjectType::Credential,
+            kind.clone(),
+            &seal(&message),
+            nw.key_pair.pk(),
+            Default::default(),
+            Default::default(),
+        )
+        .await
+        .expect("failed to create encrypted secret");
+        let result = EncryptedSecret::get_by_id(ctx, secret.id())
+            .await
+            .expect("failed to fetch encrypted secret")
+            .expect("failed to find encrypted secret for tenancy and/or visibility")
+            .validate_message(ctx)
+            .await;
+        if valid {
+            result.expect("valid message failed validation");
+        } else {
+            assert!(matches!(result, Err(SecretError::InvalidMessage(..))));
+        }
+    }
+
+    let unknown: SecretKind = "unknownKind".parse().expect("failed to parse secret kind");
+    let result = EncryptedSecret::new(
+        ctx,
+        generate_fake_name(),
+        SecretObjectType::Credential,
+        unknown,
+        &seal(&serde_json::json!({})),
+        nw.key_pair.pk(),
+        Default::default(),
+        Default::default(),
+    )
+    .await;
+    assert!(matches!(result, Err(SecretError::DefinitionNotFound(_))));
+}
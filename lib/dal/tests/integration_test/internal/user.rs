@@ -1,4 +1,7 @@
-use dal::{DalContext, User, UserPk, WorkspaceSignup};
+use dal::{
+    DalContext, HistoryActor, User, UserError, UserPk, WorkspaceError, WorkspacePermission,
+    WorkspaceRole, WorkspaceSignup,
+};
 use dal_test::test;
 
 #[test]
@@ -16,26 +19,128 @@ async fn new(ctx: &DalContext) {
 
 #[test]
 async fn authorize(ctx: &DalContext, nw: &WorkspaceSignup) {
-    let worked = User::authorize(ctx, &nw.user.pk())
+    let worked = User::authorize(ctx, &nw.user.pk(), WorkspacePermission::ManageMembers)
         .await
-        .expect("admin group user should be authorized");
-    assert!(worked, "authorized admin group user returns true");
+        .expect("could not authorize workspace owner");
+    assert!(worked, "workspace owner is authorized");
 
-    // TODO(theo,paulo): re-enable that when capabilities are back
-    /*
-    let user_no_group = User::new(
+    let user_no_workspace = User::new(
         ctx,
         UserPk::generate(),
         "funky",
         "bobotclown@systeminit.com",
+        None::<String>,
     )
     .await
     .expect("cannot create user");
 
-    let f = User::authorize(ctx, &user_no_group.pk()).await;
+    let worked = User::authorize(ctx, &user_no_workspace.pk(), WorkspacePermission::Read)
+        .await
+        .expect("could not authorize user");
+    assert!(!worked, "user not in the workspace is not authorized");
+}
+
+#[test]
+async fn workspace_roles(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let workspace_pk = *nw.workspace.pk();
+    let viewer = User::new(
+        ctx,
+        UserPk::generate(),
+        "contractor",
+        "contractor@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    viewer
+        .associate_workspace(ctx, workspace_pk)
+        .await
+        .expect("cannot associate user with workspace");
+
+    // Users associated without an invitation can only read
+    assert_eq!(
+        User::role_for_workspace(ctx, viewer.pk(), workspace_pk)
+            .await
+            .expect("cannot get role"),
+        Some(WorkspaceRole::Viewer)
+    );
+    assert!(
+        User::authorize(ctx, &viewer.pk(), WorkspacePermission::Read)
+            .await
+            .expect("could not authorize user")
+    );
+    assert!(
+        !User::authorize(ctx, &viewer.pk(), WorkspacePermission::Edit)
+            .await
+            .expect("could not authorize user")
+    );
+
+    User::set_role_for_workspace(ctx, viewer.pk(), workspace_pk, WorkspaceRole::Editor)
+        .await
+        .expect("cannot set role");
     assert!(
-        f.is_err(),
-        "user that is not in the admin group should fail"
+        User::authorize(ctx, &viewer.pk(), WorkspacePermission::Edit)
+            .await
+            .expect("could not authorize user")
+    );
+
+    assert!(WorkspaceRole::Applier.grants(WorkspacePermission::Apply));
+    assert!(!WorkspaceRole::Editor.grants(WorkspacePermission::Apply));
+    assert!(!WorkspaceRole::Applier.grants(WorkspacePermission::ManageSecrets));
+}
+
+#[test]
+async fn require_permission_for_actor(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let workspace_pk = *nw.workspace.pk();
+    assert_eq!(
+        User::role_for_workspace(ctx, nw.user.pk(), workspace_pk)
+            .await
+            .expect("cannot get role"),
+        Some(WorkspaceRole::Owner)
     );
-    */
+
+    let viewer = User::new(
+        ctx,
+        UserPk::generate(),
+        "onlooker",
+        "onlooker@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    viewer
+        .associate_workspace(ctx, workspace_pk)
+        .await
+        .expect("cannot associate user with workspace");
+    let viewer_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(viewer.pk()));
+    let owner_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+
+    User::require_permission_for_actor(&viewer_ctx, WorkspacePermission::Read)
+        .await
+        .expect("viewer cannot read");
+    assert!(matches!(
+        User::require_permission_for_actor(&viewer_ctx, WorkspacePermission::Edit).await,
+        Err(UserError::PermissionDenied(user_pk, WorkspacePermission::Edit, _))
+            if user_pk == viewer.pk()
+    ));
+    User::require_permission_for_actor(&owner_ctx, WorkspacePermission::ManageMembers)
+        .await
+        .expect("owner cannot manage members");
+    User::require_permission_for_actor(ctx, WorkspacePermission::ManageMembers)
+        .await
+        .expect("system cannot manage members");
+
+    // The role is enforced by the operations themselves, not just by sdf
+    let mut workspace = nw.workspace.clone();
+    assert!(matches!(
+        workspace
+            .set_required_change_set_approvals(&viewer_ctx, 1)
+            .await,
+        Err(WorkspaceError::User(UserError::PermissionDenied(..)))
+    ));
+    assert!(matches!(
+        User::set_role_for_workspace(&viewer_ctx, viewer.pk(), workspace_pk, WorkspaceRole::Owner)
+            .await,
+        Err(UserError::PermissionDenied(..))
+    ));
 }
@@ -1,4 +1,7 @@
-use dal::{DalContext, Workspace, WorkspacePk};
+use dal::{
+    DalContext, User, UserPk, Workspace, WorkspaceError, WorkspacePk, WorkspaceRole,
+    WorkspaceSignup,
+};
 use dal_test::test;
 
 #[test]
@@ -7,3 +10,131 @@ async fn new(ctx: &mut DalContext) {
         .await
         .expect("cannot create workspace");
 }
+
+#[test]
+async fn invitations_and_members(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let workspace = &nw.workspace;
+
+    let invitation = workspace
+        .invite_member(ctx, "Bruce@systeminit.com", WorkspaceRole::Editor)
+        .await
+        .expect("could not invite member");
+    assert_eq!(Some(nw.user.pk()), invitation.invited_by_user_pk);
+    assert_eq!(
+        vec![invitation.pk],
+        workspace
+            .list_invitations(ctx)
+            .await
+            .expect("could not list invitations")
+            .into_iter()
+            .map(|invitation| invitation.pk)
+            .collect::<Vec<_>>()
+    );
+
+    let bruce = User::new(
+        ctx,
+        UserPk::generate(),
+        "bruce",
+        "bruce@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    let accepted = workspace
+        .accept_invitation(ctx, &bruce)
+        .await
+        .expect("could not accept invitation")
+        .expect("invitation not found for email");
+    assert!(accepted.accepted_at.is_some());
+    assert!(workspace
+        .list_invitations(ctx)
+        .await
+        .expect("could not list invitations")
+        .is_empty());
+
+    let members = workspace
+        .list_members(ctx)
+        .await
+        .expect("could not list members");
+    let bruce_member = members
+        .iter()
+        .find(|member| member.user_pk == bruce.pk())
+        .expect("invited user is a member");
+    assert_eq!(WorkspaceRole::Editor, bruce_member.role);
+
+    // Members cannot be invited again.
+    let result = workspace
+        .invite_member(ctx, "BRUCE@systeminit.com", WorkspaceRole::Viewer)
+        .await;
+    assert!(matches!(result, Err(WorkspaceError::AlreadyMember(..))));
+
+    // The last owner cannot leave.
+    let result = workspace.remove_member(ctx, nw.user.pk()).await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceError::CannotRemoveLastOwner(..))
+    ));
+
+    workspace
+        .transfer_ownership(ctx, nw.user.pk(), bruce.pk())
+        .await
+        .expect("could not transfer ownership");
+    assert_eq!(
+        Some(WorkspaceRole::Owner),
+        User::role_for_workspace(ctx, bruce.pk(), *workspace.pk())
+            .await
+            .expect("could not get role")
+    );
+    assert_eq!(
+        Some(WorkspaceRole::Applier),
+        User::role_for_workspace(ctx, nw.user.pk(), *workspace.pk())
+            .await
+            .expect("could not get role")
+    );
+
+    workspace
+        .remove_member(ctx, nw.user.pk())
+        .await
+        .expect("could not remove member");
+    assert!(
+        !User::is_member_of_workspace(ctx, nw.user.pk(), *workspace.pk())
+            .await
+            .expect("could not check membership")
+    );
+}
+
+#[test]
+async fn accepting_invitation_keeps_member_role(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let workspace = &nw.workspace;
+
+    workspace
+        .invite_member(ctx, "dave@systeminit.com", WorkspaceRole::Viewer)
+        .await
+        .expect("could not invite member");
+
+    // Dave joins some other way before accepting.
+    let dave = User::new(
+        ctx,
+        UserPk::generate(),
+        "dave",
+        "dave@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    dave.associate_workspace_with_role(ctx, *workspace.pk(), WorkspaceRole::Applier)
+        .await
+        .expect("could not associate workspace");
+
+    workspace
+        .accept_invitation(ctx, &dave)
+        .await
+        .expect("could not accept invitation")
+        .expect("invitation not found for email");
+    assert_eq!(
+        Some(WorkspaceRole::Applier),
+        User::role_for_workspace(ctx, dave.pk(), *workspace.pk())
+            .await
+            .expect("could not get role")
+    );
+}
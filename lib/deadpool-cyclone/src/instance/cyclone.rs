@@ -8,6 +8,11 @@ pub use local_uds::{
     LocalUdsInstance, LocalUdsInstanceError, LocalUdsInstanceSpec, LocalUdsInstanceSpecBuilder,
     LocalUdsSocketStrategy,
 };
+pub use remote_http::{
+    RemoteHttpInstance, RemoteHttpInstanceError, RemoteHttpInstanceSpec,
+    RemoteHttpInstanceSpecBuilder,
+};
 
 mod local_http;
 mod local_uds;
+mod remote_http;
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     io,
     path::{Path, PathBuf},
     result,
@@ -12,10 +13,10 @@ use cyclone_client::{
 };
 use cyclone_core::{
     process::{self, ShutdownError},
-    ActionRunRequest, ActionRunResultSuccess, CanonicalCommand, ReconciliationRequest,
-    ReconciliationResultSuccess, ResolverFunctionRequest, ResolverFunctionResultSuccess,
-    SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess, ValidationRequest,
-    ValidationResultSuccess,
+    ActionRunRequest, ActionRunResultSuccess, CanonicalCommand, CanonicalCommandError,
+    ReconciliationRequest, ReconciliationResultSuccess, ResolverFunctionRequest,
+    ResolverFunctionResultSuccess, Runtime, SchemaVariantDefinitionRequest,
+    SchemaVariantDefinitionResultSuccess, ValidationRequest, ValidationResultSuccess,
 };
 use derive_builder::Builder;
 use futures::StreamExt;
@@ -275,10 +276,14 @@ pub struct LocalUdsInstanceSpec {
     #[builder(setter(into))]
     cyclone_decryption_key_path: String,
 
-    /// Canonical path to the language server program.
+    /// Canonical path to the language server program for the default runtime, JavaScript.
     #[builder(try_setter, setter(into))]
     lang_server_cmd_path: CanonicalCommand,
 
+    /// Canonical paths to the language server programs for other runtimes.
+    #[builder(default, setter(custom))]
+    runtime_lang_server_cmd_paths: HashMap<Runtime, CanonicalCommand>,
+
     /// Socket strategy for a spawned Cyclone server.
     #[builder(default)]
     socket_strategy: LocalUdsSocketStrategy,
@@ -302,6 +307,10 @@ pub struct LocalUdsInstanceSpec {
     /// Enables the `action` execution endpoint for a spawned Cyclone server.
     #[builder(private, setter(name = "_action"), default = "false")]
     action: bool,
+
+    /// Runs each function execution of a spawned Cyclone server in its own sandbox.
+    #[builder(private, setter(name = "_sandbox"), default = "false")]
+    sandbox: bool,
 }
 
 #[async_trait]
@@ -368,6 +377,12 @@ impl LocalUdsInstanceSpec {
             .arg("--lang-server")
             .arg(&self.lang_server_cmd_path)
             .arg("--enable-watch");
+        for (runtime, lang_server_cmd_path) in &self.runtime_lang_server_cmd_paths {
+            cmd.arg("--runtime-lang-server").arg(format!(
+                "{runtime}={}",
+                lang_server_cmd_path.as_path().display()
+            ));
+        }
         if let Some(limit_requests) = self.limit_requests {
             cmd.arg("--limit-requests").arg(limit_requests.to_string());
         }
@@ -384,6 +399,9 @@ impl LocalUdsInstanceSpec {
         if self.action {
             cmd.arg("--enable-action-run");
         }
+        if self.sandbox {
+            cmd.arg("--sandbox");
+        }
 
         cmd
     }
@@ -399,6 +417,22 @@ impl SpecBuilder for LocalUdsInstanceSpecBuilder {
 }
 
 impl LocalUdsInstanceSpecBuilder {
+    /// Registers the language server program for a runtime other than the default one for a
+    /// spawned Cyclone server.
+    pub fn try_runtime_lang_server_cmd_path<P>(
+        &mut self,
+        runtime: Runtime,
+        path: P,
+    ) -> result::Result<&mut Self, CanonicalCommandError>
+    where
+        P: TryInto<CanonicalCommand, Error = CanonicalCommandError>,
+    {
+        self.runtime_lang_server_cmd_paths
+            .get_or_insert_with(HashMap::new)
+            .insert(runtime, path.try_into()?);
+        Ok(self)
+    }
+
     /// Sets the limit requests strategy to `1` for a spawned Cyclone server.
     pub fn oneshot(&mut self) -> &mut Self {
         self.limit_requests(Some(1))
@@ -419,6 +453,12 @@ impl LocalUdsInstanceSpecBuilder {
         self._action(true)
     }
 
+    /// Runs each function execution of a spawned Cyclone server in its own sandbox, without
+    /// network access.
+    pub fn sandbox(&mut self) -> &mut Self {
+        self._sandbox(true)
+    }
+
     /// Enables all available endpoints for a spawned Cyclone server
     pub fn all_endpoints(&mut self) -> &mut Self {
         self.action().resolver()
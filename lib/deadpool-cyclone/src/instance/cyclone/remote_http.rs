@@ -0,0 +1,398 @@
+use std::{
+    result,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use cyclone_client::{
+    Client, ClientError, CycloneClient, Execution, HttpClient, LivenessStatus, PingExecution,
+    ReadinessStatus, Watch,
+};
+use cyclone_core::{
+    ActionRunRequest, ActionRunResultSuccess, ReconciliationRequest, ReconciliationResultSuccess,
+    ResolverFunctionRequest, ResolverFunctionResultSuccess, SchemaVariantDefinitionRequest,
+    SchemaVariantDefinitionResultSuccess, ValidationRequest, ValidationResultSuccess,
+};
+use derive_builder::Builder;
+use thiserror::Error;
+use tokio::{net::TcpStream, time};
+use tracing::{debug, warn};
+
+use crate::instance::{Instance, Spec, SpecBuilder};
+
+/// Error type for [`RemoteHttpInstance`].
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum RemoteHttpInstanceError {
+    /// Spec builder error.
+    #[error(transparent)]
+    Builder(#[from] RemoteHttpInstanceSpecBuilderError),
+    /// Cyclone client error.
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    /// Cyclone endpoint health check timed out.
+    #[error("timeout while checking the health of cyclone endpoint")]
+    HealthCheckTimeout,
+    /// None of the configured Cyclone endpoints passed their health checks.
+    #[error("no healthy cyclone endpoint found in: {0:?}")]
+    NoHealthyEndpoint(Vec<String>),
+    /// Instance has exhausted its predefined request count.
+    #[error("no remaining requests, cyclone server is considered unhealthy")]
+    NoRemainingRequests,
+}
+
+type Result<T> = result::Result<T, RemoteHttpInstanceError>;
+
+/// A remote Cyclone [`Instance`], already running on another host, communicating over HTTP.
+///
+/// Instances are never spawned or shut down by the pool: spawning one picks the next healthy
+/// endpoint in the spec's list and terminating one only forgets it.
+#[derive(Debug)]
+pub struct RemoteHttpInstance {
+    client: HttpClient,
+    endpoint: String,
+    limit_requests: Option<u32>,
+    health_check_timeout: Duration,
+}
+
+#[async_trait]
+impl Instance for RemoteHttpInstance {
+    type SpecBuilder = RemoteHttpInstanceSpecBuilder;
+    type Error = RemoteHttpInstanceError;
+
+    async fn terminate(mut self) -> result::Result<(), Self::Error> {
+        debug!(endpoint = %self.endpoint, "releasing remote cyclone instance");
+        Ok(())
+    }
+
+    async fn ensure_healthy(&mut self) -> result::Result<(), Self::Error> {
+        if !self.has_remaining_requests() {
+            return Err(RemoteHttpInstanceError::NoRemainingRequests);
+        }
+        check_health(&mut self.client, self.health_check_timeout).await
+    }
+}
+
+#[async_trait]
+impl CycloneClient<TcpStream> for RemoteHttpInstance {
+    async fn watch(&mut self) -> result::Result<Watch<TcpStream>, ClientError> {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        self.client.watch().await
+    }
+
+    async fn liveness(&mut self) -> result::Result<LivenessStatus, ClientError> {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        self.client.liveness().await
+    }
+
+    async fn readiness(&mut self) -> result::Result<ReadinessStatus, ClientError> {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        self.client.readiness().await
+    }
+
+    async fn execute_ping(&mut self) -> result::Result<PingExecution<TcpStream>, ClientError> {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        let result = self.client.execute_ping().await;
+        self.count_request();
+
+        result
+    }
+
+    async fn execute_resolver(
+        &mut self,
+        request: ResolverFunctionRequest,
+    ) -> result::Result<
+        Execution<TcpStream, ResolverFunctionRequest, ResolverFunctionResultSuccess>,
+        ClientError,
+    > {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        let result = self.client.execute_resolver(request).await;
+        self.count_request();
+
+        result
+    }
+
+    async fn execute_validation(
+        &mut self,
+        request: ValidationRequest,
+    ) -> result::Result<Execution<TcpStream, ValidationRequest, ValidationResultSuccess>, ClientError>
+    {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        let result = self.client.execute_validation(request).await;
+        self.count_request();
+
+        result
+    }
+
+    async fn execute_action_run(
+        &mut self,
+        request: ActionRunRequest,
+    ) -> result::Result<Execution<TcpStream, ActionRunRequest, ActionRunResultSuccess>, ClientError>
+    {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        let result = self.client.execute_action_run(request).await;
+        self.count_request();
+
+        result
+    }
+
+    async fn execute_reconciliation(
+        &mut self,
+        request: ReconciliationRequest,
+    ) -> result::Result<
+        Execution<TcpStream, ReconciliationRequest, ReconciliationResultSuccess>,
+        ClientError,
+    > {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        let result = self.client.execute_reconciliation(request).await;
+        self.count_request();
+
+        result
+    }
+
+    async fn execute_schema_variant_definition(
+        &mut self,
+        request: SchemaVariantDefinitionRequest,
+    ) -> result::Result<
+        Execution<TcpStream, SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess>,
+        ClientError,
+    > {
+        self.ensure_healthy_client()
+            .map_err(ClientError::unhealthy)?;
+
+        let result = self.client.execute_schema_variant_definition(request).await;
+        self.count_request();
+
+        result
+    }
+}
+
+impl RemoteHttpInstance {
+    /// Returns the endpoint this instance sends its requests to.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    fn ensure_healthy_client(&self) -> Result<()> {
+        if !self.has_remaining_requests() {
+            return Err(RemoteHttpInstanceError::NoRemainingRequests);
+        }
+
+        Ok(())
+    }
+
+    fn has_remaining_requests(&self) -> bool {
+        match self.limit_requests {
+            Some(remaining) if remaining == 0 => false,
+            Some(_) | None => true,
+        }
+    }
+
+    fn count_request(&mut self) {
+        if let Some(limit_requests) = self.limit_requests.as_mut() {
+            *limit_requests = limit_requests.saturating_sub(1);
+        }
+    }
+}
+
+/// The [`Spec`] for [`RemoteHttpInstance`]
+#[derive(Builder, Clone, Debug, Eq, PartialEq)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct RemoteHttpInstanceSpec {
+    /// Addresses (`host:port`) of the running Cyclone servers to balance requests across.
+    ///
+    /// Host names are resolved every time an instance is spawned.
+    #[builder(setter(custom))]
+    endpoints: Vec<String>,
+
+    /// Sets how long an endpoint's liveness and readiness checks may take before it is
+    /// considered unhealthy.
+    #[builder(setter(into), default = "Duration::from_secs(5)")]
+    health_check_timeout: Duration,
+
+    /// Sets how many requests an instance sends to its endpoint before another endpoint is
+    /// picked. Defaults to `1`, which balances every request.
+    #[builder(setter(into), default = "Some(1)")]
+    limit_requests: Option<u32>,
+
+    /// Where the next instance starts looking for a healthy endpoint, shared by every clone of
+    /// the spec.
+    #[builder(setter(skip))]
+    next_endpoint: RoundRobin,
+}
+
+#[async_trait]
+impl Spec for RemoteHttpInstanceSpec {
+    type Instance = RemoteHttpInstance;
+    type Error = RemoteHttpInstanceError;
+
+    async fn spawn(&self) -> result::Result<Self::Instance, Self::Error> {
+        let start = self.next_endpoint.next();
+
+        // Starting from the next endpoint in turn, the first one to pass its health checks gets
+        // the instance
+        for offset in 0..self.endpoints.len() {
+            let endpoint = &self.endpoints[(start + offset) % self.endpoints.len()];
+
+            let mut client = match Client::http(endpoint.as_str()) {
+                Ok(client) => client,
+                Err(err) => {
+                    warn!(error = ?err, endpoint = %endpoint, "failed to create cyclone client");
+                    continue;
+                }
+            };
+            if let Err(err) = check_health(&mut client, self.health_check_timeout).await {
+                warn!(error = ?err, endpoint = %endpoint, "cyclone endpoint is unhealthy");
+                continue;
+            }
+
+            debug!(endpoint = %endpoint, "using remote cyclone endpoint");
+            return Ok(Self::Instance {
+                client,
+                endpoint: endpoint.clone(),
+                limit_requests: self.limit_requests,
+                health_check_timeout: self.health_check_timeout,
+            });
+        }
+
+        Err(Self::Error::NoHealthyEndpoint(self.endpoints.clone()))
+    }
+}
+
+impl SpecBuilder for RemoteHttpInstanceSpecBuilder {
+    type Spec = RemoteHttpInstanceSpec;
+    type Error = RemoteHttpInstanceError;
+
+    fn build(&self) -> result::Result<Self::Spec, Self::Error> {
+        self.build().map_err(Into::into)
+    }
+}
+
+impl RemoteHttpInstanceSpecBuilder {
+    /// Adds the address (`host:port`) of a running Cyclone server.
+    pub fn endpoint(&mut self, endpoint: impl Into<String>) -> &mut Self {
+        self.endpoints
+            .get_or_insert_with(Vec::new)
+            .push(endpoint.into());
+        self
+    }
+
+    /// Adds the addresses (`host:port`) of running Cyclone servers.
+    pub fn endpoints<I, E>(&mut self, endpoints: I) -> &mut Self
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<String>,
+    {
+        self.endpoints
+            .get_or_insert_with(Vec::new)
+            .extend(endpoints.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the limit requests strategy to `1`, which balances every request.
+    pub fn oneshot(&mut self) -> &mut Self {
+        self.limit_requests(Some(1))
+    }
+
+    fn validate(&self) -> result::Result<(), String> {
+        match &self.endpoints {
+            Some(endpoints) if !endpoints.is_empty() => Ok(()),
+            Some(_) | None => Err("at least one endpoint is required".to_string()),
+        }
+    }
+}
+
+/// A counter shared by clones, handing out endpoint indices in turn.
+#[derive(Clone, Debug, Default)]
+struct RoundRobin(Arc<AtomicUsize>);
+
+impl RoundRobin {
+    fn next(&self) -> usize {
+        self.0.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+// The counter is only a position, not part of a spec's configuration
+impl PartialEq for RoundRobin {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for RoundRobin {}
+
+async fn check_health(client: &mut HttpClient, timeout: Duration) -> Result<()> {
+    time::timeout(timeout, async {
+        match client.liveness().await? {
+            LivenessStatus::Ok => {}
+        }
+        match client.readiness().await? {
+            ReadinessStatus::Ready => {}
+        }
+        Ok::<_, RemoteHttpInstanceError>(())
+    })
+    .await
+    .map_err(|_| RemoteHttpInstanceError::HealthCheckTimeout)?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_an_endpoint() {
+        let result = RemoteHttpInstance::spec().build();
+        assert!(matches!(
+            result,
+            Err(RemoteHttpInstanceSpecBuilderError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn clones_share_the_endpoint_rotation() {
+        let spec = RemoteHttpInstance::spec()
+            .endpoints(["cyclone-1:5157", "cyclone-2:5157"])
+            .build()
+            .expect("failed to build spec");
+        let clone = spec.clone();
+
+        assert_eq!(spec.next_endpoint.next(), 0);
+        assert_eq!(clone.next_endpoint.next(), 1);
+        assert_eq!(spec, clone);
+    }
+
+    #[tokio::test]
+    async fn fails_without_a_healthy_endpoint() {
+        let spec = RemoteHttpInstance::spec()
+            .endpoint("127.0.0.1:1")
+            .health_check_timeout(Duration::from_millis(500))
+            .build()
+            .expect("failed to build spec");
+
+        let result = spec.spawn().await;
+        assert!(matches!(
+            result,
+            Err(RemoteHttpInstanceError::NoHealthyEndpoint(endpoints)) if endpoints == ["127.0.0.1:1"]
+        ));
+    }
+}
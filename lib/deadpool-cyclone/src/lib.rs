@@ -22,13 +22,14 @@ use thiserror::Error;
 pub use self::instance::{Instance, Spec};
 
 pub use cyclone_client::{
-    ClientError, CycloneClient, EncryptionKey, EncryptionKeyError, ExecutionError,
+    ClientError, Connection, CycloneClient, EncryptionKey, EncryptionKeyError, ExecutionError,
+    ExecutionStarted,
 };
 pub use cyclone_core::{
     ActionRunRequest, ActionRunResultSuccess, ComponentView, FunctionResult, FunctionResultFailure,
     FunctionResultFailureError, OutputStream, ProgressMessage, ReconciliationRequest,
     ReconciliationResultSuccess, ResolverFunctionRequest, ResolverFunctionResultSuccess,
-    ResourceStatus, SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess,
+    ResourceStatus, Runtime, SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess,
     ValidationRequest, ValidationResultSuccess,
 };
 
@@ -114,10 +115,25 @@ mod tests {
             .expect("failed to determine test configuration");
 
         let spec = LocalUdsInstance::spec()
-            .try_cyclone_cmd_path(config_file.cyclone.cyclone_cmd_path())
+            .try_cyclone_cmd_path(
+                config_file
+                    .cyclone
+                    .cyclone_cmd_path()
+                    .expect("local cyclone config has a cyclone cmd path"),
+            )
             .expect("failed to find cyclone program")
-            .cyclone_decryption_key_path(config_file.cyclone.cyclone_decryption_key_path())
-            .try_lang_server_cmd_path(config_file.cyclone.lang_server_cmd_path())
+            .cyclone_decryption_key_path(
+                config_file
+                    .cyclone
+                    .cyclone_decryption_key_path()
+                    .expect("local cyclone config has a cyclone decryption key path"),
+            )
+            .try_lang_server_cmd_path(
+                config_file
+                    .cyclone
+                    .lang_server_cmd_path()
+                    .expect("local cyclone config has a lang server cmd path"),
+            )
             .expect("failed to find lang server program")
             .limit_requests(2)
             .ping()
@@ -8,7 +8,7 @@ use axum::{
 };
 use dal::{
     context::{self, DalContextBuilder},
-    User, UserClaim,
+    User, UserClaim, WorkspacePermission, WorkspaceRole,
 };
 use hyper::StatusCode;
 
@@ -121,6 +121,10 @@ impl FromRequestParts<AppState> for Authorization {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        if let Some(AuthorizedRole(claim, _)) = parts.extensions.get() {
+            return Ok(Self(*claim));
+        }
+
         let HandlerContext(builder) = HandlerContext::from_request_parts(parts, state).await?;
         let mut ctx = builder.build_default().await.map_err(internal_error)?;
         let jwt_public_signing_key = state.jwt_public_signing_key().clone();
@@ -137,14 +141,22 @@ impl FromRequestParts<AppState> for Authorization {
             .map_err(|_| unauthorized_error())?;
         ctx.update_tenancy(dal::Tenancy::new(claim.workspace_pk));
 
-        User::authorize(&ctx, &claim.user_pk)
+        let role = User::role_for_workspace(&ctx, claim.user_pk, claim.workspace_pk)
             .await
-            .map_err(|_| unauthorized_error())?;
+            .map_err(internal_error)?
+            .filter(|role| role.grants(WorkspacePermission::Read))
+            .ok_or_else(unauthorized_error)?;
+        parts.extensions.insert(AuthorizedRole(claim, role));
 
         Ok(Self(claim))
     }
 }
 
+/// The [`WorkspaceRole`] of the user authorized by [`Authorization`], kept in the request's
+/// extensions so that it is only looked up once per request, however many extractors need it.
+#[derive(Clone, Copy)]
+struct AuthorizedRole(UserClaim, WorkspaceRole);
+
 pub struct WsAuthorization(pub UserClaim);
 
 #[async_trait]
@@ -169,14 +181,78 @@ impl FromRequestParts<AppState> for WsAuthorization {
             .map_err(|_| unauthorized_error())?;
         ctx.update_tenancy(dal::Tenancy::new(claim.workspace_pk));
 
-        User::authorize(&ctx, &claim.user_pk)
+        if !User::authorize(&ctx, &claim.user_pk, WorkspacePermission::Read)
             .await
-            .map_err(|_| unauthorized_error())?;
+            .map_err(internal_error)?
+        {
+            return Err(unauthorized_error());
+        }
 
         Ok(Self(claim))
     }
 }
 
+/// Rejects the request unless the authorized user's [`WorkspaceRole`] grants the provided
+/// [`WorkspacePermission`].
+async fn require_permission(
+    parts: &mut Parts,
+    state: &AppState,
+    permission: WorkspacePermission,
+) -> Result<UserClaim, (StatusCode, Json<serde_json::Value>)> {
+    Authorization::from_request_parts(parts, state).await?;
+    let AuthorizedRole(claim, role) = parts
+        .extensions
+        .get()
+        .copied()
+        .ok_or_else(unauthorized_error)?;
+
+    if !role.grants(permission) {
+        return Err(forbidden_error(permission));
+    }
+    Ok(claim)
+}
+
+macro_rules! require_permission_extractor {
+    ($(#[$meta:meta])* $name:ident, $permission:ident) => {
+        $(#[$meta])*
+        pub struct $name;
+
+        #[async_trait]
+        impl FromRequestParts<AppState> for $name {
+            type Rejection = (StatusCode, Json<serde_json::Value>);
+
+            async fn from_request_parts(
+                parts: &mut Parts,
+                state: &AppState,
+            ) -> Result<Self, Self::Rejection> {
+                require_permission(parts, state, WorkspacePermission::$permission).await?;
+                Ok(Self)
+            }
+        }
+    };
+}
+
+require_permission_extractor!(
+    /// Requires the [`Edit`](WorkspacePermission::Edit) permission.
+    RequireEdit,
+    Edit
+);
+require_permission_extractor!(
+    /// Requires the [`Apply`](WorkspacePermission::Apply) permission.
+    RequireApply,
+    Apply
+);
+require_permission_extractor!(
+    /// Requires the [`ManageSecrets`](WorkspacePermission::ManageSecrets) permission.
+    RequireManageSecrets,
+    ManageSecrets
+);
+require_permission_extractor!(
+    /// Requires the [`ManageMembers`](WorkspacePermission::ManageMembers) permission.
+    RequireManageMembers,
+    ManageMembers
+);
+
 pub struct Tenancy(pub dal::Tenancy);
 
 #[async_trait]
@@ -225,3 +301,17 @@ fn unauthorized_error() -> (StatusCode, Json<serde_json::Value>) {
         })),
     )
 }
+
+fn forbidden_error(permission: WorkspacePermission) -> (StatusCode, Json<serde_json::Value>) {
+    let status_code = StatusCode::FORBIDDEN;
+    (
+        status_code,
+        Json(serde_json::json!({
+            "error": {
+                "message": format!("forbidden: requires the {permission} permission"),
+                "statusCode": status_code.as_u16(),
+                "code": 42,
+            },
+        })),
+    )
+}
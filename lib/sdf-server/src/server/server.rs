@@ -6,8 +6,10 @@ use axum::Router;
 use dal::tasks::{StatusReceiver, StatusReceiverError};
 use dal::JwtPublicSigningKey;
 use dal::{
-    cyclone_key_pair::CycloneKeyPairError, job::processor::JobQueueProcessor,
-    tasks::ResourceScheduler, ServicesContext,
+    cyclone_key_pair::CycloneKeyPairError,
+    job::processor::JobQueueProcessor,
+    tasks::{FixScheduler, ResourceScheduler},
+    ServicesContext,
 };
 use hyper::server::{accept::Accept, conn::AddrIncoming};
 use si_data_nats::{NatsClient, NatsConfig, NatsError};
@@ -263,6 +265,27 @@ impl Server<(), ()> {
         ResourceScheduler::new(services_context).start(shutdown_broadcast_rx);
     }
 
+    /// Start the scheduler running the fix schedules that are due
+    pub async fn start_fix_scheduler(
+        pg: PgPool,
+        nats: NatsClient,
+        job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
+        veritech: VeritechClient,
+        encryption_key: EncryptionKey,
+        shutdown_broadcast_rx: broadcast::Receiver<()>,
+    ) {
+        let services_context = ServicesContext::new(
+            pg,
+            nats,
+            job_processor,
+            veritech,
+            Arc::new(encryption_key),
+            None,
+            None,
+        );
+        FixScheduler::new(services_context).start(shutdown_broadcast_rx);
+    }
+
     pub async fn start_status_updater(
         pg: PgPool,
         nats: NatsClient,
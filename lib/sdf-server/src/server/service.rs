@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod change_set;
 pub mod component;
 pub mod diagram;
@@ -11,6 +12,7 @@ pub mod secret;
 pub mod session;
 pub mod status;
 pub mod variant_definition;
+pub mod workspace;
 pub mod ws;
 
 /// A module containing dev routes for local development only.
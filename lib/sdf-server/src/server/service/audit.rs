@@ -0,0 +1,91 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Json;
+use axum::Router;
+use chrono::{DateTime, Utc};
+use dal::history_event::HistoryEventPk;
+use dal::{
+    ChangeSetPk, HistoryActor, HistoryEventCursor, HistoryEventError, HistoryEventFilter,
+    TransactionsError, UserPk,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod export;
+pub mod list;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error(transparent)]
+    ContextTransactions(#[from] TransactionsError),
+    #[error(transparent)]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+pub type AuditResult<T> = std::result::Result<T, AuditError>;
+
+impl IntoResponse for AuditError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+
+        let body = Json(serde_json::json!({
+            "error": {
+                "message": error_message,
+                "code": 42,
+                "statusCode": status.as_u16()
+            }
+        }));
+
+        (status, body).into_response()
+    }
+}
+
+/// The query string filters shared by the audit routes. See [`HistoryEventFilter`]. Exports
+/// ignore `limit`, `offset` and the `before` cursor and include every matching event.
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFilterRequest {
+    /// Only events caused by this user.
+    pub user_pk: Option<UserPk>,
+    pub label_prefix: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub change_set_pk: Option<ChangeSetPk>,
+    pub object_id: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Together with `before_pk`, only events after this cursor. See [`HistoryEventCursor`].
+    pub before_created_at: Option<DateTime<Utc>>,
+    pub before_pk: Option<HistoryEventPk>,
+}
+
+impl From<AuditFilterRequest> for HistoryEventFilter {
+    fn from(request: AuditFilterRequest) -> Self {
+        Self {
+            actor: request.user_pk.map(HistoryActor::User),
+            label_prefix: request.label_prefix,
+            from: request.from,
+            to: request.to,
+            change_set_pk: request.change_set_pk,
+            object_id: request.object_id,
+            limit: request.limit,
+            offset: request.offset,
+            before: request
+                .before_created_at
+                .zip(request.before_pk)
+                .map(|(created_at, pk)| HistoryEventCursor { created_at, pk }),
+        }
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/list", get(list::list))
+        .route("/export", get(export::export))
+}
@@ -0,0 +1,120 @@
+use axum::body::StreamBody;
+use axum::extract::Query;
+use axum::http::header;
+use axum::response::IntoResponse;
+use dal::history_event::{HISTORY_EVENT_CSV_HEADER, MAX_HISTORY_EVENT_LIST_LIMIT};
+use dal::{DalContext, HistoryEvent, HistoryEventFilter};
+use futures::stream;
+use serde::{Deserialize, Serialize};
+
+use super::{AuditFilterRequest, AuditResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditExportFormat {
+    Csv,
+    #[default]
+    Json,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAuditRequest {
+    #[serde(default)]
+    pub format: AuditExportFormat,
+}
+
+/// Where an export is at: the next page is listed after the last event written so far.
+struct ExportPage {
+    ctx: DalContext,
+    filter: HistoryEventFilter,
+    format: AuditExportFormat,
+    written: usize,
+    done: bool,
+}
+
+impl ExportPage {
+    /// Lists and renders the next page of events, or returns `None` once every event (and the
+    /// closing bracket of a JSON array) has been written.
+    async fn next(mut self) -> AuditResult<Option<(String, Self)>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let events = HistoryEvent::list(&self.ctx, &self.filter).await?;
+        self.done = (events.len() as i64) < MAX_HISTORY_EVENT_LIST_LIMIT;
+        self.filter.before = events.last().map(HistoryEvent::cursor);
+
+        let mut chunk = String::new();
+        match self.format {
+            AuditExportFormat::Csv => {
+                if self.written == 0 {
+                    chunk.push_str(HISTORY_EVENT_CSV_HEADER);
+                }
+                for event in &events {
+                    chunk.push_str(&event.to_csv_line()?);
+                }
+            }
+            AuditExportFormat::Json => {
+                if self.written == 0 {
+                    chunk.push('[');
+                }
+                for (index, event) in events.iter().enumerate() {
+                    if self.written + index > 0 {
+                        chunk.push(',');
+                    }
+                    chunk.push_str(&serde_json::to_string(event)?);
+                }
+                if self.done {
+                    chunk.push(']');
+                }
+            }
+        }
+        self.written += events.len();
+
+        Ok(Some((chunk, self)))
+    }
+}
+
+/// Downloads every event matching the filter, newest first, as a JSON array or a CSV file. The
+/// events are listed and sent a page at a time rather than all being held in memory.
+pub async fn export(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(filter_request): Query<AuditFilterRequest>,
+    Query(request): Query<ExportAuditRequest>,
+) -> AuditResult<impl IntoResponse> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut filter: HistoryEventFilter = filter_request.into();
+    filter.limit = Some(MAX_HISTORY_EVENT_LIST_LIMIT);
+    filter.offset = None;
+    filter.before = None;
+
+    let (content_type, extension) = match request.format {
+        AuditExportFormat::Csv => ("text/csv", "csv"),
+        AuditExportFormat::Json => ("application/json", "json"),
+    };
+    let pages = stream::try_unfold(
+        ExportPage {
+            ctx,
+            filter,
+            format: request.format,
+            written: 0,
+            done: false,
+        },
+        ExportPage::next,
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_owned()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"audit-log.{extension}\""),
+            ),
+        ],
+        StreamBody::new(pages),
+    ))
+}
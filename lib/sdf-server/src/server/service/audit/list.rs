@@ -0,0 +1,40 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{HistoryEvent, HistoryEventCursor, HistoryEventFilter};
+use serde::{Deserialize, Serialize};
+
+use super::{AuditFilterRequest, AuditResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditResponse {
+    pub events: Vec<HistoryEvent>,
+    /// The offset of the next page, if there may be more events.
+    pub next_offset: Option<i64>,
+    /// The cursor of the next page, if there may be more events. Prefer this over `next_offset`.
+    pub next_cursor: Option<HistoryEventCursor>,
+}
+
+pub async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<AuditFilterRequest>,
+) -> AuditResult<Json<ListAuditResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let filter: HistoryEventFilter = request.into();
+    let limit = filter.effective_limit();
+    let offset = filter.offset.unwrap_or(0).max(0);
+    let events = HistoryEvent::list(&ctx, &filter).await?;
+
+    let has_more = events.len() as i64 >= limit;
+    let next_offset = has_more.then_some(offset + events.len() as i64);
+    let next_cursor = events.last().filter(|_| has_more).map(HistoryEvent::cursor);
+
+    Ok(Json(ListAuditResponse {
+        events,
+        next_offset,
+        next_cursor,
+    }))
+}
@@ -7,7 +7,7 @@ use axum::{
 use dal::{
     change_status::ChangeStatusError, ChangeSetError as DalChangeSetError,
     ComponentError as DalComponentError, FixError, StandardModelError, TransactionsError,
-    UserError, UserPk,
+    UserError, UserPk, WorkspaceError, WorkspacePk,
 };
 use module_index_client::IndexClientError;
 use telemetry::prelude::*;
@@ -15,13 +15,26 @@ use thiserror::Error;
 
 use crate::{server::state::AppState, service::pkg::PkgError};
 
+pub mod abandon_change_set;
 pub mod apply_change_set;
 pub mod apply_change_set2;
+pub mod close_change_set;
 pub mod create_change_set;
 pub mod get_change_set;
+pub mod get_change_set_review;
+pub mod get_diff;
 pub mod get_stats;
+pub mod list_applied_change_sets;
+pub mod list_change_sets;
 pub mod list_open_change_sets;
+pub mod rebase_change_set;
+pub mod reopen_change_set;
+pub mod request_change_set_review;
+pub mod revert_change_set;
+pub mod review_change_set;
+pub mod update_approval_policy;
 pub mod update_selected_change_set;
+pub mod view_as_of;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -46,6 +59,8 @@ pub enum ChangeSetError {
     InvalidUser(UserPk),
     #[error("invalid user system init")]
     InvalidUserSystemInit,
+    #[error("either a change set or a timestamp is required")]
+    MissingPointInTime,
     #[error(transparent)]
     Nats(#[from] si_data_nats::NatsError),
     #[error(transparent)]
@@ -58,6 +73,10 @@ pub enum ChangeSetError {
     UrlParse(#[from] url::ParseError),
     #[error(transparent)]
     User(#[from] UserError),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error("workspace not found: {0}")]
+    WorkspaceNotFound(WorkspacePk),
 }
 
 pub type ChangeSetResult<T> = std::result::Result<T, ChangeSetError>;
@@ -66,6 +85,25 @@ impl IntoResponse for ChangeSetError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
             ChangeSetError::ChangeSetNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            ChangeSetError::ChangeSet(
+                DalChangeSetError::UnresolvedConflicts(..)
+                | DalChangeSetError::NotOpen(..)
+                | DalChangeSetError::CannotReopen(..)
+                | DalChangeSetError::ApprovalRequired(..)
+                | DalChangeSetError::NotReadyForReview(..),
+            ) => (StatusCode::CONFLICT, self.to_string()),
+            ChangeSetError::ChangeSet(
+                DalChangeSetError::ReviewerNotInWorkspace(..)
+                | DalChangeSetError::SelfReview(..)
+                | DalChangeSetError::User(UserError::PermissionDenied(..)),
+            ) => (StatusCode::FORBIDDEN, self.to_string()),
+            ChangeSetError::MissingPointInTime
+            | ChangeSetError::ChangeSet(
+                DalChangeSetError::NotApplied(..) | DalChangeSetError::NothingToRevert(..),
+            )
+            | ChangeSetError::Workspace(WorkspaceError::InvalidRequiredChangeSetApprovals(..)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -87,8 +125,35 @@ pub fn routes() -> Router<AppState> {
             "/create_change_set",
             post(create_change_set::create_change_set),
         )
+        .route("/list_change_sets", get(list_change_sets::list_change_sets))
+        .route(
+            "/list_applied_change_sets",
+            get(list_applied_change_sets::list_applied_change_sets),
+        )
+        .route("/view_as_of", post(view_as_of::view_as_of))
+        .route(
+            "/revert_change_set",
+            post(revert_change_set::revert_change_set),
+        )
         .route("/get_change_set", get(get_change_set::get_change_set))
         .route("/get_stats", get(get_stats::get_stats))
+        .route("/get_diff", get(get_diff::get_diff))
+        .route(
+            "/get_change_set_review",
+            get(get_change_set_review::get_change_set_review),
+        )
+        .route(
+            "/request_change_set_review",
+            post(request_change_set_review::request_change_set_review),
+        )
+        .route(
+            "/review_change_set",
+            post(review_change_set::review_change_set),
+        )
+        .route(
+            "/update_approval_policy",
+            post(update_approval_policy::update_approval_policy),
+        )
         .route(
             "/apply_change_set",
             post(apply_change_set::apply_change_set),
@@ -97,6 +162,22 @@ pub fn routes() -> Router<AppState> {
             "/apply_change_set2",
             post(apply_change_set2::apply_change_set),
         )
+        .route(
+            "/abandon_change_set",
+            post(abandon_change_set::abandon_change_set),
+        )
+        .route(
+            "/close_change_set",
+            post(close_change_set::close_change_set),
+        )
+        .route(
+            "/reopen_change_set",
+            post(reopen_change_set::reopen_change_set),
+        )
+        .route(
+            "/rebase_change_set",
+            post(rebase_change_set::rebase_change_set),
+        )
         .route(
             "/update_selected_change_set",
             post(update_selected_change_set::update_selected_change_set),
@@ -1,5 +1,5 @@
 use super::ChangeSetResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireApply};
 use crate::server::service::change_set::ChangeSetError;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
@@ -22,6 +22,7 @@ pub struct ApplyChangeSetResponse {
 pub async fn apply_change_set(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(access_builder): AccessBuilder,
+    _: RequireApply,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<ApplyChangeSetRequest>,
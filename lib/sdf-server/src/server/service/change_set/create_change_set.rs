@@ -4,7 +4,7 @@ use dal::ChangeSet;
 use serde::{Deserialize, Serialize};
 
 use super::ChangeSetResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -22,6 +22,7 @@ pub struct CreateChangeSetResponse {
 pub async fn create_change_set(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(access_builder): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CreateChangeSetRequest>,
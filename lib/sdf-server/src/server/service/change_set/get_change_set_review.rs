@@ -0,0 +1,41 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::change_set::ChangeSetReviewSummary;
+use dal::{ChangeSet, ChangeSetPk};
+use serde::{Deserialize, Serialize};
+
+use super::{ChangeSetError, ChangeSetResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChangeSetReviewRequest {
+    pub change_set_pk: ChangeSetPk,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChangeSetReviewResponse {
+    pub change_set: ChangeSet,
+    pub summary: ChangeSetReviewSummary,
+}
+
+/// Gather everything needed to review a [`ChangeSet`]: its component changes and diffs, the
+/// reviews left so far, and whether the workspace approval policy is satisfied.
+pub async fn get_change_set_review(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<GetChangeSetReviewRequest>,
+) -> ChangeSetResult<Json<GetChangeSetReviewResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(ChangeSetError::ChangeSetNotFound)?;
+    let summary = ChangeSetReviewSummary::new(&ctx, &change_set).await?;
+
+    Ok(Json(GetChangeSetReviewResponse {
+        change_set,
+        summary,
+    }))
+}
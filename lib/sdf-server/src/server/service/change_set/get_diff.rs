@@ -0,0 +1,34 @@
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+use axum::extract::Query;
+use axum::Json;
+use dal::change_set::ChangeSetDiff;
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDiffRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDiffResponse {
+    pub diff: ChangeSetDiff,
+}
+
+/// Gather everything that differs between the _current_ change set and _head_.
+pub async fn get_diff(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetDiffRequest>,
+) -> ChangeSetResult<Json<GetDiffResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let diff = ChangeSetDiff::new(&ctx, ctx.visibility().change_set_pk).await?;
+
+    Ok(Json(GetDiffResponse { diff }))
+}
@@ -0,0 +1,34 @@
+use axum::extract::Query;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use dal::ChangeSet;
+use serde::{Deserialize, Serialize};
+
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAppliedChangeSetsRequest {
+    /// Only include change sets applied at or before this moment.
+    pub before: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAppliedChangeSetsResponse {
+    pub change_sets: Vec<ChangeSet>,
+}
+
+/// List the timeline of applied change sets, most recently applied first.
+pub async fn list_applied_change_sets(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<ListAppliedChangeSetsRequest>,
+) -> ChangeSetResult<Json<ListAppliedChangeSetsResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_sets = ChangeSet::list_applied(&ctx, request.before).await?;
+
+    Ok(Json(ListAppliedChangeSetsResponse { change_sets }))
+}
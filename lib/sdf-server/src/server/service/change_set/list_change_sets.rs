@@ -0,0 +1,31 @@
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use axum::extract::Query;
+use axum::Json;
+use dal::{ChangeSet, ChangeSetPk, ChangeSetStatus, LabelList};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListChangeSetsRequest {
+    /// Only list change sets with this status. Change sets of every status are listed if unset.
+    pub status: Option<ChangeSetStatus>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListChangeSetsResponse {
+    pub list: LabelList<ChangeSetPk>,
+}
+
+pub async fn list_change_sets(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<ListChangeSetsRequest>,
+) -> ChangeSetResult<Json<ListChangeSetsResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let list = ChangeSet::list_with_status(&ctx, request.status).await?;
+
+    Ok(Json(ListChangeSetsResponse { list }))
+}
@@ -0,0 +1,58 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::change_set::ChangeSetConflictResolution;
+use dal::{ChangeSet, ChangeSetPk, ChangeSetRebaseReport};
+use serde::{Deserialize, Serialize};
+
+use super::{ChangeSetError, ChangeSetResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseChangeSetRequest {
+    pub change_set_pk: ChangeSetPk,
+    #[serde(default)]
+    pub resolutions: Vec<ChangeSetConflictResolution>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseChangeSetResponse {
+    pub change_set: ChangeSet,
+    pub report: ChangeSetRebaseReport,
+}
+
+/// Rebase a [`ChangeSet`] onto the latest _head_. If any conflicts are found without a
+/// resolution in the request, nothing is changed and the conflicts are returned in the report.
+pub async fn rebase_change_set(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    _: RequireEdit,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RebaseChangeSetRequest>,
+) -> ChangeSetResult<Json<RebaseChangeSetResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(ChangeSetError::ChangeSetNotFound)?;
+    let report = change_set.rebase(&ctx, &request.resolutions).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "rebase_change_set",
+        serde_json::json!({
+            "change_set_pk": request.change_set_pk,
+            "rebased": report.rebased,
+            "number_of_conflicts": report.conflicts.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(RebaseChangeSetResponse { change_set, report }))
+}
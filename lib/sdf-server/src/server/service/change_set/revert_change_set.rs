@@ -0,0 +1,59 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::change_set::ChangeSetConflict;
+use dal::{ChangeSet, ChangeSetPk};
+use serde::{Deserialize, Serialize};
+
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertChangeSetRequest {
+    pub change_set_pk: ChangeSetPk,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertChangeSetResponse {
+    /// The new, open change set containing the inverse of the applied change set.
+    pub change_set: ChangeSet,
+    /// Changes made on _head_ since the reverted change set was applied that the revert would
+    /// overwrite. These must be resolved by rebasing the new change set before applying it.
+    pub conflicts: Vec<ChangeSetConflict>,
+}
+
+/// Create a new [`ChangeSet`] that undoes everything an applied [`ChangeSet`] did.
+pub async fn revert_change_set(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    _: RequireEdit,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RevertChangeSetRequest>,
+) -> ChangeSetResult<Json<RevertChangeSetResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_set = ChangeSet::revert(&ctx, request.change_set_pk).await?;
+    let conflicts = change_set.conflicts(&ctx).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "revert_change_set",
+        serde_json::json!({
+            "change_set_pk": request.change_set_pk,
+            "revert_change_set_pk": change_set.pk,
+            "conflict_count": conflicts.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(RevertChangeSetResponse {
+        change_set,
+        conflicts,
+    }))
+}
@@ -0,0 +1,55 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::change_set::{ChangeSetReview, ChangeSetReviewStatus};
+use dal::{ChangeSet, ChangeSetPk};
+use serde::{Deserialize, Serialize};
+
+use super::{ChangeSetError, ChangeSetResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewChangeSetRequest {
+    pub change_set_pk: ChangeSetPk,
+    pub status: ChangeSetReviewStatus,
+    pub comment: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewChangeSetResponse {
+    pub review: ChangeSetReview,
+}
+
+/// Approve, request changes to, or comment on a [`ChangeSet`] that is ready for review.
+pub async fn review_change_set(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    _: RequireEdit,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ReviewChangeSetRequest>,
+) -> ChangeSetResult<Json<ReviewChangeSetResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(ChangeSetError::ChangeSetNotFound)?;
+    let review = ChangeSetReview::new(&ctx, &change_set, request.status, request.comment).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "review_change_set",
+        serde_json::json!({
+            "change_set_pk": request.change_set_pk,
+            "status": request.status,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(ReviewChangeSetResponse { review }))
+}
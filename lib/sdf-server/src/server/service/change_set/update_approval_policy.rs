@@ -0,0 +1,56 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::Workspace;
+use serde::{Deserialize, Serialize};
+
+use super::{ChangeSetError, ChangeSetResult};
+use crate::server::extract::{
+    AccessBuilder, Authorization, HandlerContext, PosthogClient, RequireManageMembers,
+};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateApprovalPolicyRequest {
+    /// How many approvals a change set needs before it can be applied. Zero disables reviews.
+    pub required_change_set_approvals: i32,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateApprovalPolicyResponse {
+    pub workspace: Workspace,
+}
+
+pub async fn update_approval_policy(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    _: RequireManageMembers,
+    Authorization(claim): Authorization,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<UpdateApprovalPolicyRequest>,
+) -> ChangeSetResult<Json<UpdateApprovalPolicyResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut workspace = Workspace::get_by_pk(&ctx, &claim.workspace_pk)
+        .await?
+        .ok_or(ChangeSetError::WorkspaceNotFound(claim.workspace_pk))?;
+    workspace
+        .set_required_change_set_approvals(&ctx, request.required_change_set_approvals)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "update_approval_policy",
+        serde_json::json!({
+            "required_change_set_approvals": request.required_change_set_approvals,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(UpdateApprovalPolicyResponse { workspace }))
+}
@@ -0,0 +1,56 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use dal::{ChangeSet, ChangeSetPk};
+use serde::{Deserialize, Serialize};
+
+use super::{ChangeSetError, ChangeSetResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewAsOfRequest {
+    /// Show _head_ as it was right after this change set was applied.
+    pub change_set_pk: Option<ChangeSetPk>,
+    /// Show _head_ as it was at this moment. Ignored if `change_set_pk` is provided.
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewAsOfResponse {
+    /// A read-only change set; select it to render _head_ as of the requested point in time.
+    pub change_set: ChangeSet,
+}
+
+pub async fn view_as_of(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ViewAsOfRequest>,
+) -> ChangeSetResult<Json<ViewAsOfResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_set = match (request.change_set_pk, request.as_of) {
+        (Some(change_set_pk), _) => ChangeSet::new_as_of_change_set(&ctx, change_set_pk).await?,
+        (None, Some(as_of)) => ChangeSet::new_as_of(&ctx, as_of).await?,
+        (None, None) => return Err(ChangeSetError::MissingPointInTime),
+    };
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "view_as_of",
+        serde_json::json!({
+            "change_set_pk": request.change_set_pk,
+            "as_of": change_set.as_of,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(ViewAsOfResponse { change_set }))
+}
@@ -28,6 +28,7 @@ pub mod list_qualifications;
 pub mod list_resources;
 pub mod refresh;
 pub mod resource_domain_diff;
+pub mod set_refresh_interval;
 pub mod set_type;
 pub mod update_property_editor_value;
 
@@ -158,6 +159,10 @@ pub fn routes() -> Router<AppState> {
             get(get_property_editor_validations::get_property_editor_validations),
         )
         .route("/set_type", post(set_type::set_type))
+        .route(
+            "/set_refresh_interval",
+            post(set_refresh_interval::set_refresh_interval),
+        )
         .route("/refresh", post(refresh::refresh))
         .route("/resource_domain_diff", get(resource_domain_diff::get_diff))
         .route(
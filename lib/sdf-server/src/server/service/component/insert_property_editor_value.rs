@@ -6,7 +6,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 
 use super::ComponentResult;
-use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireEdit};
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +23,7 @@ pub struct InsertPropertyEditorValueRequest {
 pub async fn insert_property_editor_value(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     Json(request): Json<InsertPropertyEditorValueRequest>,
 ) -> ComponentResult<impl IntoResponse> {
     let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
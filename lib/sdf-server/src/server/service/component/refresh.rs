@@ -7,7 +7,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 
 use super::{ComponentError, ComponentResult};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -27,6 +27,7 @@ pub struct RefreshResponse {
 pub async fn refresh(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<RefreshRequest>,
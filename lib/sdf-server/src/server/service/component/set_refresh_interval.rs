@@ -0,0 +1,83 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+
+use dal::{ChangeSet, Component, ComponentId, StandardModel, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
+use crate::server::tracking::track;
+use crate::service::component::ComponentError;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRefreshIntervalRequest {
+    pub component_id: ComponentId,
+    /// How often the component's resource is refreshed, in seconds. When empty, the interval of
+    /// its schema variant is used.
+    pub refresh_interval_secs: Option<i64>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn set_refresh_interval(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SetRefreshIntervalRequest>,
+) -> ComponentResult<impl IntoResponse> {
+    if matches!(request.refresh_interval_secs, Some(secs) if secs <= 0) {
+        return Err(ComponentError::InvalidRequest);
+    }
+
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let mut component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(ComponentError::ComponentNotFound(request.component_id))?;
+    component
+        .set_refresh_interval_secs(&ctx, request.refresh_interval_secs)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "set_component_refresh_interval",
+        serde_json::json!({
+                    "component_id": component.id(),
+                    "refresh_interval_secs": request.refresh_interval_secs,
+        }),
+    );
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(axum::body::Empty::new())?)
+}
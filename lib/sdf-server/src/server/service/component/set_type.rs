@@ -5,7 +5,7 @@ use dal::{ChangeSet, Component, ComponentId, ComponentType, StandardModel, Visib
 use serde::{Deserialize, Serialize};
 
 use super::ComponentResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use crate::service::component::ComponentError;
 
@@ -21,6 +21,7 @@ pub struct SetTypeRequest {
 pub async fn set_type(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<SetTypeRequest>,
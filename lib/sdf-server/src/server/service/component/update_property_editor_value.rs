@@ -7,7 +7,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 
 use super::ComponentResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use crate::service::component::ComponentError;
 
@@ -27,6 +27,7 @@ pub struct UpdatePropertyEditorValueRequest {
 pub async fn update_property_editor_value(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<UpdatePropertyEditorValueRequest>,
@@ -11,7 +11,7 @@ use dal::{
 use dal::{ComponentType, Socket};
 use serde::{Deserialize, Serialize};
 
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 
 use super::{DiagramError, DiagramResult};
@@ -218,6 +218,7 @@ pub async fn connect_component_sockets_to_frame(
 pub async fn connect_component_to_frame(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CreateFrameConnectionRequest>,
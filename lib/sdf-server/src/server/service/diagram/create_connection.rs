@@ -9,7 +9,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 
 use super::{DiagramError, DiagramResult};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -35,6 +35,7 @@ pub struct CreateConnectionResponse {
 pub async fn create_connection(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CreateConnectionRequest>,
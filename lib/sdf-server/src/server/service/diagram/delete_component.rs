@@ -4,7 +4,7 @@ use dal::{ChangeSet, Component, ComponentId, DalContext, StandardModel, Visibili
 use serde::{Deserialize, Serialize};
 
 use super::{DiagramError, DiagramResult};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -55,6 +55,7 @@ async fn delete_single_component(
 pub async fn delete_component(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     posthog_client: PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<DeleteComponentRequest>,
@@ -100,6 +101,7 @@ pub struct DeleteComponentsRequest {
 pub async fn delete_components(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     posthog_client: PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<DeleteComponentsRequest>,
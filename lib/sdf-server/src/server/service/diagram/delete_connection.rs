@@ -5,7 +5,7 @@ use dal::{ChangeSet, Connection, Edge, Node, Socket, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 
 use super::DiagramResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use crate::service::diagram::DiagramError;
 use dal::standard_model::StandardModel;
@@ -22,6 +22,7 @@ pub struct DeleteConnectionRequest {
 pub async fn delete_connection(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<DeleteConnectionRequest>,
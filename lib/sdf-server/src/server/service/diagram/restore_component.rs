@@ -4,7 +4,7 @@ use dal::{ChangeSet, Component, ComponentId, DalContext, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 
 use super::DiagramResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use crate::service::diagram::DiagramError;
 use dal::standard_model::StandardModel;
@@ -62,6 +62,7 @@ async fn restore_single_component(
 pub async fn restore_component(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     posthog_client: PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<RestoreComponentRequest>,
@@ -107,6 +108,7 @@ pub struct RestoreComponentsRequest {
 pub async fn restore_components(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     posthog_client: PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<RestoreComponentsRequest>,
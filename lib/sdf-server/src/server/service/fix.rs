@@ -9,19 +9,30 @@ use thiserror::Error;
 use dal::fix::FixError as DalFixError;
 use dal::schema::SchemaError as DalSchemaError;
 use dal::{
-    ComponentError, ComponentId, FixResolverError, FuncBindingReturnValueError, StandardModelError,
+    ActionPrototypeError, ActionPrototypeId, ComponentError, ComponentId, FixBatchId,
+    FixResolverError, FixScheduleId, FuncBindingReturnValueError, StandardModelError,
     TransactionsError, UserError, UserPk,
 };
 
 use crate::server::state::AppState;
 
+pub mod cancel;
+pub mod cancel_schedule;
 pub mod confirmations;
 pub mod list;
+pub mod list_schedules;
+pub mod plan;
+pub mod retry;
 pub mod run;
+pub mod schedule;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum FixError {
+    #[error(transparent)]
+    ActionPrototype(#[from] ActionPrototypeError),
+    #[error("action prototype {0} not found")]
+    ActionPrototypeNotFound(ActionPrototypeId),
     #[error(transparent)]
     Component(#[from] ComponentError),
     #[error("component {0} not found")]
@@ -30,8 +41,12 @@ pub enum FixError {
     DalFix(#[from] DalFixError),
     #[error(transparent)]
     DalSchema(#[from] DalSchemaError),
+    #[error("fix batch {0} not found")]
+    FixBatchNotFound(FixBatchId),
     #[error(transparent)]
     FixResolver(#[from] FixResolverError),
+    #[error("fix schedule {0} not found")]
+    FixScheduleNotFound(FixScheduleId),
     #[error(transparent)]
     FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
     #[error("invalid user {0}")]
@@ -66,7 +81,13 @@ impl IntoResponse for FixError {
 
 pub fn routes() -> Router<AppState> {
     Router::new()
+        .route("/cancel", post(cancel::cancel))
+        .route("/cancel_schedule", post(cancel_schedule::cancel_schedule))
         .route("/confirmations", get(confirmations::confirmations))
         .route("/list", get(list::list))
+        .route("/list_schedules", get(list_schedules::list_schedules))
+        .route("/plan", post(plan::plan))
+        .route("/retry", post(retry::retry))
         .route("/run", post(run::run))
+        .route("/schedule", post(schedule::schedule))
 }
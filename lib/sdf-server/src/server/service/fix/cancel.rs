@@ -0,0 +1,38 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::{FixError, FixResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireApply};
+use dal::{FixBatch, FixBatchId, StandardModel, Visibility};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelFixBatchRequest {
+    pub id: FixBatchId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelFixBatchResponse {
+    pub success: bool,
+}
+
+pub async fn cancel(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireApply,
+    Json(request): Json<CancelFixBatchRequest>,
+) -> FixResult<Json<CancelFixBatchResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut batch = FixBatch::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(FixError::FixBatchNotFound(request.id))?;
+    batch.cancel(&ctx).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(CancelFixBatchResponse { success: true }))
+}
@@ -0,0 +1,38 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::{FixError, FixResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireApply};
+use dal::{FixSchedule, FixScheduleId, StandardModel, Visibility};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelFixScheduleRequest {
+    pub id: FixScheduleId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelFixScheduleResponse {
+    pub success: bool,
+}
+
+pub async fn cancel_schedule(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireApply,
+    Json(request): Json<CancelFixScheduleRequest>,
+) -> FixResult<Json<CancelFixScheduleResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut schedule = FixSchedule::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(FixError::FixScheduleNotFound(request.id))?;
+    schedule.cancel(&ctx).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(CancelFixScheduleResponse { success: true }))
+}
@@ -1,7 +1,7 @@
 use axum::{extract::Query, Json};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use dal::fix::FixHistoryView;
-use dal::{FixBatch, FixBatchId, FixCompletionStatus};
+use dal::{FixBatch, FixBatchFailurePolicy, FixBatchId, FixCompletionStatus};
 use dal::{StandardModel, Visibility};
 use serde::{Deserialize, Serialize};
 
@@ -24,6 +24,8 @@ pub struct BatchHistoryView {
     fixes: Vec<FixHistoryView>,
     started_at: Option<String>,
     finished_at: Option<String>,
+    failure_policy: FixBatchFailurePolicy,
+    cancelled_at: Option<DateTime<Utc>>,
 }
 
 pub type ListFixesResponse = Vec<BatchHistoryView>;
@@ -65,6 +67,8 @@ pub async fn list(
             author: batch.author(),
             started_at: batch.started_at().map(|s| s.to_string()),
             finished_at: batch.finished_at().map(|s| s.to_string()),
+            failure_policy: *batch.failure_policy(),
+            cancelled_at: *batch.cancelled_at(),
         })
     }
 
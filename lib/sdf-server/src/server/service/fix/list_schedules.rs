@@ -0,0 +1,65 @@
+use axum::{extract::Query, Json};
+use chrono::{DateTime, Utc};
+use dal::{
+    FixBatchFailurePolicy, FixBatchId, FixSchedule, FixScheduleId, FixScheduleItem, StandardModel,
+    Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::FixResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFixSchedulesRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FixScheduleView {
+    id: FixScheduleId,
+    author: String,
+    fixes: Vec<FixScheduleItem>,
+    failure_policy: FixBatchFailurePolicy,
+    next_run_at: Option<DateTime<Utc>>,
+    interval_secs: Option<i64>,
+    last_run_at: Option<DateTime<Utc>>,
+    last_fix_batch_id: Option<FixBatchId>,
+    last_run_error: Option<String>,
+    cancelled_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+pub type ListFixSchedulesResponse = Vec<FixScheduleView>;
+
+pub async fn list_schedules(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListFixSchedulesRequest>,
+) -> FixResult<Json<ListFixSchedulesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut schedules = FixSchedule::list(&ctx).await?;
+    schedules.sort_by_key(|schedule| std::cmp::Reverse(schedule.timestamp().created_at));
+
+    let views = schedules
+        .into_iter()
+        .map(|schedule| FixScheduleView {
+            id: *schedule.id(),
+            author: schedule.author().clone(),
+            fixes: schedule.fixes().to_vec(),
+            failure_policy: *schedule.failure_policy(),
+            next_run_at: *schedule.next_run_at(),
+            interval_secs: *schedule.interval_secs(),
+            last_run_at: *schedule.last_run_at(),
+            last_fix_batch_id: schedule.last_fix_batch_id().copied(),
+            last_run_error: schedule.last_run_error().map(ToOwned::to_owned),
+            cancelled_at: *schedule.cancelled_at(),
+            created_at: schedule.timestamp().created_at,
+        })
+        .collect();
+
+    Ok(Json(views))
+}
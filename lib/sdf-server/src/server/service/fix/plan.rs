@@ -0,0 +1,78 @@
+use axum::Json;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::run::FixRunRequest;
+use super::{FixError, FixResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireEdit};
+use dal::{
+    ActionKind, ActionPlan, ActionPrototype, ActionPrototypeId, AttributeValueId, Component,
+    ComponentId, StandardModel, Visibility,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FixesPlanRequest {
+    pub list: Vec<FixRunRequest>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FixPlanView {
+    pub attribute_value_id: AttributeValueId,
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub action_prototype_id: ActionPrototypeId,
+    pub action_kind: ActionKind,
+    pub plan: ActionPlan,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FixesPlanResponse {
+    pub plans: Vec<FixPlanView>,
+    pub planned_at: String,
+}
+
+/// Plans the fixes that running the same list with `fix/run` would apply, in the same order, by
+/// running their actions as dry runs. Nothing is applied and no fix is recorded.
+pub async fn plan(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
+    Json(request): Json<FixesPlanRequest>,
+) -> FixResult<Json<FixesPlanResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    // Components being deleted are planned too
+    let deleted_ctx = &ctx.clone_with_delete_visibility();
+
+    let mut plans = Vec::with_capacity(request.list.len());
+    for fix_run_request in request.list {
+        let action = ActionPrototype::get_by_id(&ctx, &fix_run_request.action_prototype_id)
+            .await?
+            .ok_or(FixError::ActionPrototypeNotFound(
+                fix_run_request.action_prototype_id,
+            ))?;
+        let component = Component::get_by_id(deleted_ctx, &fix_run_request.component_id)
+            .await?
+            .ok_or(FixError::ComponentNotFound(fix_run_request.component_id))?;
+
+        plans.push(FixPlanView {
+            attribute_value_id: fix_run_request.attribute_value_id,
+            component_id: fix_run_request.component_id,
+            component_name: component.name(deleted_ctx).await?,
+            action_prototype_id: fix_run_request.action_prototype_id,
+            action_kind: *action.kind(),
+            plan: action
+                .plan(deleted_ctx, fix_run_request.component_id)
+                .await?,
+        });
+    }
+
+    Ok(Json(FixesPlanResponse {
+        plans,
+        planned_at: Utc::now().to_rfc3339(),
+    }))
+}
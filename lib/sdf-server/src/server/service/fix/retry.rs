@@ -0,0 +1,66 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::{FixError, FixResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireApply};
+use crate::server::tracking::track;
+use dal::job::definition::{FixItem, FixesJob};
+use dal::{FixBatch, FixBatchId, HistoryActor, StandardModel, User, Visibility};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryFixBatchRequest {
+    pub id: FixBatchId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryFixBatchResponse {
+    pub id: FixBatchId,
+}
+
+pub async fn retry(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireApply,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RetryFixBatchRequest>,
+) -> FixResult<Json<RetryFixBatchResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let user = match ctx.history_actor() {
+        HistoryActor::User(user_pk) => User::get_by_pk(&ctx, *user_pk)
+            .await?
+            .ok_or(FixError::InvalidUser(*user_pk))?,
+
+        HistoryActor::SystemInit => return Err(FixError::InvalidUserSystemInit),
+    };
+    let previous_batch = FixBatch::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(FixError::FixBatchNotFound(request.id))?;
+    let (batch, fixes) = previous_batch.retry(&ctx, user.email()).await?;
+    let fixes: Vec<FixItem> = fixes.iter().map(FixItem::from).collect();
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "retry_fix_batch",
+        serde_json::json!({
+            "fix_batch_id": batch.id(),
+            "retried_fix_batch_id": previous_batch.id(),
+            "number_of_fixes_in_batch": fixes.len(),
+        }),
+    );
+
+    ctx.enqueue_job(FixesJob::new(&ctx, fixes, *batch.id()))
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(RetryFixBatchResponse { id: *batch.id() }))
+}
@@ -3,12 +3,12 @@ use axum::Json;
 use serde::{Deserialize, Serialize};
 
 use super::{FixError, FixResult};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireApply};
 use crate::server::tracking::track;
 use dal::job::definition::{FixItem, FixesJob};
 use dal::{
-    ActionPrototypeId, AttributeValueId, ComponentId, Fix, FixBatch, FixBatchId, HistoryActor,
-    StandardModel, User, Visibility,
+    ActionPrototypeId, AttributeValueId, ComponentId, Fix, FixBatch, FixBatchFailurePolicy,
+    FixBatchId, HistoryActor, StandardModel, User, Visibility,
 };
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -23,6 +23,8 @@ pub struct FixRunRequest {
 #[serde(rename_all = "camelCase")]
 pub struct FixesRunRequest {
     pub list: Vec<FixRunRequest>,
+    #[serde(default)]
+    pub failure_policy: FixBatchFailurePolicy,
     #[serde(flatten)]
     pub visibility: Visibility,
 }
@@ -36,6 +38,7 @@ pub struct FixesRunResponse {
 pub async fn run(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireApply,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<FixesRunRequest>,
@@ -49,7 +52,10 @@ pub async fn run(
 
         HistoryActor::SystemInit => return Err(FixError::InvalidUserSystemInit),
     };
-    let batch = FixBatch::new(&ctx, user.email()).await?;
+    let mut batch = FixBatch::new(&ctx, user.email()).await?;
+    batch
+        .set_failure_policy(&ctx, request.failure_policy)
+        .await?;
     let mut fixes = Vec::with_capacity(request.list.len());
 
     for fix_run_request in request.list {
@@ -78,6 +84,7 @@ pub async fn run(
         serde_json::json!({
             "fix_batch_id": batch.id(),
             "number_of_fixes_in_batch": fixes.len(),
+            "failure_policy": request.failure_policy,
             "fixes_applied": fixes,
         }),
     );
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use axum::extract::OriginalUri;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::run::FixRunRequest;
+use super::{FixError, FixResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireApply};
+use crate::server::tracking::track;
+use dal::{
+    FixBatchFailurePolicy, FixSchedule, FixScheduleId, FixScheduleItem, HistoryActor,
+    StandardModel, User, Visibility,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleFixesRequest {
+    pub list: Vec<FixRunRequest>,
+    #[serde(default)]
+    pub failure_policy: FixBatchFailurePolicy,
+    pub run_at: DateTime<Utc>,
+    /// Runs the fixes again every given number of seconds after `run_at` when populated.
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleFixesResponse {
+    pub id: FixScheduleId,
+}
+
+pub async fn schedule(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireApply,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ScheduleFixesRequest>,
+) -> FixResult<Json<ScheduleFixesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let user = match ctx.history_actor() {
+        HistoryActor::User(user_pk) => User::get_by_pk(&ctx, *user_pk)
+            .await?
+            .ok_or(FixError::InvalidUser(*user_pk))?,
+
+        HistoryActor::SystemInit => return Err(FixError::InvalidUserSystemInit),
+    };
+    let fixes: Vec<FixScheduleItem> = request
+        .list
+        .into_iter()
+        .map(|fix_run_request| FixScheduleItem {
+            attribute_value_id: fix_run_request.attribute_value_id,
+            component_id: fix_run_request.component_id,
+            action_prototype_id: fix_run_request.action_prototype_id,
+        })
+        .collect();
+    let number_of_fixes = fixes.len();
+
+    let schedule = FixSchedule::new(
+        &ctx,
+        user.email(),
+        user.pk(),
+        fixes,
+        request.failure_policy,
+        request.run_at,
+        request.interval_secs.map(Duration::from_secs),
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "schedule_fixes",
+        serde_json::json!({
+            "fix_schedule_id": schedule.id(),
+            "number_of_fixes_in_schedule": number_of_fixes,
+            "recurring": request.interval_secs.is_some(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(ScheduleFixesResponse { id: *schedule.id() }))
+}
@@ -5,7 +5,8 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
-use dal::func::execution::FuncExecutionError;
+use dal::func::execution::{FuncExecutionError, FuncExecutionPk};
+use dal::func::execution_cache::FuncExecutionCacheError;
 use dal::{
     attribute::context::{AttributeContextBuilder, AttributeContextBuilderError},
     func::{
@@ -28,9 +29,13 @@ use dal::{
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
+use veritech_client::Runtime;
 
 pub mod create_func;
+pub mod get_execution;
+pub mod get_execution_cache_stats;
 pub mod get_func;
+pub mod list_executions;
 pub mod list_funcs;
 pub mod list_input_sources;
 pub mod revert_func;
@@ -109,10 +114,14 @@ pub enum FuncError {
     FuncDestinationPropKindMismatch,
     #[error("Function execution: {0}")]
     FuncExecution(#[from] FuncExecutionError),
+    #[error("Function execution cache: {0}")]
+    FuncExecutionCache(#[from] FuncExecutionCacheError),
     #[error("Function execution failed: {0}")]
     FuncExecutionFailed(String),
     #[error("Function execution failed: this function is not connected to any assets, and was not executed")]
     FuncExecutionFailedNoPrototypes,
+    #[error("Function execution not found: {0}")]
+    FuncExecutionNotFound(FuncExecutionPk),
     #[error("Function named \"{0}\" already exists in this changeset")]
     FuncNameExists(String),
     #[error("Function not found")]
@@ -143,6 +152,8 @@ pub enum FuncError {
     PrototypeContext(#[from] PrototypeContextError),
     #[error("prototype list for func error: {0}")]
     PrototypeListForFunc(#[from] PrototypeListForFuncError),
+    #[error("{1:?} funcs cannot be written for the {0} runtime")]
+    RuntimeNotSupportedForVariant(Runtime, FuncVariant),
     #[error("schema variant error: {0}")]
     SchemaVariant(#[from] SchemaVariantError),
     #[error("schema variant missing schema")]
@@ -212,14 +223,18 @@ impl TryFrom<&Func> for FuncVariant {
 
     fn try_from(func: &Func) -> Result<Self, Self::Error> {
         match (func.backend_kind(), func.backend_response_type()) {
-            (FuncBackendKind::JsAttribute, response_type) => match response_type {
-                FuncBackendResponseType::CodeGeneration => Ok(FuncVariant::CodeGeneration),
-                FuncBackendResponseType::Qualification => Ok(FuncVariant::Qualification),
-                FuncBackendResponseType::Confirmation => Ok(FuncVariant::Confirmation),
-                _ => Ok(FuncVariant::Attribute),
-            },
+            (FuncBackendKind::JsAttribute | FuncBackendKind::PythonAttribute, response_type) => {
+                match response_type {
+                    FuncBackendResponseType::CodeGeneration => Ok(FuncVariant::CodeGeneration),
+                    FuncBackendResponseType::Qualification => Ok(FuncVariant::Qualification),
+                    FuncBackendResponseType::Confirmation => Ok(FuncVariant::Confirmation),
+                    _ => Ok(FuncVariant::Attribute),
+                }
+            }
             (FuncBackendKind::JsReconciliation, _) => Ok(FuncVariant::Reconciliation),
-            (FuncBackendKind::JsAction, _) => Ok(FuncVariant::Action),
+            (FuncBackendKind::JsAction | FuncBackendKind::PythonAction, _) => {
+                Ok(FuncVariant::Action)
+            }
             (FuncBackendKind::JsValidation, _) => Ok(FuncVariant::Validation),
             _ => Err(FuncError::FuncCannotBeTurnedIntoVariant(*func.id())),
         }
@@ -473,7 +488,7 @@ pub async fn get_func_view(ctx: &DalContext, func: &Func) -> FuncResult<GetFuncR
     let arguments = FuncArgument::list_for_func(ctx, *func.id()).await?;
 
     let (associations, input_type) = match func.backend_kind() {
-        FuncBackendKind::JsAttribute => {
+        FuncBackendKind::JsAttribute | FuncBackendKind::PythonAttribute => {
             let (associations, input_type) = match func.backend_response_type() {
                 FuncBackendResponseType::CodeGeneration
                 | FuncBackendResponseType::Confirmation
@@ -548,7 +563,7 @@ pub async fn get_func_view(ctx: &DalContext, func: &Func) -> FuncResult<GetFuncR
             };
             (associations, input_type)
         }
-        FuncBackendKind::JsAction => {
+        FuncBackendKind::JsAction | FuncBackendKind::PythonAction => {
             let (kind, schema_variant_ids) =
                 action_prototypes_into_schema_variants_and_components(ctx, *func.id()).await?;
 
@@ -600,6 +615,8 @@ pub async fn get_func_view(ctx: &DalContext, func: &Func) -> FuncResult<GetFuncR
         code: func.code_plaintext()?,
         is_builtin: func.builtin(),
         is_revertible,
+        deterministic: func.deterministic(),
+        supports_dry_run: func.supports_dry_run(),
         associations,
         types,
     })
@@ -608,14 +625,15 @@ pub async fn get_func_view(ctx: &DalContext, func: &Func) -> FuncResult<GetFuncR
 // TODO FIXME(paulo): cleanup code repetition
 
 pub fn compile_return_types(ty: FuncBackendResponseType, kind: FuncBackendKind) -> &'static str {
-    if matches!(kind, FuncBackendKind::JsAttribute)
-        && !matches!(
-            ty,
-            FuncBackendResponseType::CodeGeneration
-                | FuncBackendResponseType::Confirmation
-                | FuncBackendResponseType::Qualification
-        )
-    {
+    if matches!(
+        kind,
+        FuncBackendKind::JsAttribute | FuncBackendKind::PythonAttribute
+    ) && !matches!(
+        ty,
+        FuncBackendResponseType::CodeGeneration
+            | FuncBackendResponseType::Confirmation
+            | FuncBackendResponseType::Qualification
+    ) {
         return ""; // attribute functions have their output compiled dynamically
     }
 
@@ -863,6 +881,12 @@ pub fn routes() -> Router<AppState> {
             "/get_func_last_execution",
             get(get_func::get_latest_func_execution),
         )
+        .route("/get_execution", get(get_execution::get_execution))
+        .route(
+            "/get_execution_cache_stats",
+            get(get_execution_cache_stats::get_execution_cache_stats),
+        )
+        .route("/list_executions", get(list_executions::list_executions))
         .route("/create_func", post(create_func::create_func))
         .route("/save_func", post(save_func::save_func))
         .route("/save_and_exec", post(save_and_exec::save_and_exec))
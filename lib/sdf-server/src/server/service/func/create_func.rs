@@ -1,5 +1,5 @@
 use super::{FuncResult, FuncVariant};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use crate::service::func::FuncError;
 use axum::extract::OriginalUri;
@@ -7,11 +7,12 @@ use axum::Json;
 use dal::{
     generate_name, validation::prototype::context::ValidationPrototypeContext, ActionKind,
     ActionPrototype, ActionPrototypeContext, AttributeContextBuilder, AttributePrototype,
-    DalContext, ExternalProviderId, Func, FuncBackendResponseType, FuncId, LeafInputLocation,
-    LeafKind, PropId, SchemaVariant, SchemaVariantId, StandardModel, ValidationPrototype,
-    Visibility, WsEvent,
+    DalContext, ExternalProviderId, Func, FuncBackendKind, FuncBackendResponseType, FuncId,
+    LeafInputLocation, LeafKind, PropId, SchemaVariant, SchemaVariantId, StandardModel,
+    ValidationPrototype, Visibility, WsEvent,
 };
 use serde::{Deserialize, Serialize};
+use veritech_client::Runtime;
 
 #[remain::sorted]
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
@@ -58,6 +59,10 @@ pub struct CreateFuncRequest {
     variant: FuncVariant,
     name: Option<String>,
     options: Option<CreateFuncOptions>,
+    /// The language the func is written in. Only actions and attribute funcs can be written in
+    /// something other than JavaScript.
+    #[serde(default)]
+    runtime: Runtime,
     #[serde(flatten)]
     pub visibility: Visibility,
 }
@@ -84,11 +89,15 @@ pub static DEFAULT_ACTION_HANDLER: &str = "action";
 pub static DEFAULT_ACTION_CODE: &str = include_str!("./defaults/action.ts");
 pub static DEFAULT_VALIDATION_HANDLER: &str = "validate";
 pub static DEFAULT_VALIDATION_CODE: &str = include_str!("./defaults/validation.ts");
+pub static DEFAULT_PYTHON_ATTRIBUTE_CODE_HANDLER: &str = "set_attribute";
+pub static DEFAULT_PYTHON_ATTRIBUTE_CODE: &str = include_str!("./defaults/attribute.py");
+pub static DEFAULT_PYTHON_ACTION_HANDLER: &str = "action";
+pub static DEFAULT_PYTHON_ACTION_CODE: &str = include_str!("./defaults/action.py");
 
 async fn create_func_stub(
     ctx: &DalContext,
     name: Option<String>,
-    variant: FuncVariant,
+    backend_kind: FuncBackendKind,
     response_type: FuncBackendResponseType,
     code: &str,
     handler: &str,
@@ -98,7 +107,7 @@ async fn create_func_stub(
         return Err(FuncError::FuncNameExists(name));
     }
 
-    let mut func = Func::new(ctx, name, variant.into(), response_type).await?;
+    let mut func = Func::new(ctx, name, backend_kind, response_type).await?;
 
     func.set_code_plaintext(ctx, Some(code)).await?;
     func.set_handler(ctx, Some(handler)).await?;
@@ -114,7 +123,7 @@ async fn create_validation_func(
     let func = create_func_stub(
         ctx,
         name,
-        FuncVariant::Validation,
+        FuncVariant::Validation.into(),
         FuncBackendResponseType::Validation,
         DEFAULT_VALIDATION_CODE,
         DEFAULT_VALIDATION_HANDLER,
@@ -161,15 +170,29 @@ async fn create_validation_func(
 async fn create_action_func(
     ctx: &DalContext,
     name: Option<String>,
+    runtime: Runtime,
     options: Option<CreateFuncOptions>,
 ) -> FuncResult<Func> {
+    let (backend_kind, code, handler) = match runtime {
+        Runtime::JavaScript => (
+            FuncBackendKind::JsAction,
+            DEFAULT_ACTION_CODE,
+            DEFAULT_ACTION_HANDLER,
+        ),
+        Runtime::Python => (
+            FuncBackendKind::PythonAction,
+            DEFAULT_PYTHON_ACTION_CODE,
+            DEFAULT_PYTHON_ACTION_HANDLER,
+        ),
+    };
+
     let func = create_func_stub(
         ctx,
         name,
-        FuncVariant::Action,
+        backend_kind,
         FuncBackendResponseType::Action,
-        DEFAULT_ACTION_CODE,
-        DEFAULT_ACTION_HANDLER,
+        code,
+        handler,
     )
     .await?;
 
@@ -226,25 +249,31 @@ async fn create_attribute_func(
     ctx: &DalContext,
     name: Option<String>,
     variant: FuncVariant,
+    runtime: Runtime,
     options: Option<CreateFuncOptions>,
 ) -> FuncResult<Func> {
-    let (code, handler, response_type) = match variant {
-        FuncVariant::Attribute => (
+    let (code, handler, response_type) = match (variant, runtime) {
+        (FuncVariant::Attribute, Runtime::Python) => (
+            DEFAULT_PYTHON_ATTRIBUTE_CODE,
+            DEFAULT_PYTHON_ATTRIBUTE_CODE_HANDLER,
+            FuncBackendResponseType::Unset,
+        ),
+        (FuncVariant::Attribute, Runtime::JavaScript) => (
             DEFAULT_ATTRIBUTE_CODE,
             DEFAULT_ATTRIBUTE_CODE_HANDLER,
             FuncBackendResponseType::Unset,
         ),
-        FuncVariant::CodeGeneration => (
+        (FuncVariant::CodeGeneration, Runtime::JavaScript) => (
             DEFAULT_CODE_GENERATION_CODE,
             DEFAULT_CODE_GENERATION_HANDLER,
             FuncBackendResponseType::CodeGeneration,
         ),
-        FuncVariant::Confirmation => (
+        (FuncVariant::Confirmation, Runtime::JavaScript) => (
             DEFAULT_CONFIRMATION_CODE,
             DEFAULT_CONFIRMATION_HANDLER,
             FuncBackendResponseType::Confirmation,
         ),
-        FuncVariant::Qualification => (
+        (FuncVariant::Qualification, Runtime::JavaScript) => (
             DEFAULT_QUALIFICATION_CODE,
             DEFAULT_QUALIFICATION_HANDLER,
             FuncBackendResponseType::Qualification,
@@ -256,7 +285,11 @@ async fn create_attribute_func(
         }
     };
 
-    let func = create_func_stub(ctx, name, variant, response_type, code, handler).await?;
+    let backend_kind = match runtime {
+        Runtime::JavaScript => FuncBackendKind::JsAttribute,
+        Runtime::Python => FuncBackendKind::PythonAttribute,
+    };
+    let func = create_func_stub(ctx, name, backend_kind, response_type, code, handler).await?;
 
     if let Some(options) = options {
         match (variant, options) {
@@ -327,22 +360,42 @@ async fn create_attribute_func(
 pub async fn create_func(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CreateFuncRequest>,
 ) -> FuncResult<Json<CreateFuncResponse>> {
     let ctx = builder.build(request_ctx.build(request.visibility)).await?;
 
+    if request.runtime == Runtime::Python
+        && !matches!(
+            request.variant,
+            FuncVariant::Action | FuncVariant::Attribute
+        )
+    {
+        return Err(FuncError::RuntimeNotSupportedForVariant(
+            request.runtime,
+            request.variant,
+        ));
+    }
+
     let func = match request.variant {
         FuncVariant::Attribute => {
-            create_attribute_func(&ctx, request.name, FuncVariant::Attribute, request.options)
-                .await?
+            create_attribute_func(
+                &ctx,
+                request.name,
+                FuncVariant::Attribute,
+                request.runtime,
+                request.options,
+            )
+            .await?
         }
         FuncVariant::CodeGeneration => {
             create_attribute_func(
                 &ctx,
                 request.name,
                 FuncVariant::CodeGeneration,
+                request.runtime,
                 request.options,
             )
             .await?
@@ -352,11 +405,14 @@ pub async fn create_func(
                 &ctx,
                 request.name,
                 FuncVariant::Confirmation,
+                request.runtime,
                 request.options,
             )
             .await?
         }
-        FuncVariant::Action => create_action_func(&ctx, request.name, request.options).await?,
+        FuncVariant::Action => {
+            create_action_func(&ctx, request.name, request.runtime, request.options).await?
+        }
         FuncVariant::Validation => {
             create_validation_func(&ctx, request.name, request.options).await?
         }
@@ -365,6 +421,7 @@ pub async fn create_func(
                 &ctx,
                 request.name,
                 FuncVariant::Qualification,
+                request.runtime,
                 request.options,
             )
             .await?
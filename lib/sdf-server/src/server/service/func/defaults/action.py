@@ -0,0 +1,2 @@
+def action(args):
+    raise NotImplementedError("unimplemented!")
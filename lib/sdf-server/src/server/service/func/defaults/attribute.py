@@ -0,0 +1,2 @@
+def set_attribute(input):
+    return None
@@ -0,0 +1,33 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::func::execution::{FuncExecution, FuncExecutionPk};
+use serde::{Deserialize, Serialize};
+
+use super::{FuncError, FuncResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFuncExecutionRequest {
+    pub pk: FuncExecutionPk,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFuncExecutionResponse {
+    pub execution: FuncExecution,
+}
+
+pub async fn get_execution(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<GetFuncExecutionRequest>,
+) -> FuncResult<Json<GetFuncExecutionResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let execution = FuncExecution::find_by_pk(&ctx, request.pk)
+        .await?
+        .ok_or(FuncError::FuncExecutionNotFound(request.pk))?;
+
+    Ok(Json(GetFuncExecutionResponse { execution }))
+}
@@ -0,0 +1,23 @@
+use axum::Json;
+use dal::func::execution_cache::{FuncExecutionCache, FuncExecutionCacheStats};
+use serde::{Deserialize, Serialize};
+
+use super::FuncResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetExecutionCacheStatsResponse {
+    pub stats: FuncExecutionCacheStats,
+}
+
+pub async fn get_execution_cache_stats(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> FuncResult<Json<GetExecutionCacheStatsResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let stats = FuncExecutionCache::stats(&ctx).await?;
+
+    Ok(Json(GetExecutionCacheStatsResponse { stats }))
+}
@@ -45,6 +45,8 @@ pub struct GetFuncResponse {
     pub types: String,
     pub is_builtin: bool,
     pub is_revertible: bool,
+    pub deterministic: bool,
+    pub supports_dry_run: bool,
     pub associations: Option<FuncAssociations>,
 }
 
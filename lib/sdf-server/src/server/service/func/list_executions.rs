@@ -0,0 +1,61 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::func::execution::{
+    FuncExecution, FuncExecutionFilter, FuncExecutionState, DEFAULT_FUNC_EXECUTION_LIST_LIMIT,
+    MAX_FUNC_EXECUTION_LIST_LIMIT,
+};
+use dal::{ComponentId, FixId, FuncId};
+use serde::{Deserialize, Serialize};
+
+use super::FuncResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFuncExecutionsRequest {
+    pub func_id: Option<FuncId>,
+    pub component_id: Option<ComponentId>,
+    pub fix_id: Option<FixId>,
+    pub state: Option<FuncExecutionState>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFuncExecutionsResponse {
+    pub executions: Vec<FuncExecution>,
+    /// The offset of the next page, if there may be more executions.
+    pub next_offset: Option<i64>,
+}
+
+pub async fn list_executions(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<ListFuncExecutionsRequest>,
+) -> FuncResult<Json<ListFuncExecutionsResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_FUNC_EXECUTION_LIST_LIMIT)
+        .clamp(1, MAX_FUNC_EXECUTION_LIST_LIMIT);
+    let offset = request.offset.unwrap_or(0).max(0);
+    let filter = FuncExecutionFilter {
+        func_id: request.func_id,
+        component_id: request.component_id,
+        fix_id: request.fix_id,
+        state: request.state,
+        limit: Some(limit),
+        offset: Some(offset),
+    };
+    let executions = FuncExecution::list(&ctx, &filter).await?;
+
+    let next_offset =
+        (executions.len() as i64 >= limit).then_some(offset + executions.len() as i64);
+
+    Ok(Json(ListFuncExecutionsResponse {
+        executions,
+        next_offset,
+    }))
+}
@@ -42,6 +42,8 @@ pub async fn list_funcs(
             &FuncBackendKind::JsAction.as_ref().to_string(),
             &FuncBackendKind::JsAttribute.as_ref().to_string(),
             &FuncBackendKind::JsValidation.as_ref().to_string(),
+            &FuncBackendKind::PythonAction.as_ref().to_string(),
+            &FuncBackendKind::PythonAttribute.as_ref().to_string(),
         ],
     )
     .await?
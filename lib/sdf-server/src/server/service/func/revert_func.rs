@@ -4,7 +4,7 @@ use dal::{AttributePrototype, Func, FuncBackendKind, FuncId, StandardModel, Visi
 use serde::{Deserialize, Serialize};
 
 use super::{FuncError, FuncResult};
-use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireEdit};
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +23,7 @@ pub struct RevertFuncResponse {
 pub async fn revert_func(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     Json(request): Json<RevertFuncRequest>,
 ) -> FuncResult<Json<RevertFuncResponse>> {
     let ctx = builder.build(request_ctx.build(request.visibility)).await?;
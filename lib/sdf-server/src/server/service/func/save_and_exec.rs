@@ -2,7 +2,7 @@ use super::{
     save_func::{do_save_func, SaveFuncRequest, SaveFuncResponse},
     FuncError, FuncResult,
 };
-use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireEdit};
 use axum::Json;
 use dal::{
     job::definition::DependentValuesUpdate, ActionPrototype, AttributePrototype, AttributeValue,
@@ -153,7 +153,7 @@ async fn run_actions(ctx: &DalContext, func: &Func) -> FuncResult<()> {
         }
         let components = Component::list_for_schema_variant(ctx, schema_variant_id).await?;
         for component in components {
-            proto.run(ctx, *component.id(), true).await?;
+            proto.run(ctx, *component.id(), None, true).await?;
         }
     }
 
@@ -163,6 +163,7 @@ async fn run_actions(ctx: &DalContext, func: &Func) -> FuncResult<()> {
 pub async fn save_and_exec(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     Json(request): Json<SaveFuncRequest>,
 ) -> FuncResult<Json<SaveFuncResponse>> {
     let ctx = builder.build(request_ctx.build(request.visibility)).await?;
@@ -170,13 +171,13 @@ pub async fn save_and_exec(
     let (save_func_response, func) = do_save_func(&ctx, request).await?;
 
     match func.backend_kind() {
-        FuncBackendKind::JsAttribute => {
+        FuncBackendKind::JsAttribute | FuncBackendKind::PythonAttribute => {
             update_values_for_func(&ctx, &func).await?;
         }
         FuncBackendKind::JsValidation => {
             run_validations(&ctx, &func).await?;
         }
-        FuncBackendKind::JsAction => {
+        FuncBackendKind::JsAction | FuncBackendKind::PythonAction => {
             run_actions(&ctx, &func).await?;
         }
         _ => {}
@@ -8,7 +8,7 @@ use super::{
     FuncError, FuncResult,
 };
 use super::{FuncDescriptionView, ValidationPrototypeView};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use dal::{
     attribute::context::AttributeContextBuilder,
@@ -31,6 +31,12 @@ pub struct SaveFuncRequest {
     pub name: String,
     pub description: Option<String>,
     pub code: Option<String>,
+    /// Whether or not the func's results can be cached. Left as-is when not given.
+    #[serde(default)]
+    pub deterministic: Option<bool>,
+    /// Whether or not the func's action can be planned. Left as-is when not given.
+    #[serde(default)]
+    pub supports_dry_run: Option<bool>,
     pub associations: Option<FuncAssociations>,
     #[serde(flatten)]
     pub visibility: Visibility,
@@ -623,9 +629,15 @@ pub async fn do_save_func(
     func.set_handler(ctx, request.handler).await?;
     func.set_code_plaintext(ctx, request.code.as_deref())
         .await?;
+    if let Some(deterministic) = request.deterministic {
+        func.set_deterministic(ctx, deterministic).await?;
+    }
+    if let Some(supports_dry_run) = request.supports_dry_run {
+        func.set_supports_dry_run(ctx, supports_dry_run).await?;
+    }
 
     match func.backend_kind() {
-        FuncBackendKind::JsAction => {
+        FuncBackendKind::JsAction | FuncBackendKind::PythonAction => {
             if let Some(FuncAssociations::Action {
                 schema_variant_ids,
                 kind,
@@ -639,85 +651,87 @@ pub async fn do_save_func(
                 save_validation_func_prototypes(ctx, &func, prototypes).await?;
             }
         }
-        FuncBackendKind::JsAttribute => match func.backend_response_type() {
-            FuncBackendResponseType::CodeGeneration => {
-                if let Some(FuncAssociations::CodeGeneration {
-                    schema_variant_ids,
-                    component_ids,
-                    inputs,
-                }) = request.associations
-                {
-                    save_leaf_prototypes(
-                        ctx,
-                        &func,
+        FuncBackendKind::JsAttribute | FuncBackendKind::PythonAttribute => {
+            match func.backend_response_type() {
+                FuncBackendResponseType::CodeGeneration => {
+                    if let Some(FuncAssociations::CodeGeneration {
                         schema_variant_ids,
                         component_ids,
-                        &inputs,
-                        LeafKind::CodeGeneration,
-                    )
-                    .await?;
+                        inputs,
+                    }) = request.associations
+                    {
+                        save_leaf_prototypes(
+                            ctx,
+                            &func,
+                            schema_variant_ids,
+                            component_ids,
+                            &inputs,
+                            LeafKind::CodeGeneration,
+                        )
+                        .await?;
+                    }
                 }
-            }
-            FuncBackendResponseType::Confirmation => {
-                if let Some(FuncAssociations::Confirmation {
-                    schema_variant_ids,
-                    component_ids,
-                    descriptions,
-                    inputs,
-                }) = request.associations
-                {
-                    save_leaf_prototypes(
-                        ctx,
-                        &func,
+                FuncBackendResponseType::Confirmation => {
+                    if let Some(FuncAssociations::Confirmation {
                         schema_variant_ids,
                         component_ids,
-                        &inputs,
-                        LeafKind::Confirmation,
-                    )
-                    .await?;
+                        descriptions,
+                        inputs,
+                    }) = request.associations
+                    {
+                        save_leaf_prototypes(
+                            ctx,
+                            &func,
+                            schema_variant_ids,
+                            component_ids,
+                            &inputs,
+                            LeafKind::Confirmation,
+                        )
+                        .await?;
 
-                    save_func_descriptions(ctx, &func, descriptions).await?;
+                        save_func_descriptions(ctx, &func, descriptions).await?;
+                    }
                 }
-            }
-            FuncBackendResponseType::Qualification => {
-                if let Some(FuncAssociations::Qualification {
-                    schema_variant_ids,
-                    component_ids,
-                    inputs,
-                }) = request.associations
-                {
-                    save_leaf_prototypes(
-                        ctx,
-                        &func,
+                FuncBackendResponseType::Qualification => {
+                    if let Some(FuncAssociations::Qualification {
                         schema_variant_ids,
                         component_ids,
-                        &inputs,
-                        LeafKind::Qualification,
-                    )
-                    .await?;
+                        inputs,
+                    }) = request.associations
+                    {
+                        save_leaf_prototypes(
+                            ctx,
+                            &func,
+                            schema_variant_ids,
+                            component_ids,
+                            &inputs,
+                            LeafKind::Qualification,
+                        )
+                        .await?;
+                    }
                 }
-            }
-            _ => {
-                if let Some(FuncAssociations::Attribute {
-                    prototypes,
-                    arguments,
-                }) = request.associations
-                {
-                    let backend_response_type = save_attr_func_prototypes(
-                        ctx,
-                        &func,
+                _ => {
+                    if let Some(FuncAssociations::Attribute {
                         prototypes,
-                        RemovedPrototypeOp::Reset,
-                        None,
-                    )
-                    .await?;
-                    save_attr_func_arguments(ctx, &func, arguments).await?;
-
-                    func.set_backend_response_type(ctx, backend_response_type)
+                        arguments,
+                    }) = request.associations
+                    {
+                        let backend_response_type = save_attr_func_prototypes(
+                            ctx,
+                            &func,
+                            prototypes,
+                            RemovedPrototypeOp::Reset,
+                            None,
+                        )
                         .await?;
+                        save_attr_func_arguments(ctx, &func, arguments).await?;
+
+                        func.set_backend_response_type(ctx, backend_response_type)
+                            .await?;
+                    }
                 }
             }
-        },
+        }
         _ => {}
     }
 
@@ -740,6 +754,7 @@ pub async fn do_save_func(
 pub async fn save_func<'a>(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<SaveFuncRequest>,
@@ -2,7 +2,7 @@ use super::PkgResult;
 use crate::server::extract::RawAccessToken;
 use crate::server::tracking::track;
 use crate::{
-    server::extract::{AccessBuilder, HandlerContext, PosthogClient},
+    server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit},
     service::pkg::PkgError,
 };
 use axum::extract::OriginalUri;
@@ -30,6 +30,7 @@ pub struct InstallPkgResponse {
 pub async fn install_pkg(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     RawAccessToken(raw_access_token): RawAccessToken,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
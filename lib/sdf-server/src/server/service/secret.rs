@@ -4,15 +4,23 @@ use axum::routing::{get, post};
 use axum::Json;
 use axum::Router;
 use dal::{
-    KeyPairError, StandardModelError, TransactionsError, UserError, WorkspacePk, WsEventError,
+    KeyPairError, SecretBackendError, SecretId, SecretKind, StandardModelError, TransactionsError,
+    UserError, WorkspacePk, WsEventError,
 };
 use thiserror::Error;
 
 use crate::server::state::AppState;
 
+pub mod create_external_secret;
 pub mod create_secret;
+pub mod create_secret_definition;
+pub mod delete_secret;
 pub mod get_public_key;
+pub mod get_usage;
+pub mod list_secret_definitions;
 pub mod list_secrets;
+pub mod rotate_key_pair;
+pub mod update_secret;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -27,6 +35,10 @@ pub enum SecretError {
     Pg(#[from] si_data_pg::PgError),
     #[error(transparent)]
     Secret(#[from] dal::SecretError),
+    #[error("secret definition already exists for kind: {0}")]
+    SecretDefinitionExists(SecretKind),
+    #[error("secret not found: {0}")]
+    SecretNotFound(SecretId),
     #[error(transparent)]
     StandardModel(#[from] StandardModelError),
     #[error(transparent)]
@@ -41,8 +53,25 @@ pub type SecretResult<T> = std::result::Result<T, SecretError>;
 
 impl IntoResponse for SecretError {
     fn into_response(self) -> Response {
-        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
-        //SecretError::SecretNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+        let (status, error_message) = match self {
+            SecretError::Secret(dal::SecretError::Backend(
+                SecretBackendError::ReferenceRequired(_) | SecretBackendError::NotConfigured(_),
+            ))
+            | SecretError::Secret(
+                dal::SecretError::DefinitionNotFound(_)
+                | dal::SecretError::ExternalMessage(..)
+                | dal::SecretError::InvalidDefinition(..)
+                | dal::SecretError::InvalidKind(_)
+                | dal::SecretError::InvalidMessage(..),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            SecretError::Secret(dal::SecretError::InUse(..))
+            | SecretError::SecretDefinitionExists(_) => (StatusCode::CONFLICT, self.to_string()),
+            SecretError::Secret(dal::SecretError::User(UserError::PermissionDenied(..))) => {
+                (StatusCode::FORBIDDEN, self.to_string())
+            }
+            SecretError::SecretNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
 
         let body = Json(serde_json::json!({
             "error": {
@@ -60,5 +89,21 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/get_public_key", get(get_public_key::get_public_key))
         .route("/create_secret", post(create_secret::create_secret))
+        .route(
+            "/create_external_secret",
+            post(create_external_secret::create_external_secret),
+        )
         .route("/list_secrets", get(list_secrets::list_secrets))
+        .route(
+            "/list_secret_definitions",
+            get(list_secret_definitions::list_secret_definitions),
+        )
+        .route(
+            "/create_secret_definition",
+            post(create_secret_definition::create_secret_definition),
+        )
+        .route("/update_secret", post(update_secret::update_secret))
+        .route("/delete_secret", post(delete_secret::delete_secret))
+        .route("/get_usage", get(get_usage::get_usage))
+        .route("/rotate_key_pair", post(rotate_key_pair::rotate_key_pair))
 }
@@ -0,0 +1,56 @@
+use axum::Json;
+use dal::{
+    EncryptedSecret, Secret, SecretBackendKind, SecretKind, SecretObjectType, Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireManageSecrets};
+
+use super::SecretResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateExternalSecretRequest {
+    pub name: String,
+    pub object_type: SecretObjectType,
+    pub kind: SecretKind,
+    pub backend: SecretBackendKind,
+    /// Where the secret can be found in the backend, e.g. a Vault KV path.
+    pub backend_reference: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateExternalSecretResponse {
+    pub secret: Secret,
+}
+
+pub async fn create_external_secret(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_tx): AccessBuilder,
+    _: RequireManageSecrets,
+    Json(request): Json<CreateExternalSecretRequest>,
+) -> SecretResult<Json<CreateExternalSecretResponse>> {
+    let ctx = builder.build(request_tx.build(request.visibility)).await?;
+
+    let secret = EncryptedSecret::new_external(
+        &ctx,
+        request.name,
+        request.object_type,
+        request.kind,
+        request.backend,
+        request.backend_reference,
+    )
+    .await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateExternalSecretResponse { secret }))
+}
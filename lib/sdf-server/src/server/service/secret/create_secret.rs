@@ -1,13 +1,13 @@
 use axum::Json;
 use dal::{
     key_pair::KeyPairPk, EncryptedSecret, Secret, SecretAlgorithm, SecretKind, SecretObjectType,
-    SecretVersion, Visibility, WsEvent,
+    SecretVersion, StandardModel, Visibility, WsEvent,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireManageSecrets};
 
-use super::SecretResult;
+use super::{SecretError, SecretResult};
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -32,6 +32,7 @@ pub struct CreateSecretResponse {
 pub async fn create_secret(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_tx): AccessBuilder,
+    _: RequireManageSecrets,
     Json(request): Json<CreateSecretRequest>,
 ) -> SecretResult<Json<CreateSecretResponse>> {
     let ctx = builder.build(request_tx.build(request.visibility)).await?;
@@ -48,6 +49,13 @@ pub async fn create_secret(
     )
     .await?;
 
+    // The message can only be checked against the kind's definition once it has been decrypted.
+    EncryptedSecret::get_by_id(&ctx, secret.id())
+        .await?
+        .ok_or(SecretError::SecretNotFound(*secret.id()))?
+        .validate_message(&ctx)
+        .await?;
+
     WsEvent::change_set_written(&ctx)
         .await?
         .publish_on_commit(&ctx)
@@ -0,0 +1,62 @@
+use axum::Json;
+use dal::{
+    SecretDefinition, SecretDefinitionField, SecretDefinitionView, SecretKind, Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireManageSecrets};
+
+use super::{SecretError, SecretResult};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSecretDefinitionRequest {
+    pub kind: SecretKind,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub fields: Vec<SecretDefinitionField>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSecretDefinitionResponse {
+    pub definition: SecretDefinitionView,
+}
+
+pub async fn create_secret_definition(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_tx): AccessBuilder,
+    _: RequireManageSecrets,
+    Json(request): Json<CreateSecretDefinitionRequest>,
+) -> SecretResult<Json<CreateSecretDefinitionResponse>> {
+    let ctx = builder.build(request_tx.build(request.visibility)).await?;
+
+    if SecretDefinition::find_for_kind(&ctx, &request.kind)
+        .await?
+        .is_some()
+    {
+        return Err(SecretError::SecretDefinitionExists(request.kind));
+    }
+
+    let definition = SecretDefinition::new(
+        &ctx,
+        &request.kind,
+        request.display_name,
+        request.description,
+        request.fields,
+    )
+    .await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateSecretDefinitionResponse {
+        definition: definition.into(),
+    }))
+}
@@ -0,0 +1,41 @@
+use axum::Json;
+use dal::{Secret, SecretId, StandardModel, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireManageSecrets};
+
+use super::{SecretError, SecretResult};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSecretRequest {
+    pub id: SecretId,
+    /// Delete the secret even if [`Components`](dal::Component) still reference it.
+    #[serde(default)]
+    pub force: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn delete_secret(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_tx): AccessBuilder,
+    _: RequireManageSecrets,
+    Json(request): Json<DeleteSecretRequest>,
+) -> SecretResult<Json<()>> {
+    let ctx = builder.build(request_tx.build(request.visibility)).await?;
+
+    let mut secret = Secret::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(SecretError::SecretNotFound(request.id))?;
+    secret.delete(&ctx, request.force).await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(()))
+}
@@ -0,0 +1,32 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{Secret, SecretConsumers, SecretId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{SecretError, SecretResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSecretUsageRequest {
+    pub id: SecretId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type GetSecretUsageResponse = SecretConsumers;
+
+pub async fn get_usage(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetSecretUsageRequest>,
+) -> SecretResult<Json<GetSecretUsageResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let secret = Secret::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(SecretError::SecretNotFound(request.id))?;
+    let consumers = secret.list_consumers(&ctx).await?;
+
+    Ok(Json(consumers))
+}
@@ -0,0 +1,36 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{SecretDefinition, SecretDefinitionView, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::SecretResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSecretDefinitionsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSecretDefinitionsResponse {
+    pub list: Vec<SecretDefinitionView>,
+}
+
+pub async fn list_secret_definitions(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListSecretDefinitionsRequest>,
+) -> SecretResult<Json<ListSecretDefinitionsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let list: Vec<SecretDefinitionView> = SecretDefinition::list(&ctx)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListSecretDefinitionsResponse { list }))
+}
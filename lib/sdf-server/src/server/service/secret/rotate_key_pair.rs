@@ -0,0 +1,24 @@
+use axum::Json;
+use dal::{KeyPair, PublicKey};
+
+use super::SecretResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireManageSecrets};
+
+pub type RotateKeyPairResponse = PublicKey;
+
+/// Rotates the workspace's key pair, re-encrypting every secret with the new one, and returns the
+/// new public key.
+pub async fn rotate_key_pair(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    _: RequireManageSecrets,
+) -> SecretResult<Json<RotateKeyPairResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    KeyPair::rotate(&ctx).await?;
+    let response: RotateKeyPairResponse = PublicKey::get_current(&ctx).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(response))
+}
@@ -0,0 +1,72 @@
+use axum::Json;
+use dal::{
+    key_pair::KeyPairPk, Secret, SecretAlgorithm, SecretId, SecretVersion, StandardModel,
+    Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, RequireManageSecrets};
+
+use super::{SecretError, SecretResult};
+
+/// A replacement message for a secret, sealed with the public key for `key_pair_pk`.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSecretMessage {
+    pub crypted: Vec<u8>,
+    pub key_pair_pk: KeyPairPk,
+    pub version: SecretVersion,
+    pub algorithm: SecretAlgorithm,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSecretRequest {
+    pub id: SecretId,
+    pub name: Option<String>,
+    pub message: Option<UpdateSecretMessage>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSecretResponse {
+    pub secret: Secret,
+}
+
+pub async fn update_secret(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_tx): AccessBuilder,
+    _: RequireManageSecrets,
+    Json(request): Json<UpdateSecretRequest>,
+) -> SecretResult<Json<UpdateSecretResponse>> {
+    let ctx = builder.build(request_tx.build(request.visibility)).await?;
+
+    let mut secret = Secret::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(SecretError::SecretNotFound(request.id))?;
+    if let Some(name) = request.name {
+        secret.set_name(&ctx, name).await?;
+    }
+    if let Some(message) = request.message {
+        secret
+            .update_message(
+                &ctx,
+                &message.crypted,
+                message.key_pair_pk,
+                message.version,
+                message.algorithm,
+            )
+            .await?;
+    }
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(UpdateSecretResponse { secret }))
+}
@@ -1,7 +1,7 @@
 use super::{SessionError, SessionResult};
 use crate::server::extract::HandlerContext;
 use axum::Json;
-use dal::{HistoryActor, KeyPair, Tenancy, User, UserPk, Workspace, WorkspacePk};
+use dal::{HistoryActor, KeyPair, Tenancy, User, UserPk, Workspace, WorkspacePk, WorkspaceRole};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -120,12 +120,19 @@ pub async fn auth_connect(
             .await?;
             let _key_pair = KeyPair::new(&ctx, "default").await?;
             ctx.import_builtins().await?;
+
+            // whoever creates the workspace owns it
+            user.associate_workspace_with_role(&ctx, *workspace.pk(), WorkspaceRole::Owner)
+                .await?;
             workspace
         }
     };
 
-    // ensure workspace is associated to user
-    user.associate_workspace(&ctx, *workspace.pk()).await?;
+    // ensure workspace is associated to user, with the role they were invited with (if any);
+    // anyone else can only read until an owner changes their role
+    if workspace.accept_invitation(&ctx, &user).await?.is_none() {
+        user.associate_workspace(&ctx, *workspace.pk()).await?;
+    }
 
     ctx.commit().await?;
 
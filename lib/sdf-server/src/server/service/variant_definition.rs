@@ -148,6 +148,9 @@ pub async fn save_variant_def(
     variant_def
         .set_component_type(ctx, request.component_type)
         .await?;
+    variant_def
+        .set_refresh_interval_secs(ctx, request.refresh_interval_secs)
+        .await?;
 
     let mut asset_func = Func::get_by_id(ctx, &variant_def.func_id()).await?.ok_or(
         SchemaVariantDefinitionError::FuncNotFound(variant_def.func_id()),
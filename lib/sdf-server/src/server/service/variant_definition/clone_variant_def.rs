@@ -1,5 +1,5 @@
 use super::{SchemaVariantDefinitionError, SchemaVariantDefinitionResult};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
@@ -31,6 +31,7 @@ pub struct CloneVariantDefResponse {
 pub async fn clone_variant_def(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CloneVariantDefRequest>,
@@ -67,7 +68,8 @@ pub async fn clone_variant_def(
     )?;
     let duplicated_func = func.duplicate(&ctx).await?;
 
-    let variant_def = SchemaVariantDefinition::new(
+    let refresh_interval_secs = variant_def.refresh_interval_secs().copied();
+    let mut variant_def = SchemaVariantDefinition::new(
         &ctx,
         name,
         menu_name,
@@ -79,6 +81,9 @@ pub async fn clone_variant_def(
         *duplicated_func.id(),
     )
     .await?;
+    variant_def
+        .set_refresh_interval_secs(&ctx, refresh_interval_secs)
+        .await?;
 
     track(
         &posthog_client,
@@ -1,5 +1,5 @@
 use super::SchemaVariantDefinitionResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
@@ -38,6 +38,7 @@ pub struct CreateVariantDefResponse {
 pub async fn create_variant_def(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CreateVariantDefRequest>,
@@ -3,7 +3,7 @@ use super::{
     migrate_leaf_functions_to_new_schema_variant, SchemaVariantDefinitionError,
     SchemaVariantDefinitionResult,
 };
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
@@ -34,6 +34,7 @@ pub struct ExecVariantDefResponse {
 pub async fn exec_variant_def(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<ExecVariantDefRequest>,
@@ -33,6 +33,7 @@ pub struct GetVariantDefResponse {
     pub handler: String,
     pub schema_variant_id: Option<SchemaVariantId>,
     pub component_type: ComponentType,
+    pub refresh_interval_secs: Option<i64>,
     pub funcs: Vec<ListedFuncView>,
     pub types: String,
     pub has_components: bool,
@@ -56,6 +57,7 @@ impl From<SchemaVariantDefinition> for GetVariantDefResponse {
             funcs: vec![],
             schema_variant_id: None,
             component_type: *def.component_type(),
+            refresh_interval_secs: def.refresh_interval_secs().copied(),
             handler: "".to_string(), //TODO @stack72
             types: "".to_string(),
             has_components: false,
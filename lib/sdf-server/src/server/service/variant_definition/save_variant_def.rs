@@ -1,5 +1,5 @@
 use super::SchemaVariantDefinitionResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RequireEdit};
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
@@ -20,6 +20,9 @@ pub struct SaveVariantDefRequest {
     pub handler: String,
     pub description: Option<String>,
     pub component_type: ComponentType,
+    /// How often the resources of the variant's components are refreshed, in seconds.
+    #[serde(default)]
+    pub refresh_interval_secs: Option<i64>,
     #[serde(flatten)]
     pub visibility: Visibility,
 }
@@ -33,6 +36,7 @@ pub struct SaveVariantDefResponse {
 pub async fn save_variant_def(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequireEdit,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<SaveVariantDefRequest>,
@@ -0,0 +1,78 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Json;
+use axum::Router;
+use dal::{TransactionsError, UserError, WorkspaceError as DalWorkspaceError, WorkspacePk};
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod invite_member;
+pub mod list_members;
+pub mod remove_member;
+pub mod revoke_invitation;
+pub mod transfer_ownership;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum WorkspaceError {
+    #[error(transparent)]
+    ContextTransactions(#[from] TransactionsError),
+    #[error(transparent)]
+    Nats(#[from] si_data_nats::NatsError),
+    #[error(transparent)]
+    Pg(#[from] si_data_pg::PgError),
+    #[error(transparent)]
+    User(#[from] UserError),
+    #[error(transparent)]
+    Workspace(#[from] DalWorkspaceError),
+    #[error("workspace not found: {0}")]
+    WorkspaceNotFound(WorkspacePk),
+}
+
+pub type WorkspaceResult<T> = std::result::Result<T, WorkspaceError>;
+
+impl IntoResponse for WorkspaceError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            WorkspaceError::Workspace(
+                DalWorkspaceError::AlreadyMember(..) | DalWorkspaceError::CannotRemoveLastOwner(..),
+            ) => (StatusCode::CONFLICT, self.to_string()),
+            WorkspaceError::Workspace(DalWorkspaceError::InvitationNotFound(_))
+            | WorkspaceError::Workspace(DalWorkspaceError::NotMemberOfWorkspace(..))
+            | WorkspaceError::WorkspaceNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            WorkspaceError::User(UserError::PermissionDenied(..))
+            | WorkspaceError::Workspace(
+                DalWorkspaceError::NotOwner(..)
+                | DalWorkspaceError::User(UserError::PermissionDenied(..)),
+            ) => (StatusCode::FORBIDDEN, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(serde_json::json!({
+            "error": {
+                "message": error_message,
+                "code": 42,
+                "statusCode": status.as_u16()
+            }
+        }));
+
+        (status, body).into_response()
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/list_members", get(list_members::list_members))
+        .route("/invite_member", post(invite_member::invite_member))
+        .route(
+            "/revoke_invitation",
+            post(revoke_invitation::revoke_invitation),
+        )
+        .route("/remove_member", post(remove_member::remove_member))
+        .route(
+            "/transfer_ownership",
+            post(transfer_ownership::transfer_ownership),
+        )
+}
@@ -0,0 +1,57 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{Workspace, WorkspaceInvitation, WorkspaceRole};
+use serde::{Deserialize, Serialize};
+
+use super::{WorkspaceError, WorkspaceResult};
+use crate::server::extract::{
+    AccessBuilder, Authorization, HandlerContext, PosthogClient, RequireManageMembers,
+};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteMemberRequest {
+    pub email: String,
+    pub role: WorkspaceRole,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteMemberResponse {
+    pub invitation: WorkspaceInvitation,
+}
+
+pub async fn invite_member(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    _: RequireManageMembers,
+    Authorization(claim): Authorization,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<InviteMemberRequest>,
+) -> WorkspaceResult<Json<InviteMemberResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let workspace = Workspace::get_by_pk(&ctx, &claim.workspace_pk)
+        .await?
+        .ok_or(WorkspaceError::WorkspaceNotFound(claim.workspace_pk))?;
+    let invitation = workspace
+        .invite_member(&ctx, &request.email, request.role)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "invite_workspace_member",
+        serde_json::json!({
+            "invitation_pk": invitation.pk,
+            "role": request.role,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(InviteMemberResponse { invitation }))
+}
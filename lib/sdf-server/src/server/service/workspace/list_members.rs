@@ -0,0 +1,30 @@
+use axum::Json;
+use dal::{Workspace, WorkspaceInvitation, WorkspaceMember};
+use serde::{Deserialize, Serialize};
+
+use super::{WorkspaceError, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, Authorization, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMembersResponse {
+    pub members: Vec<WorkspaceMember>,
+    pub pending_invitations: Vec<WorkspaceInvitation>,
+}
+
+pub async fn list_members(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Authorization(claim): Authorization,
+) -> WorkspaceResult<Json<ListMembersResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let workspace = Workspace::get_by_pk(&ctx, &claim.workspace_pk)
+        .await?
+        .ok_or(WorkspaceError::WorkspaceNotFound(claim.workspace_pk))?;
+
+    Ok(Json(ListMembersResponse {
+        members: workspace.list_members(&ctx).await?,
+        pending_invitations: workspace.list_invitations(&ctx).await?,
+    }))
+}
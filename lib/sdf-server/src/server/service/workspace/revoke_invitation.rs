@@ -0,0 +1,49 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{Workspace, WorkspaceInvitationPk};
+use serde::{Deserialize, Serialize};
+
+use super::{WorkspaceError, WorkspaceResult};
+use crate::server::extract::{
+    AccessBuilder, Authorization, HandlerContext, PosthogClient, RequireManageMembers,
+};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeInvitationRequest {
+    pub invitation_pk: WorkspaceInvitationPk,
+}
+
+pub async fn revoke_invitation(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    _: RequireManageMembers,
+    Authorization(claim): Authorization,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RevokeInvitationRequest>,
+) -> WorkspaceResult<Json<()>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let workspace = Workspace::get_by_pk(&ctx, &claim.workspace_pk)
+        .await?
+        .ok_or(WorkspaceError::WorkspaceNotFound(claim.workspace_pk))?;
+    workspace
+        .revoke_invitation(&ctx, request.invitation_pk)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "revoke_workspace_invitation",
+        serde_json::json!({
+            "invitation_pk": request.invitation_pk,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(()))
+}
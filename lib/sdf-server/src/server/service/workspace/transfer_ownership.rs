@@ -0,0 +1,50 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{UserPk, Workspace};
+use serde::{Deserialize, Serialize};
+
+use super::{WorkspaceError, WorkspaceResult};
+use crate::server::extract::{
+    AccessBuilder, Authorization, HandlerContext, PosthogClient, RequireManageMembers,
+};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferOwnershipRequest {
+    /// The member who will become an owner. The requesting owner is demoted to an applier.
+    pub user_pk: UserPk,
+}
+
+pub async fn transfer_ownership(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    _: RequireManageMembers,
+    Authorization(claim): Authorization,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<TransferOwnershipRequest>,
+) -> WorkspaceResult<Json<()>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let workspace = Workspace::get_by_pk(&ctx, &claim.workspace_pk)
+        .await?
+        .ok_or(WorkspaceError::WorkspaceNotFound(claim.workspace_pk))?;
+    workspace
+        .transfer_ownership(&ctx, claim.user_pk, request.user_pk)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "transfer_workspace_ownership",
+        serde_json::json!({
+            "user_pk": request.user_pk,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(()))
+}
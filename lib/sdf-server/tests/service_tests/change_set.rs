@@ -8,6 +8,7 @@ use sdf_server::service::change_set::{
     create_change_set::{CreateChangeSetRequest, CreateChangeSetResponse},
     get_change_set::{GetChangeSetRequest, GetChangeSetResponse},
     list_open_change_sets::ListOpenChangeSetsResponse,
+    rebase_change_set::{RebaseChangeSetRequest, RebaseChangeSetResponse},
 };
 
 use crate::service_tests::{
@@ -87,3 +88,28 @@ async fn apply_change_set(
     )
     .await;
 }
+
+#[sdf_test]
+async fn rebase_change_set(
+    DalContextHead(ctx): DalContextHead,
+    app: Router,
+    AuthTokenRef(auth_token): AuthTokenRef<'_>,
+) {
+    let change_set = dal_create_change_set(&ctx).await;
+    ctx.commit().await.expect("cannot commit txn");
+    let request = RebaseChangeSetRequest {
+        change_set_pk: change_set.pk,
+        resolutions: vec![],
+    };
+
+    let response: RebaseChangeSetResponse = api_request_auth_json_body(
+        app,
+        Method::POST,
+        "/api/change_set/rebase_change_set",
+        auth_token,
+        &request,
+    )
+    .await;
+    assert!(response.report.rebased);
+    assert!(response.change_set.rebased_at.is_some());
+}
@@ -425,6 +425,7 @@ impl ScenarioHarness {
             link: Some("https://www.systeminit.com/".to_string()),
             code,
             component_type: ComponentType::Component,
+            refresh_interval_secs: None,
             handler: "createAsset".to_string(),
             description: None,
             visibility: *visibility,
@@ -453,6 +454,7 @@ impl ScenarioHarness {
             link: Some("https://www.systeminit.com/".to_string()),
             code,
             component_type: ComponentType::Component,
+            refresh_interval_secs: None,
             handler: "createAsset".to_string(),
             description: None,
             visibility: *visibility,
@@ -597,6 +599,7 @@ impl ScenarioHarness {
     ) -> FixBatchId {
         let request = dbg!(FixesRunRequest {
             list: fixes,
+            failure_policy: Default::default(),
             visibility: *visibility,
         });
         let response: FixesRunResponse = dbg!(self.query_post("/api/fix/run", &request).await);
@@ -18,13 +18,13 @@ async fn create_secret(
 ) {
     let visibility = Visibility::new_head(false);
 
-    let message = serde_json::json!({"artist":"Billy Talent"});
+    let message = serde_json::json!({"username": "billy-talent", "password": "reckless-paradise"});
     let crypted = encrypt_message(&ctx, nw.key_pair.pk(), &message).await;
 
     let request = CreateSecretRequest {
         name: "reckless-paradise".to_string(),
         object_type: SecretObjectType::Credential,
-        kind: SecretKind::DockerHub,
+        kind: SecretKind::DOCKER_HUB,
         crypted,
         key_pair_pk: nw.key_pair.pk(),
         version: SecretVersion::V1,
@@ -42,7 +42,7 @@ async fn create_secret(
     .await;
     assert_eq!(response.secret.name(), "reckless-paradise");
     assert_eq!(response.secret.object_type(), &SecretObjectType::Credential);
-    assert_eq!(response.secret.kind(), &SecretKind::DockerHub);
+    assert_eq!(response.secret.kind(), &SecretKind::DOCKER_HUB);
 
     let decrypted_secret = EncryptedSecret::get_by_id(&ctx, response.secret.id())
         .await
@@ -54,7 +54,7 @@ async fn create_secret(
 
     assert_eq!(decrypted_secret.name(), "reckless-paradise");
     assert_eq!(decrypted_secret.object_type(), SecretObjectType::Credential);
-    assert_eq!(decrypted_secret.kind(), SecretKind::DockerHub);
+    assert_eq!(decrypted_secret.kind(), SecretKind::DOCKER_HUB);
     // We don't provide a direct getter for the raw decrypted message (higher effort should mean
     // less chance of developer error when handling `DecryptedSecret` types), so we'll serialize to
     // a `Value` to compare messages
@@ -5,7 +5,8 @@ mod spec;
 pub use pkg::{
     SiPkg, SiPkgActionFunc, SiPkgAttrFuncInput, SiPkgAttrFuncInputView, SiPkgError, SiPkgFunc,
     SiPkgFuncDescription, SiPkgLeafFunction, SiPkgMapKeyFunc, SiPkgMetadata, SiPkgProp,
-    SiPkgSchema, SiPkgSchemaVariant, SiPkgSocket, SiPkgValidation,
+    SiPkgSchema, SiPkgSchemaVariant, SiPkgSecretDefinition, SiPkgSecretDefinitionField,
+    SiPkgSocket, SiPkgValidation,
 };
 pub use spec::{
     ActionFuncSpec, ActionFuncSpecBuilder, ActionFuncSpecKind, AttrFuncInputSpec,
@@ -15,8 +16,10 @@ pub use spec::{
     LeafInputLocation, LeafKind, MapKeyFuncSpec, MapKeyFuncSpecBuilder, PkgSpec, PkgSpecBuilder,
     PropSpec, PropSpecBuilder, PropSpecKind, PropSpecWidgetKind, SchemaSpec, SchemaSpecBuilder,
     SchemaVariantSpec, SchemaVariantSpecBuilder, SchemaVariantSpecComponentType,
-    SchemaVariantSpecPropRoot, SiPropFuncSpec, SiPropFuncSpecBuilder, SiPropFuncSpecKind,
-    SocketSpec, SocketSpecArity, SocketSpecKind, SpecError, ValidationSpec, ValidationSpecKind,
+    SchemaVariantSpecPropRoot, SecretDefinitionFieldSpec, SecretDefinitionFieldSpecBuilder,
+    SecretDefinitionSpec, SecretDefinitionSpecBuilder, SiPropFuncSpec, SiPropFuncSpecBuilder,
+    SiPropFuncSpecKind, SocketSpec, SocketSpecArity, SocketSpecKind, SpecError, ValidationSpec,
+    ValidationSpecKind,
 };
 
 #[cfg(test)]
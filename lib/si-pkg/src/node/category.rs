@@ -6,12 +6,13 @@ use object_tree::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{FuncSpec, SchemaSpec};
+use crate::{FuncSpec, SchemaSpec, SecretDefinitionSpec};
 
 use super::PkgNode;
 
 const CATEGORY_TYPE_SCHEMAS: &str = "schemas";
 const CATEGORY_TYPE_FUNCS: &str = "funcs";
+const CATEGORY_TYPE_SECRET_DEFINITIONS: &str = "secret_definitions";
 
 const KEY_KIND_STR: &str = "kind";
 
@@ -21,6 +22,7 @@ const KEY_KIND_STR: &str = "kind";
 pub enum PackageCategory {
     Funcs(Vec<FuncSpec>),
     Schemas(Vec<SchemaSpec>),
+    SecretDefinitions(Vec<SecretDefinitionSpec>),
 }
 
 #[remain::sorted]
@@ -28,6 +30,7 @@ pub enum PackageCategory {
 pub enum CategoryNode {
     Funcs,
     Schemas,
+    SecretDefinitions,
 }
 
 impl CategoryNode {
@@ -35,6 +38,7 @@ impl CategoryNode {
         match self {
             Self::Schemas => CATEGORY_TYPE_SCHEMAS,
             Self::Funcs => CATEGORY_TYPE_FUNCS,
+            Self::SecretDefinitions => CATEGORY_TYPE_SECRET_DEFINITIONS,
         }
     }
 }
@@ -44,6 +48,7 @@ impl NameStr for CategoryNode {
         match self {
             Self::Schemas => CATEGORY_TYPE_SCHEMAS,
             Self::Funcs => CATEGORY_TYPE_FUNCS,
+            Self::SecretDefinitions => CATEGORY_TYPE_SECRET_DEFINITIONS,
         }
     }
 }
@@ -65,6 +70,7 @@ impl ReadBytes for CategoryNode {
         let node = match kind_str.as_str() {
             CATEGORY_TYPE_SCHEMAS => Self::Schemas,
             CATEGORY_TYPE_FUNCS => Self::Funcs,
+            CATEGORY_TYPE_SECRET_DEFINITIONS => Self::SecretDefinitions,
             invalid_kind => {
                 return Err(GraphError::parse_custom(format!(
                     "invalid package category node kind: {invalid_kind}"
@@ -109,6 +115,20 @@ impl NodeChild for PackageCategory {
                     children,
                 )
             }
+            Self::SecretDefinitions(entries) => {
+                let mut children = Vec::new();
+                for entry in entries {
+                    children
+                        .push(Box::new(entry.clone())
+                            as Box<dyn NodeChild<NodeType = Self::NodeType>>);
+                }
+
+                NodeWithChildren::new(
+                    NodeKind::Tree,
+                    Self::NodeType::Category(CategoryNode::SecretDefinitions),
+                    children,
+                )
+            }
         }
     }
 }
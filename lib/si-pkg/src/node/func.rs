@@ -21,6 +21,8 @@ const KEY_CODE_STR: &str = "code_base64";
 const KEY_BACKEND_KIND_STR: &str = "backend_kind";
 const KEY_RESPONSE_TYPE_STR: &str = "response_type";
 const KEY_HIDDEN_STR: &str = "hidden";
+const KEY_DETERMINISTIC_STR: &str = "deterministic";
+const KEY_SUPPORTS_DRY_RUN_STR: &str = "supports_dry_run";
 const KEY_LINK_STR: &str = "link";
 const KEY_UNIQUE_ID_STR: &str = "unique_id";
 
@@ -34,6 +36,8 @@ pub struct FuncNode {
     pub backend_kind: FuncSpecBackendKind,
     pub response_type: FuncSpecBackendResponseType,
     pub hidden: bool,
+    pub deterministic: bool,
+    pub supports_dry_run: bool,
     pub link: Option<Url>,
     pub unique_id: FuncUniqueId,
 }
@@ -62,6 +66,8 @@ impl WriteBytes for FuncNode {
         write_key_value_line(writer, KEY_BACKEND_KIND_STR, self.backend_kind)?;
         write_key_value_line(writer, KEY_RESPONSE_TYPE_STR, self.response_type)?;
         write_key_value_line(writer, KEY_HIDDEN_STR, self.hidden)?;
+        write_key_value_line(writer, KEY_DETERMINISTIC_STR, self.deterministic)?;
+        write_key_value_line(writer, KEY_SUPPORTS_DRY_RUN_STR, self.supports_dry_run)?;
         write_key_value_line(
             writer,
             KEY_LINK_STR,
@@ -101,6 +107,11 @@ impl ReadBytes for FuncNode {
             FuncSpecBackendResponseType::from_str(&response_type_str).map_err(GraphError::parse)?;
         let hidden = bool::from_str(&read_key_value_line(reader, KEY_HIDDEN_STR)?)
             .map_err(GraphError::parse)?;
+        let deterministic = bool::from_str(&read_key_value_line(reader, KEY_DETERMINISTIC_STR)?)
+            .map_err(GraphError::parse)?;
+        let supports_dry_run =
+            bool::from_str(&read_key_value_line(reader, KEY_SUPPORTS_DRY_RUN_STR)?)
+                .map_err(GraphError::parse)?;
         let link_str = read_key_value_line(reader, KEY_LINK_STR)?;
         let link = if link_str.is_empty() {
             None
@@ -119,6 +130,8 @@ impl ReadBytes for FuncNode {
             backend_kind,
             response_type,
             hidden,
+            deterministic,
+            supports_dry_run,
             link,
             unique_id,
         })
@@ -146,6 +159,8 @@ impl NodeChild for FuncSpec {
                 backend_kind: self.backend_kind,
                 response_type: self.response_type,
                 hidden: self.hidden,
+                deterministic: self.deterministic,
+                supports_dry_run: self.supports_dry_run,
                 link: self.link.as_ref().cloned(),
                 unique_id: self.unique_id,
             }),
@@ -18,6 +18,8 @@ mod prop_child;
 mod schema;
 mod schema_variant;
 mod schema_variant_child;
+mod secret_definition;
+mod secret_definition_field;
 mod si_prop_func;
 mod socket;
 mod validation;
@@ -37,6 +39,8 @@ pub(crate) use self::{
     schema::SchemaNode,
     schema_variant::SchemaVariantNode,
     schema_variant_child::{SchemaVariantChild, SchemaVariantChildNode},
+    secret_definition::SecretDefinitionNode,
+    secret_definition_field::SecretDefinitionFieldNode,
     si_prop_func::SiPropFuncNode,
     socket::SocketNode,
     validation::ValidationNode,
@@ -56,6 +60,8 @@ const NODE_KIND_PROP_CHILD: &str = "prop_child";
 const NODE_KIND_SCHEMA: &str = "schema";
 const NODE_KIND_SCHEMA_VARIANT: &str = "schema_variant";
 const NODE_KIND_SCHEMA_VARIANT_CHILD: &str = "schema_variant_child";
+const NODE_KIND_SECRET_DEFINITION: &str = "secret_definition";
+const NODE_KIND_SECRET_DEFINITION_FIELD: &str = "secret_definition_field";
 const NODE_KIND_SOCKET: &str = "socket";
 const NODE_KIND_SI_PROP_FUNC: &str = "si_prop_func";
 const NODE_KIND_VALIDATION: &str = "validation";
@@ -79,6 +85,8 @@ pub enum PkgNode {
     Schema(SchemaNode),
     SchemaVariant(SchemaVariantNode),
     SchemaVariantChild(SchemaVariantChildNode),
+    SecretDefinition(SecretDefinitionNode),
+    SecretDefinitionField(SecretDefinitionFieldNode),
     SiPropFunc(SiPropFuncNode),
     Socket(SocketNode),
     Validation(ValidationNode),
@@ -99,6 +107,8 @@ impl PkgNode {
     pub const SCHEMA_KIND_STR: &str = NODE_KIND_SCHEMA;
     pub const SCHEMA_VARIANT_KIND_STR: &str = NODE_KIND_SCHEMA_VARIANT;
     pub const SCHEMA_VARIANT_KIND_CHILD_STR: &str = NODE_KIND_SCHEMA_VARIANT_CHILD;
+    pub const SECRET_DEFINITION_KIND_STR: &str = NODE_KIND_SECRET_DEFINITION;
+    pub const SECRET_DEFINITION_FIELD_KIND_STR: &str = NODE_KIND_SECRET_DEFINITION_FIELD;
     pub const SOCKET_KIND_STR: &str = NODE_KIND_SOCKET;
     pub const SI_PROP_FUNC_KIND_STR: &str = NODE_KIND_SI_PROP_FUNC;
     pub const VALIDATION_KIND_STR: &str = NODE_KIND_VALIDATION;
@@ -119,6 +129,8 @@ impl PkgNode {
             Self::Schema(_) => NODE_KIND_SCHEMA,
             Self::SchemaVariant(_) => NODE_KIND_SCHEMA_VARIANT,
             Self::SchemaVariantChild(_) => NODE_KIND_SCHEMA_VARIANT_CHILD,
+            Self::SecretDefinition(_) => NODE_KIND_SECRET_DEFINITION,
+            Self::SecretDefinitionField(_) => NODE_KIND_SECRET_DEFINITION_FIELD,
             Self::Socket(_) => NODE_KIND_SOCKET,
             Self::SiPropFunc(_) => NODE_KIND_SI_PROP_FUNC,
             Self::Validation(_) => NODE_KIND_VALIDATION,
@@ -143,6 +155,8 @@ impl NameStr for PkgNode {
             Self::Schema(node) => node.name(),
             Self::SchemaVariant(node) => node.name(),
             Self::SchemaVariantChild(node) => node.name(),
+            Self::SecretDefinition(node) => node.name(),
+            Self::SecretDefinitionField(node) => node.name(),
             Self::Socket(node) => node.name(),
             Self::SiPropFunc(_) => NODE_KIND_SI_PROP_FUNC,
             Self::Validation(_) => NODE_KIND_VALIDATION,
@@ -169,6 +183,8 @@ impl WriteBytes for PkgNode {
             Self::Schema(node) => node.write_bytes(writer)?,
             Self::SchemaVariant(node) => node.write_bytes(writer)?,
             Self::SchemaVariantChild(node) => node.write_bytes(writer)?,
+            Self::SecretDefinition(node) => node.write_bytes(writer)?,
+            Self::SecretDefinitionField(node) => node.write_bytes(writer)?,
             Self::Socket(node) => node.write_bytes(writer)?,
             Self::SiPropFunc(node) => node.write_bytes(writer)?,
             Self::Validation(node) => node.write_bytes(writer)?,
@@ -206,6 +222,12 @@ impl ReadBytes for PkgNode {
             NODE_KIND_SCHEMA_VARIANT_CHILD => {
                 Self::SchemaVariantChild(SchemaVariantChildNode::read_bytes(reader)?)
             }
+            NODE_KIND_SECRET_DEFINITION => {
+                Self::SecretDefinition(SecretDefinitionNode::read_bytes(reader)?)
+            }
+            NODE_KIND_SECRET_DEFINITION_FIELD => {
+                Self::SecretDefinitionField(SecretDefinitionFieldNode::read_bytes(reader)?)
+            }
             NODE_KIND_SOCKET => Self::Socket(SocketNode::read_bytes(reader)?),
             NODE_KIND_SI_PROP_FUNC => Self::SiPropFunc(SiPropFuncNode::read_bytes(reader)?),
             NODE_KIND_VALIDATION => Self::Validation(ValidationNode::read_bytes(reader)?),
@@ -85,6 +85,9 @@ impl NodeChild for PkgSpec {
                     as Box<dyn NodeChild<NodeType = Self::NodeType>>,
                 Box::new(PackageCategory::Funcs(self.funcs.clone()))
                     as Box<dyn NodeChild<NodeType = Self::NodeType>>,
+                Box::new(PackageCategory::SecretDefinitions(
+                    self.secret_definitions.clone(),
+                )) as Box<dyn NodeChild<NodeType = Self::NodeType>>,
             ],
         )
     }
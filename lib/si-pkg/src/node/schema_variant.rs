@@ -19,6 +19,7 @@ const KEY_COLOR_STR: &str = "color";
 const KEY_LINK_STR: &str = "link";
 const KEY_NAME_STR: &str = "name";
 const KEY_COMPONENT_TYPE_STR: &str = "component_type";
+const KEY_REFRESH_INTERVAL_SECS_STR: &str = "refresh_interval_secs";
 const KEY_FUNC_UNIQUE_ID_STR: &str = "func_unique_id";
 
 #[derive(Clone, Debug)]
@@ -27,6 +28,7 @@ pub struct SchemaVariantNode {
     pub link: Option<Url>,
     pub color: Option<String>,
     pub component_type: SchemaVariantSpecComponentType,
+    pub refresh_interval_secs: Option<i64>,
     pub func_unique_id: FuncUniqueId,
 }
 
@@ -46,6 +48,13 @@ impl WriteBytes for SchemaVariantNode {
         )?;
         write_key_value_line(writer, KEY_COLOR_STR, self.color.as_deref().unwrap_or(""))?;
         write_key_value_line(writer, KEY_COMPONENT_TYPE_STR, self.component_type)?;
+        write_key_value_line(
+            writer,
+            KEY_REFRESH_INTERVAL_SECS_STR,
+            self.refresh_interval_secs
+                .map(|secs| secs.to_string())
+                .unwrap_or_default(),
+        )?;
         write_key_value_line(
             writer,
             KEY_FUNC_UNIQUE_ID_STR,
@@ -77,6 +86,12 @@ impl ReadBytes for SchemaVariantNode {
         let component_type_str = read_key_value_line(reader, KEY_COMPONENT_TYPE_STR)?;
         let component_type = SchemaVariantSpecComponentType::from_str(&component_type_str)
             .map_err(GraphError::parse)?;
+        let refresh_interval_secs_str = read_key_value_line(reader, KEY_REFRESH_INTERVAL_SECS_STR)?;
+        let refresh_interval_secs = if refresh_interval_secs_str.is_empty() {
+            None
+        } else {
+            Some(i64::from_str(&refresh_interval_secs_str).map_err(GraphError::parse)?)
+        };
 
         let func_unique_id_str = read_key_value_line(reader, KEY_FUNC_UNIQUE_ID_STR)?;
         let func_unique_id =
@@ -87,6 +102,7 @@ impl ReadBytes for SchemaVariantNode {
             link,
             color,
             component_type,
+            refresh_interval_secs,
             func_unique_id,
         })
     }
@@ -103,6 +119,7 @@ impl NodeChild for SchemaVariantSpec {
                 link: self.link.as_ref().cloned(),
                 color: self.color.as_ref().cloned(),
                 component_type: self.component_type,
+                refresh_interval_secs: self.refresh_interval_secs,
                 func_unique_id: self.func_unique_id,
             }),
             vec![
@@ -0,0 +1,93 @@
+use std::io::{BufRead, Write};
+
+use object_tree::{
+    read_key_value_line, write_key_value_line, GraphError, NameStr, NodeChild, NodeKind,
+    NodeWithChildren, ReadBytes, WriteBytes,
+};
+
+use crate::SecretDefinitionSpec;
+
+use super::PkgNode;
+
+const KEY_DESCRIPTION_STR: &str = "description";
+const KEY_DISPLAY_NAME_STR: &str = "display_name";
+const KEY_NAME_STR: &str = "name";
+
+#[derive(Clone, Debug)]
+pub struct SecretDefinitionNode {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+}
+
+impl NameStr for SecretDefinitionNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl WriteBytes for SecretDefinitionNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(writer, KEY_NAME_STR, self.name())?;
+        write_key_value_line(
+            writer,
+            KEY_DISPLAY_NAME_STR,
+            self.display_name.as_deref().unwrap_or(""),
+        )?;
+        write_key_value_line(
+            writer,
+            KEY_DESCRIPTION_STR,
+            self.description.as_deref().unwrap_or(""),
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for SecretDefinitionNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Self, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let name = read_key_value_line(reader, KEY_NAME_STR)?;
+        let display_name_str = read_key_value_line(reader, KEY_DISPLAY_NAME_STR)?;
+        let display_name = if display_name_str.is_empty() {
+            None
+        } else {
+            Some(display_name_str)
+        };
+        let description_str = read_key_value_line(reader, KEY_DESCRIPTION_STR)?;
+        let description = if description_str.is_empty() {
+            None
+        } else {
+            Some(description_str)
+        };
+
+        Ok(Self {
+            name,
+            display_name,
+            description,
+        })
+    }
+}
+
+impl NodeChild for SecretDefinitionSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        NodeWithChildren::new(
+            NodeKind::Tree,
+            Self::NodeType::SecretDefinition(SecretDefinitionNode {
+                name: self.name.clone(),
+                display_name: self.display_name.clone(),
+                description: self.description.clone(),
+            }),
+            self.fields
+                .iter()
+                .map(|field| {
+                    Box::new(field.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>
+                })
+                .collect(),
+        )
+    }
+}
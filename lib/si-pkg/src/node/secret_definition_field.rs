@@ -0,0 +1,105 @@
+use std::{
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use object_tree::{
+    read_key_value_line, write_key_value_line, GraphError, NameStr, NodeChild, NodeKind,
+    NodeWithChildren, ReadBytes, WriteBytes,
+};
+
+use crate::SecretDefinitionFieldSpec;
+
+use super::PkgNode;
+
+const KEY_DISPLAY_NAME_STR: &str = "display_name";
+const KEY_NAME_STR: &str = "name";
+const KEY_PATTERN_STR: &str = "pattern";
+const KEY_REQUIRED_STR: &str = "required";
+const KEY_SENSITIVE_STR: &str = "sensitive";
+
+#[derive(Clone, Debug)]
+pub struct SecretDefinitionFieldNode {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub sensitive: bool,
+    pub required: bool,
+    pub pattern: Option<String>,
+}
+
+impl NameStr for SecretDefinitionFieldNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl WriteBytes for SecretDefinitionFieldNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(writer, KEY_NAME_STR, self.name())?;
+        write_key_value_line(
+            writer,
+            KEY_DISPLAY_NAME_STR,
+            self.display_name.as_deref().unwrap_or(""),
+        )?;
+        write_key_value_line(writer, KEY_SENSITIVE_STR, self.sensitive)?;
+        write_key_value_line(writer, KEY_REQUIRED_STR, self.required)?;
+        write_key_value_line(
+            writer,
+            KEY_PATTERN_STR,
+            self.pattern.as_deref().unwrap_or(""),
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for SecretDefinitionFieldNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Self, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let name = read_key_value_line(reader, KEY_NAME_STR)?;
+        let display_name_str = read_key_value_line(reader, KEY_DISPLAY_NAME_STR)?;
+        let display_name = if display_name_str.is_empty() {
+            None
+        } else {
+            Some(display_name_str)
+        };
+        let sensitive = bool::from_str(&read_key_value_line(reader, KEY_SENSITIVE_STR)?)
+            .map_err(GraphError::parse)?;
+        let required = bool::from_str(&read_key_value_line(reader, KEY_REQUIRED_STR)?)
+            .map_err(GraphError::parse)?;
+        let pattern_str = read_key_value_line(reader, KEY_PATTERN_STR)?;
+        let pattern = if pattern_str.is_empty() {
+            None
+        } else {
+            Some(pattern_str)
+        };
+
+        Ok(Self {
+            name,
+            display_name,
+            sensitive,
+            required,
+            pattern,
+        })
+    }
+}
+
+impl NodeChild for SecretDefinitionFieldSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        NodeWithChildren::new(
+            NodeKind::Leaf,
+            Self::NodeType::SecretDefinitionField(SecretDefinitionFieldNode {
+                name: self.name.clone(),
+                display_name: self.display_name.clone(),
+                sensitive: self.sensitive,
+                required: self.required,
+                pattern: self.pattern.clone(),
+            }),
+            vec![],
+        )
+    }
+}
@@ -17,6 +17,7 @@ mod leaf_function;
 mod map_key_func;
 mod prop;
 mod schema;
+mod secret_definition;
 mod si_prop_func;
 mod socket;
 mod validation;
@@ -24,12 +25,13 @@ mod variant;
 
 pub use {
     action_func::*, attr_func_input::*, func::*, func_description::*, leaf_function::*,
-    map_key_func::*, prop::*, schema::*, si_prop_func::*, socket::*, validation::*, variant::*,
+    map_key_func::*, prop::*, schema::*, secret_definition::*, si_prop_func::*, socket::*,
+    validation::*, variant::*,
 };
 
 use crate::{
     node::{CategoryNode, PkgNode},
-    spec::{FuncSpec, PkgSpec, SchemaVariantSpecPropRoot, SpecError},
+    spec::{FuncSpec, PkgSpec, SchemaVariantSpecPropRoot, SecretDefinitionSpec, SpecError},
 };
 
 #[remain::sorted]
@@ -170,6 +172,24 @@ impl SiPkg {
         Ok(schemas)
     }
 
+    /// Lists the secret definitions shipped in the package. Packages built before secret
+    /// definitions were introduced have none.
+    pub fn secret_definitions(&self) -> PkgResult<Vec<SiPkgSecretDefinition>> {
+        let (graph, root_idx) = self.as_petgraph();
+
+        let node_idxs = match secret_definition_node_idxs(graph, root_idx) {
+            Ok(node_idxs) => node_idxs,
+            Err(SiPkgError::CategoryNotFound(_)) => vec![],
+            Err(err) => return Err(err),
+        };
+        let mut secret_definitions = Vec::with_capacity(node_idxs.len());
+        for node_idx in node_idxs {
+            secret_definitions.push(SiPkgSecretDefinition::from_graph(graph, node_idx)?);
+        }
+
+        Ok(secret_definitions)
+    }
+
     pub fn schema_by_name(&self, name: impl AsRef<str>) -> PkgResult<SiPkgSchema> {
         let (graph, root_idx) = self.as_petgraph();
 
@@ -210,6 +230,10 @@ impl SiPkg {
             builder.schema(schema.to_spec().await?);
         }
 
+        for secret_definition in self.secret_definitions()? {
+            builder.secret_definition(SecretDefinitionSpec::try_from(secret_definition)?);
+        }
+
         Ok(builder.build()?)
     }
 }
@@ -269,6 +293,13 @@ fn func_node_idxs(
     category_node_idxs(CategoryNode::Funcs, graph, root_idx)
 }
 
+fn secret_definition_node_idxs(
+    graph: &Graph<HashedNode<PkgNode>, ()>,
+    root_idx: NodeIndex,
+) -> PkgResult<Vec<NodeIndex>> {
+    category_node_idxs(CategoryNode::SecretDefinitions, graph, root_idx)
+}
+
 #[derive(Clone)]
 pub struct Source<'a> {
     graph: &'a Graph<HashedNode<PkgNode>, ()>,
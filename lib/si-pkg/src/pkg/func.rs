@@ -92,6 +92,8 @@ pub struct SiPkgFunc<'a> {
     backend_kind: FuncSpecBackendKind,
     response_type: FuncSpecBackendResponseType,
     hidden: bool,
+    deterministic: bool,
+    supports_dry_run: bool,
     link: Option<Url>,
     unique_id: Hash,
 
@@ -124,6 +126,8 @@ impl<'a> SiPkgFunc<'a> {
             backend_kind: func_node.backend_kind,
             response_type: func_node.response_type,
             hidden: func_node.hidden,
+            deterministic: func_node.deterministic,
+            supports_dry_run: func_node.supports_dry_run,
             link: func_node.link,
             hash: func_hashed_node.hash(),
             unique_id: func_node.unique_id,
@@ -176,6 +180,14 @@ impl<'a> SiPkgFunc<'a> {
         self.hidden
     }
 
+    pub fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    pub fn supports_dry_run(&self) -> bool {
+        self.supports_dry_run
+    }
+
     pub fn link(&self) -> Option<&Url> {
         self.link.as_ref()
     }
@@ -205,7 +217,9 @@ impl<'a> TryFrom<SiPkgFunc<'a>> for FuncSpec {
             .code_base64(&value.code_base64)
             .backend_kind(value.backend_kind)
             .response_type(value.response_type)
-            .hidden(value.hidden);
+            .hidden(value.hidden)
+            .deterministic(value.deterministic)
+            .supports_dry_run(value.supports_dry_run);
 
         if let Some(display_name) = &value.display_name {
             builder.display_name(display_name);
@@ -0,0 +1,175 @@
+use object_tree::{Hash, HashedNode};
+use petgraph::prelude::*;
+
+use super::{PkgResult, SiPkgError, Source};
+
+use crate::{node::PkgNode, SecretDefinitionFieldSpec, SecretDefinitionSpec};
+
+#[derive(Clone, Debug)]
+pub struct SiPkgSecretDefinition<'a> {
+    name: String,
+    display_name: Option<String>,
+    description: Option<String>,
+
+    hash: Hash,
+    source: Source<'a>,
+}
+
+impl<'a> SiPkgSecretDefinition<'a> {
+    pub fn from_graph(
+        graph: &'a Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::SecretDefinition(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::SECRET_DEFINITION_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: node.name,
+            display_name: node.display_name,
+            description: node.description,
+            hash: hashed_node.hash(),
+            source: Source::new(graph, node_idx),
+        })
+    }
+
+    pub fn fields(&self) -> PkgResult<Vec<SiPkgSecretDefinitionField>> {
+        let mut fields = vec![];
+
+        for idx in self
+            .source
+            .graph
+            .neighbors_directed(self.source.node_idx, Outgoing)
+        {
+            fields.push(SiPkgSecretDefinitionField::from_graph(
+                self.source.graph,
+                idx,
+            )?);
+        }
+
+        Ok(fields)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+}
+
+impl<'a> TryFrom<SiPkgSecretDefinition<'a>> for SecretDefinitionSpec {
+    type Error = SiPkgError;
+
+    fn try_from(value: SiPkgSecretDefinition<'a>) -> Result<Self, Self::Error> {
+        let mut builder = SecretDefinitionSpec::builder();
+
+        builder.name(value.name());
+        if let Some(display_name) = value.display_name() {
+            builder.display_name(display_name);
+        }
+        if let Some(description) = value.description() {
+            builder.description(description);
+        }
+
+        for field in value.fields()? {
+            builder.field(SecretDefinitionFieldSpec::from(field));
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SiPkgSecretDefinitionField {
+    name: String,
+    display_name: Option<String>,
+    sensitive: bool,
+    required: bool,
+    pattern: Option<String>,
+
+    hash: Hash,
+}
+
+impl SiPkgSecretDefinitionField {
+    pub fn from_graph(
+        graph: &Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::SecretDefinitionField(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::SECRET_DEFINITION_FIELD_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: node.name,
+            display_name: node.display_name,
+            sensitive: node.sensitive,
+            required: node.required,
+            pattern: node.pattern,
+            hash: hashed_node.hash(),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    pub fn sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_deref()
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+}
+
+impl From<SiPkgSecretDefinitionField> for SecretDefinitionFieldSpec {
+    fn from(value: SiPkgSecretDefinitionField) -> Self {
+        Self {
+            name: value.name,
+            display_name: value.display_name,
+            sensitive: value.sensitive,
+            required: value.required,
+            pattern: value.pattern,
+        }
+    }
+}
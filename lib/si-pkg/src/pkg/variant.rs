@@ -24,6 +24,7 @@ pub struct SiPkgSchemaVariant<'a> {
     link: Option<Url>,
     color: Option<String>,
     component_type: SchemaVariantSpecComponentType,
+    refresh_interval_secs: Option<i64>,
     func_unique_id: FuncUniqueId,
 
     hash: Hash,
@@ -83,6 +84,7 @@ impl<'a> SiPkgSchemaVariant<'a> {
             link: schema_variant_node.link,
             color: schema_variant_node.color,
             component_type: schema_variant_node.component_type,
+            refresh_interval_secs: schema_variant_node.refresh_interval_secs,
             hash: schema_variant_hashed_node.hash(),
             source: Source::new(graph, node_idx),
             func_unique_id: schema_variant_node.func_unique_id,
@@ -107,6 +109,10 @@ impl<'a> SiPkgSchemaVariant<'a> {
         self.component_type
     }
 
+    pub fn refresh_interval_secs(&self) -> Option<i64> {
+        self.refresh_interval_secs
+    }
+
     pub fn func_unique_id(&self) -> FuncUniqueId {
         self.func_unique_id
     }
@@ -337,6 +343,10 @@ impl<'a> SiPkgSchemaVariant<'a> {
             builder.color(color);
         }
 
+        if let Some(refresh_interval_secs) = self.refresh_interval_secs() {
+            builder.refresh_interval_secs(refresh_interval_secs);
+        }
+
         for action_func in self.action_funcs()? {
             builder.action_func(action_func.try_into()?);
         }
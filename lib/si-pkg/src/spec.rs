@@ -11,6 +11,7 @@ mod leaf_function;
 mod map_key_func;
 mod prop;
 mod schema;
+mod secret_definition;
 mod si_prop_func;
 mod socket;
 mod validation;
@@ -18,7 +19,8 @@ mod variant;
 
 pub use {
     action_func::*, attr_func_input::*, func::*, func_description::*, leaf_function::*,
-    map_key_func::*, prop::*, schema::*, si_prop_func::*, socket::*, validation::*, variant::*,
+    map_key_func::*, prop::*, schema::*, secret_definition::*, si_prop_func::*, socket::*,
+    validation::*, variant::*,
 };
 
 #[derive(Builder, Clone, Debug, Deserialize, Serialize)]
@@ -42,6 +44,10 @@ pub struct PkgSpec {
 
     #[builder(setter(each(name = "func", into)), default)]
     pub funcs: Vec<FuncSpec>,
+
+    #[builder(setter(each(name = "secret_definition", into)), default)]
+    #[serde(default)]
+    pub secret_definitions: Vec<SecretDefinitionSpec>,
 }
 
 impl PkgSpec {
@@ -82,6 +88,15 @@ impl PkgSpecBuilder {
         let converted: FuncSpec = item.try_into()?;
         Ok(self.func(converted))
     }
+
+    #[allow(unused_mut)]
+    pub fn try_secret_definition<I>(&mut self, item: I) -> Result<&mut Self, I::Error>
+    where
+        I: TryInto<SecretDefinitionSpec>,
+    {
+        let converted: SecretDefinitionSpec = item.try_into()?;
+        Ok(self.secret_definition(converted))
+    }
 }
 
 impl TryFrom<PkgSpecBuilder> for PkgSpec {
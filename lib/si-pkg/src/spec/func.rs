@@ -66,6 +66,8 @@ pub enum FuncSpecBackendKind {
     JsValidation,
     Map,
     Object,
+    PythonAction,
+    PythonAttribute,
     String,
     Unset,
     Validation,
@@ -115,6 +117,14 @@ pub struct FuncSpec {
     pub response_type: FuncSpecBackendResponseType,
     #[builder(setter(into), default)]
     pub hidden: bool,
+    /// Whether or not the func returns the same result for the same code and arguments, and so
+    /// whether or not its results can be cached.
+    #[builder(setter(into), default)]
+    pub deterministic: bool,
+    /// Whether or not the func's action honours dry runs, describing the changes it would make
+    /// instead of making them, and so whether or not it can be planned.
+    #[builder(setter(into), default)]
+    pub supports_dry_run: bool,
     #[builder(field(type = "FuncUniqueId", build = "self.build_func_unique_id()"))]
     pub unique_id: FuncUniqueId,
 
@@ -185,6 +195,8 @@ impl FuncSpecBuilder {
                 .as_bytes(),
         );
         bytes.extend(&[self.hidden.unwrap_or(false).into()]);
+        bytes.extend(&[self.deterministic.unwrap_or(false).into()]);
+        bytes.extend(&[self.supports_dry_run.unwrap_or(false).into()]);
 
         Hash::new(&bytes)
     }
@@ -0,0 +1,65 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::SpecError;
+
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct SecretDefinitionSpec {
+    #[builder(setter(into))]
+    pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub display_name: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(each(name = "field", into)), default)]
+    pub fields: Vec<SecretDefinitionFieldSpec>,
+}
+
+impl SecretDefinitionSpec {
+    #[must_use]
+    pub fn builder() -> SecretDefinitionSpecBuilder {
+        SecretDefinitionSpecBuilder::default()
+    }
+}
+
+impl TryFrom<SecretDefinitionSpecBuilder> for SecretDefinitionSpec {
+    type Error = SpecError;
+
+    fn try_from(value: SecretDefinitionSpecBuilder) -> Result<Self, Self::Error> {
+        value.build()
+    }
+}
+
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct SecretDefinitionFieldSpec {
+    #[builder(setter(into))]
+    pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub display_name: Option<String>,
+    #[builder(setter(into), default = "true")]
+    pub sensitive: bool,
+    #[builder(setter(into), default = "true")]
+    pub required: bool,
+    /// A regular expression the field's value must match.
+    #[builder(setter(into, strip_option), default)]
+    pub pattern: Option<String>,
+}
+
+impl SecretDefinitionFieldSpec {
+    #[must_use]
+    pub fn builder() -> SecretDefinitionFieldSpecBuilder {
+        SecretDefinitionFieldSpecBuilder::default()
+    }
+}
+
+impl TryFrom<SecretDefinitionFieldSpecBuilder> for SecretDefinitionFieldSpec {
+    type Error = SpecError;
+
+    fn try_from(value: SecretDefinitionFieldSpecBuilder) -> Result<Self, Self::Error> {
+        value.build()
+    }
+}
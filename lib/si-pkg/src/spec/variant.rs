@@ -71,6 +71,10 @@ pub struct SchemaVariantSpec {
     #[builder(setter(into), default)]
     pub component_type: SchemaVariantSpecComponentType,
 
+    /// How often the resources of the variant's components are refreshed, in seconds.
+    #[builder(setter(into, strip_option), default)]
+    pub refresh_interval_secs: Option<i64>,
+
     #[builder(private, default = "Self::default_domain()")]
     pub domain: PropSpec,
 
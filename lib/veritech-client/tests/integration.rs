@@ -3,7 +3,7 @@ use std::env;
 use base64::{engine::general_purpose, Engine};
 use cyclone_core::{
     ComponentKind, ComponentView, FunctionResult, ResolverFunctionComponent,
-    ResolverFunctionRequest, ResolverFunctionResponseType, SchemaVariantDefinitionRequest,
+    ResolverFunctionRequest, ResolverFunctionResponseType, Runtime, SchemaVariantDefinitionRequest,
     ValidationRequest,
 };
 use si_data_nats::{NatsClient, NatsConfig};
@@ -43,10 +43,25 @@ async fn veritech_server_for_uds_cyclone(subject_prefix: String) -> Server {
 
     let cyclone_spec = CycloneSpec::LocalUds(
         LocalUdsInstance::spec()
-            .try_cyclone_cmd_path(config_file.cyclone.cyclone_cmd_path())
+            .try_cyclone_cmd_path(
+                config_file
+                    .cyclone
+                    .cyclone_cmd_path()
+                    .expect("local cyclone config has a cyclone cmd path"),
+            )
             .expect("failed to setup cyclone_cmd_path")
-            .cyclone_decryption_key_path(config_file.cyclone.cyclone_decryption_key_path())
-            .try_lang_server_cmd_path(config_file.cyclone.lang_server_cmd_path())
+            .cyclone_decryption_key_path(
+                config_file
+                    .cyclone
+                    .cyclone_decryption_key_path()
+                    .expect("local cyclone config has a cyclone decryption key path"),
+            )
+            .try_lang_server_cmd_path(
+                config_file
+                    .cyclone
+                    .lang_server_cmd_path()
+                    .expect("local cyclone config has a lang server cmd path"),
+            )
             .expect("failed to setup lang_js_cmd_path")
             .all_endpoints()
             .build()
@@ -105,6 +120,8 @@ async fn executes_simple_resolver_function() {
         code_base64: base64_encode(
             "function numberOfInputs(input) { return Object.keys(input)?.length ?? 0; }",
         ),
+        timeout_secs: None,
+        runtime: Runtime::JavaScript,
     };
 
     let result = client
@@ -171,6 +188,8 @@ async fn type_checks_resolve_function() {
             },
             response_type,
             code_base64: base64_encode("function returnInputValue(input) { return input.value; }"),
+            timeout_secs: None,
+            runtime: Runtime::JavaScript,
         };
 
         let result = client
@@ -232,6 +251,8 @@ async fn type_checks_resolve_function() {
             },
             response_type: response_type.clone(),
             code_base64: base64_encode("function returnInputValue(input) { return input.value; }"),
+            timeout_secs: None,
+            runtime: Runtime::JavaScript,
         };
 
         let result = client
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     env,
     net::{SocketAddr, ToSocketAddrs},
     path::{Path, PathBuf},
@@ -9,9 +10,9 @@ use buck2_resources::Buck2Resources;
 use deadpool_cyclone::{
     instance::cyclone::{
         LocalHttpInstance, LocalHttpInstanceSpec, LocalHttpSocketStrategy, LocalUdsInstance,
-        LocalUdsInstanceSpec, LocalUdsSocketStrategy,
+        LocalUdsInstanceSpec, LocalUdsSocketStrategy, RemoteHttpInstance, RemoteHttpInstanceSpec,
     },
-    Instance,
+    Instance, Runtime,
 };
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
@@ -57,6 +58,7 @@ pub struct Config {
 pub enum CycloneSpec {
     LocalHttp(LocalHttpInstanceSpec),
     LocalUds(LocalUdsInstanceSpec),
+    RemoteHttp(RemoteHttpInstanceSpec),
 }
 
 impl StandardConfig for Config {
@@ -164,6 +166,8 @@ pub enum CycloneConfig {
         cyclone_decryption_key_path: String,
         #[serde(default = "default_lang_server_cmd_path")]
         lang_server_cmd_path: String,
+        #[serde(default = "default_runtime_lang_server_cmd_paths")]
+        runtime_lang_server_cmd_paths: HashMap<Runtime, String>,
         #[serde(default)]
         socket_strategy: LocalHttpSocketStrategy,
         #[serde(default)]
@@ -176,6 +180,8 @@ pub enum CycloneConfig {
         resolver: bool,
         #[serde(default = "default_enable_endpoint")]
         action: bool,
+        #[serde(default)]
+        sandbox: bool,
     },
     LocalUds {
         #[serde(default = "default_cyclone_cmd_path")]
@@ -184,6 +190,8 @@ pub enum CycloneConfig {
         cyclone_decryption_key_path: String,
         #[serde(default = "default_lang_server_cmd_path")]
         lang_server_cmd_path: String,
+        #[serde(default = "default_runtime_lang_server_cmd_paths")]
+        runtime_lang_server_cmd_paths: HashMap<Runtime, String>,
         #[serde(default)]
         socket_strategy: LocalUdsSocketStrategy,
         #[serde(default)]
@@ -196,6 +204,17 @@ pub enum CycloneConfig {
         resolver: bool,
         #[serde(default = "default_enable_endpoint")]
         action: bool,
+        #[serde(default)]
+        sandbox: bool,
+    },
+    /// Already-running Cyclone servers on other hosts, which are configured with their own
+    /// language servers, decryption key and endpoints.
+    RemoteHttp {
+        endpoints: Vec<String>,
+        #[serde(default)]
+        health_check_timeout: Option<Duration>,
+        #[serde(default = "default_limit_requests")]
+        limit_requets: Option<u32>,
     },
 }
 
@@ -205,12 +224,14 @@ impl CycloneConfig {
             cyclone_cmd_path: default_cyclone_cmd_path(),
             cyclone_decryption_key_path: default_cyclone_decryption_key_path(),
             lang_server_cmd_path: default_lang_server_cmd_path(),
+            runtime_lang_server_cmd_paths: default_runtime_lang_server_cmd_paths(),
             socket_strategy: Default::default(),
             watch_timeout: Default::default(),
             limit_requets: default_limit_requests(),
             ping: default_enable_endpoint(),
             resolver: default_enable_endpoint(),
             action: default_enable_endpoint(),
+            sandbox: Default::default(),
         }
     }
 
@@ -219,23 +240,26 @@ impl CycloneConfig {
             cyclone_cmd_path: default_cyclone_cmd_path(),
             cyclone_decryption_key_path: default_cyclone_decryption_key_path(),
             lang_server_cmd_path: default_lang_server_cmd_path(),
+            runtime_lang_server_cmd_paths: default_runtime_lang_server_cmd_paths(),
             socket_strategy: Default::default(),
             watch_timeout: Default::default(),
             limit_requets: default_limit_requests(),
             ping: default_enable_endpoint(),
             resolver: default_enable_endpoint(),
             action: default_enable_endpoint(),
+            sandbox: Default::default(),
         }
     }
 
-    pub fn cyclone_cmd_path(&self) -> &str {
+    pub fn cyclone_cmd_path(&self) -> Option<&str> {
         match self {
             CycloneConfig::LocalUds {
                 cyclone_cmd_path, ..
-            } => cyclone_cmd_path,
+            } => Some(cyclone_cmd_path),
             CycloneConfig::LocalHttp {
                 cyclone_cmd_path, ..
-            } => cyclone_cmd_path,
+            } => Some(cyclone_cmd_path),
+            CycloneConfig::RemoteHttp { .. } => None,
         }
     }
 
@@ -247,19 +271,21 @@ impl CycloneConfig {
             CycloneConfig::LocalHttp {
                 cyclone_cmd_path, ..
             } => *cyclone_cmd_path = value,
+            CycloneConfig::RemoteHttp { .. } => {}
         };
     }
 
-    pub fn cyclone_decryption_key_path(&self) -> &str {
+    pub fn cyclone_decryption_key_path(&self) -> Option<&str> {
         match self {
             CycloneConfig::LocalUds {
                 cyclone_decryption_key_path,
                 ..
-            } => cyclone_decryption_key_path,
+            } => Some(cyclone_decryption_key_path),
             CycloneConfig::LocalHttp {
                 cyclone_decryption_key_path,
                 ..
-            } => cyclone_decryption_key_path,
+            } => Some(cyclone_decryption_key_path),
+            CycloneConfig::RemoteHttp { .. } => None,
         }
     }
 
@@ -273,19 +299,21 @@ impl CycloneConfig {
                 cyclone_decryption_key_path,
                 ..
             } => *cyclone_decryption_key_path = value,
+            CycloneConfig::RemoteHttp { .. } => {}
         };
     }
 
-    pub fn lang_server_cmd_path(&self) -> &str {
+    pub fn lang_server_cmd_path(&self) -> Option<&str> {
         match self {
             CycloneConfig::LocalUds {
                 lang_server_cmd_path,
                 ..
-            } => lang_server_cmd_path,
+            } => Some(lang_server_cmd_path),
             CycloneConfig::LocalHttp {
                 lang_server_cmd_path,
                 ..
-            } => lang_server_cmd_path,
+            } => Some(lang_server_cmd_path),
+            CycloneConfig::RemoteHttp { .. } => None,
         }
     }
 
@@ -299,6 +327,25 @@ impl CycloneConfig {
                 lang_server_cmd_path,
                 ..
             } => *lang_server_cmd_path = value,
+            CycloneConfig::RemoteHttp { .. } => {}
+        };
+    }
+
+    pub fn set_runtime_lang_server_cmd_path(&mut self, runtime: Runtime, value: String) {
+        match self {
+            CycloneConfig::LocalUds {
+                runtime_lang_server_cmd_paths,
+                ..
+            } => {
+                runtime_lang_server_cmd_paths.insert(runtime, value);
+            }
+            CycloneConfig::LocalHttp {
+                runtime_lang_server_cmd_paths,
+                ..
+            } => {
+                runtime_lang_server_cmd_paths.insert(runtime, value);
+            }
+            CycloneConfig::RemoteHttp { .. } => {}
         };
     }
 
@@ -306,6 +353,7 @@ impl CycloneConfig {
         match self {
             CycloneConfig::LocalUds { limit_requets, .. } => *limit_requets = value.into(),
             CycloneConfig::LocalHttp { limit_requets, .. } => *limit_requets = value.into(),
+            CycloneConfig::RemoteHttp { limit_requets, .. } => *limit_requets = value.into(),
         };
     }
 
@@ -313,6 +361,7 @@ impl CycloneConfig {
         match self {
             CycloneConfig::LocalUds { ping, .. } => *ping = value,
             CycloneConfig::LocalHttp { ping, .. } => *ping = value,
+            CycloneConfig::RemoteHttp { .. } => {}
         };
     }
 
@@ -320,6 +369,7 @@ impl CycloneConfig {
         match self {
             CycloneConfig::LocalUds { resolver, .. } => *resolver = value,
             CycloneConfig::LocalHttp { resolver, .. } => *resolver = value,
+            CycloneConfig::RemoteHttp { .. } => {}
         };
     }
 
@@ -327,6 +377,7 @@ impl CycloneConfig {
         match self {
             CycloneConfig::LocalUds { action, .. } => *action = value,
             CycloneConfig::LocalHttp { action, .. } => *action = value,
+            CycloneConfig::RemoteHttp { .. } => {}
         };
     }
 }
@@ -346,12 +397,14 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 cyclone_cmd_path,
                 cyclone_decryption_key_path,
                 lang_server_cmd_path,
+                runtime_lang_server_cmd_paths,
                 socket_strategy,
                 watch_timeout,
                 limit_requets,
                 ping,
                 resolver,
                 action,
+                sandbox,
             } => {
                 let mut builder = LocalUdsInstance::spec();
                 builder
@@ -361,6 +414,11 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 builder
                     .try_lang_server_cmd_path(lang_server_cmd_path)
                     .map_err(ConfigError::cyclone_spec_build)?;
+                for (runtime, path) in runtime_lang_server_cmd_paths {
+                    builder
+                        .try_runtime_lang_server_cmd_path(runtime, path)
+                        .map_err(ConfigError::cyclone_spec_build)?;
+                }
                 builder.socket_strategy(socket_strategy);
                 if let Some(watch_timeout) = watch_timeout {
                     builder.watch_timeout(watch_timeout);
@@ -375,6 +433,9 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 if action {
                     builder.action();
                 }
+                if sandbox {
+                    builder.sandbox();
+                }
 
                 Ok(Self::LocalUds(
                     builder.build().map_err(ConfigError::cyclone_spec_build)?,
@@ -384,12 +445,14 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 cyclone_cmd_path,
                 cyclone_decryption_key_path,
                 lang_server_cmd_path,
+                runtime_lang_server_cmd_paths,
                 socket_strategy,
                 watch_timeout,
                 limit_requets,
                 ping,
                 resolver,
                 action,
+                sandbox,
             } => {
                 let mut builder = LocalHttpInstance::spec();
                 builder
@@ -399,6 +462,11 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 builder
                     .try_lang_server_cmd_path(lang_server_cmd_path)
                     .map_err(ConfigError::cyclone_spec_build)?;
+                for (runtime, path) in runtime_lang_server_cmd_paths {
+                    builder
+                        .try_runtime_lang_server_cmd_path(runtime, path)
+                        .map_err(ConfigError::cyclone_spec_build)?;
+                }
                 builder.socket_strategy(socket_strategy);
                 if let Some(watch_timeout) = watch_timeout {
                     builder.watch_timeout(watch_timeout);
@@ -413,11 +481,30 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 if action {
                     builder.action();
                 }
+                if sandbox {
+                    builder.sandbox();
+                }
 
                 Ok(Self::LocalHttp(
                     builder.build().map_err(ConfigError::cyclone_spec_build)?,
                 ))
             }
+            CycloneConfig::RemoteHttp {
+                endpoints,
+                health_check_timeout,
+                limit_requets,
+            } => {
+                let mut builder = RemoteHttpInstance::spec();
+                builder.endpoints(endpoints);
+                if let Some(health_check_timeout) = health_check_timeout {
+                    builder.health_check_timeout(health_check_timeout);
+                }
+                builder.limit_requests(limit_requets);
+
+                Ok(Self::RemoteHttp(
+                    builder.build().map_err(ConfigError::cyclone_spec_build)?,
+                ))
+            }
         }
     }
 }
@@ -434,6 +521,10 @@ fn default_lang_server_cmd_path() -> String {
     "/usr/local/bin/lang-js".to_string()
 }
 
+fn default_runtime_lang_server_cmd_paths() -> HashMap<Runtime, String> {
+    HashMap::from([(Runtime::Python, "/usr/local/bin/lang-py".to_string())])
+}
+
 fn default_limit_requests() -> Option<u32> {
     Some(1)
 }
@@ -444,6 +535,11 @@ fn default_enable_endpoint() -> bool {
 
 #[allow(clippy::disallowed_methods)] // Used to determine if running in development
 pub fn detect_and_configure_development(config: &mut ConfigFile) -> Result<()> {
+    // Remote Cyclone servers are configured on their own hosts
+    if let CycloneConfig::RemoteHttp { .. } = config.cyclone {
+        return Ok(());
+    }
+
     if env::var("BUCK_RUN_BUILD_ID").is_ok() || env::var("BUCK_BUILD_ID").is_ok() {
         buck2_development(config)
     } else if let Ok(dir) = env::var("CARGO_MANIFEST_DIR") {
@@ -471,11 +567,17 @@ fn buck2_development(config: &mut ConfigFile) -> Result<()> {
         .map_err(ConfigError::cyclone_spec_build)?
         .to_string_lossy()
         .to_string();
+    let python_lang_server_cmd_path = resources
+        .get_ends_with("lang-py")
+        .map_err(ConfigError::cyclone_spec_build)?
+        .to_string_lossy()
+        .to_string();
 
     warn!(
         cyclone_cmd_path = cyclone_cmd_path.as_str(),
         cyclone_decryption_key_path = cyclone_decryption_key_path.as_str(),
         lang_server_cmd_path = lang_server_cmd_path.as_str(),
+        python_lang_server_cmd_path = python_lang_server_cmd_path.as_str(),
         "detected development run",
     );
 
@@ -486,6 +588,9 @@ fn buck2_development(config: &mut ConfigFile) -> Result<()> {
     config
         .cyclone
         .set_lang_server_cmd_path(lang_server_cmd_path);
+    config
+        .cyclone
+        .set_runtime_lang_server_cmd_path(Runtime::Python, python_lang_server_cmd_path);
 
     Ok(())
 }
@@ -511,11 +616,18 @@ fn cargo_development(dir: String, config: &mut ConfigFile) -> Result<()> {
         .expect("failed to canonicalize local dev build of <root>/bin/lang-js/target/lang-js")
         .to_string_lossy()
         .to_string();
+    let python_lang_server_cmd_path = Path::new(&dir)
+        .join("../../bin/lang-py/lang-py")
+        .canonicalize()
+        .expect("failed to canonicalize local lang server at <root>/bin/lang-py/lang-py")
+        .to_string_lossy()
+        .to_string();
 
     warn!(
         cyclone_cmd_path = cyclone_cmd_path.as_str(),
         cyclone_decryption_key_path = cyclone_decryption_key_path.as_str(),
         lang_server_cmd_path = lang_server_cmd_path.as_str(),
+        python_lang_server_cmd_path = python_lang_server_cmd_path.as_str(),
         "detected development run",
     );
 
@@ -526,6 +638,9 @@ fn cargo_development(dir: String, config: &mut ConfigFile) -> Result<()> {
     config
         .cyclone
         .set_lang_server_cmd_path(lang_server_cmd_path);
+    config
+        .cyclone
+        .set_runtime_lang_server_cmd_path(Runtime::Python, python_lang_server_cmd_path);
 
     Ok(())
 }
@@ -1,18 +1,21 @@
 use chrono::Utc;
 use deadpool_cyclone::{
-    instance::cyclone::LocalUdsInstanceSpec, ActionRunRequest, ActionRunResultSuccess,
-    CycloneClient, FunctionResult, FunctionResultFailure, FunctionResultFailureError, Manager,
+    instance::cyclone::{LocalUdsInstanceSpec, RemoteHttpInstanceSpec},
+    ActionRunRequest, ActionRunResultSuccess, Connection, CycloneClient, ExecutionError,
+    ExecutionStarted, FunctionResult, FunctionResultFailure, FunctionResultFailureError, Manager,
     Pool, ProgressMessage, ReconciliationRequest, ReconciliationResultSuccess,
     ResolverFunctionRequest, ResolverFunctionResultSuccess, SchemaVariantDefinitionRequest,
     SchemaVariantDefinitionResultSuccess, ValidationRequest, ValidationResultSuccess,
 };
 use futures::{channel::oneshot, join, StreamExt};
 use nats_subscriber::Request;
+use serde::de::DeserializeOwned;
 use si_data_nats::NatsClient;
-use std::io;
+use std::{fmt, io};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::{
+    io::{AsyncRead, AsyncWrite},
     signal::unix,
     sync::{broadcast, mpsc},
 };
@@ -58,10 +61,42 @@ pub enum ServerError {
 
 type ServerResult<T> = Result<T, ServerError>;
 
+/// The pool of Cyclone instances that functions are executed on.
+#[derive(Clone)]
+enum CyclonePool {
+    LocalUds(Pool<LocalUdsInstanceSpec>),
+    RemoteHttp(Pool<RemoteHttpInstanceSpec>),
+}
+
+/// Executes a request on an instance from a [`CyclonePool`], publishing its output and returning
+/// its result. The instance is held until the execution has finished.
+macro_rules! execute {
+    ($cyclone_pool:expr, $publisher:expr, $execute_fn:ident($request:expr)) => {
+        match $cyclone_pool {
+            CyclonePool::LocalUds(pool) => {
+                let mut client = pool
+                    .get()
+                    .await
+                    .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
+                let progress = client.$execute_fn($request).await?.start().await?;
+                forward_progress($publisher, progress).await
+            }
+            CyclonePool::RemoteHttp(pool) => {
+                let mut client = pool
+                    .get()
+                    .await
+                    .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
+                let progress = client.$execute_fn($request).await?.start().await?;
+                forward_progress($publisher, progress).await
+            }
+        }
+    };
+}
+
 pub struct Server {
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     shutdown_broadcast_tx: broadcast::Sender<()>,
     shutdown_tx: mpsc::Sender<ShutdownSource>,
     shutdown_rx: oneshot::Receiver<()>,
@@ -88,10 +123,9 @@ impl Server {
                 // Ok(Server { nats, cyclone_pool })
                 unimplemented!("get ready for a surprise!!")
             }
-            wrong @ CycloneSpec::LocalUds(_) => Err(ServerError::WrongCycloneSpec(
-                "LocalHttp",
-                Box::new(wrong.clone()),
-            )),
+            wrong @ (CycloneSpec::LocalUds(_) | CycloneSpec::RemoteHttp(_)) => Err(
+                ServerError::WrongCycloneSpec("LocalHttp", Box::new(wrong.clone())),
+            ),
         }
     }
 
@@ -99,38 +133,59 @@ impl Server {
     pub async fn for_cyclone_uds(config: Config) -> ServerResult<Server> {
         match config.cyclone_spec() {
             CycloneSpec::LocalUds(spec) => {
-                let (shutdown_tx, shutdown_rx) = mpsc::channel(4);
-                // Note the channel parameter corresponds to the number of channels that may be
-                // maintained when the sender is guaranteeing delivery. While this number may end
-                // of being related to the number of subscribers, it's not
-                // necessarily the same number.
-                let (shutdown_broadcast_tx, _) = broadcast::channel(16);
-
-                let nats = connect_to_nats(&config).await?;
                 let manager = Manager::new(spec.clone());
                 let cyclone_pool = Pool::builder(manager)
                     .build()
                     .map_err(|err| ServerError::CycloneSpec(Box::new(err)))?;
 
-                let graceful_shutdown_rx =
-                    prepare_graceful_shutdown(shutdown_rx, shutdown_broadcast_tx.clone())?;
-
-                Ok(Server {
-                    nats,
-                    subject_prefix: config.subject_prefix().map(|s| s.to_string()),
-                    cyclone_pool,
-                    shutdown_broadcast_tx,
-                    shutdown_tx,
-                    shutdown_rx: graceful_shutdown_rx,
-                })
+                Self::new(&config, CyclonePool::LocalUds(cyclone_pool)).await
             }
-            wrong @ CycloneSpec::LocalHttp(_) => Err(ServerError::WrongCycloneSpec(
-                "LocalUds",
-                Box::new(wrong.clone()),
-            )),
+            wrong @ (CycloneSpec::LocalHttp(_) | CycloneSpec::RemoteHttp(_)) => Err(
+                ServerError::WrongCycloneSpec("LocalUds", Box::new(wrong.clone())),
+            ),
         }
     }
 
+    #[instrument(name = "veritech.init.cyclone.remote_http", skip(config))]
+    pub async fn for_cyclone_remote_http(config: Config) -> ServerResult<Server> {
+        match config.cyclone_spec() {
+            CycloneSpec::RemoteHttp(spec) => {
+                let manager = Manager::new(spec.clone());
+                let cyclone_pool = Pool::builder(manager)
+                    .build()
+                    .map_err(|err| ServerError::CycloneSpec(Box::new(err)))?;
+
+                Self::new(&config, CyclonePool::RemoteHttp(cyclone_pool)).await
+            }
+            wrong @ (CycloneSpec::LocalHttp(_) | CycloneSpec::LocalUds(_)) => Err(
+                ServerError::WrongCycloneSpec("RemoteHttp", Box::new(wrong.clone())),
+            ),
+        }
+    }
+
+    async fn new(config: &Config, cyclone_pool: CyclonePool) -> ServerResult<Server> {
+        let (shutdown_tx, shutdown_rx) = mpsc::channel(4);
+        // Note the channel parameter corresponds to the number of channels that may be
+        // maintained when the sender is guaranteeing delivery. While this number may end
+        // of being related to the number of subscribers, it's not
+        // necessarily the same number.
+        let (shutdown_broadcast_tx, _) = broadcast::channel(16);
+
+        let nats = connect_to_nats(config).await?;
+
+        let graceful_shutdown_rx =
+            prepare_graceful_shutdown(shutdown_rx, shutdown_broadcast_tx.clone())?;
+
+        Ok(Server {
+            nats,
+            subject_prefix: config.subject_prefix().map(|s| s.to_string()),
+            cyclone_pool,
+            shutdown_broadcast_tx,
+            shutdown_tx,
+            shutdown_rx: graceful_shutdown_rx,
+        })
+    }
+
     /// Gets a shutdown handle that can trigger the server's graceful shutdown process.
     pub fn shutdown_handle(&self) -> VeritechShutdownHandle {
         VeritechShutdownHandle {
@@ -201,7 +256,7 @@ impl VeritechShutdownHandle {
 async fn process_resolver_function_requests_task(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) {
     if let Err(err) = process_resolver_function_requests(
@@ -219,7 +274,7 @@ async fn process_resolver_function_requests_task(
 async fn process_resolver_function_requests(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     mut shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) -> ServerResult<()> {
     let mut requests =
@@ -268,7 +323,7 @@ async fn process_resolver_function_requests(
 
 async fn resolver_function_request_task(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<ResolverFunctionRequest>,
 ) {
     let (cyclone_request, reply_mailbox) = request.into_parts();
@@ -295,6 +350,7 @@ async fn resolver_function_request_task(
                     message: "failed to finalize output by sending final message".to_string(),
                 },
                 timestamp: timestamp(),
+                redacted: false,
             },
         );
         if let Err(err) = publisher.publish_result(&result).await {
@@ -315,6 +371,7 @@ async fn resolver_function_request_task(
                         message: err.to_string(),
                     },
                     timestamp: timestamp(),
+                    redacted: false,
                 },
             )
         }
@@ -327,43 +384,16 @@ async fn resolver_function_request_task(
 
 async fn resolver_function_request(
     publisher: &Publisher<'_>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     cyclone_request: ResolverFunctionRequest,
 ) -> ServerResult<FunctionResult<ResolverFunctionResultSuccess>> {
-    let mut client = cyclone_pool
-        .get()
-        .await
-        .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
-    let mut progress = client
-        .execute_resolver(cyclone_request)
-        .await?
-        .start()
-        .await?;
-
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
-            }
-        }
-    }
-
-    let function_result = progress.finish().await?;
-
-    Ok(function_result)
+    execute!(cyclone_pool, publisher, execute_resolver(cyclone_request))
 }
 
 async fn process_validation_requests_task(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) {
     if let Err(err) =
@@ -376,7 +406,7 @@ async fn process_validation_requests_task(
 async fn process_validation_requests(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     mut shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) -> ServerResult<()> {
     let mut requests = FunctionSubscriber::validation(&nats, subject_prefix.as_deref()).await?;
@@ -424,7 +454,7 @@ async fn process_validation_requests(
 
 async fn validation_request_task(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<ValidationRequest>,
 ) {
     if let Err(err) = validation_request(nats, cyclone_pool, request).await {
@@ -434,40 +464,20 @@ async fn validation_request_task(
 
 async fn validation_request(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<ValidationRequest>,
 ) -> ServerResult<()> {
     let (cyclone_request, reply_mailbox) = request.into_parts();
     let reply_mailbox = reply_mailbox.ok_or(ServerError::NoReplyMailboxFound)?;
 
     let publisher = Publisher::new(&nats, &reply_mailbox);
-    let mut client = cyclone_pool
-        .get()
-        .await
-        .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
-    let mut progress = client
-        .execute_validation(cyclone_request)
-        .await?
-        .start()
-        .await?;
-
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
-            }
-        }
-    }
+    let function_result = execute!(
+        cyclone_pool,
+        &publisher,
+        execute_validation(cyclone_request)
+    )?;
     publisher.finalize_output().await?;
 
-    let function_result = progress.finish().await?;
     publisher.publish_result(&function_result).await?;
 
     Ok(())
@@ -476,7 +486,7 @@ async fn validation_request(
 async fn process_schema_variant_definition_requests_task(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) {
     if let Err(err) = process_schema_variant_definition_requests(
@@ -494,7 +504,7 @@ async fn process_schema_variant_definition_requests_task(
 async fn process_schema_variant_definition_requests(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     mut shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) -> ServerResult<()> {
     let mut requests =
@@ -543,7 +553,7 @@ async fn process_schema_variant_definition_requests(
 
 async fn schema_variant_definition_request_task(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<SchemaVariantDefinitionRequest>,
 ) {
     if let Err(err) = schema_variant_definition_request(nats, cyclone_pool, request).await {
@@ -553,41 +563,20 @@ async fn schema_variant_definition_request_task(
 
 async fn schema_variant_definition_request(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<SchemaVariantDefinitionRequest>,
 ) -> ServerResult<()> {
     let (cyclone_request, reply_mailbox) = request.into_parts();
     let reply_mailbox = reply_mailbox.ok_or(ServerError::NoReplyMailboxFound)?;
 
     let publisher = Publisher::new(&nats, &reply_mailbox);
-    let mut client = cyclone_pool
-        .get()
-        .await
-        .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
-
-    let mut progress = client
-        .execute_schema_variant_definition(cyclone_request)
-        .await?
-        .start()
-        .await?;
-
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
-            }
-        }
-    }
+    let function_result = execute!(
+        cyclone_pool,
+        &publisher,
+        execute_schema_variant_definition(cyclone_request)
+    )?;
     publisher.finalize_output().await?;
 
-    let function_result = progress.finish().await?;
     publisher.publish_result(&function_result).await?;
 
     Ok(())
@@ -596,7 +585,7 @@ async fn schema_variant_definition_request(
 async fn process_action_run_requests_task(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) {
     if let Err(err) =
@@ -609,7 +598,7 @@ async fn process_action_run_requests_task(
 async fn process_action_run_requests(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     mut shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) -> ServerResult<()> {
     let mut requests = FunctionSubscriber::action_run(&nats, subject_prefix.as_deref()).await?;
@@ -657,7 +646,7 @@ async fn process_action_run_requests(
 
 async fn action_run_request_task(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<ActionRunRequest>,
 ) {
     if let Err(err) = action_run_request(nats, cyclone_pool, request).await {
@@ -667,41 +656,20 @@ async fn action_run_request_task(
 
 async fn action_run_request(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<ActionRunRequest>,
 ) -> ServerResult<()> {
     let (cyclone_request, reply_mailbox) = request.into_parts();
     let reply_mailbox = reply_mailbox.ok_or(ServerError::NoReplyMailboxFound)?;
 
     let publisher = Publisher::new(&nats, &reply_mailbox);
-    let mut client = cyclone_pool
-        .get()
-        .await
-        .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
-
-    let mut progress = client
-        .execute_action_run(cyclone_request)
-        .await?
-        .start()
-        .await?;
-
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
-            }
-        }
-    }
+    let function_result = execute!(
+        cyclone_pool,
+        &publisher,
+        execute_action_run(cyclone_request)
+    )?;
     publisher.finalize_output().await?;
 
-    let function_result = progress.finish().await?;
     publisher.publish_result(&function_result).await?;
 
     Ok(())
@@ -710,7 +678,7 @@ async fn action_run_request(
 async fn process_reconciliation_requests_task(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) {
     if let Err(err) =
@@ -724,7 +692,7 @@ async fn process_reconciliation_requests_task(
 async fn process_reconciliation_requests(
     nats: NatsClient,
     subject_prefix: Option<String>,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     mut shutdown_broadcast_rx: broadcast::Receiver<()>,
 ) -> ServerResult<()> {
     let mut requests = FunctionSubscriber::reconciliation(&nats, subject_prefix.as_deref()).await?;
@@ -772,7 +740,7 @@ async fn process_reconciliation_requests(
 
 async fn reconciliation_request_task(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<ReconciliationRequest>,
 ) {
     if let Err(err) = reconciliation_request(nats, cyclone_pool, request).await {
@@ -782,24 +750,34 @@ async fn reconciliation_request_task(
 
 async fn reconciliation_request(
     nats: NatsClient,
-    cyclone_pool: Pool<LocalUdsInstanceSpec>,
+    cyclone_pool: CyclonePool,
     request: Request<ReconciliationRequest>,
 ) -> ServerResult<()> {
     let (cyclone_request, reply_mailbox) = request.into_parts();
     let reply_mailbox = reply_mailbox.ok_or(ServerError::NoReplyMailboxFound)?;
 
     let publisher = Publisher::new(&nats, &reply_mailbox);
-    let mut client = cyclone_pool
-        .get()
-        .await
-        .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
+    let function_result = execute!(
+        cyclone_pool,
+        &publisher,
+        execute_reconciliation(cyclone_request)
+    )?;
+    publisher.finalize_output().await?;
 
-    let mut progress = client
-        .execute_reconciliation(cyclone_request)
-        .await?
-        .start()
-        .await?;
+    publisher.publish_result(&function_result).await?;
 
+    Ok(())
+}
+
+async fn forward_progress<Strm, Success>(
+    publisher: &Publisher<'_>,
+    mut progress: ExecutionStarted<Strm, Success>,
+) -> ServerResult<FunctionResult<Success>>
+where
+    Strm: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static,
+    Success: DeserializeOwned + Unpin + fmt::Debug,
+    ServerError: From<ExecutionError<Success>>,
+{
     while let Some(msg) = progress.next().await {
         match msg {
             Ok(ProgressMessage::OutputStream(output)) => {
@@ -814,12 +792,8 @@ async fn reconciliation_request(
             }
         }
     }
-    publisher.finalize_output().await?;
 
-    let function_result = progress.finish().await?;
-    publisher.publish_result(&function_result).await?;
-
-    Ok(())
+    Ok(progress.finish().await?)
 }
 
 async fn connect_to_nats(config: &Config) -> ServerResult<NatsClient> {